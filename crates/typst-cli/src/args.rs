@@ -83,6 +83,9 @@ pub enum Command {
     /// Processes an input file to extract provided metadata.
     Query(QueryCommand),
 
+    /// Compares the rendered pages of two documents.
+    Diff(DiffCommand),
+
     /// Lists all discovered fonts in system and custom font paths.
     Fonts(FontsCommand),
 
@@ -165,6 +168,14 @@ pub struct QueryCommand {
     #[clap(long)]
     pub pretty: bool,
 
+    /// Exports the works cited in the document from the retrieved
+    /// bibliographies instead of serializing the bibliographies themselves.
+    ///
+    /// For example, `typst query main.typ bibliography --cited biblatex`
+    /// prints a `.bib` file with all cited works.
+    #[clap(long, value_name = "FORMAT", conflicts_with = "field")]
+    pub cited: Option<CitedFormat>,
+
     /// The target to compile for.
     #[clap(long, default_value_t)]
     pub target: Target,
@@ -178,6 +189,49 @@ pub struct QueryCommand {
     pub process: ProcessArgs,
 }
 
+/// Compares the rendered pages of two documents.
+///
+/// Writes an image highlighting the changes for each changed page and a JSON
+/// summary into the output directory. Exits with a failure code if any page
+/// changed.
+#[derive(Debug, Clone, Parser)]
+pub struct DiffCommand {
+    /// The old version of the document: A Typst file, a PDF file, or a
+    /// directory of PNG files with one page each.
+    #[clap(value_hint = ValueHint::AnyPath)]
+    pub old: PathBuf,
+
+    /// The new version of the document, in one of the same forms.
+    #[clap(value_hint = ValueHint::AnyPath)]
+    pub new: PathBuf,
+
+    /// The directory to write the highlight images and the summary to.
+    #[clap(long, short, default_value = "diff", value_hint = ValueHint::DirPath)]
+    pub output: PathBuf,
+
+    /// The PPI (pixels per inch) to render pages with. Must match the PPI
+    /// that PNG files to compare against were exported with.
+    #[arg(long = "ppi", default_value_t = 144.0)]
+    pub ppi: f32,
+
+    /// How much a color component of a pixel may differ before the pixel
+    /// counts as changed.
+    #[clap(long, default_value_t = 0)]
+    pub tolerance: u8,
+
+    /// Whether to pretty-print the JSON summary.
+    #[clap(long)]
+    pub pretty: bool,
+
+    /// World arguments.
+    #[clap(flatten)]
+    pub world: WorldArgs,
+
+    /// Processing arguments.
+    #[clap(flatten)]
+    pub process: ProcessArgs,
+}
+
 /// Lists all discovered fonts in system and custom font paths.
 #[derive(Debug, Clone, Parser)]
 pub struct FontsCommand {
@@ -295,10 +349,142 @@ pub struct CompileArgs {
     #[arg(long = "no-pdf-tags")]
     pub no_pdf_tags: bool,
 
+    /// Path to an ICC profile describing the printing condition a PDF/X
+    /// document is prepared for. Required when exporting to PDF/X.
+    #[arg(long = "pdf-output-intent", value_name = "PATH")]
+    pub pdf_output_intent: Option<PathBuf>,
+
+    /// The identifier of the printing condition described by the output
+    /// intent, preferably one registered with the ICC (e.g. 'FOGRA39').
+    #[arg(long = "pdf-output-condition", value_name = "NAME", default_value = "Custom")]
+    pub pdf_output_condition: String,
+
+    /// Encrypts the PDF so that it can only be opened with the given password.
+    #[arg(
+        long = "pdf-user-password",
+        value_name = "PASSWORD",
+        env = "TYPST_PDF_USER_PASSWORD",
+        hide_env_values = true
+    )]
+    pub pdf_user_password: Option<String>,
+
+    /// Encrypts the PDF with a password that lifts the restrictions set with
+    /// --pdf-permissions. Without it, the restrictions can't be lifted.
+    #[arg(
+        long = "pdf-owner-password",
+        value_name = "PASSWORD",
+        env = "TYPST_PDF_OWNER_PASSWORD",
+        hide_env_values = true
+    )]
+    pub pdf_owner_password: Option<String>,
+
+    /// One (or multiple comma-separated) permissions that readers of an
+    /// encrypted PDF have without the owner password. Use 'none' to grant
+    /// none of them. When unspecified, all are granted.
+    #[arg(long = "pdf-permissions", value_delimiter = ',')]
+    pub pdf_permissions: Option<Vec<PdfPermission>>,
+
+    /// Path to a PKCS#12 file (.p12 or .pfx) with a certificate and private
+    /// key to digitally sign the PDF with.
+    #[arg(long = "pdf-sign", value_name = "PATH")]
+    pub pdf_sign: Option<PathBuf>,
+
+    /// The password of the PKCS#12 file passed to --pdf-sign.
+    #[arg(
+        long = "pdf-sign-password",
+        value_name = "PASSWORD",
+        env = "TYPST_PDF_SIGN_PASSWORD",
+        hide_env_values = true,
+        requires = "pdf_sign"
+    )]
+    pub pdf_sign_password: Option<String>,
+
+    /// The reason for signing the PDF, e.g. 'I approve this document'.
+    #[arg(long = "pdf-sign-reason", value_name = "REASON", requires = "pdf_sign")]
+    pub pdf_sign_reason: Option<String>,
+
+    /// The place where the PDF was signed.
+    #[arg(long = "pdf-sign-location", value_name = "LOCATION", requires = "pdf_sign")]
+    pub pdf_sign_location: Option<String>,
+
+    /// Shows the signature in a field on a page, given as the page number
+    /// followed by the x and y coordinates of the field's top-left corner and
+    /// its width and height, all in points (e.g. '1,72,700,150,50'). When
+    /// unspecified, the signature is invisible.
+    #[arg(
+        long = "pdf-sign-field",
+        value_name = "PAGE,X,Y,WIDTH,HEIGHT",
+        requires = "pdf_sign"
+    )]
+    pub pdf_sign_field: Option<SignField>,
+
     /// The PPI (pixels per inch) to use for PNG export.
     #[arg(long = "ppi", default_value_t = 144.0)]
     pub ppi: f32,
 
+    /// One (or multiple comma-separated) PDF layers to include in PNG and SVG
+    /// export even if they aren't visible by default.
+    #[arg(long = "show-layer", value_name = "NAME", value_delimiter = ',')]
+    pub show_layers: Vec<String>,
+
+    /// One (or multiple comma-separated) PDF layers to exclude from PNG and SVG
+    /// export even if they are visible by default.
+    #[arg(long = "hide-layer", value_name = "NAME", value_delimiter = ',')]
+    pub hide_layers: Vec<String>,
+
+    /// Leaves the background of PNGs transparent instead of filling it with
+    /// the page's fill.
+    #[arg(long = "png-transparent")]
+    pub png_transparent: bool,
+
+    /// Renders PNGs without anti-aliasing, so that each pixel is either fully
+    /// covered by shapes and text or not at all.
+    #[arg(long = "no-png-anti-alias")]
+    pub no_png_anti_alias: bool,
+
+    /// How to scale raster images in PNGs. By default, the scaling that each
+    /// image requests is respected.
+    #[arg(long = "png-image-smoothing", value_name = "MODE")]
+    pub png_image_smoothing: Option<ImageSmoothing>,
+
+    /// Renders PNGs in shades of gray, e.g. for e-ink displays.
+    #[arg(long = "png-grayscale")]
+    pub png_grayscale: bool,
+
+    /// Records the PPI in PNGs, so that image viewers and editors can display
+    /// them at their physical size.
+    #[arg(long = "png-embed-ppi")]
+    pub png_embed_ppi: bool,
+
+    /// Overlays the glyphs of an SVG with invisible text, so that the text can
+    /// be selected and searched, and read by screen readers.
+    #[arg(long = "svg-selectable-text")]
+    pub svg_selectable_text: bool,
+
+    /// The maximum number of fractional digits of coordinates in SVG paths,
+    /// transforms and glyph positions. Fewer digits make the file smaller.
+    #[arg(long = "svg-precision", value_name = "DIGITS")]
+    pub svg_precision: Option<u8>,
+
+    /// Draws each glyph in place in an SVG, instead of defining it once and
+    /// referencing it wherever it appears.
+    #[arg(long = "no-svg-glyph-dedup")]
+    pub no_svg_glyph_dedup: bool,
+
+    /// Writes the images of an SVG into the given directory, relative to the
+    /// output file, and references them from there instead of embedding them.
+    /// Images that Typst converts on export are still embedded.
+    #[arg(long = "svg-image-dir", value_name = "DIR")]
+    pub svg_image_dir: Option<PathBuf>,
+
+    /// A prefix for all ids in an SVG. Allows inlining several SVGs into one
+    /// HTML page without their ids clashing.
+    ///
+    /// The prefix must start with a letter or an underscore, followed by
+    /// letters, digits, underscores, hyphens, and periods.
+    #[arg(long = "svg-id-prefix", value_name = "PREFIX", default_value = "")]
+    pub svg_id_prefix: String,
+
     /// File path to which a Makefile with the current compilation's
     /// dependencies will be written.
     #[clap(long = "make-deps", value_name = "PATH", hide = true)]
@@ -386,6 +572,16 @@ pub struct ProcessArgs {
     #[arg(long = "features", value_delimiter = ',', env = "TYPST_FEATURES")]
     pub features: Vec<Feature>,
 
+    /// Limits how many WebAssembly instructions a single plugin call may
+    /// execute. Unlimited by default.
+    #[clap(long, env = "TYPST_PLUGIN_FUEL", value_name = "FUEL")]
+    pub plugin_fuel: Option<u64>,
+
+    /// Limits the memory of each WebAssembly plugin instance, in mebibytes.
+    /// Unlimited by default.
+    #[clap(long, env = "TYPST_PLUGIN_MAX_MEMORY", value_name = "MIB")]
+    pub plugin_max_memory: Option<usize>,
+
     /// The format to emit diagnostics in.
     #[clap(long, default_value_t)]
     pub diagnostic_format: DiagnosticFormat,
@@ -654,10 +850,45 @@ pub enum PdfStandard {
     /// PDF/UA-1.
     #[value(name = "ua-1")]
     UA_1,
+    /// PDF/X-1a:2003.
+    #[value(name = "x-1a")]
+    X_1a,
+    /// PDF/X-3:2003.
+    #[value(name = "x-3")]
+    X_3,
+    /// PDF/X-4.
+    #[value(name = "x-4")]
+    X_4,
 }
 
 display_possible_values!(PdfStandard);
 
+/// A permission for readers of an encrypted PDF.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum PdfPermission {
+    /// Printing the document.
+    Print,
+    /// Copying text and graphics.
+    Copy,
+    /// Modifying and annotating the document and filling in forms.
+    Modify,
+    /// None of the permissions.
+    None,
+}
+
+display_possible_values!(PdfPermission);
+
+/// How raster images are scaled when rendering PNGs.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum ImageSmoothing {
+    /// Smooth all images.
+    Smooth,
+    /// Scale all images without smoothing.
+    Pixelated,
+}
+
+display_possible_values!(ImageSmoothing);
+
 /// Output file format for query and info commands
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, ValueEnum)]
 pub enum SerializationFormat {
@@ -668,6 +899,17 @@ pub enum SerializationFormat {
 
 display_possible_values!(SerializationFormat);
 
+/// Format into which cited works are exported by the query command.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum CitedFormat {
+    /// A BibLaTeX `.bib` file.
+    Biblatex,
+    /// A Hayagriva `.yaml` file.
+    Hayagriva,
+}
+
+display_possible_values!(CitedFormat);
+
 /// Implements parsing of page ranges (`1-3`, `4`, `5-`, `-2`), used by the
 /// `CompileCommand.pages` argument, through the `FromStr` trait instead of a
 /// value parser, in order to generate better errors.
@@ -712,6 +954,44 @@ fn parse_page_number(value: &str) -> Result<NonZeroUsize, &'static str> {
     }
 }
 
+/// The placement of a visible PDF signature.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SignField {
+    /// The number of the page the field is on.
+    pub page: NonZeroUsize,
+    /// The position of the field's top-left corner and its size, in points.
+    pub rect: [f64; 4],
+}
+
+impl FromStr for SignField {
+    type Err = &'static str;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = value.split(',').map(str::trim).collect();
+        let [page, rest @ ..] = parts.as_slice() else {
+            return Err("signature field must not be empty");
+        };
+        if rest.len() != 4 {
+            return Err("signature field must have a page, position and size");
+        }
+
+        let page = parse_page_number(page)?;
+        let mut rect = [0.0; 4];
+        for (value, part) in rect.iter_mut().zip(rest) {
+            *value = part
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .ok_or("not a valid length in points")?;
+        }
+        if rect[2] <= 0.0 || rect[3] <= 0.0 {
+            return Err("signature field must have a positive width and height");
+        }
+
+        Ok(SignField { page, rect })
+    }
+}
+
 /// The clap value parser used by `SharedArgs.input`
 fn input_value_parser() -> impl TypedValueParser<Value = Input> {
     clap::builder::OsStringValueParser::new().try_map(|value| {
@@ -1,26 +1,37 @@
+use std::borrow::Cow;
 use std::ffi::OsStr;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Datelike, Timelike, Utc};
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::term;
-use ecow::eco_format;
+use ecow::{EcoString, eco_format};
 use parking_lot::RwLock;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rustc_hash::FxHashMap;
 use typst::WorldExt;
 use typst::diag::{
     At, HintedStrResult, HintedString, Severity, SourceDiagnostic, SourceResult,
     StrResult, Warned, bail,
 };
-use typst::foundations::{Datetime, Smart};
-use typst::layout::{Page, PageRanges, PagedDocument};
+use typst::foundations::{Bytes, Datetime, Smart};
+use typst::layout::{
+    Abs, Frame, FrameItem, Page, PageRanges, PagedDocument, Point, Rect, Size,
+};
+use typst::pdf::LayerFilter;
 use typst::syntax::{FileId, Lines, Span};
+use typst::visualize::{ExchangeFormat, Image, ImageKind, Paint, RasterFormat};
 use typst_html::HtmlDocument;
-use typst_pdf::{PdfOptions, PdfStandards, Timestamp};
+use typst_pdf::{
+    OutputIntent, PdfEncryption, PdfOptions, PdfPermissions, PdfSignature,
+    PdfSignatureField, PdfStandards, Timestamp,
+};
+use typst_render::{RenderColorSpace, RenderOptions};
+use typst_svg::{IdPrefix, SvgOptions};
 
 use crate::args::{
-    CompileArgs, CompileCommand, DepsFormat, DiagnosticFormat, Input, Output,
-    OutputFormat, PdfStandard, WatchCommand,
+    CompileArgs, CompileCommand, DepsFormat, DiagnosticFormat, ImageSmoothing, Input,
+    Output, OutputFormat, PdfPermission, PdfStandard, WatchCommand,
 };
 use crate::deps::write_deps;
 #[cfg(feature = "http-server")]
@@ -68,12 +79,27 @@ pub struct CompileConfig {
     pub pdf_standards: PdfStandards,
     /// Whether to write PDF (accessibility) tags.
     pub tagged: bool,
+    /// The printing condition for PDF/X export.
+    pub pdf_output_intent: Option<OutputIntent>,
+    /// The passwords and permissions for PDF encryption.
+    pub pdf_encryption: Option<PdfEncryption>,
+    /// The certificate and details for signing the PDF.
+    pub pdf_signature: Option<PdfSignature>,
     /// A destination to write a list of dependencies to.
     pub deps: Option<Output>,
     /// The format to use for dependencies.
     pub deps_format: DepsFormat,
     /// The PPI (pixels per inch) to use for PNG export.
     pub ppi: f32,
+    /// Whether to record the PPI in exported PNGs.
+    pub png_embed_ppi: bool,
+    /// Settings for PNG export.
+    pub render_options: RenderOptions,
+    /// Settings for SVG export.
+    pub svg_options: SvgOptions<'static>,
+    /// The directory, relative to the output, into which the images of SVGs
+    /// are written instead of embedding them.
+    pub svg_image_dir: Option<PathBuf>,
     /// The export cache for images, used for caching output files in `typst
     /// watch` sessions with images.
     pub export_cache: ExportCache,
@@ -174,6 +200,96 @@ impl CompileConfig {
             &args.pdf_standard.iter().copied().map(Into::into).collect::<Vec<_>>(),
         )?;
 
+        let pdf_output_intent = args
+            .pdf_output_intent
+            .as_ref()
+            .map(|path| {
+                let profile = std::fs::read(path).map_err(|err| {
+                    eco_format!("failed to read output intent {} ({err})", path.display())
+                })?;
+                Ok::<_, EcoString>(OutputIntent {
+                    condition: args.pdf_output_condition.as_str().into(),
+                    profile: Bytes::new(profile),
+                })
+            })
+            .transpose()?;
+
+        let pdf_encryption = match (&args.pdf_user_password, &args.pdf_owner_password) {
+            (None, None) => {
+                if args.pdf_permissions.is_some() {
+                    bail!(
+                        "PDF permissions only apply to encrypted documents";
+                        hint: "set a password with --pdf-owner-password"
+                    );
+                }
+                None
+            }
+            (user, owner) => {
+                let permissions = match &args.pdf_permissions {
+                    Some(list) => PdfPermissions {
+                        print: list.contains(&PdfPermission::Print),
+                        copy: list.contains(&PdfPermission::Copy),
+                        modify: list.contains(&PdfPermission::Modify),
+                    },
+                    None => PdfPermissions::default(),
+                };
+                Some(PdfEncryption {
+                    user_password: user.as_deref().unwrap_or_default().into(),
+                    owner_password: owner.as_deref().unwrap_or_default().into(),
+                    permissions,
+                })
+            }
+        };
+
+        let pdf_signature = args
+            .pdf_sign
+            .as_ref()
+            .map(|path| {
+                let certificate = std::fs::read(path).map_err(|err| {
+                    eco_format!("failed to read certificate {} ({err})", path.display())
+                })?;
+                Ok::<_, EcoString>(PdfSignature {
+                    certificate: Bytes::new(certificate),
+                    password: args
+                        .pdf_sign_password
+                        .as_deref()
+                        .unwrap_or_default()
+                        .into(),
+                    reason: args.pdf_sign_reason.as_deref().map(Into::into),
+                    location: args.pdf_sign_location.as_deref().map(Into::into),
+                    field: args.pdf_sign_field.map(|field| {
+                        let [x, y, width, height] = field.rect.map(Abs::pt);
+                        PdfSignatureField {
+                            page: field.page,
+                            pos: Point::new(x, y),
+                            size: Size::new(width, height),
+                        }
+                    }),
+                })
+            })
+            .transpose()?;
+
+        let layers = LayerFilter {
+            show: args.show_layers.iter().map(|name| name.as_str().into()).collect(),
+            hide: args.hide_layers.iter().map(|name| name.as_str().into()).collect(),
+        };
+
+        let render_options = RenderOptions {
+            transparent: args.png_transparent,
+            anti_alias: !args.no_png_anti_alias,
+            image_smoothing: match args.png_image_smoothing {
+                Some(ImageSmoothing::Smooth) => Smart::Custom(true),
+                Some(ImageSmoothing::Pixelated) => Smart::Custom(false),
+                None => Smart::Auto,
+            },
+            color_space: if args.png_grayscale {
+                RenderColorSpace::Grayscale
+            } else {
+                RenderColorSpace::Srgb
+            },
+            layers: layers.clone(),
+        };
+
         #[cfg(feature = "http-server")]
         let server = match watch {
             Some(command)
@@ -219,8 +335,22 @@ impl CompileConfig {
             pages,
             pdf_standards,
             tagged,
+            pdf_output_intent,
+            pdf_encryption,
+            pdf_signature,
             creation_timestamp: args.world.creation_timestamp,
             ppi: args.ppi,
+            png_embed_ppi: args.png_embed_ppi,
+            render_options,
+            svg_options: SvgOptions {
+                selectable_text: args.svg_selectable_text,
+                precision: args.svg_precision,
+                deduplicate_glyphs: !args.no_svg_glyph_dedup,
+                image_href: None,
+                id_prefix: IdPrefix::new(&args.svg_id_prefix)?,
+                layers,
+            },
+            svg_image_dir: args.svg_image_dir.clone(),
             diagnostic_format: args.process.diagnostic_format,
             open: args.open.clone(),
             export_cache: ExportCache::new(),
@@ -373,6 +503,9 @@ fn export_pdf(document: &PagedDocument, config: &CompileConfig) -> SourceResult<
         page_ranges: config.pages.clone(),
         standards: config.pdf_standards.clone(),
         tagged: config.tagged,
+        output_intent: config.pdf_output_intent.clone(),
+        encryption: config.pdf_encryption.clone(),
+        signature: config.pdf_signature.clone(),
     };
     let buffer = typst_pdf::pdf(document, &options)?;
     config
@@ -479,6 +612,46 @@ fn export_image(
         .collect::<StrResult<Vec<Output>>>()
 }
 
+/// Encode demultiplied RGBA pixels as a PNG file.
+///
+/// When `grayscale` is set, the pixels must be gray. When a PPI is given, it
+/// is embedded into the file.
+pub(crate) fn encode_png(
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+    grayscale: bool,
+    ppi: Option<f32>,
+) -> Result<Vec<u8>, png::EncodingError> {
+    let mut buf = vec![];
+    let mut encoder = png::Encoder::new(&mut buf, width, height);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    if let Some(ppi) = ppi {
+        // PNG files store the number of pixels per meter.
+        let ppm = (ppi / 0.0254).round() as u32;
+        encoder.set_pixel_dims(Some(png::PixelDimensions {
+            xppu: ppm,
+            yppu: ppm,
+            unit: png::Unit::Meter,
+        }));
+    }
+
+    // Gray pixels have equal color components, so one of them suffices.
+    let data = if grayscale {
+        encoder.set_color(png::ColorType::GrayscaleAlpha);
+        Cow::Owned(rgba.chunks_exact(4).flat_map(|px| [px[0], px[3]]).collect())
+    } else {
+        encoder.set_color(png::ColorType::Rgba);
+        Cow::Borrowed(rgba)
+    };
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&data)?;
+    writer.finish()?;
+    Ok(buf)
+}
+
 mod output_template {
     const INDEXABLE: [&str; 3] = ["{p}", "{0p}", "{n}"];
 
@@ -517,16 +690,52 @@ fn export_image_page(
 ) -> StrResult<()> {
     match fmt {
         ImageExportFormat::Png => {
-            let pixmap = typst_render::render(page, config.ppi / 72.0);
-            let buf = pixmap
-                .encode_png()
+            let region = Rect::from_pos_size(Point::zero(), page.frame.size());
+            let pixmap = typst_render::render_region(
+                page,
+                config.ppi / 72.0,
+                region,
+                &config.render_options,
+            )
+            .map_err(|err| eco_format!("failed to render PNG file ({err})"))?;
+            let rgba: Vec<u8> = pixmap
+                .pixels()
+                .iter()
+                .flat_map(|pixel| {
+                    let color = pixel.demultiply();
+                    [color.red(), color.green(), color.blue(), color.alpha()]
+                })
+                .collect();
+            let grayscale =
+                config.render_options.color_space == RenderColorSpace::Grayscale;
+            let ppi = config.png_embed_ppi.then_some(config.ppi);
+            let buf = encode_png(pixmap.width(), pixmap.height(), &rgba, grayscale, ppi)
                 .map_err(|err| eco_format!("failed to encode PNG file ({err})"))?;
             output
                 .write(&buf)
                 .map_err(|err| eco_format!("failed to write PNG file ({err})"))?;
         }
         ImageExportFormat::Svg => {
-            let svg = typst_svg::svg(page);
+            // Write the images up front, so that the SVG can reference them
+            // and errors can be reported.
+            let mut hrefs = FxHashMap::default();
+            if let Some(dir) = &config.svg_image_dir {
+                let mut images = vec![];
+                collect_images(&page.frame, &config.svg_options.layers, &mut images);
+                for image in images {
+                    if let Some(href) = write_svg_image(&image, output, dir)? {
+                        hrefs.insert(image, href);
+                    }
+                }
+            }
+
+            let href = |image: &Image| hrefs.get(image).cloned();
+            let options = SvgOptions {
+                image_href: Some(&href),
+                ..config.svg_options.clone()
+            };
+            let svg = typst_svg::svg_with_options(page, &options);
+
             output
                 .write(svg.as_bytes())
                 .map_err(|err| eco_format!("failed to write SVG file ({err})"))?;
@@ -535,6 +744,94 @@ fn export_image_page(
     Ok(())
 }
 
+/// Collects the images in a frame that are exported into an SVG.
+fn collect_images(frame: &Frame, layers: &LayerFilter, images: &mut Vec<Image>) {
+    let collect_paint = |paint: &Paint, images: &mut Vec<Image>| {
+        if let Paint::Tiling(tiling) = paint {
+            collect_images(tiling.frame(), layers, images);
+        }
+    };
+
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => {
+                if group.layer.as_ref().is_none_or(|layer| layers.includes(layer)) {
+                    collect_images(&group.frame, layers, images);
+                }
+            }
+            FrameItem::Text(text) => {
+                collect_paint(&text.fill, images);
+                if let Some(stroke) = &text.stroke {
+                    collect_paint(&stroke.paint, images);
+                }
+            }
+            FrameItem::Shape(shape, _) => {
+                if let Some(fill) = &shape.fill {
+                    collect_paint(fill, images);
+                }
+                if let Some(stroke) = &shape.stroke {
+                    collect_paint(&stroke.paint, images);
+                }
+            }
+            FrameItem::Image(image, _, _) => images.push(image.clone()),
+            FrameItem::Link(..)
+            | FrameItem::Widget(..)
+            | FrameItem::Annotation(..)
+            | FrameItem::Tag(_) => {}
+        }
+    }
+}
+
+/// Writes an image of an SVG into the image directory, returning the URL by
+/// which the SVG references it. Returns `None` for images that must be
+/// embedded because they are converted on export.
+fn write_svg_image(
+    image: &Image,
+    output: &Output,
+    dir: &Path,
+) -> StrResult<Option<EcoString>> {
+    let (ext, data) = match image.kind() {
+        ImageKind::Raster(raster) => match raster.format() {
+            RasterFormat::Exchange(format) => (
+                match format {
+                    ExchangeFormat::Png => "png",
+                    ExchangeFormat::Jpg => "jpg",
+                    ExchangeFormat::Gif => "gif",
+                    ExchangeFormat::Webp => "webp",
+                },
+                raster.data(),
+            ),
+            RasterFormat::Pixel(_) => return Ok(None),
+        },
+        ImageKind::Svg(svg) => ("svg", svg.data()),
+        ImageKind::Pdf(_) => return Ok(None),
+    };
+
+    // Images are named by their contents, so that each one is written once.
+    let name = format!("{:032x}.{ext}", typst::utils::hash128(data));
+    let base = match output {
+        Output::Path(path) => path.parent().unwrap_or(Path::new("")),
+        Output::Stdout => Path::new(""),
+    };
+
+    let path = base.join(dir).join(&name);
+    if !path.exists() {
+        std::fs::create_dir_all(base.join(dir))
+            .and_then(|_| std::fs::write(&path, data))
+            .map_err(|err| {
+                eco_format!("failed to write image {} ({err})", path.display())
+            })?;
+    }
+
+    let mut url = EcoString::new();
+    for component in dir.components() {
+        url.push_str(&component.as_os_str().to_string_lossy());
+        url.push('/');
+    }
+    url.push_str(&name);
+    Ok(Some(url))
+}
+
 /// Caches exported files so that we can avoid re-exporting them if they haven't
 /// changed.
 ///
@@ -755,6 +1052,9 @@ impl From<PdfStandard> for typst_pdf::PdfStandard {
             PdfStandard::A_4f => typst_pdf::PdfStandard::A_4f,
             PdfStandard::A_4e => typst_pdf::PdfStandard::A_4e,
             PdfStandard::UA_1 => typst_pdf::PdfStandard::Ua_1,
+            PdfStandard::X_1a => typst_pdf::PdfStandard::X_1a,
+            PdfStandard::X_3 => typst_pdf::PdfStandard::X_3,
+            PdfStandard::X_4 => typst_pdf::PdfStandard::X_4,
         }
     }
 }
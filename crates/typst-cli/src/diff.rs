@@ -0,0 +1,807 @@
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ecow::eco_format;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use serde::Serialize;
+use typst::diag::{HintedStrResult, StrResult, Warned, bail};
+use typst::foundations::{Bytes, Content, NativeElement, Smart};
+use typst::layout::{
+    Abs, Frame, FrameItem, Page, PagedDocument, Point, Rect, Sides, Size,
+};
+use typst::model::HeadingElem;
+use typst::syntax::Span;
+use typst::visualize::{
+    ExchangeFormat, Image, ImageKind, PdfDocument, PdfImage, RasterImage,
+};
+use typst_render::RenderOptions;
+
+use crate::args::{DiffCommand, Input, SerializationFormat};
+use crate::compile::{encode_png, print_diagnostics};
+use crate::set_failed;
+use crate::world::SystemWorld;
+
+/// The size of the cells that changed pixels are grouped into, in pixels.
+const CELL: u32 = 16;
+
+/// Execute a diff command.
+pub fn diff(command: &DiffCommand) -> HintedStrResult<()> {
+    let (Some(old), Some(new)) =
+        (load(&command.old, command)?, load(&command.new, command)?)
+    else {
+        return Ok(());
+    };
+
+    // Clearing the output directory would remove the PNG files to compare.
+    let output = fs::canonicalize(&command.output).ok();
+    for path in [&command.old, &command.new] {
+        if path.is_dir() && output.is_some() && fs::canonicalize(path).ok() == output {
+            bail!(
+                "the output directory must not contain the PNG files to compare";
+                hint: "choose a different directory with `--output`"
+            );
+        }
+    }
+
+    fs::create_dir_all(&command.output)
+        .map_err(|err| eco_format!("failed to create output directory ({err})"))?;
+    remove_images(&command.output)?;
+
+    let old_headings = old.headings();
+    let new_headings = new.headings();
+
+    let count = old.len().max(new.len());
+    let pages = (0..count)
+        .into_par_iter()
+        .map(|index| {
+            // Prefer the headings of the side that has the page.
+            let (primary, secondary) = if index < new.len() {
+                (&new_headings, &old_headings)
+            } else {
+                (&old_headings, &new_headings)
+            };
+            let headings = if primary.is_empty() { secondary } else { primary };
+            diff_page(command, &old, &new, index, headings)
+        })
+        .collect::<StrResult<Vec<_>>>()?;
+
+    let summary = Summary {
+        old_pages: old.len(),
+        new_pages: new.len(),
+        changed_pages: pages
+            .iter()
+            .filter(|page| page.status != PageStatus::Unchanged)
+            .map(|page| page.page)
+            .collect(),
+        pages,
+    };
+
+    let json = crate::serialize(&summary, SerializationFormat::Json, command.pretty)?;
+    fs::write(command.output.join("summary.json"), json)
+        .map_err(|err| eco_format!("failed to write summary ({err})"))?;
+
+    for page in &summary.pages {
+        match page.status {
+            PageStatus::Unchanged => continue,
+            PageStatus::Added => println!("page {}: added", page.page),
+            PageStatus::Removed => println!("page {}: removed", page.page),
+            PageStatus::Resized => {
+                let [old, new] = page.sizes.unwrap();
+                println!(
+                    "page {}: size differs ({}x{} pixels in old version, {}x{} pixels \
+                     in new version)",
+                    page.page, old[0], old[1], new[0], new[1],
+                );
+            }
+            PageStatus::Changed => println!(
+                "page {}: changed ({:.2}% of pixels)",
+                page.page,
+                page.changed_ratio * 100.0,
+            ),
+        }
+
+        let mut sections: Vec<&str> = vec![];
+        for region in &page.regions {
+            for section in &region.sections {
+                if !sections.contains(&section.as_str()) {
+                    sections.push(section);
+                }
+            }
+        }
+
+        for section in sections {
+            println!("  in {section}");
+        }
+    }
+
+    match summary.changed_pages.len() {
+        0 => println!("no pages changed"),
+        n => {
+            println!("{n} of {count} pages changed");
+            set_failed();
+        }
+    }
+
+    Ok(())
+}
+
+/// Compare the page with the given index on both sides.
+fn diff_page(
+    command: &DiffCommand,
+    old: &Side,
+    new: &Side,
+    index: usize,
+    headings: &[Heading],
+) -> StrResult<PageSummary> {
+    let old = old.page(index, command.ppi)?;
+    let new = new.page(index, command.ppi)?;
+    let page = index + 1;
+
+    let (status, base, other) = match (&old, &new) {
+        (Some(old), Some(new)) => (PageStatus::Changed, new, Some(old)),
+        (None, Some(new)) => (PageStatus::Added, new, None),
+        (Some(old), None) => (PageStatus::Removed, old, None),
+        (None, None) => unreachable!(),
+    };
+
+    // Comparing pages of different sizes pixel by pixel would mark almost
+    // everything as changed, so we only report the sizes.
+    if let Some(other) = other
+        && (other.width, other.height) != (base.width, base.height)
+    {
+        return Ok(PageSummary {
+            page,
+            status: PageStatus::Resized,
+            changed_pixels: 0,
+            changed_ratio: 0.0,
+            sizes: Some([[other.width, other.height], [base.width, base.height]]),
+            unit: Unit::Px,
+            regions: vec![],
+            image: None,
+        });
+    }
+
+    let width = old.iter().chain(&new).map(|pixels| pixels.width).max().unwrap();
+    let height = old.iter().chain(&new).map(|pixels| pixels.height).max().unwrap();
+    let mut mask = vec![false; width as usize * height as usize];
+    let mut changed_pixels = 0;
+    for y in 0..height {
+        for x in 0..width {
+            let a = old.as_ref().and_then(|pixels| pixels.get(x, y));
+            let b = new.as_ref().and_then(|pixels| pixels.get(x, y));
+            if differs(a, b, command.tolerance) {
+                mask[(y * width + x) as usize] = true;
+                changed_pixels += 1;
+            }
+        }
+    }
+
+    if changed_pixels == 0 {
+        return Ok(PageSummary {
+            page,
+            status: PageStatus::Unchanged,
+            changed_pixels,
+            changed_ratio: 0.0,
+            sizes: None,
+            unit: Unit::Px,
+            regions: vec![],
+            image: None,
+        });
+    }
+
+    let rects = regions(&mask, width, height);
+    let image = format!("page-{page}.png");
+    let buf = highlight(base, &mask, width, height, &rects);
+    fs::write(command.output.join(&image), buf)
+        .map_err(|err| eco_format!("failed to write highlight image ({err})"))?;
+
+    // Convert from pixels to points if we know the resolution of either side.
+    // Without it, the regions stay in pixels and can't be matched with the
+    // sections.
+    let scale = base.scale.or(other.and_then(|other| other.scale));
+    let regions = rects
+        .iter()
+        .map(|rect| {
+            let convert = |px: u32| f64::from(px) * scale.unwrap_or(1.0);
+            let (top, bottom) = (convert(rect.y), convert(rect.y + rect.height));
+            RegionSummary {
+                x: round(convert(rect.x)),
+                y: round(top),
+                width: round(convert(rect.width)),
+                height: round(convert(rect.height)),
+                sections: match scale {
+                    Some(_) => sections(headings, page, top, bottom),
+                    None => vec![],
+                },
+            }
+        })
+        .collect();
+
+    Ok(PageSummary {
+        page,
+        status,
+        changed_pixels,
+        changed_ratio: changed_pixels as f64 / (width as f64 * height as f64),
+        sizes: None,
+        unit: if scale.is_some() { Unit::Pt } else { Unit::Px },
+        regions,
+        image: Some(image),
+    })
+}
+
+/// One side of the comparison.
+enum Side {
+    /// A compiled Typst document.
+    Document(Box<PagedDocument>),
+    /// A PDF file.
+    Pdf(PdfDocument),
+    /// PNG files, one per page.
+    Images(Vec<PathBuf>),
+}
+
+/// Load one side of the comparison.
+///
+/// Returns `None` if a Typst file failed to compile. The diagnostics are
+/// printed in that case.
+fn load(path: &Path, command: &DiffCommand) -> HintedStrResult<Option<Side>> {
+    if path.is_dir() {
+        return Ok(Some(Side::Images(find_images(path)?)));
+    }
+
+    if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("pdf")) {
+        let data = fs::read(path).map_err(|err| {
+            eco_format!("failed to read PDF file {} ({err})", path.display())
+        })?;
+        let document = PdfDocument::new(Bytes::new(data))
+            .map_err(|_| eco_format!("failed to load PDF file {}", path.display()))?;
+        return Ok(Some(Side::Pdf(document)));
+    }
+
+    let world =
+        SystemWorld::new(&Input::Path(path.into()), &command.world, &command.process)?;
+    let Warned { output, warnings } = typst::compile::<PagedDocument>(&world);
+    let errors = match &output {
+        Ok(_) => &[][..],
+        Err(errors) => errors.as_slice(),
+    };
+
+    print_diagnostics(&world, errors, &warnings, command.process.diagnostic_format)
+        .map_err(|err| eco_format!("failed to print diagnostics ({err})"))?;
+
+    match output {
+        Ok(document) => Ok(Some(Side::Document(Box::new(document)))),
+        Err(_) => {
+            set_failed();
+            Ok(None)
+        }
+    }
+}
+
+/// Find the PNG files in a directory, ordered by the page number at the end
+/// of their names.
+fn find_images(dir: &Path) -> StrResult<Vec<PathBuf>> {
+    let entries = fs::read_dir(dir).map_err(|err| {
+        eco_format!("failed to read directory {} ({err})", dir.display())
+    })?;
+
+    let mut paths = vec![];
+    for entry in entries {
+        let path = entry
+            .map_err(|err| eco_format!("failed to read directory entry ({err})"))?
+            .path();
+        if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("png")) {
+            paths.push(path);
+        }
+    }
+
+    if paths.is_empty() {
+        bail!("directory {} contains no PNG files", dir.display());
+    }
+
+    paths.sort_by_cached_key(|path| (page_number(path), path.clone()));
+    Ok(paths)
+}
+
+/// Remove the highlight images of an earlier comparison from the output
+/// directory, so that only images of changed pages remain.
+fn remove_images(dir: &Path) -> StrResult<()> {
+    let entries = fs::read_dir(dir).map_err(|err| {
+        eco_format!("failed to read directory {} ({err})", dir.display())
+    })?;
+
+    for entry in entries {
+        let path = entry
+            .map_err(|err| eco_format!("failed to read directory entry ({err})"))?
+            .path();
+        if is_highlight_image(&path) {
+            fs::remove_file(&path).map_err(|err| {
+                eco_format!("failed to remove {} ({err})", path.display())
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether a file name is that of a highlight image, like `page-12.png`.
+fn is_highlight_image(path: &Path) -> bool {
+    path.file_name()
+        .and_then(OsStr::to_str)
+        .and_then(|name| name.strip_prefix("page-")?.strip_suffix(".png"))
+        .is_some_and(|number| {
+            !number.is_empty() && number.bytes().all(|c| c.is_ascii_digit())
+        })
+}
+
+/// Extract the page number at the end of a file name, like in `page-12.png`.
+fn page_number(path: &Path) -> Option<usize> {
+    let stem = path.file_stem().and_then(OsStr::to_str)?;
+    let text = stem.trim_end_matches(|c: char| c.is_ascii_digit());
+    stem[text.len()..].parse().ok()
+}
+
+impl Side {
+    /// The number of pages.
+    fn len(&self) -> usize {
+        match self {
+            Self::Document(document) => document.pages.len(),
+            Self::Pdf(document) => document.num_pages(),
+            Self::Images(paths) => paths.len(),
+        }
+    }
+
+    /// Render the page with the given index, if it exists.
+    fn page(&self, index: usize, ppi: f32) -> StrResult<Option<Pixels>> {
+        if index >= self.len() {
+            return Ok(None);
+        }
+
+        match self {
+            Self::Document(document) => render(&document.pages[index], ppi).map(Some),
+            Self::Pdf(document) => {
+                let image = PdfImage::new(document.clone(), index).ok_or_else(|| {
+                    eco_format!("failed to load PDF page {}", index + 1)
+                })?;
+                let size = Size::new(
+                    Abs::pt(image.width().into()),
+                    Abs::pt(image.height().into()),
+                );
+                let mut frame = Frame::hard(size);
+                frame.push(
+                    Point::zero(),
+                    FrameItem::Image(
+                        Image::plain(ImageKind::Pdf(image)),
+                        size,
+                        Span::detached(),
+                    ),
+                );
+                let page = Page {
+                    frame,
+                    fill: Smart::Auto,
+                    numbering: None,
+                    supplement: Content::empty(),
+                    number: index as u64 + 1,
+                    bleed: Sides::splat(Abs::zero()),
+                    slug: Sides::splat(Abs::zero()),
+                };
+                render(&page, ppi).map(Some)
+            }
+            Self::Images(paths) => {
+                let path = &paths[index];
+                let data = fs::read(path).map_err(|err| {
+                    eco_format!("failed to read PNG file {} ({err})", path.display())
+                })?;
+                let image = RasterImage::plain(Bytes::new(data), ExchangeFormat::Png)
+                    .map_err(|err| {
+                        eco_format!("failed to load PNG file {} ({err})", path.display())
+                    })?;
+                let rgba = image.dynamic().to_rgba8();
+                Ok(Some(Pixels {
+                    width: rgba.width(),
+                    height: rgba.height(),
+                    data: rgba.into_raw(),
+                    scale: None,
+                }))
+            }
+        }
+    }
+
+    /// The headings of the document, in document order.
+    ///
+    /// Only Typst documents have headings.
+    fn headings(&self) -> Vec<Heading> {
+        let Self::Document(document) = self else { return vec![] };
+        let introspector = &document.introspector;
+        introspector
+            .query(&HeadingElem::ELEM.select())
+            .iter()
+            .filter_map(|elem| {
+                let heading = elem.to_packed::<HeadingElem>()?;
+                let position = introspector.position(elem.location()?);
+                let text = heading.body.plain_text();
+                let title = match &heading.numbers {
+                    Some(numbers) => format!("{numbers} {text}"),
+                    None => text.to_string(),
+                };
+                Some(Heading {
+                    page: position.page.get(),
+                    y: position.point.y.to_pt(),
+                    title,
+                })
+            })
+            .collect()
+    }
+}
+
+/// A heading's title and position.
+struct Heading {
+    /// The page, starting at 1.
+    page: usize,
+    /// The vertical position on the page, in points.
+    y: f64,
+    /// The title, including the heading's numbers.
+    title: String,
+}
+
+/// The titles of the sections that overlap the vertical range between `top`
+/// and `bottom` on the given page.
+///
+/// These are the section the range starts in and all sections that start
+/// within the range.
+fn sections(headings: &[Heading], page: usize, top: f64, bottom: f64) -> Vec<String> {
+    let before = |heading: &Heading| {
+        heading.page < page || (heading.page == page && heading.y <= top)
+    };
+
+    let start = headings.iter().rposition(before);
+    let inside = headings
+        .iter()
+        .filter(|heading| heading.page == page && heading.y > top && heading.y < bottom);
+
+    start
+        .map(|i| &headings[i])
+        .into_iter()
+        .chain(inside)
+        .map(|heading| heading.title.clone())
+        .collect()
+}
+
+/// Demultiplied RGBA pixels.
+struct Pixels {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+    /// The size of a pixel in points, if the resolution is known.
+    scale: Option<f64>,
+}
+
+impl Pixels {
+    /// The pixel at the given position, if it is in bounds.
+    fn get(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = (y * self.width + x) as usize * 4;
+        self.data[i..i + 4].try_into().ok()
+    }
+}
+
+/// Render a page into pixels.
+fn render(page: &Page, ppi: f32) -> StrResult<Pixels> {
+    let region = Rect::from_pos_size(Point::zero(), page.frame.size());
+    let pixmap =
+        typst_render::render_region(page, ppi / 72.0, region, &RenderOptions::default())
+            .map_err(|err| {
+                eco_format!("failed to render page {} ({err})", page.number)
+            })?;
+    let data = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    Ok(Pixels {
+        width: pixmap.width(),
+        height: pixmap.height(),
+        data,
+        scale: Some(72.0 / f64::from(ppi)),
+    })
+}
+
+/// Whether two pixels differ by more than the tolerance.
+///
+/// A pixel that only exists on one side always differs and fully transparent
+/// pixels never differ.
+fn differs(a: Option<[u8; 4]>, b: Option<[u8; 4]>, tolerance: u8) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            (a[3] != 0 || b[3] != 0)
+                && a.iter().zip(b).any(|(&x, y)| x.abs_diff(y) > tolerance)
+        }
+        (None, None) => false,
+        _ => true,
+    }
+}
+
+/// A rectangle on a page, in pixels.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct PixelRect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+/// Group the changed pixels into rectangular regions.
+///
+/// Changed pixels in the same or in adjacent cells end up in the same region.
+fn regions(mask: &[bool], width: u32, height: u32) -> Vec<PixelRect> {
+    let cols = width.div_ceil(CELL);
+    let rows = height.div_ceil(CELL);
+    let mut cells = vec![false; (cols * rows) as usize];
+    for (i, &changed) in mask.iter().enumerate() {
+        if changed {
+            let (x, y) = (i as u32 % width, i as u32 / width);
+            cells[((y / CELL) * cols + x / CELL) as usize] = true;
+        }
+    }
+
+    let mut rects = vec![];
+    let mut stack = vec![];
+    for start in 0..cells.len() {
+        if !cells[start] {
+            continue;
+        }
+
+        // Flood fill the connected cells, tracking their bounding box.
+        cells[start] = false;
+        stack.push(start as u32);
+        let (mut x0, mut y0, mut x1, mut y1) = (u32::MAX, u32::MAX, 0, 0);
+        while let Some(cell) = stack.pop() {
+            let (cx, cy) = (cell % cols, cell / cols);
+            (x0, y0, x1, y1) = (x0.min(cx), y0.min(cy), x1.max(cx), y1.max(cy));
+            for ny in cy.saturating_sub(1)..=(cy + 1).min(rows - 1) {
+                for nx in cx.saturating_sub(1)..=(cx + 1).min(cols - 1) {
+                    let neighbour = ny * cols + nx;
+                    if cells[neighbour as usize] {
+                        cells[neighbour as usize] = false;
+                        stack.push(neighbour);
+                    }
+                }
+            }
+        }
+
+        let (x, y) = (x0 * CELL, y0 * CELL);
+        rects.push(PixelRect {
+            x,
+            y,
+            width: ((x1 + 1) * CELL).min(width) - x,
+            height: ((y1 + 1) * CELL).min(height) - y,
+        });
+    }
+
+    rects
+}
+
+/// Encode an image of a page that highlights the changes.
+///
+/// Unchanged pixels are faded, changed pixels are tinted red and the changed
+/// regions are outlined.
+fn highlight(
+    base: &Pixels,
+    mask: &[bool],
+    width: u32,
+    height: u32,
+    rects: &[PixelRect],
+) -> Vec<u8> {
+    const RED: [u8; 4] = [220, 30, 30, 255];
+
+    let mut data = Vec::with_capacity(mask.len() * 4);
+    for y in 0..height {
+        for x in 0..width {
+            // Composite onto white.
+            let [r, g, b, a] = base.get(x, y).unwrap_or([255; 4]);
+            let over = |c: u8| u32::from(c) * u32::from(a) / 255 + 255 - u32::from(a);
+            let [r, g, b] = [over(r), over(g), over(b)];
+            let pixel = if mask[(y * width + x) as usize] {
+                [(r + 255) / 2, g / 2, b / 2]
+            } else {
+                [r / 4 + 191, g / 4 + 191, b / 4 + 191]
+            };
+            data.extend(pixel.map(|c| c as u8));
+            data.push(255);
+        }
+    }
+
+    for rect in rects {
+        let mut paint = |x: u32, y: u32| {
+            let i = (y * width + x) as usize * 4;
+            data[i..i + 4].copy_from_slice(&RED);
+        };
+        let (right, bottom) = (rect.x + rect.width - 1, rect.y + rect.height - 1);
+        for x in rect.x..=right {
+            paint(x, rect.y);
+            paint(x, bottom);
+        }
+        for y in rect.y..=bottom {
+            paint(rect.x, y);
+            paint(right, y);
+        }
+    }
+
+    // Encoding the pixels we just produced can't fail.
+    encode_png(width, height, &data, false, None).unwrap()
+}
+
+/// Round a length in points to two decimal places.
+fn round(pt: f64) -> f64 {
+    (pt * 100.0).round() / 100.0
+}
+
+/// The JSON summary of a comparison.
+#[derive(Serialize)]
+struct Summary {
+    /// The number of pages of the old version.
+    old_pages: usize,
+    /// The number of pages of the new version.
+    new_pages: usize,
+    /// The numbers of the pages that aren't unchanged, starting at 1.
+    changed_pages: Vec<usize>,
+    /// The result for each page.
+    pages: Vec<PageSummary>,
+}
+
+/// The result of comparing one page.
+#[derive(Serialize)]
+struct PageSummary {
+    /// The page number, starting at 1.
+    page: usize,
+    /// How the page changed.
+    status: PageStatus,
+    /// The number of pixels that differ.
+    changed_pixels: usize,
+    /// The fraction of the page's pixels that differ.
+    changed_ratio: f64,
+    /// The widths and heights of the old and the new page in pixels, if they
+    /// differ.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sizes: Option<[[u32; 2]; 2]>,
+    /// The unit of the regions.
+    unit: Unit,
+    /// The regions that contain changes.
+    regions: Vec<RegionSummary>,
+    /// The file name of the highlight image, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+}
+
+/// How a page changed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum PageStatus {
+    /// The page looks the same in both versions.
+    Unchanged,
+    /// The page differs between the versions.
+    Changed,
+    /// The page only exists in the new version.
+    Added,
+    /// The page only exists in the old version.
+    Removed,
+    /// The page has a different size in the new version. Its pixels aren't
+    /// compared then.
+    Resized,
+}
+
+/// The unit of a page's regions.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum Unit {
+    /// Points, if the resolution of either version is known.
+    Pt,
+    /// Pixels, if both versions are PNG files.
+    Px,
+}
+
+/// A region of a page that contains changes, measured from the top left.
+#[derive(Serialize)]
+struct RegionSummary {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    /// The titles of the sections the region overlaps.
+    sections: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_differs() {
+        let white = Some([255, 255, 255, 255]);
+        let gray = Some([250, 255, 255, 255]);
+        let clear = Some([0, 0, 0, 0]);
+        let tinted = Some([255, 0, 0, 0]);
+
+        assert!(!differs(white, white, 0));
+        assert!(differs(white, gray, 0));
+        assert!(differs(white, gray, 4));
+        assert!(!differs(white, gray, 5));
+        assert!(!differs(clear, tinted, 0));
+        assert!(differs(clear, white, 254));
+        assert!(differs(white, None, 255));
+        assert!(differs(None, clear, 0));
+        assert!(!differs(None, None, 0));
+    }
+
+    #[test]
+    fn test_regions() {
+        let (width, height) = (100, 40);
+        let mut mask = vec![false; (width * height) as usize];
+        for (x, y) in [(3, 5), (20, 20), (99, 39)] {
+            mask[(y * width + x) as usize] = true;
+        }
+
+        // The first two pixels are in diagonally adjacent cells, the last one
+        // is far away in the clipped cell at the bottom right.
+        assert_eq!(
+            regions(&mask, width, height),
+            [
+                PixelRect { x: 0, y: 0, width: 32, height: 32 },
+                PixelRect { x: 96, y: 32, width: 4, height: 8 },
+            ]
+        );
+
+        mask.fill(false);
+        assert!(regions(&mask, width, height).is_empty());
+    }
+
+    #[test]
+    fn test_sections() {
+        let heading = |page, y, title: &str| Heading { page, y, title: title.into() };
+        let headings = [
+            heading(1, 10.0, "Intro"),
+            heading(2, 50.0, "Method"),
+            heading(2, 80.0, "Data"),
+            heading(3, 20.0, "Results"),
+        ];
+
+        assert!(sections(&headings, 1, 0.0, 5.0).is_empty());
+        assert_eq!(sections(&headings, 1, 0.0, 20.0), ["Intro"]);
+        assert_eq!(sections(&headings, 2, 0.0, 40.0), ["Intro"]);
+        assert_eq!(sections(&headings, 2, 40.0, 90.0), ["Intro", "Method", "Data"]);
+        assert_eq!(sections(&headings, 2, 50.0, 60.0), ["Method"]);
+        assert_eq!(sections(&headings, 3, 0.0, 10.0), ["Data"]);
+    }
+
+    #[test]
+    fn test_is_highlight_image() {
+        let is = |name: &str| is_highlight_image(Path::new(name));
+        assert!(is("page-1.png"));
+        assert!(is("diff/page-12.png"));
+        assert!(!is("page-.png"));
+        assert!(!is("page-1a.png"));
+        assert!(!is("cover-1.png"));
+        assert!(!is("summary.json"));
+    }
+
+    #[test]
+    fn test_page_number() {
+        let number = |name: &str| page_number(Path::new(name));
+        assert_eq!(number("page-12.png"), Some(12));
+        assert_eq!(number("dir/7.png"), Some(7));
+        assert_eq!(number("cover.png"), None);
+
+        let mut paths =
+            ["page-10.png", "page-9.png", "cover.png", "page-1.png"].map(PathBuf::from);
+        paths.sort_by_cached_key(|path| (page_number(path), path.clone()));
+        assert_eq!(
+            paths,
+            ["cover.png", "page-1.png", "page-9.png", "page-10.png"].map(PathBuf::from)
+        );
+    }
+}
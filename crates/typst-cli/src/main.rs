@@ -2,6 +2,7 @@ mod args;
 mod compile;
 mod completions;
 mod deps;
+mod diff;
 mod download;
 mod fonts;
 mod greet;
@@ -77,6 +78,7 @@ fn dispatch() -> HintedStrResult<()> {
         Command::Watch(command) => crate::watch::watch(&mut timer, command)?,
         Command::Init(command) => crate::init::init(command)?,
         Command::Query(command) => crate::query::query(command)?,
+        Command::Diff(command) => crate::diff::diff(command)?,
         Command::Fonts(command) => crate::fonts::fonts(command),
         Command::Update(command) => crate::update::update(command)?,
         Command::Completions(command) => crate::completions::completions(command),
@@ -1,16 +1,17 @@
 use comemo::Track;
 use ecow::{EcoString, eco_format};
 use typst::World;
-use typst::diag::{HintedStrResult, StrResult, Warned, bail};
+use typst::diag::{HintedStrResult, SourceResult, StrResult, Warned, bail};
 use typst::engine::Sink;
 use typst::foundations::{Content, IntoValue, LocatableSelector, Scope};
 use typst::introspection::Introspector;
 use typst::layout::PagedDocument;
+use typst::model::{BibliographyElem, BibliographyFormat, export_cited};
 use typst::syntax::{Span, SyntaxMode};
 use typst_eval::eval_string;
 use typst_html::HtmlDocument;
 
-use crate::args::{QueryCommand, Target};
+use crate::args::{CitedFormat, QueryCommand, Target};
 use crate::compile::print_diagnostics;
 use crate::set_failed;
 use crate::world::SystemWorld;
@@ -30,11 +31,15 @@ pub fn query(command: &QueryCommand) -> HintedStrResult<()> {
             .map(|output| output.map(|document| document.introspector)),
     };
 
+    // Retrieve the query results.
+    let output = match output {
+        Ok(introspector) => serialize(&world, command, &introspector)?,
+        Err(errors) => Err(errors),
+    };
+
     match output {
-        // Retrieve and print query results.
-        Ok(introspector) => {
-            let data = retrieve(&world, command, &introspector)?;
-            let serialized = format(data, command)?;
+        // Print query results.
+        Ok(serialized) => {
             println!("{serialized}");
             print_diagnostics(&world, &[], &warnings, command.process.diagnostic_format)
                 .map_err(|err| eco_format!("failed to print diagnostics ({err})"))?;
@@ -56,6 +61,19 @@ pub fn query(command: &QueryCommand) -> HintedStrResult<()> {
     Ok(())
 }
 
+/// Retrieve the matches for the selector and serialize them.
+fn serialize(
+    world: &dyn World,
+    command: &QueryCommand,
+    introspector: &Introspector,
+) -> HintedStrResult<SourceResult<String>> {
+    let data = retrieve(world, command, introspector)?;
+    Ok(match command.cited {
+        Some(format) => cited(world, data, command, introspector, format)?,
+        None => Ok(format(data, command)?),
+    })
+}
+
 /// Retrieve the matches for the selector.
 fn retrieve(
     world: &dyn World,
@@ -85,6 +103,35 @@ fn retrieve(
     Ok(introspector.query(&selector.0).into_iter().collect::<Vec<_>>())
 }
 
+/// Export the works cited from the retrieved bibliographies.
+fn cited(
+    world: &dyn World,
+    elements: Vec<Content>,
+    command: &QueryCommand,
+    introspector: &Introspector,
+    format: CitedFormat,
+) -> StrResult<SourceResult<String>> {
+    if command.one && elements.len() != 1 {
+        bail!("expected exactly one element, found {}", elements.len());
+    }
+
+    let bibliographies = elements
+        .into_iter()
+        .map(|elem| {
+            elem.into_packed::<BibliographyElem>().map_err(|elem| {
+                eco_format!("expected only bibliographies, found {}", elem.elem().name())
+            })
+        })
+        .collect::<StrResult<Vec<_>>>()?;
+
+    let format = match format {
+        CitedFormat::Biblatex => BibliographyFormat::BibLaTeX,
+        CitedFormat::Hayagriva => BibliographyFormat::Hayagriva,
+    };
+
+    Ok(export_cited(world.track(), introspector.track(), &bibliographies, format))
+}
+
 /// Format the query result in the output format.
 fn format(elements: Vec<Content>, command: &QueryCommand) -> StrResult<String> {
     if command.one && elements.len() != 1 {
@@ -8,7 +8,7 @@ use ecow::{EcoString, eco_format};
 use parking_lot::Mutex;
 use rustc_hash::FxHashMap;
 use typst::diag::{FileError, FileResult};
-use typst::foundations::{Bytes, Datetime, Dict, IntoValue};
+use typst::foundations::{Bytes, Datetime, Dict, IntoValue, PluginLimits};
 use typst::syntax::{FileId, Lines, Source, VirtualPath};
 use typst::text::{Font, FontBook};
 use typst::utils::LazyHash;
@@ -121,7 +121,18 @@ impl SystemWorld {
                 })
                 .collect();
 
-            Library::builder().with_inputs(inputs).with_features(features).build()
+            let plugin_limits = PluginLimits {
+                fuel: process_args.plugin_fuel,
+                memory: process_args
+                    .plugin_max_memory
+                    .map(|mib| mib.saturating_mul(1024 * 1024)),
+            };
+
+            Library::builder()
+                .with_inputs(inputs)
+                .with_features(features)
+                .with_plugin_limits(plugin_limits)
+                .build()
         };
 
         let mut fonts = Fonts::searcher();
@@ -0,0 +1,293 @@
+//! Tests that run the `typst` binary on small projects.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use tempfile::TempDir;
+
+/// Creates a temporary project directory containing the given files.
+fn project(files: &[(&str, &[u8])]) -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for (path, data) in files {
+        fs::write(dir.path().join(path), data).unwrap();
+    }
+    dir
+}
+
+/// Runs the CLI with the given arguments in a project directory.
+///
+/// System fonts are ignored such that the output only depends on the fonts
+/// embedded into the binary.
+fn typst(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_typst"))
+        .args(args)
+        .arg("--ignore-system-fonts")
+        .current_dir(dir)
+        .output()
+        .unwrap()
+}
+
+/// The standard output of a successful run.
+fn stdout(output: Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "typst failed: {stderr}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// The standard error of a failed run.
+fn stderr(output: Output) -> String {
+    assert!(!output.status.success(), "typst unexpectedly succeeded");
+    String::from_utf8(output.stderr).unwrap()
+}
+
+/// Decodes a PNG file into its header and pixel data.
+fn decode_png(path: &Path) -> (png::OutputInfo, Vec<u8>) {
+    let mut decoder = png::Decoder::new(fs::File::open(path).unwrap());
+    decoder.set_transformations(png::Transformations::EXPAND);
+    let mut reader = decoder.read_info().unwrap();
+    let mut data = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut data).unwrap();
+    data.truncate(info.buffer_size());
+    (info, data)
+}
+
+/// The first channel of a pixel in decoded PNG data.
+fn pixel(info: &png::OutputInfo, data: &[u8], x: usize, y: usize) -> u8 {
+    data[y * info.line_size + x * info.color_type.samples()]
+}
+
+const WORKS_BIB: &[u8] = br"@article{netwok,
+  title = {At-scale impact of the {Net Wok}},
+  author = {Astley, Rick and Morris, Linda},
+  journal = {Armenian Journal of Proceedings},
+  year = {2020},
+}
+
+@book{glacier,
+  title = {Glacier Melt},
+  author = {Regan, Cathy},
+  year = {2021},
+}
+
+@misc{unused,
+  title = {Never Cited},
+  year = {2019},
+}
+";
+
+const WORKS_YAML: &[u8] = b"netwok:
+  type: article
+  title: At-scale impact of the Net Wok
+  author: [Astley, Rick]
+  date: 2020
+";
+
+#[test]
+fn test_query_cited_biblatex() {
+    let dir = project(&[
+        ("main.typ", b"@glacier @netwok @glacier\n#bibliography(\"works.bib\")"),
+        ("works.bib", WORKS_BIB),
+    ]);
+
+    let out = stdout(typst(
+        dir.path(),
+        &["query", "main.typ", "bibliography", "--cited", "biblatex"],
+    ));
+
+    let glacier = out.find("@book{glacier,").unwrap();
+    let netwok = out.find("@article{netwok,").unwrap();
+    assert!(glacier < netwok);
+    assert_eq!(out.matches("@book{glacier,").count(), 1);
+    assert!(out.contains("journaltitle = {Armenian Journal of Proceedings}"));
+    assert!(!out.contains("unused"));
+}
+
+#[test]
+fn test_query_cited_hayagriva() {
+    let dir = project(&[
+        ("main.typ", b"@netwok\n#bibliography(\"works.bib\")"),
+        ("works.bib", WORKS_BIB),
+    ]);
+
+    let out = stdout(typst(
+        dir.path(),
+        &["query", "main.typ", "bibliography", "--cited", "hayagriva"],
+    ));
+
+    assert!(out.contains("netwok:"));
+    assert!(!out.contains("glacier"));
+    assert!(!out.contains("unused"));
+}
+
+#[test]
+fn test_query_cited_biblatex_from_hayagriva() {
+    let dir = project(&[
+        ("main.typ", b"@netwok\n#bibliography(\"works.yaml\")"),
+        ("works.yaml", WORKS_YAML),
+    ]);
+
+    let err = stderr(typst(
+        dir.path(),
+        &["query", "main.typ", "bibliography", "--cited", "biblatex"],
+    ));
+
+    assert!(err.contains("cannot export `netwok` as BibLaTeX"));
+    assert!(err.contains("the work comes from a Hayagriva file"));
+}
+
+#[test]
+fn test_query_cited_not_a_bibliography() {
+    let dir = project(&[("main.typ", b"= Heading")]);
+    let err = stderr(typst(
+        dir.path(),
+        &["query", "main.typ", "heading", "--cited", "biblatex"],
+    ));
+    assert!(err.contains("expected only bibliographies, found heading"));
+}
+
+const LAYERS: &[u8] = br#"#set page(width: 30pt, height: 10pt, margin: 0pt)
+#pdf.layer("A", place(square(size: 10pt, fill: black)))
+#pdf.layer("B", visible: false, place(dx: 20pt, square(size: 10pt, fill: black)))
+"#;
+
+#[test]
+fn test_compile_png_layers() {
+    let dir = project(&[("main.typ", LAYERS)]);
+    let shown = |args: &[&str]| {
+        let base = ["compile", "main.typ", "out.png", "--ppi", "72"];
+        stdout(typst(dir.path(), &[&base[..], args].concat()));
+        let (info, data) = decode_png(&dir.path().join("out.png"));
+        let dark = |x| pixel(&info, &data, x, 5) < 128;
+        (dark(5), dark(25))
+    };
+
+    assert_eq!(shown(&[]), (true, false));
+    assert_eq!(shown(&["--hide-layer", "A"]), (false, false));
+    assert_eq!(shown(&["--show-layer", "B"]), (true, true));
+    assert_eq!(shown(&["--show-layer", "A,B", "--hide-layer", "B"]), (true, false));
+}
+
+#[test]
+fn test_compile_png_embed_ppi() {
+    let dir = project(&[("main.typ", b"Hello")]);
+    let dims = |args: &[&str]| {
+        let base = ["compile", "main.typ", "out.png", "--ppi", "300"];
+        stdout(typst(dir.path(), &[&base[..], args].concat()));
+        let decoder =
+            png::Decoder::new(fs::File::open(dir.path().join("out.png")).unwrap());
+        let reader = decoder.read_info().unwrap();
+        reader.info().pixel_dims.map(|dims| (dims.xppu, dims.yppu, dims.unit))
+    };
+
+    // 300 pixels per inch are 11811 pixels per meter.
+    assert_eq!(dims(&[]), None);
+    assert_eq!(dims(&["--png-embed-ppi"]), Some((11811, 11811, png::Unit::Meter)));
+}
+
+/// Runs the diff command and returns its standard output and the parsed
+/// summary.
+fn diff(dir: &Path, old: &str, new: &str, changed: bool) -> (String, serde_json::Value) {
+    let output = typst(dir, &["diff", old, new, "--output", "diff"]);
+    let out = if changed {
+        assert!(!output.status.success(), "typst unexpectedly succeeded");
+        String::from_utf8(output.stdout).unwrap()
+    } else {
+        stdout(output)
+    };
+    let summary = fs::read(dir.join("diff/summary.json")).unwrap();
+    (out, serde_json::from_slice(&summary).unwrap())
+}
+
+const PAGE: &str = "#set page(width: 100pt, height: 100pt, margin: 0pt)\n";
+
+#[test]
+fn test_diff_typst() {
+    let dir = project(&[
+        ("old.typ", format!("{PAGE}= Intro\n#square(size: 10pt)").as_bytes()),
+        ("new.typ", format!("{PAGE}= Intro\n#square(size: 10pt, fill: red)").as_bytes()),
+    ]);
+
+    let (out, summary) = diff(dir.path(), "old.typ", "old.typ", false);
+    assert!(out.contains("no pages changed"));
+    assert_eq!(summary["changed_pages"], serde_json::json!([]));
+    assert!(!dir.path().join("diff/page-1.png").exists());
+
+    let (out, summary) = diff(dir.path(), "old.typ", "new.typ", true);
+    assert!(out.contains("page 1: changed"));
+    assert!(out.contains("  in Intro"));
+    assert!(out.contains("1 of 1 pages changed"));
+    assert_eq!(summary["changed_pages"], serde_json::json!([1]));
+    assert_eq!(summary["pages"][0]["status"], "changed");
+    assert_eq!(summary["pages"][0]["image"], "page-1.png");
+    assert!(dir.path().join("diff/page-1.png").exists());
+
+    // The image of the earlier run is removed.
+    diff(dir.path(), "old.typ", "old.typ", false);
+    assert!(!dir.path().join("diff/page-1.png").exists());
+}
+
+#[test]
+fn test_diff_png() {
+    let dir = project(&[
+        ("old.typ", format!("{PAGE}#square(size: 10pt)").as_bytes()),
+        ("new.typ", format!("{PAGE}#square(size: 10pt, fill: red)").as_bytes()),
+    ]);
+    for (name, ppi) in [("old", "72"), ("new", "72"), ("big", "144")] {
+        let file = if name == "big" { "old.typ" } else { &format!("{name}.typ") };
+        fs::create_dir(dir.path().join(name)).unwrap();
+        let output = format!("{name}/page-{{p}}.png");
+        stdout(typst(dir.path(), &["compile", file, &output, "--ppi", ppi]));
+    }
+
+    // Without a Typst file or PDF, the regions are in pixels.
+    let (_, summary) = diff(dir.path(), "old", "new", true);
+    assert_eq!(summary["pages"][0]["unit"], "px");
+    assert_eq!(summary["pages"][0]["regions"][0]["width"], 16.0);
+
+    // With one, they are in points.
+    let output =
+        typst(dir.path(), &["diff", "old.typ", "new", "--output", "diff", "--ppi", "72"]);
+    assert!(!output.status.success());
+    let summary: serde_json::Value =
+        serde_json::from_slice(&fs::read(dir.path().join("diff/summary.json")).unwrap())
+            .unwrap();
+    assert_eq!(summary["pages"][0]["unit"], "pt");
+
+    // Pages of different sizes aren't compared.
+    let (out, summary) = diff(dir.path(), "old", "big", true);
+    assert!(out.contains("page 1: size differs (100x100 pixels in old version, 200x200 pixels in new version)"));
+    assert_eq!(summary["pages"][0]["status"], "resized");
+    assert_eq!(summary["pages"][0]["sizes"], serde_json::json!([[100, 100], [200, 200]]));
+    assert!(!dir.path().join("diff/page-1.png").exists());
+
+    // The PNG files to compare are never removed.
+    let output = typst(dir.path(), &["diff", "old", "new", "--output", "new"]);
+    assert!(stderr(output).contains("must not contain the PNG files"));
+    assert!(dir.path().join("new/page-1.png").exists());
+}
+
+#[test]
+fn test_diff_page_count() {
+    let dir = project(&[
+        ("one.typ", format!("{PAGE}One").as_bytes()),
+        ("two.typ", format!("{PAGE}One\n#pagebreak()\nTwo").as_bytes()),
+    ]);
+
+    let (out, summary) = diff(dir.path(), "one.typ", "two.typ", true);
+    assert!(!out.contains("page 1"));
+    assert!(out.contains("page 2: added"));
+    assert!(out.contains("1 of 2 pages changed"));
+    assert_eq!(
+        (summary["old_pages"].as_u64(), summary["new_pages"].as_u64()),
+        (Some(1), Some(2))
+    );
+    assert_eq!(summary["pages"][0]["status"], "unchanged");
+    assert_eq!(summary["pages"][1]["status"], "added");
+
+    let (out, summary) = diff(dir.path(), "two.typ", "one.typ", true);
+    assert!(out.contains("page 2: removed"));
+    assert_eq!(summary["changed_pages"], serde_json::json!([2]));
+    assert_eq!(summary["pages"][1]["status"], "removed");
+    assert_eq!(summary["pages"][1]["changed_ratio"], 1.0);
+}
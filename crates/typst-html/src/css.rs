@@ -60,7 +60,11 @@ pub fn length(length: Length) -> impl Display {
 
 pub fn color(color: Color) -> impl Display {
     typst_utils::display(move |f| match color {
-        Color::Rgb(_) | Color::Cmyk(_) | Color::Luma(_) => rgb(f, color.to_rgb()),
+        Color::Rgb(_)
+        | Color::Cmyk(_)
+        | Color::Luma(_)
+        | Color::Spot(_)
+        | Color::Icc(_) => rgb(f, color.to_rgb()),
         Color::Oklab(v) => oklab(f, v),
         Color::Oklch(v) => oklch(f, v),
         Color::LinearRgb(v) => linear_rgb(f, v),
@@ -4,6 +4,7 @@ use ecow::{EcoString, eco_format};
 use typst_library::diag::{At, SourceResult, StrResult, bail};
 use typst_library::foundations::Repr;
 use typst_library::introspection::Introspector;
+use typst_svg::SvgOptions;
 use typst_syntax::Span;
 
 use crate::{
@@ -344,6 +345,7 @@ fn write_frame(w: &mut Writer, frame: &HtmlFrame) {
         frame.id.as_deref(),
         &frame.link_points,
         w.introspector,
+        &SvgOptions::default(),
     );
     w.buf.push_str(&svg);
 }
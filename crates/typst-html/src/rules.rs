@@ -18,13 +18,14 @@ use typst_library::layout::{
 use typst_library::model::{
     Attribution, BibliographyElem, CiteElem, CiteGroup, CslIndentElem, CslLightElem,
     Destination, DirectLinkElem, EmphElem, EnumElem, FigureCaption, FigureElem,
-    FootnoteElem, FootnoteEntry, FootnoteMarker, HeadingElem, LinkElem, LinkTarget,
-    ListElem, OutlineElem, OutlineEntry, OutlineNode, ParElem, ParbreakElem, QuoteElem,
-    RefElem, StrongElem, TableCell, TableElem, TermsElem, TitleElem, Works,
+    FootnoteElem, FootnoteEntry, FootnoteMarker, HeadingElem, IndexElem, IndexTerm,
+    LinkElem, LinkTarget, ListElem, OutlineElem, OutlineEntry, OutlineNode, ParElem,
+    ParbreakElem, QuoteElem, RefElem, StrongElem, TableCell, TableElem, TermsElem,
+    TitleElem, Works,
 };
 use typst_library::text::{
     HighlightElem, LinebreakElem, OverlineElem, RawElem, RawLine, SmallcapsElem,
-    SpaceElem, StrikeElem, SubElem, SuperElem, UnderlineElem,
+    SpaceElem, StrikeElem, SubElem, SuperElem, TextElem, UnderlineElem,
 };
 use typst_library::visualize::{Color, ImageElem};
 use typst_macros::elem;
@@ -57,6 +58,7 @@ pub fn register(rules: &mut NativeRuleMap) {
     rules.register(Html, FOOTNOTE_ENTRY_RULE);
     rules.register(Html, OUTLINE_RULE);
     rules.register(Html, OUTLINE_ENTRY_RULE);
+    rules.register(Html, INDEX_RULE);
     rules.register(Html, REF_RULE);
     rules.register(Html, CITE_GROUP_RULE);
     rules.register(Html, BIBLIOGRAPHY_RULE);
@@ -512,6 +514,59 @@ const OUTLINE_ENTRY_RULE: ShowFn<OutlineEntry> = |elem, engine, styles| {
     Ok(LinkElem::new(dest.into(), realized).pack())
 };
 
+// Since there are no pages in HTML, the index links to the individual
+// occurrences of a term, numbered in document order.
+const INDEX_RULE: ShowFn<IndexElem> = |elem, engine, styles| {
+    fn convert_list(terms: &[IndexTerm], styles: StyleChain) -> Content {
+        HtmlElem::new(tag::ul)
+            .with_styles(css::Properties::new().with("list-style-type", "none"))
+            .with_body(Some(Content::sequence(
+                terms.iter().map(|term| convert_term(term, styles)),
+            )))
+            .pack()
+    }
+
+    fn convert_term(term: &IndexTerm, styles: StyleChain) -> Content {
+        let mut seq = vec![TextElem::packed(term.term.clone())];
+        for (i, &loc) in term.locations.iter().enumerate() {
+            seq.push(TextElem::packed(", "));
+            seq.push(
+                LinkElem::new(
+                    Destination::Location(loc).into(),
+                    TextElem::packed(eco_format!("{}", i + 1)),
+                )
+                .pack(),
+            );
+        }
+        if let Some(see_also) = term.see_also(styles) {
+            seq.push(TextElem::packed(if term.locations.is_empty() {
+                ", "
+            } else {
+                "; "
+            }));
+            seq.push(see_also);
+        }
+
+        let mut body = Content::sequence(seq);
+        if !term.children.is_empty() {
+            // See the outline rule for why the `<div>` is needed.
+            body = HtmlElem::new(tag::div).with_body(Some(body)).pack()
+                + convert_list(&term.children, styles);
+        }
+        HtmlElem::new(tag::li).with_body(Some(body)).pack()
+    }
+
+    let title = elem.realize_title(styles);
+    let terms = elem.realize_terms(engine, styles);
+    let list = convert_list(&terms, styles);
+
+    Ok(HtmlElem::new(tag::section)
+        .with_attr(attr::role, "doc-index")
+        .with_body(Some(title.unwrap_or_default() + list))
+        .pack()
+        .spanned(elem.span()))
+};
+
 const REF_RULE: ShowFn<RefElem> = |elem, engine, styles| elem.realize(engine, styles);
 
 const CITE_GROUP_RULE: ShowFn<CiteGroup> = |elem, engine, _| {
@@ -565,7 +620,10 @@ const BIBLIOGRAPHY_RULE: ShowFn<BibliographyElem> = |elem, engine, styles| {
 
     Ok(HtmlElem::new(tag::section)
         .with_attr(attr::role, "doc-bibliography")
-        .with_optional_attr(attr::class, works.hanging_indent.then_some("hanging-indent"))
+        .with_optional_attr(
+            attr::class,
+            works.hanging_indent(elem).then_some("hanging-indent"),
+        )
         .with_body(Some(title.unwrap_or_default() + list))
         .pack())
 };
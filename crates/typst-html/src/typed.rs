@@ -571,6 +571,7 @@ impl IntoAttr for Datetime {
             Self::Date(date) => datetime::date(f, date),
             Self::Time(time) => datetime::time(f, time),
             Self::Datetime(datetime) => datetime::datetime(f, datetime),
+            Self::Offset(datetime) => datetime::offset(f, datetime),
         });
         eco_format!("{fmt}")
     }
@@ -586,6 +587,20 @@ mod datetime {
         time(f, datetime.time())
     }
 
+    pub fn offset(f: &mut Formatter, datetime: time::OffsetDateTime) -> fmt::Result {
+        // https://html.spec.whatwg.org/#valid-global-date-and-time-string
+        date(f, datetime.date())?;
+        f.write_char('T')?;
+        time(f, datetime.time())?;
+        let offset = datetime.offset();
+        if offset.is_utc() {
+            return f.write_char('Z');
+        }
+        let sign = if offset.is_negative() { '-' } else { '+' };
+        let (hours, minutes, _) = offset.as_hms();
+        write!(f, "{sign}{:02}:{:02}", hours.unsigned_abs(), minutes.unsigned_abs())
+    }
+
     pub fn date(f: &mut Formatter, date: time::Date) -> fmt::Result {
         // https://html.spec.whatwg.org/#valid-date-string
         write!(f, "{:04}-{:02}-{:02}", date.year(), date.month() as u8, date.day())
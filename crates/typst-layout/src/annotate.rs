@@ -0,0 +1,59 @@
+use typst_library::diag::SourceResult;
+use typst_library::engine::Engine;
+use typst_library::foundations::{Packed, StyleChain};
+use typst_library::introspection::Locator;
+use typst_library::layout::{Abs, Frame, FrameItem, Point, Region, Size};
+use typst_library::pdf::{Annotation, AnnotationKind, FreeTextElem, NoteElem};
+use typst_library::text::TextElem;
+
+/// The size of the area that PDF viewers display a note's icon in, in points.
+const NOTE_ICON_SIZE: f64 = 20.0;
+
+/// Layout the icon of a sticky note.
+///
+/// The note doesn't take up any space. Its icon sits on top of the baseline.
+pub fn layout_note(
+    elem: &Packed<NoteElem>,
+    _: &mut Engine,
+    _: Locator,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<Frame> {
+    let annotation = Annotation {
+        kind: AnnotationKind::Note {
+            color: elem.color.get_cloned(styles),
+            open: elem.open.get(styles),
+        },
+        text: elem.text.clone(),
+        author: elem.author.get_cloned(styles),
+        date: elem.date.get(styles),
+        text_size: styles.resolve(TextElem::size),
+        span: elem.span(),
+    };
+    let size = Abs::pt(NOTE_ICON_SIZE);
+    let mut frame = Frame::soft(region.size);
+    frame
+        .push(Point::with_y(-size), FrameItem::Annotation(annotation, Size::splat(size)));
+    Ok(frame)
+}
+
+/// Layout the text box of a free text annotation.
+pub fn layout_free_text(
+    elem: &Packed<FreeTextElem>,
+    _: &mut Engine,
+    _: Locator,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<Frame> {
+    let annotation = Annotation {
+        kind: AnnotationKind::FreeText,
+        text: elem.text.clone(),
+        author: elem.author.get_cloned(styles),
+        date: elem.date.get(styles),
+        text_size: styles.resolve(TextElem::size),
+        span: elem.span(),
+    };
+    let mut frame = Frame::soft(region.size);
+    frame.push(Point::zero(), FrameItem::Annotation(annotation, region.size));
+    Ok(frame)
+}
@@ -111,13 +111,47 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
             return self.column(locator, regions);
         }
 
+        // Only keep a checkpoint around if we may need to balance.
+        let checkpoint = self.config.columns.balance.then(|| self.work.clone());
+        let output = self.columns(locator.relayout(), regions, None)?;
+
+        // Balancing only applies to the final region, i.e. when the columns
+        // consumed all remaining work.
+        match checkpoint {
+            Some(checkpoint) if self.work.done() => {
+                self.balance(locator, regions, checkpoint, output)
+            }
+            _ => Ok(output),
+        }
+    }
+
+    /// Lay out the columns of a container/page and stitch them together.
+    ///
+    /// If a `height` is given, the columns in this region are restricted to
+    /// it. This is used for balancing.
+    fn columns(
+        &mut self,
+        locator: Locator,
+        regions: Regions,
+        height: Option<Abs>,
+    ) -> FlowResult<Frame> {
         // Create a backlog for multi-column layout.
-        let column_height = regions.size.y;
-        let backlog: Vec<_> = std::iter::once(&column_height)
-            .chain(regions.backlog)
-            .flat_map(|&h| std::iter::repeat_n(h, self.config.columns.count))
-            .skip(1)
-            .collect();
+        let column_height = height.unwrap_or(regions.size.y);
+        let backlog: Vec<_> = match height {
+            // When balancing, the backlog ends with a full-height region
+            // that is never laid out. It only keeps the last column from
+            // being the last region, so content that doesn't fit is left in
+            // the work instead of overflowing, which signals that the height
+            // was too small.
+            Some(height) => std::iter::repeat_n(height, self.config.columns.count - 1)
+                .chain(std::iter::once(regions.size.y))
+                .collect(),
+            None => std::iter::once(&column_height)
+                .chain(regions.backlog)
+                .flat_map(|&h| std::iter::repeat_n(h, self.config.columns.count))
+                .skip(1)
+                .collect(),
+        };
 
         // Subregions for column layout.
         let mut inner = Regions {
@@ -161,6 +195,55 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         Ok(output)
     }
 
+    /// Balance the columns of the final region.
+    ///
+    /// Searches for the smallest column height with which all remaining work
+    /// still fits into this region's columns and lays them out with it. If no
+    /// such height is found, the unbalanced `output` is kept.
+    fn balance(
+        &mut self,
+        locator: Locator,
+        regions: Regions,
+        checkpoint: Work<'a, 'b>,
+        output: Frame,
+    ) -> FlowResult<Frame> {
+        // The height of the unbalanced columns is an upper bound for the
+        // balanced height.
+        let mut hi = output.height();
+        let mut lo = Abs::zero();
+        if !hi.is_finite() {
+            return Ok(output);
+        }
+
+        // The precision with which we determine the balanced height.
+        let tolerance = Abs::pt(1.0);
+        let unbalanced = self.work.clone();
+        let mut best = None;
+
+        while hi - lo > tolerance {
+            let mid = (lo + hi) / 2.0;
+            *self.work = checkpoint.clone();
+            let frame = self.columns(locator.relayout(), regions, Some(mid))?;
+            if self.work.done() {
+                hi = mid;
+                best = Some((frame, self.work.clone()));
+            } else {
+                lo = mid;
+            }
+        }
+
+        Ok(match best {
+            Some((frame, work)) => {
+                *self.work = work;
+                frame
+            }
+            None => {
+                *self.work = unbalanced;
+                output
+            }
+        })
+    }
+
     /// Lay out a column, including column insertions.
     fn column(&mut self, locator: Locator, regions: Regions) -> FlowResult<Frame> {
         // Reset column insertion when starting a new column.
@@ -71,8 +71,7 @@ pub fn layout_fragment(
         locator.track(),
         styles,
         regions,
-        NonZeroUsize::ONE,
-        Rel::zero(),
+        (NonZeroUsize::ONE, Rel::zero(), false),
     )
 }
 
@@ -99,12 +98,14 @@ pub fn layout_columns(
         locator.track(),
         styles,
         regions,
-        elem.count.get(styles),
-        elem.gutter.resolve(styles),
+        (elem.count.get(styles), elem.gutter.resolve(styles), elem.balance.get(styles)),
     )
 }
 
 /// The cached, internal implementation of [`layout_fragment`].
+///
+/// The column count, gutter, and whether to balance are grouped into one
+/// argument as memoized functions can take at most twelve.
 #[comemo::memoize]
 #[allow(clippy::too_many_arguments)]
 fn layout_fragment_impl(
@@ -118,9 +119,9 @@ fn layout_fragment_impl(
     locator: Tracked<Locator>,
     styles: StyleChain,
     regions: Regions,
-    columns: NonZeroUsize,
-    column_gutter: Rel<Abs>,
+    columns: (NonZeroUsize, Rel<Abs>, bool),
 ) -> SourceResult<Fragment> {
+    let (columns, column_gutter, balance) = columns;
     if !regions.size.x.is_finite() && regions.expand.x {
         bail!(content.span(), "cannot expand into infinite width");
     }
@@ -161,6 +162,7 @@ fn layout_fragment_impl(
         regions,
         columns,
         column_gutter,
+        balance,
         kind.into(),
     )
 }
@@ -196,10 +198,11 @@ pub fn layout_flow<'a>(
     mut regions: Regions,
     columns: NonZeroUsize,
     column_gutter: Rel<Abs>,
+    balance: bool,
     mode: FlowMode,
 ) -> SourceResult<Fragment> {
     // Prepare configuration that is shared across the whole flow.
-    let config = configuration(shared, regions, columns, column_gutter, mode);
+    let config = configuration(shared, regions, columns, column_gutter, balance, mode);
 
     // Collect the elements into pre-processed children. These are much easier
     // to handle than the raw elements.
@@ -240,6 +243,7 @@ fn configuration<'x>(
     regions: Regions,
     columns: NonZeroUsize,
     column_gutter: Rel<Abs>,
+    balance: bool,
     mode: FlowMode,
 ) -> Config<'x> {
     Config {
@@ -254,7 +258,7 @@ fn configuration<'x>(
             let gutter = column_gutter.relative_to(regions.base().x);
             let width = (regions.size.x - gutter * (count - 1) as f64) / count as f64;
             let dir = shared.resolve(TextElem::dir);
-            ColumnConfig { count, width, gutter, dir }
+            ColumnConfig { count, width, gutter, dir, balance }
         },
         footnote: FootnoteConfig {
             separator: shared
@@ -391,6 +395,8 @@ struct ColumnConfig {
     /// The horizontal direction in which columns progress. Defined by
     /// `text.dir`.
     dir: Dir,
+    /// Whether the columns in the final region should be balanced.
+    balance: bool,
 }
 
 /// Configuration of line numbers.
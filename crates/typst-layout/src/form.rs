@@ -0,0 +1,107 @@
+use ecow::EcoString;
+use typst_library::diag::{SourceResult, bail};
+use typst_library::engine::Engine;
+use typst_library::foundations::{Packed, StyleChain};
+use typst_library::introspection::Locator;
+use typst_library::layout::{Frame, FrameItem, Point, Region};
+use typst_library::pdf::{
+    ButtonElem, CheckboxElem, DropdownElem, RadioElem, TextFieldElem, Widget, WidgetKind,
+};
+use typst_library::text::TextElem;
+use typst_syntax::Span;
+
+/// Layout the widget of a text field.
+pub fn layout_text_field(
+    elem: &Packed<TextFieldElem>,
+    _: &mut Engine,
+    _: Locator,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<Frame> {
+    let kind = WidgetKind::Text {
+        value: elem.value.get_cloned(styles),
+        multiline: elem.multiline.get(styles),
+    };
+    Ok(layout_widget(elem.name.clone(), kind, elem.span(), styles, region))
+}
+
+/// Layout the widget of a checkbox.
+pub fn layout_checkbox(
+    elem: &Packed<CheckboxElem>,
+    _: &mut Engine,
+    _: Locator,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<Frame> {
+    let kind = WidgetKind::Checkbox { checked: elem.checked.get(styles) };
+    Ok(layout_widget(elem.name.clone(), kind, elem.span(), styles, region))
+}
+
+/// Layout the widget of a radio button.
+pub fn layout_radio(
+    elem: &Packed<RadioElem>,
+    _: &mut Engine,
+    _: Locator,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<Frame> {
+    let kind = WidgetKind::Radio {
+        value: elem.value.clone(),
+        checked: elem.checked.get(styles),
+    };
+    Ok(layout_widget(elem.name.clone(), kind, elem.span(), styles, region))
+}
+
+/// Layout the widget of a drop-down list.
+pub fn layout_dropdown(
+    elem: &Packed<DropdownElem>,
+    _: &mut Engine,
+    _: Locator,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<Frame> {
+    let options = elem.options.clone();
+    let value = elem.value.get_cloned(styles);
+    if let Some(value) = &value
+        && !options.contains(value)
+    {
+        bail!(elem.span(), "the selected value must be one of the options");
+    }
+
+    let kind = WidgetKind::Dropdown { options, value };
+    Ok(layout_widget(elem.name.clone(), kind, elem.span(), styles, region))
+}
+
+/// Layout the widget of a push button.
+pub fn layout_button(
+    elem: &Packed<ButtonElem>,
+    _: &mut Engine,
+    _: Locator,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<Frame> {
+    let kind = WidgetKind::Button {
+        caption: elem.caption.clone(),
+        submit: elem.submit.get_cloned(styles),
+    };
+    Ok(layout_widget(elem.name.clone(), kind, elem.span(), styles, region))
+}
+
+/// Produce a frame that fills the region with a widget.
+fn layout_widget(
+    name: EcoString,
+    kind: WidgetKind,
+    span: Span,
+    styles: StyleChain,
+    region: Region,
+) -> Frame {
+    let widget = Widget {
+        name,
+        kind,
+        text_size: styles.resolve(TextElem::size),
+        span,
+    };
+    let mut frame = Frame::soft(region.size);
+    frame.push(Point::zero(), FrameItem::Widget(widget, region.size));
+    frame
+}
@@ -1,6 +1,8 @@
 //! Typst's layout engine.
 
+mod annotate;
 mod flow;
+mod form;
 mod grid;
 mod image;
 mod inline;
@@ -1,6 +1,7 @@
 use typst_library::foundations::StyleChain;
 use typst_library::layout::{Abs, Fragment, Frame, FrameItem, HideElem, Point, Sides};
 use typst_library::model::{Destination, LinkElem, ParElem};
+use typst_library::pdf::{Annotation, Layer, LayerElem, MarkElem};
 
 /// Frame-level modifications resulting from styles that do not impose any
 /// layout structure.
@@ -17,10 +18,16 @@ use typst_library::model::{Destination, LinkElem, ParElem};
 /// Currently existing frame modifiers are:
 /// - `HideElem::hidden`
 /// - `LinkElem::dests`
+/// - `MarkElem::current`
+/// - `LayerElem::current`
 #[derive(Debug, Clone)]
 pub struct FrameModifiers {
     /// A destination to link to.
     dest: Option<Destination>,
+    /// An annotation that marks up the frame.
+    annotation: Option<Annotation>,
+    /// A PDF layer the frame belongs to.
+    layer: Option<Layer>,
     /// Whether the contents of the frame should be hidden.
     hidden: bool,
 }
@@ -30,6 +37,8 @@ impl FrameModifiers {
     pub fn get_in(styles: StyleChain) -> Self {
         Self {
             dest: styles.get_cloned(LinkElem::current),
+            annotation: styles.get_cloned(MarkElem::current),
+            layer: styles.get_cloned(LayerElem::current),
             hidden: styles.get(HideElem::hidden),
         }
     }
@@ -94,15 +103,25 @@ fn modify_frame(
     modifiers: &FrameModifiers,
     link_box_outset: Option<Sides<Abs>>,
 ) {
+    let mut pos = Point::zero();
+    let mut size = frame.size();
+    if let Some(outset) = link_box_outset {
+        pos.y -= outset.top;
+        pos.x -= outset.left;
+        size += outset.sum_by_axis();
+    }
+
     if let Some(dest) = &modifiers.dest {
-        let mut pos = Point::zero();
-        let mut size = frame.size();
-        if let Some(outset) = link_box_outset {
-            pos.y -= outset.top;
-            pos.x -= outset.left;
-            size += outset.sum_by_axis();
-        }
         frame.push(pos, FrameItem::Link(dest.clone(), size));
+    } else if let Some(annotation) = &modifiers.annotation {
+        // Links take precedence since, in PDF, a piece of content can only
+        // belong to a single annotation. The show rules of links and marks
+        // warn when they are nested.
+        frame.push(pos, FrameItem::Annotation(annotation.clone(), size));
+    }
+
+    if let Some(layer) = &modifiers.layer {
+        frame.assign_layer(layer.clone());
     }
 
     if modifiers.hidden {
@@ -123,14 +142,14 @@ where
 {
     let modifiers = FrameModifiers::get_in(styles);
 
-    // Disable the current link internally since it's already applied at this
-    // level of layout. This means we don't generate redundant nested links,
-    // which may bloat the output considerably.
+    // Disable the current link and annotation internally since they're already
+    // applied at this level of layout. This means we don't generate redundant
+    // nested links, which may bloat the output considerably.
     let reset;
     let outer = styles;
     let mut styles = styles;
-    if modifiers.dest.is_some() {
-        reset = LinkElem::current.set(None).wrap();
+    if modifiers.dest.is_some() || modifiers.annotation.is_some() {
+        reset = [LinkElem::current.set(None).wrap(), MarkElem::current.set(None).wrap()];
         styles = outer.chain(&reset);
     }
 
@@ -1,9 +1,13 @@
 use typst_library::diag::SourceResult;
 use typst_library::engine::Engine;
+use typst_library::foundations::Smart;
 use typst_library::introspection::{ManualPageCounter, Tag};
-use typst_library::layout::{Frame, FrameItem, Page, Point};
+use typst_library::layout::{Frame, FrameItem, Page, Point, Rect};
+use typst_library::visualize::Geometry;
+use typst_syntax::Span;
 
 use super::LayoutedPage;
+use super::marks::{draw_marks, slug};
 
 /// Piece together the inner page frame and the marginals. We can only do this
 /// at the very end because inside/outside margins require knowledge of the
@@ -21,7 +25,9 @@ pub fn finalize(
         footer,
         background,
         foreground,
-        fill,
+        bleed,
+        marks,
+        mut fill,
         numbering,
         supplement,
     }: LayoutedPage,
@@ -33,34 +39,62 @@ pub fn finalize(
         std::mem::swap(&mut margin.left, &mut margin.right);
     }
 
+    // The area hosting the printer's marks surrounds the bleed, which in turn
+    // surrounds the trimmed page.
+    let slug = slug(marks);
+    let trim_size = inner.size() + margin.sum_by_axis();
+    let bleed_size = trim_size + bleed.sum_by_axis();
+    let origin = Point::new(slug.left + bleed.left, slug.top + bleed.top);
+    let bleed_origin = Point::new(slug.left, slug.top);
+
     // Create a frame for the full page.
-    let mut frame = Frame::hard(inner.size() + margin.sum_by_axis());
+    let mut frame = Frame::hard(bleed_size + slug.sum_by_axis());
 
     // Add tags.
     for tag in tags.drain(..) {
         frame.push(Point::zero(), FrameItem::Tag(tag));
     }
 
+    // If there are printer's marks, the fill may not cover them. Thus, we paint
+    // it ourselves and only up to the bleed.
+    if marks.any()
+        && let Smart::Custom(Some(paint)) = &fill
+    {
+        let shape = Geometry::Rect(bleed_size).filled(paint.clone());
+        frame.push(bleed_origin, FrameItem::Shape(shape, Span::detached()));
+        fill = Smart::Auto;
+    }
+
     // Add the "before" marginals. The order in which we push things here is
     // important as it affects the relative ordering of introspectable elements
     // and thus how counters resolve.
     if let Some(background) = background {
-        frame.push_frame(Point::zero(), background);
+        frame.push_frame(bleed_origin, background);
     }
     if let Some(header) = header {
-        frame.push_frame(Point::with_x(margin.left), header);
+        frame.push_frame(origin + Point::with_x(margin.left), header);
     }
 
     // Add the inner contents.
-    frame.push_frame(Point::new(margin.left, margin.top), inner);
+    frame.push_frame(origin + Point::new(margin.left, margin.top), inner);
 
     // Add the "after" marginals.
     if let Some(footer) = footer {
-        let y = frame.height() - footer.height();
-        frame.push_frame(Point::new(margin.left, y), footer);
+        let y = trim_size.y - footer.height();
+        frame.push_frame(origin + Point::new(margin.left, y), footer);
     }
     if let Some(foreground) = foreground {
-        frame.push_frame(Point::zero(), foreground);
+        frame.push_frame(bleed_origin, foreground);
+    }
+
+    // Add the printer's marks.
+    if marks.any() {
+        draw_marks(
+            &mut frame,
+            marks,
+            Rect::from_pos_size(bleed_origin, bleed_size),
+            Rect::from_pos_size(origin, trim_size),
+        );
     }
 
     // Apply counter updates from within the page to the manual page counter.
@@ -70,5 +104,13 @@ pub fn finalize(
     let number = counter.logical();
     counter.step();
 
-    Ok(Page { frame, fill, numbering, supplement, number })
+    Ok(Page {
+        frame,
+        fill,
+        numbering,
+        supplement,
+        number,
+        bleed,
+        slug,
+    })
 }
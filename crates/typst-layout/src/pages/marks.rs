@@ -0,0 +1,121 @@
+use typst_library::layout::{
+    Abs, Frame, FrameItem, Point, PrintMarks, Rect, Sides, Size,
+};
+use typst_library::visualize::{Cmyk, Color, Curve, FixedStroke, Geometry, Paint};
+use typst_syntax::Span;
+
+/// The distance between the bleed and the printer's marks.
+const OFFSET: f64 = 3.0;
+
+/// The length of crop marks and the size of the area hosting the marks.
+const LENGTH: f64 = 18.0;
+
+/// The thickness of the strokes used for printer's marks.
+const THICKNESS: f64 = 0.25;
+
+/// The space that printer's marks need around the page's bleed.
+pub fn slug(marks: PrintMarks) -> Sides<Abs> {
+    if marks.any() {
+        Sides::splat(Abs::pt(OFFSET + LENGTH))
+    } else {
+        Sides::splat(Abs::zero())
+    }
+}
+
+/// Draw the enabled printer's marks into the area around the `bleed` box of
+/// a page `frame`. The `trim` box is the area of the final page.
+pub fn draw_marks(frame: &mut Frame, marks: PrintMarks, bleed: Rect, trim: Rect) {
+    let offset = Abs::pt(OFFSET);
+    let length = Abs::pt(LENGTH);
+    let size = frame.size();
+    let stroke = FixedStroke::from_pair(registration(), Abs::pt(THICKNESS));
+    let line = |frame: &mut Frame, pos: Point, delta: Point| {
+        let shape = Geometry::Line(delta).stroked(stroke.clone());
+        frame.push(pos, FrameItem::Shape(shape, Span::detached()));
+    };
+
+    if marks.crop {
+        for x in [trim.min.x, trim.max.x] {
+            line(frame, Point::with_x(x), Point::with_y(length));
+            line(frame, Point::new(x, bleed.max.y + offset), Point::with_y(length));
+        }
+        for y in [trim.min.y, trim.max.y] {
+            line(frame, Point::with_y(y), Point::with_x(length));
+            line(frame, Point::new(bleed.max.x + offset, y), Point::with_x(length));
+        }
+    }
+
+    let center =
+        Point::new((trim.min.x + trim.max.x) / 2.0, (trim.min.y + trim.max.y) / 2.0);
+
+    if marks.registration {
+        let half = length / 2.0;
+        for pos in [
+            Point::new(center.x, half),
+            Point::new(center.x, size.y - half),
+            Point::new(half, center.y),
+            Point::new(size.x - half, center.y),
+        ] {
+            draw_registration_mark(frame, pos, &stroke);
+        }
+    }
+
+    if marks.color_bar {
+        // The swatches are placed in the area below the page, starting after
+        // the crop marks and ending before the registration mark.
+        let swatch = length / 2.0;
+        let end =
+            if marks.registration { center.x - length } else { trim.max.x - length };
+        let y = bleed.max.y + offset + (length - swatch) / 2.0;
+        let mut x = trim.min.x + length;
+        for color in color_bar() {
+            if x + swatch > end {
+                break;
+            }
+            let shape = Geometry::Rect(Size::splat(swatch)).filled(color);
+            frame.push(Point::new(x, y), FrameItem::Shape(shape, Span::detached()));
+            x += swatch;
+        }
+    }
+}
+
+/// Draw a registration mark (a circle with a crosshair) centered at `pos`.
+fn draw_registration_mark(frame: &mut Frame, pos: Point, stroke: &FixedStroke) {
+    let radius = Abs::pt(LENGTH) * 0.3;
+    let circle = Geometry::Curve(Curve::ellipse(Size::splat(2.0 * radius)))
+        .stroked(stroke.clone());
+    frame.push(pos - Point::splat(radius), FrameItem::Shape(circle, Span::detached()));
+
+    let arm = radius * 1.5;
+    for (start, delta) in [
+        (Point::with_x(-arm), Point::with_x(2.0 * arm)),
+        (Point::with_y(-arm), Point::with_y(2.0 * arm)),
+    ] {
+        let shape = Geometry::Line(delta).stroked(stroke.clone());
+        frame.push(pos + start, FrameItem::Shape(shape, Span::detached()));
+    }
+}
+
+/// The registration colour, which prints on all process colour plates.
+fn registration() -> Paint {
+    Color::Cmyk(Cmyk::new(1.0, 1.0, 1.0, 1.0)).into()
+}
+
+/// The swatches of the colour bar: The process colours, their overprints, and
+/// tints of black.
+fn color_bar() -> impl Iterator<Item = Color> {
+    [
+        (1.0, 0.0, 0.0, 0.0),
+        (0.0, 1.0, 0.0, 0.0),
+        (0.0, 0.0, 1.0, 0.0),
+        (0.0, 0.0, 0.0, 1.0),
+        (1.0, 1.0, 0.0, 0.0),
+        (1.0, 0.0, 1.0, 0.0),
+        (0.0, 1.0, 1.0, 0.0),
+        (0.0, 0.0, 0.0, 0.75),
+        (0.0, 0.0, 0.0, 0.5),
+        (0.0, 0.0, 0.0, 0.25),
+    ]
+    .into_iter()
+    .map(|(c, m, y, k)| Color::Cmyk(Cmyk::new(c, m, y, k)))
+}
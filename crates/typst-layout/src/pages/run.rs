@@ -10,8 +10,8 @@ use typst_library::introspection::{
 };
 use typst_library::layout::{
     Abs, AlignElem, Alignment, Axes, Binding, ColumnsElem, Dir, Frame, HAlignment,
-    Length, OuterVAlignment, PageElem, Paper, Region, Regions, Rel, Sides, Size,
-    VAlignment,
+    Length, OuterVAlignment, PageElem, Paper, PrintMarks, Region, Regions, Rel, Sides,
+    Size, VAlignment,
 };
 use typst_library::model::Numbering;
 use typst_library::pdf::ArtifactKind;
@@ -35,6 +35,8 @@ pub struct LayoutedPage {
     pub footer: Option<Frame>,
     pub background: Option<Frame>,
     pub foreground: Option<Frame>,
+    pub bleed: Sides<Abs>,
+    pub marks: PrintMarks,
     pub fill: Smart<Option<Paint>>,
     pub numbering: Option<Numbering>,
     pub supplement: Content,
@@ -125,6 +127,8 @@ fn layout_page_run_impl(
         .resolve(styles)
         .relative_to(size);
 
+    let bleed = styles.resolve(PageElem::bleed).unwrap_or_default();
+    let marks = styles.get(PageElem::marks);
     let fill = styles.get_cloned(PageElem::fill);
     let foreground = styles.get_ref(PageElem::foreground);
     let background = styles.get_ref(PageElem::background);
@@ -185,6 +189,7 @@ fn layout_page_run_impl(
         Regions::repeat(area, area.map(Abs::is_finite)),
         styles.get(PageElem::columns),
         styles.get(ColumnsElem::gutter).resolve(styles),
+        styles.get(PageElem::balance),
         FlowMode::Root,
     )?;
 
@@ -212,7 +217,7 @@ fn layout_page_run_impl(
     for inner in fragment {
         let header_size = Size::new(inner.width(), margin.top - header_ascent);
         let footer_size = Size::new(inner.width(), margin.bottom - footer_descent);
-        let full_size = inner.size() + margin.sum_by_axis();
+        let full_size = inner.size() + margin.sum_by_axis() + bleed.sum_by_axis();
         let mid = HAlignment::Center + VAlignment::Horizon;
         layouted.push(LayoutedPage {
             inner,
@@ -226,6 +231,8 @@ fn layout_page_run_impl(
             margin,
             binding,
             two_sided,
+            bleed,
+            marks,
         });
     }
 
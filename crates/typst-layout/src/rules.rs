@@ -1,28 +1,34 @@
 use comemo::Track;
-use ecow::{EcoVec, eco_format};
+use ecow::{EcoString, EcoVec, eco_format};
 use smallvec::smallvec;
-use typst_library::diag::{At, SourceResult, bail};
+use typst_library::diag::{At, SourceResult, bail, warning};
+use typst_library::engine::Engine;
 use typst_library::foundations::{
     Content, Context, NativeElement, NativeRuleMap, Packed, Resolve, ShowFn, Smart,
     StyleChain, Synthesize, Target, dict,
 };
 use typst_library::introspection::{Counter, Locator, LocatorLink};
 use typst_library::layout::{
-    Abs, AlignElem, Alignment, Axes, BlockBody, BlockElem, ColumnsElem, Em,
-    FixedAlignment, GridCell, GridChild, GridElem, GridItem, HAlignment, HElem, HideElem,
-    InlineElem, LayoutElem, Length, MoveElem, OuterVAlignment, PadElem, PageElem,
-    PlaceElem, PlacementScope, Region, Rel, RepeatElem, RotateElem, ScaleElem, Sides,
-    Size, Sizing, SkewElem, Spacing, StackChild, StackElem, TrackSizings, VElem,
+    Abs, AlignElem, Alignment, Axes, BlockBody, BlockElem, BoxElem, ColumnsElem, Corners,
+    Em, FixedAlignment, GridCell, GridChild, GridElem, GridItem, HAlignment, HElem,
+    HideElem, InlineElem, LayoutElem, Length, MoveElem, OuterVAlignment, PadElem,
+    PageElem, PlaceElem, PlacementScope, Ratio, Region, Rel, RepeatElem, RotateElem,
+    ScaleElem, Sides, Size, Sizing, SkewElem, Spacing, StackChild, StackElem,
+    TrackSizings, VElem,
 };
 use typst_library::math::EquationElem;
 use typst_library::model::{
     Attribution, BibliographyElem, CiteElem, CiteGroup, CslIndentElem, CslLightElem,
     Destination, DirectLinkElem, EmphElem, EnumElem, FigureCaption, FigureElem,
-    FootnoteElem, FootnoteEntry, HeadingElem, LinkElem, LinkMarker, ListElem,
-    OutlineElem, OutlineEntry, ParElem, ParbreakElem, QuoteElem, RefElem, StrongElem,
-    TableCell, TableElem, TermsElem, TitleElem, Works,
+    FootnoteElem, FootnoteEntry, HeadingElem, IndexElem, IndexTerm, LinkElem, LinkMarker,
+    ListElem, OutlineElem, OutlineEntry, ParElem, ParbreakElem, QuoteElem, RefElem,
+    StrongElem, TableCell, TableElem, TermsElem, TitleElem, Works,
+};
+use typst_library::pdf::{
+    Annotation, AnnotationKind, ArtifactElem, ArtifactKind, AttachElem, ButtonElem,
+    CheckboxElem, DropdownElem, FreeTextElem, Layer, LayerElem, MarkElem, MarkKind,
+    NoteElem, PdfMarkerTag, RadioElem, TextFieldElem,
 };
-use typst_library::pdf::{ArtifactElem, ArtifactKind, AttachElem, PdfMarkerTag};
 use typst_library::text::{
     DecoLine, Decoration, HighlightElem, ItalicToggle, LinebreakElem, LocalName,
     OverlineElem, RawElem, RawLine, ScriptKind, ShiftSettings, Smallcaps, SmallcapsElem,
@@ -30,9 +36,10 @@ use typst_library::text::{
     TextSize, UnderlineElem, WeightDelta,
 };
 use typst_library::visualize::{
-    CircleElem, CurveElem, EllipseElem, ImageElem, LineElem, PathElem, PolygonElem,
-    RectElem, SquareElem, Stroke,
+    CircleElem, Color, CurveElem, EllipseElem, ImageElem, LineElem, Paint, PathElem,
+    PolygonElem, RectElem, SquareElem, Stroke,
 };
+use typst_syntax::Span;
 use typst_utils::{Get, Numeric};
 
 /// Register show rules for the [paged target](Target::Paged).
@@ -57,6 +64,7 @@ pub fn register(rules: &mut NativeRuleMap) {
     rules.register(Paged, FOOTNOTE_ENTRY_RULE);
     rules.register(Paged, OUTLINE_RULE);
     rules.register(Paged, OUTLINE_ENTRY_RULE);
+    rules.register(Paged, INDEX_RULE);
     rules.register(Paged, REF_RULE);
     rules.register(Paged, CITE_GROUP_RULE);
     rules.register(Paged, BIBLIOGRAPHY_RULE);
@@ -108,6 +116,15 @@ pub fn register(rules: &mut NativeRuleMap) {
     // PDF.
     rules.register(Paged, ATTACH_RULE);
     rules.register(Paged, ARTIFACT_RULE);
+    rules.register(Paged, TEXT_FIELD_RULE);
+    rules.register(Paged, CHECKBOX_RULE);
+    rules.register(Paged, RADIO_RULE);
+    rules.register(Paged, DROPDOWN_RULE);
+    rules.register(Paged, BUTTON_RULE);
+    rules.register(Paged, NOTE_RULE);
+    rules.register(Paged, MARK_RULE);
+    rules.register(Paged, FREE_TEXT_RULE);
+    rules.register(Paged, LAYER_RULE);
     rules.register(Paged, PDF_MARKER_TAG_RULE);
 }
 
@@ -224,6 +241,7 @@ const LINK_RULE: ShowFn<LinkElem> = |elem, engine, styles| {
     let body = elem.body.clone();
     let dest = elem.dest.resolve(engine, span)?;
     let alt = dest.alt_text(engine, styles, span)?;
+    warn_if_marked(engine, styles, span);
     // Manually construct link marker that spans the whole link elem, not just
     // the body.
     Ok(LinkMarker::new(body, Some(alt))
@@ -232,7 +250,8 @@ const LINK_RULE: ShowFn<LinkElem> = |elem, engine, styles| {
         .set(LinkElem::current, Some(dest)))
 };
 
-const DIRECT_LINK_RULE: ShowFn<DirectLinkElem> = |elem, _, _| {
+const DIRECT_LINK_RULE: ShowFn<DirectLinkElem> = |elem, engine, styles| {
+    warn_if_marked(engine, styles, elem.span());
     let dest = Destination::Location(elem.loc);
     Ok(elem.body.clone().linked(dest, elem.alt.clone()))
 };
@@ -460,6 +479,68 @@ const OUTLINE_ENTRY_RULE: ShowFn<OutlineEntry> = |elem, engine, styles| {
     Ok(block.linked(Destination::Location(loc), Some(alt)))
 };
 
+const INDEX_RULE: ShowFn<IndexElem> = |elem, engine, styles| {
+    let span = elem.span();
+    let mut blocks = vec![];
+    for term in elem.realize_terms(engine, styles) {
+        show_index_term(&mut blocks, engine, styles, &term, 0, span)?;
+    }
+
+    let columns = ColumnsElem::new(Content::sequence(blocks))
+        .with_count(elem.columns.get(styles))
+        .with_balance(true)
+        .pack()
+        .spanned(span);
+
+    let title = elem.realize_title(styles);
+    Ok(Content::sequence(title.into_iter().chain(Some(columns))))
+};
+
+/// Realizes a term of an index and, recursively, its subterms as blocks with
+/// a hanging indent.
+fn show_index_term(
+    blocks: &mut Vec<Content>,
+    engine: &mut Engine,
+    styles: StyleChain,
+    term: &IndexTerm,
+    depth: usize,
+    span: Span,
+) -> SourceResult<()> {
+    const INDENT: Em = Em::new(1.0);
+    const HANGING_INDENT: Em = Em::new(1.5);
+
+    let pages = term.pages(engine, styles, span)?;
+    let mut seq = vec![
+        HElem::new((-HANGING_INDENT).into()).pack(),
+        TextElem::packed(term.term.clone()),
+    ];
+    for page in &pages {
+        seq.push(TextElem::packed(", "));
+        seq.push(page.clone());
+    }
+    if let Some(see_also) = term.see_also(styles) {
+        seq.push(TextElem::packed(if pages.is_empty() { ", " } else { "; " }));
+        seq.push(see_also);
+    }
+
+    let indent = INDENT * depth as f64 + HANGING_INDENT;
+    let inset =
+        Sides::default().with(styles.resolve(TextElem::dir).start(), Some(indent.into()));
+    blocks.push(
+        BlockElem::new()
+            .with_body(Some(BlockBody::Content(Content::sequence(seq))))
+            .with_inset(inset)
+            .pack()
+            .spanned(span),
+    );
+
+    for child in &term.children {
+        show_index_term(blocks, engine, styles, child, depth + 1, span)?;
+    }
+
+    Ok(())
+}
+
 const REF_RULE: ShowFn<RefElem> = |elem, engine, styles| elem.realize(engine, styles);
 
 const CITE_GROUP_RULE: ShowFn<CiteGroup> = |elem, engine, _| elem.realize(engine);
@@ -510,7 +591,7 @@ const BIBLIOGRAPHY_RULE: ShowFn<BibliographyElem> = |elem, engine, styles| {
         let mut body = vec![];
         for (_, reference, loc) in references {
             let realized = PdfMarkerTag::BibEntry(reference.clone().located(*loc));
-            let block = if works.hanging_indent {
+            let block = if works.hanging_indent(elem) {
                 let body = HElem::new((-INDENT).into()).pack() + realized;
                 let inset = Sides::default()
                     .with(styles.resolve(TextElem::dir).start(), Some(INDENT.into()));
@@ -835,3 +916,181 @@ const ATTACH_RULE: ShowFn<AttachElem> = |_, _, _| Ok(Content::empty());
 const ARTIFACT_RULE: ShowFn<ArtifactElem> = |elem, _, _| Ok(elem.body.clone());
 
 const PDF_MARKER_TAG_RULE: ShowFn<PdfMarkerTag> = |elem, _, _| Ok(elem.body.clone());
+
+const TEXT_FIELD_RULE: ShowFn<TextFieldElem> = |elem, _, styles| {
+    Ok(show_field(
+        BlockElem::single_layouter(elem.clone(), crate::form::layout_text_field),
+        elem.alt.get_cloned(styles).unwrap_or_else(|| elem.name.clone()),
+        Axes::new(elem.width.get(styles), elem.height.get(styles)),
+        elem.fill.get_cloned(styles),
+        elem.stroke.get_cloned(styles),
+        false,
+        elem.span(),
+    ))
+};
+
+const CHECKBOX_RULE: ShowFn<CheckboxElem> = |elem, _, styles| {
+    Ok(show_field(
+        BlockElem::single_layouter(elem.clone(), crate::form::layout_checkbox),
+        elem.alt.get_cloned(styles).unwrap_or_else(|| elem.name.clone()),
+        Axes::splat(elem.size.get(styles).into()),
+        elem.fill.get_cloned(styles),
+        elem.stroke.get_cloned(styles),
+        false,
+        elem.span(),
+    ))
+};
+
+const RADIO_RULE: ShowFn<RadioElem> = |elem, _, styles| {
+    Ok(show_field(
+        BlockElem::single_layouter(elem.clone(), crate::form::layout_radio),
+        elem.alt.get_cloned(styles).unwrap_or_else(|| elem.value.clone()),
+        Axes::splat(elem.size.get(styles).into()),
+        elem.fill.get_cloned(styles),
+        elem.stroke.get_cloned(styles),
+        true,
+        elem.span(),
+    ))
+};
+
+const DROPDOWN_RULE: ShowFn<DropdownElem> = |elem, _, styles| {
+    Ok(show_field(
+        BlockElem::single_layouter(elem.clone(), crate::form::layout_dropdown),
+        elem.alt.get_cloned(styles).unwrap_or_else(|| elem.name.clone()),
+        Axes::new(elem.width.get(styles), elem.height.get(styles)),
+        elem.fill.get_cloned(styles),
+        elem.stroke.get_cloned(styles),
+        false,
+        elem.span(),
+    ))
+};
+
+const BUTTON_RULE: ShowFn<ButtonElem> = |elem, _, styles| {
+    Ok(show_field(
+        BlockElem::single_layouter(elem.clone(), crate::form::layout_button),
+        elem.alt.get_cloned(styles).unwrap_or_else(|| elem.caption.clone()),
+        Axes::new(elem.width.get(styles), elem.height.get(styles)),
+        elem.fill.get_cloned(styles),
+        elem.stroke.get_cloned(styles),
+        false,
+        elem.span(),
+    ))
+};
+
+const NOTE_RULE: ShowFn<NoteElem> = |elem, _, _| {
+    let span = elem.span();
+    let icon = BlockElem::single_layouter(elem.clone(), crate::annotate::layout_note)
+        .pack()
+        .spanned(span);
+    let note = BoxElem::new()
+        .with_width(Sizing::Rel(Rel::zero()))
+        .with_height(Smart::Custom(Rel::zero()))
+        .with_body(Some(icon));
+    Ok(LinkMarker::new(note.pack().spanned(span), Some(elem.text.clone()))
+        .pack()
+        .spanned(span))
+};
+
+const MARK_RULE: ShowFn<MarkElem> = |elem, engine, styles| {
+    let span = elem.span();
+    if styles.get_ref(LinkElem::current).is_some() {
+        engine.sink.warn(warning!(
+            span,
+            "mark is not exported because its content is a link";
+            hint: "in PDF, content can only belong to a single annotation";
+            hint: "try marking the link instead"
+        ));
+    }
+    let comment = elem.comment.get_cloned(styles);
+    let kind = elem.kind.get(styles);
+    let alt = comment.clone().unwrap_or_else(|| {
+        match kind {
+            MarkKind::Highlight => "Highlighted text",
+            MarkKind::Underline => "Underlined text",
+            MarkKind::Squiggly => "Text with a squiggly underline",
+            MarkKind::Strike => "Struck out text",
+        }
+        .into()
+    });
+    let annotation = Annotation {
+        kind: AnnotationKind::Mark { kind, color: elem.color.get_cloned(styles) },
+        text: comment.unwrap_or_default(),
+        author: elem.author.get_cloned(styles),
+        date: elem.date.get(styles),
+        text_size: styles.resolve(TextElem::size),
+        span,
+    };
+    Ok(LinkMarker::new(elem.body.clone(), Some(alt))
+        .pack()
+        .spanned(span)
+        .set(MarkElem::current, Some(annotation)))
+};
+
+const FREE_TEXT_RULE: ShowFn<FreeTextElem> = |elem, _, styles| {
+    Ok(show_field(
+        BlockElem::single_layouter(elem.clone(), crate::annotate::layout_free_text),
+        elem.text.clone(),
+        Axes::new(elem.width.get(styles), elem.height.get(styles)),
+        elem.fill.get_cloned(styles),
+        elem.stroke.get_cloned(styles),
+        false,
+        elem.span(),
+    ))
+};
+
+const LAYER_RULE: ShowFn<LayerElem> = |elem, _, styles| {
+    let visible = elem.visible.get(styles);
+    let layer = Layer {
+        name: elem.name.clone(),
+        visible,
+        print: elem.print.get(styles).unwrap_or(visible),
+        span: elem.span(),
+    };
+    Ok(elem.body.clone().set(LayerElem::current, Some(layer)))
+};
+
+/// Warns that a link inside of a mark takes precedence over it.
+fn warn_if_marked(engine: &mut Engine, styles: StyleChain, span: Span) {
+    if styles.get_ref(MarkElem::current).is_some() {
+        engine.sink.warn(warning!(
+            span,
+            "linked content is not covered by the surrounding mark";
+            hint: "in PDF, content can only belong to a single annotation"
+        ));
+    }
+}
+
+/// Shows a form field or a free text annotation as a box around the frame
+/// produced by its layouter.
+fn show_field(
+    widget: BlockElem,
+    alt: EcoString,
+    size: Axes<Rel<Length>>,
+    fill: Option<Paint>,
+    stroke: Smart<Option<Stroke>>,
+    round: bool,
+    span: Span,
+) -> Content {
+    let stroke = stroke
+        .unwrap_or_else(|| Some(Stroke::from_pair(Color::GRAY, Abs::pt(0.5).into())));
+    let body = widget
+        .with_width(Smart::Custom(Rel::one()))
+        .with_height(Sizing::Rel(Rel::one()))
+        .pack()
+        .spanned(span);
+    let radius = if round { Some(Ratio::new(0.5).into()) } else { None };
+    let field = BoxElem::new()
+        .with_width(Sizing::Rel(size.x))
+        .with_height(Smart::Custom(size.y))
+        .with_fill(fill)
+        .with_stroke(Sides::splat(Some(stroke)))
+        .with_radius(Corners::splat(radius))
+        .with_body(Some(body));
+
+    // The PDF exporter turns the link annotation of the marker into the
+    // field's widget or annotation, which keeps it in the right place in the
+    // tag tree.
+    LinkMarker::new(field.pack().spanned(span), Some(alt))
+        .pack()
+        .spanned(span)
+}
@@ -15,6 +15,7 @@ use crate::foundations::{
     Args, Bytes, CastInfo, Context, Dict, FromValue, Func, IntoValue, Reflect, Repr, Str,
     Value, Version, cast, func, ops, repr, scope, ty,
 };
+use crate::text::Collation;
 
 /// Create a new [`Array`] from values.
 #[macro_export]
@@ -893,7 +894,39 @@ impl Array {
         /// ```
         #[named]
         by: Option<Func>,
+        /// If given, orders strings by the rules of a language instead of by
+        /// their Unicode code points. This also applies to strings in arrays
+        /// returned by the `key` function.
+        ///
+        /// Can be `{auto}` to use the current [text language]($text.lang) and
+        /// region, a language code like `{"de"}`, or a dictionary with the
+        /// following keys:
+        ///
+        /// - `lang`: The language code, or `{auto}` for the text language.
+        ///   Defaults to `{auto}`.
+        /// - `region`: The region code, or `{none}`. Defaults to `{none}`,
+        ///   which means the text region when the language is `{auto}`.
+        /// - `case`: Whether to distinguish strings that only differ in case.
+        ///   Defaults to `{true}`.
+        /// - `accents`: Whether to distinguish strings that only differ in
+        ///   accents. Defaults to `{true}`.
+        ///
+        /// Strings that are equal under these rules keep their order.
+        ///
+        /// ```example
+        /// #let words = ("Zebra", "Ärger", "apfel")
+        /// #words.sorted() \
+        /// #words.sorted(collation: "de") \
+        /// #set text(lang: "sv")
+        /// #context words.sorted(collation: auto)
+        /// ```
+        #[named]
+        collation: Option<Collation>,
     ) -> SourceResult<Array> {
+        if by.is_some() && collation.is_some() {
+            bail!(span, "cannot use `collation` together with `by`");
+        }
+
         match by {
             Some(by) => {
                 let mut are_in_order = |mut x, mut y| {
@@ -964,6 +997,12 @@ impl Array {
             }
 
             None => {
+                let collator =
+                    collation.map(|c| c.collator(context)).transpose().at(span)?;
+                let compare = |a: &Value, b: &Value| match &collator {
+                    Some(collator) => ops::compare_collated(a, b, collator),
+                    None => ops::compare(a, b),
+                };
                 let mut key_of = |x: Value| match &key {
                     // We rely on `comemo`'s memoization of function evaluation
                     // to not excessively reevaluate the key.
@@ -977,7 +1016,7 @@ impl Array {
                 let mut vec = self.0;
                 vec.make_mut().sort_by(|a, b| {
                     match (key_of(a.clone()), key_of(b.clone())) {
-                        (Ok(a), Ok(b)) => ops::compare(&a, &b).unwrap_or_else(|err| {
+                        (Ok(a), Ok(b)) => compare(&a, &b).unwrap_or_else(|err| {
                             if result.is_ok() {
                                 result = Err(err).at(span);
                             }
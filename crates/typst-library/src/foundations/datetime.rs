@@ -2,17 +2,22 @@ use std::cmp::Ordering;
 use std::hash::Hash;
 use std::ops::{Add, Sub};
 
+use comemo::Tracked;
 use ecow::{EcoString, EcoVec, eco_format};
 use time::error::{Format, InvalidFormatDescription};
+use time::format_description::modifier::{MonthRepr, WeekdayRepr};
+use time::format_description::{Component, OwnedFormatItem};
 use time::macros::format_description;
-use time::{Month, PrimitiveDateTime, format_description};
+use time::parsing::Parsed;
+use time::{Month, OffsetDateTime, PrimitiveDateTime, UtcOffset, format_description};
 
 use crate::World;
 use crate::diag::{StrResult, bail};
 use crate::engine::Engine;
 use crate::foundations::{
-    Dict, Duration, Repr, Smart, Str, Value, cast, func, repr, scope, ty,
+    Cast, Context, Dict, Duration, Repr, Smart, Str, Value, cast, func, repr, scope, ty,
 };
+use crate::text::{Lang, Region, TextElem, localized_str};
 
 /// Represents a date, a time, or a combination of both.
 ///
@@ -66,6 +71,39 @@ use crate::foundations::{
 /// #two-days-later.display()
 /// ```
 ///
+/// Since months and years vary in length, they cannot be expressed as a
+/// duration. To move by calendar units, use the [`add`]($datetime.add) method.
+/// With [`start-of`]($datetime.start-of), you can find the beginning of the
+/// surrounding year, month, or week:
+/// ```example
+/// #let date = datetime(day: 31, month: 1, year: 2024)
+/// #date.add(months: 1).display() \
+/// #date.start-of("week").display()
+/// ```
+///
+/// # Parsing
+/// Datetimes can be parsed from strings with [`datetime.parse`]. Without a
+/// pattern, it accepts the common [ISO 8601](https://en.wikipedia.org/wiki/ISO_8601)
+/// formats. With a pattern, it uses the same [format syntax]($datetime/#format)
+/// as [`display`]($datetime.display).
+/// ```example
+/// #datetime.parse("2024-03-01T09:30:00").display() \
+/// #datetime.parse(
+///   "01.03.2024",
+///   pattern: "[day].[month].[year]",
+/// ).display()
+/// ```
+///
+/// # Offsets
+/// A datetime with both a date and a time can carry an offset from UTC. Such a
+/// datetime refers to an unambiguous point in time: Comparing or subtracting
+/// two of them takes their offsets into account.
+/// ```example
+/// #let meeting = datetime.parse("2024-03-01T09:30:00+01:00")
+/// #meeting.offset().hours() \
+/// #meeting.to-offset(duration(hours: -5)).display()
+/// ```
+///
 /// # Format
 /// You can specify a customized formatting using the
 /// [`display`]($datetime.display) method. The format of a datetime is
@@ -92,9 +130,8 @@ use crate::foundations::{
 ///   - `padding`: Can be either `zero`, `space` or `none`. Specifies how the
 ///     month is padded.
 ///   - `repr`: Can be either `numerical`, `long` or `short`. Specifies if the
-///     month should be displayed as a number or a word. Unfortunately, when
-///     choosing the word representation, it can currently only display the
-///     English version. In the future, it is planned to support localization.
+///     month should be displayed as a number or a word. When displayed in a
+///     [context], the word is in the current [text language]($text.lang).
 /// - `day`: Displays the day of the datetime.
 ///   - `padding`: Can be either `zero`, `space` or `none`. Specifies how the
 ///     day is padded.
@@ -106,10 +143,10 @@ use crate::foundations::{
 ///      and 53.
 /// - `weekday`: Displays the weekday of the date.
 ///   - `repr` Can be either `long`, `short`, `sunday` or `monday`. In the case
-///     of `long` and `short`, the corresponding English name will be displayed
-///     (same as for the month, other languages are currently not supported). In
-///     the case of `sunday` and `monday`, the numerical value will be displayed
-///     (assuming Sunday and Monday as the first day of the week, respectively).
+///     of `long` and `short`, the name of the weekday will be displayed (in the
+///     same language as the month). In the case of `sunday` and `monday`, the
+///     numerical value will be displayed (assuming Sunday and Monday as the
+///     first day of the week, respectively).
 ///   - `one_indexed`: Can be either `true` or `false`. Defines whether the
 ///     numerical representation of the week starts with 0 or 1.
 /// - `hour`: Displays the hour of the date.
@@ -126,12 +163,21 @@ use crate::foundations::{
 /// - `second`: Displays the second of the date.
 ///   - `padding`: Can be either `zero`, `space` or `none`. Specifies how the
 ///     second is padded.
+/// - `offset_hour`: Displays the hours of the UTC offset.
+///   - `padding`: Can be either `zero`, `space` or `none`. Specifies how the
+///     hours are padded.
+///   - `sign`: Can be either `automatic` or `mandatory`. Specifies when the
+///     sign should be displayed.
+/// - `offset_minute`: Displays the minutes of the UTC offset, within the hour.
+///   - `padding`: Can be either `zero`, `space` or `none`. Specifies how the
+///     minutes are padded.
 ///
 /// Keep in mind that not always all components can be used. For example, if you
 /// create a new datetime with `{datetime(year: 2023, month: 10, day: 13)}`, it
 /// will be stored as a plain date internally, meaning that you cannot use
 /// components such as `hour` or `minute`, which would only work on datetimes
-/// that have a specified time.
+/// that have a specified time. Similarly, the offset components only work on
+/// datetimes with an offset.
 #[ty(scope, cast)]
 #[derive(Debug, Copy, Clone, PartialEq, Hash)]
 pub enum Datetime {
@@ -141,6 +187,8 @@ pub enum Datetime {
     Time(time::Time),
     /// Representation as a combination of date and time.
     Datetime(time::PrimitiveDateTime),
+    /// Representation as a combination of date, time, and offset from UTC.
+    Offset(time::OffsetDateTime),
 }
 
 impl Datetime {
@@ -224,10 +272,110 @@ impl Datetime {
     pub fn kind(&self) -> &'static str {
         match self {
             Datetime::Datetime(_) => "datetime",
+            Datetime::Offset(_) => "datetime with offset",
             Datetime::Date(_) => "date",
             Datetime::Time(_) => "time",
         }
     }
+
+    /// The date part, if any.
+    pub fn date(&self) -> Option<time::Date> {
+        match self {
+            Self::Date(date) => Some(*date),
+            Self::Time(_) => None,
+            Self::Datetime(datetime) => Some(datetime.date()),
+            Self::Offset(datetime) => Some(datetime.date()),
+        }
+    }
+
+    /// The time part, if any.
+    pub fn time(&self) -> Option<time::Time> {
+        match self {
+            Self::Date(_) => None,
+            Self::Time(time) => Some(*time),
+            Self::Datetime(datetime) => Some(datetime.time()),
+            Self::Offset(datetime) => Some(datetime.time()),
+        }
+    }
+
+    /// The offset from UTC, if any.
+    pub fn utc_offset(&self) -> Option<UtcOffset> {
+        match self {
+            Self::Offset(datetime) => Some(datetime.offset()),
+            _ => None,
+        }
+    }
+
+    /// Replaces the date and time parts, keeping the variant and offset.
+    fn with_parts(self, date: Option<time::Date>, time: Option<time::Time>) -> Self {
+        match self {
+            Self::Date(_) => Self::Date(date.unwrap()),
+            Self::Time(_) => Self::Time(time.unwrap()),
+            Self::Datetime(_) => {
+                Self::Datetime(PrimitiveDateTime::new(date.unwrap(), time.unwrap()))
+            }
+            Self::Offset(datetime) => Self::Offset(
+                PrimitiveDateTime::new(date.unwrap(), time.unwrap())
+                    .assume_offset(datetime.offset()),
+            ),
+        }
+    }
+
+    /// Parses an ISO 8601 date, time, or datetime with optional offset.
+    fn parse_iso(text: &str) -> Option<Self> {
+        let date = "[year]-[month]-[day]";
+        let time = "[hour]:[minute]:[second][optional [.[subsecond]]]";
+        let offset = "[offset_hour sign:mandatory][optional [:]][offset_minute]";
+        let datetime = format!("{date}[first [T] [t] [ ]]{time}");
+        let pat = |s: &str| format_description::parse_owned::<2>(s).unwrap();
+
+        // The `Z` suffix denotes UTC, but does not set the offset components
+        // when parsed as a literal.
+        let zulu = text.strip_suffix(['Z', 'z']).map(|rest| format!("{rest}+00:00"));
+
+        if let Ok(v) = OffsetDateTime::parse(
+            zulu.as_deref().unwrap_or(text),
+            &pat(&format!("{datetime}{offset}")),
+        ) {
+            Some(Self::Offset(v))
+        } else if let Ok(v) = PrimitiveDateTime::parse(text, &pat(&datetime)) {
+            Some(Self::Datetime(v))
+        } else if let Ok(v) = time::Date::parse(text, &pat(date)) {
+            Some(Self::Date(v))
+        } else if let Ok(v) = time::Time::parse(text, &pat(time)) {
+            Some(Self::Time(v))
+        } else {
+            None
+        }
+    }
+
+    /// Parses a datetime with a custom pattern.
+    ///
+    /// The result is the most specific kind of datetime that the pattern
+    /// determines.
+    fn parse_with(text: &str, pattern: &DisplayPattern) -> StrResult<Self> {
+        let mut parsed = Parsed::new();
+        let rest = parsed
+            .parse_item(text.as_bytes(), &pattern.1)
+            .map_err(|err| eco_format!("failed to parse datetime ({err})"))?;
+        if !rest.is_empty() {
+            bail!("failed to parse datetime (unexpected trailing characters)");
+        }
+
+        if parsed.offset_hour().is_some()
+            && let Ok(v) = OffsetDateTime::try_from(parsed)
+        {
+            Ok(Self::Offset(v))
+        } else if let Ok(v) = PrimitiveDateTime::try_from(parsed) {
+            Ok(Self::Datetime(v))
+        } else if let Ok(v) = time::Date::try_from(parsed) {
+            Ok(Self::Date(v))
+        } else if let Ok(v) = time::Time::try_from(parsed) {
+            Ok(Self::Time(v))
+        } else {
+            bail!("failed to parse datetime (pattern does not determine a date or time)")
+        }
+    }
 }
 
 #[scope]
@@ -274,6 +422,12 @@ impl Datetime {
         /// The second of the datetime.
         #[named]
         second: Option<u8>,
+        /// The offset of the datetime from UTC, either as an integer number of
+        /// hours or as a [duration].
+        ///
+        /// An offset can only be specified for a full datetime.
+        #[named]
+        offset: Option<DatetimeOffset>,
     ) -> StrResult<Datetime> {
         let time = match (hour, minute, second) {
             (Some(hour), Some(minute), Some(second)) => {
@@ -298,8 +452,14 @@ impl Datetime {
         };
 
         Ok(match (date, time) {
-            (Some(date), Some(time)) => {
-                Datetime::Datetime(PrimitiveDateTime::new(date, time))
+            (Some(date), Some(time)) => match offset {
+                Some(DatetimeOffset(offset)) => Datetime::Offset(
+                    PrimitiveDateTime::new(date, time).assume_offset(offset),
+                ),
+                None => Datetime::Datetime(PrimitiveDateTime::new(date, time)),
+            },
+            _ if offset.is_some() => {
+                bail!("an offset can only be specified for a full datetime")
             }
             (Some(date), None) => Datetime::Date(date),
             (None, Some(time)) => Datetime::Time(time),
@@ -309,6 +469,45 @@ impl Datetime {
         })
     }
 
+    /// Parses a datetime from a string.
+    ///
+    /// Without a pattern, dates (`2024-03-01`), times (`09:30:00`), and
+    /// datetimes (`2024-03-01T09:30:00`) in ISO 8601 format are accepted. A
+    /// datetime may have a UTC offset like `+01:00` or `Z`.
+    ///
+    /// With a pattern in the [format syntax]($datetime/#format), the string
+    /// must match the pattern exactly. The result is a date, a time, a full
+    /// datetime, or a datetime with offset, depending on which components the
+    /// pattern contains.
+    ///
+    /// ```example
+    /// #datetime.parse("2024-03-01") \
+    /// #datetime.parse(
+    ///   "March 1, 2024",
+    ///   pattern: "[month repr:long] [day padding:none], [year]",
+    /// )
+    /// ```
+    #[func]
+    pub fn parse(
+        /// The string to parse.
+        text: Str,
+        /// The pattern that the string follows. If set to `{auto}`, ISO 8601
+        /// formats are accepted.
+        #[named]
+        #[default]
+        pattern: Smart<DisplayPattern>,
+    ) -> StrResult<Datetime> {
+        match pattern {
+            Smart::Auto => Self::parse_iso(&text).ok_or_else(|| {
+                eco_format!(
+                    "failed to parse datetime ({} is not in ISO 8601 format)",
+                    text.repr()
+                )
+            }),
+            Smart::Custom(pattern) => Self::parse_with(&text, &pattern),
+        }
+    }
+
     /// Returns the current date.
     ///
     /// In the CLI, this can be overridden with the `--creation-timestamp`
@@ -342,12 +541,27 @@ impl Datetime {
     /// default format will be different. If you specified a date, it will be
     /// `[[year]-[month]-[day]]`. If you specified a time, it will be
     /// `[[hour]:[minute]:[second]]`. In the case of a datetime, it will be
-    /// `[[year]-[month]-[day] [hour]:[minute]:[second]]`.
+    /// `[[year]-[month]-[day] [hour]:[minute]:[second]]`. A datetime with an
+    /// offset additionally displays the offset as
+    /// `[[offset_hour sign:mandatory]:[offset_minute]]`.
+    ///
+    /// When used in a [context], month and weekday names are displayed in the
+    /// current [text language]($text.lang). Otherwise, and in languages for
+    /// which no names are available, they are in English.
+    ///
+    /// ```example
+    /// #set text(lang: "de")
+    /// #let date = datetime(year: 2024, month: 3, day: 1)
+    /// #context date.display(
+    ///   "[weekday], [day]. [month repr:long] [year]"
+    /// )
+    /// ```
     ///
     /// See the [format syntax]($datetime/#format) for more information.
     #[func]
     pub fn display(
         &self,
+        context: Tracked<Context>,
         /// The format used to display the datetime.
         #[default]
         pattern: Smart<DisplayPattern>,
@@ -360,13 +574,26 @@ impl Datetime {
                 Self::Datetime(datetime) => {
                     datetime.format(&pat("[year]-[month]-[day] [hour]:[minute]:[second]"))
                 }
+                Self::Offset(datetime) => datetime
+                    .format(&pat("[year]-[month]-[day] [hour]:[minute]:[second] \
+                     [offset_hour sign:mandatory]:[offset_minute]")),
             },
 
-            Smart::Custom(DisplayPattern(_, format)) => match self {
-                Self::Date(date) => date.format(&format),
-                Self::Time(time) => time.format(&format),
-                Self::Datetime(datetime) => datetime.format(&format),
-            },
+            Smart::Custom(DisplayPattern(_, mut format)) => {
+                if let Ok(styles) = context.styles()
+                    && let Some(date) = self.date()
+                {
+                    let lang = styles.get(TextElem::lang);
+                    let region = styles.get(TextElem::region);
+                    format = localize(format, date, lang, region);
+                }
+                match self {
+                    Self::Date(date) => date.format(&format),
+                    Self::Time(time) => time.format(&format),
+                    Self::Datetime(datetime) => datetime.format(&format),
+                    Self::Offset(datetime) => datetime.format(&format),
+                }
+            }
         };
         result.map(EcoString::from).map_err(format_time_format_error)
     }
@@ -378,6 +605,7 @@ impl Datetime {
             Self::Date(date) => Some(date.year()),
             Self::Time(_) => None,
             Self::Datetime(datetime) => Some(datetime.year()),
+            Self::Offset(datetime) => Some(datetime.year()),
         }
     }
 
@@ -388,6 +616,7 @@ impl Datetime {
             Self::Date(date) => Some(date.month().into()),
             Self::Time(_) => None,
             Self::Datetime(datetime) => Some(datetime.month().into()),
+            Self::Offset(datetime) => Some(datetime.month().into()),
         }
     }
 
@@ -398,6 +627,7 @@ impl Datetime {
             Self::Date(date) => Some(date.weekday().number_from_monday()),
             Self::Time(_) => None,
             Self::Datetime(datetime) => Some(datetime.weekday().number_from_monday()),
+            Self::Offset(datetime) => Some(datetime.weekday().number_from_monday()),
         }
     }
 
@@ -408,6 +638,7 @@ impl Datetime {
             Self::Date(date) => Some(date.day()),
             Self::Time(_) => None,
             Self::Datetime(datetime) => Some(datetime.day()),
+            Self::Offset(datetime) => Some(datetime.day()),
         }
     }
 
@@ -418,6 +649,7 @@ impl Datetime {
             Self::Date(_) => None,
             Self::Time(time) => Some(time.hour()),
             Self::Datetime(datetime) => Some(datetime.hour()),
+            Self::Offset(datetime) => Some(datetime.hour()),
         }
     }
 
@@ -428,6 +660,7 @@ impl Datetime {
             Self::Date(_) => None,
             Self::Time(time) => Some(time.minute()),
             Self::Datetime(datetime) => Some(datetime.minute()),
+            Self::Offset(datetime) => Some(datetime.minute()),
         }
     }
 
@@ -438,6 +671,7 @@ impl Datetime {
             Self::Date(_) => None,
             Self::Time(time) => Some(time.second()),
             Self::Datetime(datetime) => Some(datetime.second()),
+            Self::Offset(datetime) => Some(datetime.second()),
         }
     }
 
@@ -446,10 +680,217 @@ impl Datetime {
     pub fn ordinal(&self) -> Option<u16> {
         match self {
             Self::Datetime(datetime) => Some(datetime.ordinal()),
+            Self::Offset(datetime) => Some(datetime.ordinal()),
             Self::Date(date) => Some(date.ordinal()),
             Self::Time(_) => None,
         }
     }
+
+    /// The offset from UTC, or `{none}` for datetimes without an offset.
+    #[func]
+    pub fn offset(&self) -> Option<Duration> {
+        self.utc_offset()
+            .map(|offset| time::Duration::seconds(offset.whole_seconds().into()).into())
+    }
+
+    /// Converts a datetime with an offset to the same point in time at a
+    /// different offset.
+    ///
+    /// ```example
+    /// #let utc = datetime.parse("2024-03-01T12:00:00Z")
+    /// #utc.to-offset(2).display()
+    /// ```
+    #[func]
+    pub fn to_offset(
+        &self,
+        /// The new offset from UTC, either as an integer number of hours or as
+        /// a [duration].
+        offset: DatetimeOffset,
+    ) -> StrResult<Datetime> {
+        let Self::Offset(datetime) = self else {
+            bail!("cannot convert {} to a different offset", self.kind());
+        };
+        datetime
+            .checked_to_offset(offset.0)
+            .map(Self::Offset)
+            .ok_or_else(|| "datetime is out of range".into())
+    }
+
+    /// Moves the datetime by the given number of calendar units.
+    ///
+    /// Unlike adding a [duration], this takes the varying lengths of months
+    /// and years into account. If the resulting month is too short for the
+    /// day, the day is clamped to the last day of the month. Years and months
+    /// are applied before weeks and days.
+    ///
+    /// ```example
+    /// #let date = datetime(year: 2024, month: 1, day: 31)
+    /// #date.add(months: 1).display() \
+    /// #date.add(years: 1, days: -1).display()
+    /// ```
+    #[func]
+    pub fn add(
+        &self,
+        /// The number of years to add.
+        #[named]
+        #[default(0)]
+        years: i64,
+        /// The number of months to add.
+        #[named]
+        #[default(0)]
+        months: i64,
+        /// The number of weeks to add.
+        #[named]
+        #[default(0)]
+        weeks: i64,
+        /// The number of days to add.
+        #[named]
+        #[default(0)]
+        days: i64,
+    ) -> StrResult<Datetime> {
+        let Some(date) = self.date() else {
+            bail!("cannot add calendar units to a time");
+        };
+
+        let out_of_range = || "datetime is out of range";
+        let months = years
+            .checked_mul(12)
+            .and_then(|years| years.checked_add(months))
+            .ok_or_else(out_of_range)?;
+        let total = i64::from(date.year()) * 12 + i64::from(u8::from(date.month())) - 1;
+        let total = total.checked_add(months).ok_or_else(out_of_range)?;
+        let year = i32::try_from(total.div_euclid(12)).map_err(|_| out_of_range())?;
+        let month = Month::try_from((total.rem_euclid(12) + 1) as u8).unwrap();
+        let day = date.day().min(time::util::days_in_month(month, year));
+        let date = time::Date::from_calendar_date(year, month, day)
+            .map_err(|_| out_of_range())?;
+
+        let days = weeks
+            .checked_mul(7)
+            .and_then(|weeks| weeks.checked_add(days))
+            .ok_or_else(out_of_range)?;
+        let date = date
+            .checked_add(time::Duration::days(days))
+            .ok_or_else(out_of_range)?;
+
+        Ok(self.with_parts(Some(date), self.time()))
+    }
+
+    /// Returns the beginning of the calendar unit that contains this datetime.
+    ///
+    /// All smaller units are reset, e.g. the start of the month of a datetime
+    /// is midnight on the first day of its month.
+    ///
+    /// ```example
+    /// #let date = datetime(year: 2024, month: 3, day: 14)
+    /// #date.start-of("year").display() \
+    /// #date.start-of("week").display() \
+    /// #date.start-of("week", first-day: 7).display()
+    /// ```
+    #[func]
+    pub fn start_of(
+        &self,
+        /// The calendar unit.
+        unit: CalendarUnit,
+        /// The first day of the week (counting Monday as 1), used for the
+        /// `{"week"}` unit.
+        #[named]
+        #[default(1)]
+        first_day: u8,
+    ) -> StrResult<Datetime> {
+        if !(1..=7).contains(&first_day) {
+            bail!("first day must be between 1 and 7");
+        }
+
+        let date = self.date();
+        let time = self.time();
+        let (date, time) = match unit {
+            CalendarUnit::Year | CalendarUnit::Month | CalendarUnit::Week => {
+                let Some(date) = date else {
+                    bail!(
+                        "cannot determine the start of the {} of a time",
+                        unit.as_str()
+                    );
+                };
+                let date = match unit {
+                    CalendarUnit::Year => {
+                        time::Date::from_ordinal_date(date.year(), 1).unwrap()
+                    }
+                    CalendarUnit::Month => date.replace_day(1).unwrap(),
+                    _ => {
+                        let weekday = date.weekday().number_from_monday();
+                        let back = (weekday + 7 - first_day) % 7;
+                        date.checked_sub(time::Duration::days(back.into()))
+                            .ok_or("datetime is out of range")?
+                    }
+                };
+                (Some(date), time.map(|_| time::Time::MIDNIGHT))
+            }
+            CalendarUnit::Day => (date, time.map(|_| time::Time::MIDNIGHT)),
+            CalendarUnit::Hour | CalendarUnit::Minute => {
+                let Some(time) = time else {
+                    bail!(
+                        "cannot determine the start of the {} of a date",
+                        unit.as_str()
+                    );
+                };
+                let minute = if unit == CalendarUnit::Hour { 0 } else { time.minute() };
+                (date, Some(time::Time::from_hms(time.hour(), minute, 0).unwrap()))
+            }
+        };
+
+        Ok(self.with_parts(date, time))
+    }
+}
+
+/// A calendar unit.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum CalendarUnit {
+    /// A calendar year.
+    Year,
+    /// A calendar month.
+    Month,
+    /// A week.
+    Week,
+    /// A day.
+    Day,
+    /// An hour.
+    Hour,
+    /// A minute.
+    Minute,
+}
+
+impl CalendarUnit {
+    /// The name of the unit.
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Year => "year",
+            Self::Month => "month",
+            Self::Week => "week",
+            Self::Day => "day",
+            Self::Hour => "hour",
+            Self::Minute => "minute",
+        }
+    }
+}
+
+/// An offset from UTC.
+pub struct DatetimeOffset(UtcOffset);
+
+cast! {
+    DatetimeOffset,
+    self => Value::Duration(time::Duration::seconds(self.0.whole_seconds().into()).into()),
+    v: i64 => i32::try_from(v)
+        .ok()
+        .and_then(|hours| hours.checked_mul(3600))
+        .and_then(|seconds| UtcOffset::from_whole_seconds(seconds).ok())
+        .map(Self)
+        .ok_or("offset is invalid")?,
+    v: Duration => i32::try_from(time::Duration::from(v).whole_seconds())
+        .ok()
+        .and_then(|seconds| UtcOffset::from_whole_seconds(seconds).ok())
+        .map(Self)
+        .ok_or("offset is invalid")?,
 }
 
 impl Repr for Datetime {
@@ -460,7 +901,8 @@ impl Repr for Datetime {
         let hour = self.hour().map(|h| eco_format!("hour: {}", (h as i64).repr()));
         let minute = self.minute().map(|m| eco_format!("minute: {}", (m as i64).repr()));
         let second = self.second().map(|s| eco_format!("second: {}", (s as i64).repr()));
-        let filtered = [year, month, day, hour, minute, second]
+        let offset = self.offset().map(|o| eco_format!("offset: {}", o.repr()));
+        let filtered = [year, month, day, hour, minute, second, offset]
             .into_iter()
             .flatten()
             .collect::<EcoVec<_>>();
@@ -473,6 +915,7 @@ impl PartialOrd for Datetime {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match (self, other) {
             (Self::Datetime(a), Self::Datetime(b)) => a.partial_cmp(b),
+            (Self::Offset(a), Self::Offset(b)) => a.partial_cmp(b),
             (Self::Date(a), Self::Date(b)) => a.partial_cmp(b),
             (Self::Time(a), Self::Time(b)) => a.partial_cmp(b),
             _ => None,
@@ -487,6 +930,7 @@ impl Add<Duration> for Datetime {
         let rhs: time::Duration = rhs.into();
         match self {
             Self::Datetime(datetime) => Self::Datetime(datetime + rhs),
+            Self::Offset(datetime) => Self::Offset(datetime + rhs),
             Self::Date(date) => Self::Date(date + rhs),
             Self::Time(time) => Self::Time(time + rhs),
         }
@@ -500,6 +944,7 @@ impl Sub<Duration> for Datetime {
         let rhs: time::Duration = rhs.into();
         match self {
             Self::Datetime(datetime) => Self::Datetime(datetime - rhs),
+            Self::Offset(datetime) => Self::Offset(datetime - rhs),
             Self::Date(date) => Self::Date(date - rhs),
             Self::Time(time) => Self::Time(time - rhs),
         }
@@ -512,6 +957,7 @@ impl Sub for Datetime {
     fn sub(self, rhs: Self) -> Self::Output {
         match (self, rhs) {
             (Self::Datetime(a), Self::Datetime(b)) => Ok((a - b).into()),
+            (Self::Offset(a), Self::Offset(b)) => Ok((a - b).into()),
             (Self::Date(a), Self::Date(b)) => Ok((a - b).into()),
             (Self::Time(a), Self::Time(b)) => Ok((a - b).into()),
             (a, b) => bail!("cannot subtract {} from {}", b.kind(), a.kind()),
@@ -537,6 +983,47 @@ cast! {
     v: u8 => Self::try_from(v).map_err(|_| "month is invalid")?
 }
 
+/// Replaces month and weekday names in a format description with literals in
+/// the given language.
+fn localize(
+    item: OwnedFormatItem,
+    date: time::Date,
+    lang: Lang,
+    region: Option<Region>,
+) -> OwnedFormatItem {
+    let name = |key: EcoString| {
+        OwnedFormatItem::Literal(localized_str(lang, region, &key).as_bytes().into())
+    };
+
+    let month = u8::from(date.month());
+    let weekday = date.weekday().number_from_monday();
+    let each = |items: Box<[OwnedFormatItem]>| {
+        items
+            .into_iter()
+            .map(|item| localize(item, date, lang, region))
+            .collect::<Box<[_]>>()
+    };
+
+    match item {
+        OwnedFormatItem::Component(Component::Month(modifier)) => match modifier.repr {
+            MonthRepr::Long => name(eco_format!("month-long-{month}")),
+            MonthRepr::Short => name(eco_format!("month-short-{month}")),
+            _ => item,
+        },
+        OwnedFormatItem::Component(Component::Weekday(modifier)) => match modifier.repr {
+            WeekdayRepr::Long => name(eco_format!("weekday-long-{weekday}")),
+            WeekdayRepr::Short => name(eco_format!("weekday-short-{weekday}")),
+            _ => item,
+        },
+        OwnedFormatItem::Compound(items) => OwnedFormatItem::Compound(each(items)),
+        OwnedFormatItem::First(items) => OwnedFormatItem::First(each(items)),
+        OwnedFormatItem::Optional(item) => {
+            OwnedFormatItem::Optional(Box::new(localize(*item, date, lang, region)))
+        }
+        item => item,
+    }
+}
+
 /// Format the `Format` error of the time crate in an appropriate way.
 fn format_time_format_error(error: Format) -> EcoString {
     match error {
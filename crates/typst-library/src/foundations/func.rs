@@ -329,7 +329,14 @@ impl Func {
             ),
             Repr::Plugin(func) => {
                 let inputs = args.all::<Bytes>()?;
-                let output = func.call(inputs).at(args.span)?;
+                let output = func
+                    .call(
+                        engine.world,
+                        TrackedMut::reborrow_mut(&mut engine.sink),
+                        args.span,
+                        inputs,
+                    )
+                    .at(args.span)?;
                 args.finish()?;
                 Ok(Value::Bytes(output))
             }
@@ -3,6 +3,7 @@
 use std::cmp::Ordering;
 
 use ecow::eco_format;
+use icu_collator::Collator;
 use typst_utils::Numeric;
 
 use crate::diag::{HintedStrResult, StrResult, bail};
@@ -495,12 +496,30 @@ pub fn compare(lhs: &Value, rhs: &Value) -> StrResult<Ordering> {
 
         (Duration(a), Duration(b)) => a.cmp(b),
         (Datetime(a), Datetime(b)) => try_cmp_datetimes(a, b)?,
-        (Array(a), Array(b)) => try_cmp_arrays(a.as_slice(), b.as_slice())?,
+        (Array(a), Array(b)) => try_cmp_arrays(a.as_slice(), b.as_slice(), compare)?,
 
         _ => mismatch!("cannot compare {} and {}", lhs, rhs),
     })
 }
 
+/// Compare two values like [`compare`], but order strings (also within
+/// arrays) with the given collator instead of by code point.
+pub fn compare_collated(
+    lhs: &Value,
+    rhs: &Value,
+    collator: &Collator,
+) -> StrResult<Ordering> {
+    match (lhs, rhs) {
+        (Value::Str(a), Value::Str(b)) => Ok(collator.compare(a, b)),
+        (Value::Array(a), Value::Array(b)) => {
+            try_cmp_arrays(a.as_slice(), b.as_slice(), |x, y| {
+                compare_collated(x, y, collator)
+            })
+        }
+        _ => compare(lhs, rhs),
+    }
+}
+
 /// Try to compare two values.
 fn try_cmp_values<T: PartialOrd + Repr>(a: &T, b: &T) -> StrResult<Ordering> {
     a.partial_cmp(b)
@@ -514,7 +533,11 @@ fn try_cmp_datetimes(a: &Datetime, b: &Datetime) -> StrResult<Ordering> {
 }
 
 /// Try to compare arrays of values lexicographically.
-fn try_cmp_arrays(a: &[Value], b: &[Value]) -> StrResult<Ordering> {
+fn try_cmp_arrays(
+    a: &[Value],
+    b: &[Value],
+    compare: impl Fn(&Value, &Value) -> StrResult<Ordering>,
+) -> StrResult<Ordering> {
     a.iter()
         .zip(b.iter())
         .find_map(|(first, second)| {
@@ -2,14 +2,17 @@ use std::fmt::{self, Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::sync::{Arc, Mutex};
 
+use comemo::{Tracked, TrackedMut};
 use ecow::{EcoString, eco_format};
-use typst_syntax::Spanned;
+use typst_syntax::{FileId, Span, Spanned};
 use wasmi::Memory;
 
-use crate::diag::{At, SourceResult, StrResult, bail};
-use crate::engine::Engine;
+use crate::World;
+use crate::diag::{At, SourceResult, StrResult, bail, warning};
+use crate::engine::{Engine, Sink};
 use crate::foundations::{Binding, Bytes, Func, Module, Scope, Value, cast, func, scope};
 use crate::loading::{DataSource, Load};
+use crate::text::FontVariant;
 
 /// Loads a WebAssembly module.
 ///
@@ -26,7 +29,13 @@ use crate::loading::{DataSource, Load};
 /// [data loading functions]($reference/data-loading).
 ///
 /// For security reasons, plugins run in isolation from your system. This means
-/// that printing, reading files, or similar things are not supported.
+/// that printing, reading files, or similar things are not supported. A plugin
+/// that is loaded with [`host: true`]($plugin.host) can, however, use the
+/// [host API]($plugin/#host-api) to read files from the project and to query
+/// fonts in the same way Typst code can.
+///
+/// Depending on how Typst is run, plugins may also be subject to
+/// [resource limits](#resource-limits).
 ///
 /// # Example
 /// ```example
@@ -72,6 +81,14 @@ use crate::loading::{DataSource, Load};
 /// will observe the side effects produced by the transition call. The original
 /// plugin remains unaffected.
 ///
+/// # Resource limits
+/// The environment that runs Typst can restrict how much work a plugin may do.
+/// When compiling with the CLI, the `--plugin-fuel` flag limits the number of
+/// WebAssembly instructions a single plugin call may execute and the
+/// `--plugin-max-memory` flag limits the size of a plugin's memory. A plugin
+/// call that exceeds one of these limits fails with an error. By default,
+/// plugins are not restricted.
+///
 /// # Plugins and Packages
 /// Any Typst code can make use of a plugin simply by including a WebAssembly
 /// file and loading it. However, because the byte-based plugin interface is
@@ -137,6 +154,37 @@ use crate::loading::{DataSource, Load};
 ///   immediately after this function returns. If the message should be
 ///   interpreted as an error message, it should be encoded as UTF-8.
 ///
+/// ## Host API
+/// Plugins that are loaded with [`host: true`]($plugin.host) may additionally
+/// import the following functions. Loading a plugin that imports them without
+/// enabling host access fails. All of them are deterministic: They only observe
+/// the same project files and fonts that Typst code can access, so the purity
+/// requirement is not affected.
+///
+/// - `(import "typst_host" "read_file" (func (param i32 i32) (result i32)))`
+///
+///   Reads a file. The parameters are a pointer to and the length of a UTF-8
+///   encoded path. Relative paths are resolved relative to the file that
+///   loaded the plugin. Returns the length of the file or `-1` if it could not
+///   be read. The file's contents are retrieved with `take_buffer`.
+///
+/// - `(import "typst_host" "find_font" (func (param i32 i32) (result i32)))`
+///
+///   Looks up the regular variant of a font family. The parameters are a
+///   pointer to and the length of a UTF-8 encoded family name. Returns the
+///   length of the font file or `-1` if no such font is available. The font
+///   file is retrieved with `take_buffer`.
+///
+/// - `(import "typst_host" "take_buffer" (func (param i32)))`
+///
+///   Writes the result of the last `read_file` or `find_font` call into a
+///   plugin-allocated buffer whose capacity is at least the returned length.
+///
+/// - `(import "typst_host" "warn" (func (param i32 i32)))`
+///
+///   Emits a warning at the plugin call. The parameters are a pointer to and
+///   the length of a UTF-8 encoded message.
+///
 /// # Resources
 /// For more resources, check out the [wasm-minimal-protocol
 /// repository](https://github.com/astrale-sharp/wasm-minimal-protocol). It
@@ -152,9 +200,16 @@ pub fn plugin(
     engine: &mut Engine,
     /// A [path]($syntax/#paths) to a WebAssembly file or raw WebAssembly bytes.
     source: Spanned<DataSource>,
+    /// Whether the plugin may use the [host API]($plugin/#host-api) to read
+    /// files and query fonts.
+    #[named]
+    #[default(false)]
+    host: bool,
 ) -> SourceResult<Module> {
     let loaded = source.load(engine.world)?;
-    Plugin::module(loaded.data).at(source.span)
+    let limits = engine.world.library().plugin_limits;
+    let access = host.then(|| HostAccess { file: source.span.id() });
+    Plugin::module(loaded.data, limits, access).at(source.span)
 }
 
 #[scope]
@@ -193,13 +248,21 @@ impl plugin {
     /// ```
     #[func]
     pub fn transition(
+        engine: &mut Engine,
+        span: Span,
         /// The plugin function to call.
         func: PluginFunc,
         /// The byte buffers to call the function with.
         #[variadic]
         arguments: Vec<Bytes>,
-    ) -> StrResult<Module> {
-        func.transition(arguments)
+    ) -> SourceResult<Module> {
+        func.transition(
+            engine.world,
+            TrackedMut::reborrow_mut(&mut engine.sink),
+            span,
+            arguments,
+        )
+        .at(span)
     }
 }
 
@@ -219,17 +282,86 @@ impl PluginFunc {
     }
 
     /// Call the WebAssembly function with the given arguments.
+    ///
+    /// The world and sink are used by the host API. Warnings emitted by the
+    /// plugin are attached to the given span.
     #[comemo::memoize]
     #[typst_macros::time(name = "call plugin")]
-    pub fn call(&self, args: Vec<Bytes>) -> StrResult<Bytes> {
-        self.plugin.call(&self.name, args)
+    pub fn call(
+        &self,
+        world: Tracked<dyn World + '_>,
+        sink: TrackedMut<Sink>,
+        span: Span,
+        args: Vec<Bytes>,
+    ) -> StrResult<Bytes> {
+        let mut host = Host { world, sink, span };
+        self.plugin.call(&self.name, args, &mut host)
     }
 
     /// Transition a plugin and turn the result into a module.
     #[comemo::memoize]
     #[typst_macros::time(name = "transition plugin")]
-    pub fn transition(&self, args: Vec<Bytes>) -> StrResult<Module> {
-        self.plugin.transition(&self.name, args).map(Plugin::into_module)
+    pub fn transition(
+        &self,
+        world: Tracked<dyn World + '_>,
+        sink: TrackedMut<Sink>,
+        span: Span,
+        args: Vec<Bytes>,
+    ) -> StrResult<Module> {
+        let mut host = Host { world, sink, span };
+        self.plugin
+            .transition(&self.name, args, &mut host)
+            .map(Plugin::into_module)
+    }
+}
+
+/// Resource limits for WebAssembly plugins.
+///
+/// Without limits, a plugin that loops forever or allocates without bound
+/// stalls or exhausts the whole compilation. Limits are configured through
+/// the [`Library`](crate::Library).
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PluginLimits {
+    /// The amount of fuel available to a single plugin call.
+    ///
+    /// Executing a WebAssembly instruction consumes roughly one unit of fuel.
+    /// Instantiating a plugin counts as a call, too. `None` means unlimited.
+    pub fuel: Option<u64>,
+    /// The maximum size of a plugin's memory in bytes. `None` means
+    /// unlimited.
+    pub memory: Option<usize>,
+}
+
+impl PluginLimits {
+    /// Turns an error produced by the WebAssembly runtime into a message,
+    /// taking into account which limits might have been hit.
+    fn describe(&self, err: wasmi::Error) -> EcoString {
+        self.exceeded(&err)
+            .unwrap_or_else(|| eco_format!("plugin panicked: {err}"))
+    }
+
+    /// Describes which limit an error produced by the WebAssembly runtime
+    /// indicates to be exceeded, if any.
+    fn exceeded(&self, err: &wasmi::Error) -> Option<EcoString> {
+        use wasmi::core::TrapCode;
+        use wasmi::errors::{ErrorKind, MemoryError};
+
+        // Running out of fuel traps. With a memory limit, a memory growth that
+        // exceeds it traps during execution and fails with a memory error
+        // during instantiation.
+        let memory = match (err.as_trap_code(), err.kind()) {
+            (Some(TrapCode::OutOfFuel), _) => {
+                return Some(eco_format!(
+                    "plugin exceeded its fuel limit of {}",
+                    self.fuel.unwrap_or_default(),
+                ));
+            }
+            (Some(TrapCode::GrowthOperationLimited), _)
+            | (_, ErrorKind::Memory(MemoryError::OutOfBoundsGrowth)) => self.memory?,
+            _ => return None,
+        };
+
+        Some(eco_format!("plugin exceeded its memory limit of {memory} bytes"))
     }
 }
 
@@ -262,17 +394,29 @@ impl Plugin {
     /// Create a plugin and turn it into a module.
     #[comemo::memoize]
     #[typst_macros::time(name = "load plugin")]
-    fn module(bytes: Bytes) -> StrResult<Module> {
-        Self::new(bytes).map(Self::into_module)
+    fn module(
+        bytes: Bytes,
+        limits: PluginLimits,
+        access: Option<HostAccess>,
+    ) -> StrResult<Module> {
+        Self::new(bytes, limits, access).map(Self::into_module)
     }
 
     /// Create a new plugin from raw WebAssembly bytes.
-    fn new(bytes: Bytes) -> StrResult<Self> {
+    fn new(
+        bytes: Bytes,
+        limits: PluginLimits,
+        access: Option<HostAccess>,
+    ) -> StrResult<Self> {
         let mut config = wasmi::Config::default();
 
         // Disable relaxed SIMD as it can introduce non-determinism.
         config.wasm_relaxed_simd(false);
 
+        // Only meter fuel if there is a limit since metering slows down
+        // execution.
+        config.consume_fuel(limits.fuel.is_some());
+
         let engine = wasmi::Engine::new(&config);
         let module = wasmi::Module::new(&engine, bytes.as_slice())
             .map_err(|err| format!("failed to load WebAssembly module ({err})"))?;
@@ -282,6 +426,17 @@ impl Plugin {
             bail!("plugin does not export its memory");
         }
 
+        // Ensure that the plugin only uses the host API if it was granted
+        // access.
+        if access.is_none()
+            && module.imports().any(|import| import.module() == "typst_host")
+        {
+            bail!(
+                "plugin uses the host API, but host access was not enabled \
+                 (load it with `host: true` to enable it)"
+            );
+        }
+
         let mut linker = wasmi::Linker::new(&engine);
         linker
             .func_wrap(
@@ -298,7 +453,20 @@ impl Plugin {
             )
             .unwrap();
 
-        let base = Arc::new(PluginBase { bytes, linker, module });
+        if access.is_some() {
+            linker
+                .func_wrap("typst_host", "read_file", typst_host_read_file)
+                .unwrap();
+            linker
+                .func_wrap("typst_host", "find_font", typst_host_find_font)
+                .unwrap();
+            linker
+                .func_wrap("typst_host", "take_buffer", typst_host_take_buffer)
+                .unwrap();
+            linker.func_wrap("typst_host", "warn", typst_host_warn).unwrap();
+        }
+
+        let base = Arc::new(PluginBase { bytes, limits, access, linker, module });
         let instance = PluginInstance::new(&base, None)?;
 
         Ok(Self {
@@ -310,14 +478,14 @@ impl Plugin {
     }
 
     /// Execute a function with access to an instsance.
-    fn call(&self, func: &str, args: Vec<Bytes>) -> StrResult<Bytes> {
+    fn call(&self, func: &str, args: Vec<Bytes>, host: &mut Host) -> StrResult<Bytes> {
         // Acquire an instance from the pool (potentially creating a new one).
         let mut instance = self.acquire()?;
 
         // Execute the call on an instance from the pool. If the call fails, we
         // return early and _don't_ return the instance to the pool as it might
         // be irrecoverably damaged.
-        let output = instance.call(func, args)?;
+        let output = instance.call(func, args, host)?;
 
         // Return the instance to the pool.
         self.pool.lock().unwrap().push(instance);
@@ -327,7 +495,12 @@ impl Plugin {
 
     /// Call a mutable plugin function, producing a new mutable whose functions
     /// are guaranteed to be able to observe the mutation.
-    fn transition(&self, func: &str, args: Vec<Bytes>) -> StrResult<Plugin> {
+    fn transition(
+        &self,
+        func: &str,
+        args: Vec<Bytes>,
+        host: &mut Host,
+    ) -> StrResult<Plugin> {
         // Derive a new transition hash from the old one and the function and arguments.
         let fingerprint = typst_utils::hash128(&(self.fingerprint, func, &args));
 
@@ -336,7 +509,7 @@ impl Plugin {
 
         // Call the function. If the call fails, we return early and _don't_
         // return the instance to the pool as it might be irrecoverably damaged.
-        instance.call(func, args)?;
+        instance.call(func, args, host)?;
 
         // Snapshot the instance after the mutable call.
         let snapshot = instance.snapshot();
@@ -390,13 +563,18 @@ impl Debug for Plugin {
 
 impl PartialEq for Plugin {
     fn eq(&self, other: &Self) -> bool {
-        self.base.bytes == other.base.bytes && self.fingerprint == other.fingerprint
+        self.base.bytes == other.base.bytes
+            && self.base.limits == other.base.limits
+            && self.base.access == other.base.access
+            && self.fingerprint == other.fingerprint
     }
 }
 
 impl Hash for Plugin {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.base.bytes.hash(state);
+        self.base.limits.hash(state);
+        self.base.access.hash(state);
         self.fingerprint.hash(state);
     }
 }
@@ -405,6 +583,10 @@ impl Hash for Plugin {
 struct PluginBase {
     /// The raw WebAssembly bytes.
     bytes: Bytes,
+    /// The resource limits for all instances.
+    limits: PluginLimits,
+    /// Whether and how the plugin may use the host API.
+    access: Option<HostAccess>,
     /// The compiled WebAssembly module.
     module: wasmi::Module,
     /// A linker used to create a `Store` for execution.
@@ -417,6 +599,10 @@ struct PluginInstance {
     instance: wasmi::Instance,
     /// The execution store of this concrete plugin instance.
     store: wasmi::Store<CallData>,
+    /// The resource limits of this instance.
+    limits: PluginLimits,
+    /// Whether and how the plugin may use the host API.
+    access: Option<HostAccess>,
 }
 
 /// A snapshot of a plugin instance.
@@ -432,13 +618,28 @@ impl PluginInstance {
     /// a snapshot.
     #[typst_macros::time(name = "create plugin instance")]
     fn new(base: &PluginBase, snapshot: Option<&Snapshot>) -> StrResult<PluginInstance> {
-        let mut store = wasmi::Store::new(base.linker.engine(), CallData::default());
-        let instance = base
-            .linker
-            .instantiate_and_start(&mut store, &base.module)
-            .map_err(|e| eco_format!("{e}"))?;
+        let limits = base.limits;
+        let mut data = CallData::default();
+        if let Some(memory) = limits.memory {
+            data.limits = wasmi::StoreLimitsBuilder::new()
+                .memory_size(memory)
+                .trap_on_grow_failure(true)
+                .build();
+        }
 
-        let mut instance = PluginInstance { instance, store };
+        let mut store = wasmi::Store::new(base.linker.engine(), data);
+        store.limiter(|data| &mut data.limits);
+        if let Some(fuel) = limits.fuel {
+            store.set_fuel(fuel).unwrap();
+        }
+
+        let instance =
+            base.linker.instantiate_and_start(&mut store, &base.module).map_err(
+                |err| limits.exceeded(&err).unwrap_or_else(|| eco_format!("{err}")),
+            )?;
+
+        let mut instance =
+            PluginInstance { instance, store, limits, access: base.access };
         if let Some(snapshot) = snapshot {
             instance.restore(snapshot);
         }
@@ -446,7 +647,12 @@ impl PluginInstance {
     }
 
     /// Call a plugin function with byte arguments.
-    fn call(&mut self, func: &str, args: Vec<Bytes>) -> StrResult<Bytes> {
+    fn call(
+        &mut self,
+        func: &str,
+        args: Vec<Bytes>,
+        host: &mut Host,
+    ) -> StrResult<Bytes> {
         let handle = self
             .instance
             .get_export(&self.store, func)
@@ -487,11 +693,38 @@ impl PluginInstance {
         // Store the input data.
         self.store.data_mut().args = args;
 
-        // Call the function.
+        // Refuel the store so that each call has the full budget.
+        if let Some(fuel) = self.limits.fuel {
+            self.store.set_fuel(fuel).unwrap();
+        }
+
+        // Call the function. Calls to the host API interrupt the execution
+        // such that we can serve them with access to the world.
         let mut code = wasmi::Val::I32(-1);
-        handle
-            .call(&mut self.store, &lengths, std::slice::from_mut(&mut code))
-            .map_err(|err| eco_format!("plugin panicked: {err}"))?;
+        let mut state = handle
+            .call_resumable(&mut self.store, &lengths, std::slice::from_mut(&mut code))
+            .map_err(|err| self.limits.describe(err))?;
+
+        loop {
+            let invocation = match state {
+                wasmi::ResumableCall::Finished => break,
+                wasmi::ResumableCall::HostTrap(invocation) => invocation,
+                wasmi::ResumableCall::OutOfFuel(_) => bail!(
+                    "plugin exceeded its fuel limit of {}",
+                    self.limits.fuel.unwrap_or_default(),
+                ),
+            };
+
+            let Some(request) = invocation.host_error().downcast_ref::<HostRequest>()
+            else {
+                return Err(self.limits.describe(invocation.into_host_error()));
+            };
+
+            let result = self.serve(request, host);
+            state = invocation
+                .resume(&mut self.store, &result, std::slice::from_mut(&mut code))
+                .map_err(|err| self.limits.describe(err))?;
+        }
 
         if let Some(MemoryError { offset, length, write }) =
             self.store.data_mut().memory_error.take()
@@ -521,6 +754,32 @@ impl PluginInstance {
         Ok(Bytes::new(output))
     }
 
+    /// Serves a request to the host API, returning the results with which
+    /// execution should be resumed.
+    fn serve(&mut self, request: &HostRequest, host: &mut Host) -> Vec<wasmi::Val> {
+        let file = self.access.and_then(|access| access.file);
+        let data = match request {
+            HostRequest::ReadFile(path) => file
+                .map(|file| file.join(path))
+                .and_then(|id| host.world.file(id).ok()),
+            HostRequest::FindFont(family) => host
+                .world
+                .book()
+                .select(&family.to_lowercase(), FontVariant::default())
+                .and_then(|index| host.world.font(index))
+                .map(|font| font.data().clone()),
+            HostRequest::Warn(message) => {
+                host.sink.warn(warning!(host.span, "{message}"));
+                return vec![];
+            }
+        };
+
+        let data = data.filter(|data| data.len() <= i32::MAX as usize);
+        let length = data.as_ref().map_or(-1, |data| data.len() as i32);
+        self.store.data_mut().buffer = data;
+        vec![wasmi::Val::I32(length)]
+    }
+
     /// Creates a snapshot of this instance from which another one can be
     /// initialized.
     #[typst_macros::time(name = "save snapshot")]
@@ -565,8 +824,50 @@ struct CallData {
     output: Vec<u8>,
     /// A memory error that occurred during execution of the current call.
     memory_error: Option<MemoryError>,
+    /// Limits the growth of the plugin's memory.
+    limits: wasmi::StoreLimits,
+    /// The result of the last host API call, to be retrieved with
+    /// `take_buffer`.
+    buffer: Option<Bytes>,
+}
+
+/// How a plugin may use the host API.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+struct HostAccess {
+    /// The file relative to which paths are resolved.
+    file: Option<FileId>,
+}
+
+/// Access to the compilation environment while serving host API calls.
+struct Host<'a> {
+    /// The world from which files and fonts are retrieved.
+    world: Tracked<'a, dyn World + 'a>,
+    /// The sink into which warnings are emitted.
+    sink: TrackedMut<'a, Sink>,
+    /// The span of the plugin call.
+    span: Span,
 }
 
+/// A host API call that interrupts the plugin's execution to be served with
+/// access to the world.
+#[derive(Debug)]
+enum HostRequest {
+    /// Read the file at the given path.
+    ReadFile(EcoString),
+    /// Find the regular variant of the given font family.
+    FindFont(EcoString),
+    /// Emit a warning.
+    Warn(EcoString),
+}
+
+impl fmt::Display for HostRequest {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("plugin used the host API outside of a function call")
+    }
+}
+
+impl wasmi::errors::HostError for HostRequest {}
+
 /// If there was an error reading/writing memory, keep the offset + length to
 /// display an error message.
 struct MemoryError {
@@ -612,3 +913,68 @@ fn wasm_minimal_protocol_send_result_to_host(
     }
     caller.data_mut().output = buffer;
 }
+
+/// Reads a UTF-8 string from the plugin's memory.
+fn read_str(
+    caller: &mut wasmi::Caller<CallData>,
+    ptr: u32,
+    len: u32,
+) -> Option<EcoString> {
+    let memory = caller.get_export("memory").unwrap().into_memory().unwrap();
+    let mut buffer = vec![0; len as usize];
+    if memory.read(&*caller, ptr as _, &mut buffer).is_err() {
+        caller.data_mut().memory_error =
+            Some(MemoryError { offset: ptr, length: len, write: false });
+        return None;
+    }
+    Some(String::from_utf8_lossy(&buffer).into())
+}
+
+/// Reads a file from the world.
+fn typst_host_read_file(
+    mut caller: wasmi::Caller<CallData>,
+    ptr: u32,
+    len: u32,
+) -> Result<i32, wasmi::Error> {
+    match read_str(&mut caller, ptr, len) {
+        Some(path) => Err(wasmi::Error::host(HostRequest::ReadFile(path))),
+        None => Ok(-1),
+    }
+}
+
+/// Looks up a font in the world's font book.
+fn typst_host_find_font(
+    mut caller: wasmi::Caller<CallData>,
+    ptr: u32,
+    len: u32,
+) -> Result<i32, wasmi::Error> {
+    match read_str(&mut caller, ptr, len) {
+        Some(family) => Err(wasmi::Error::host(HostRequest::FindFont(family))),
+        None => Ok(-1),
+    }
+}
+
+/// Writes the result of the last host API call into the plugin's memory.
+fn typst_host_take_buffer(mut caller: wasmi::Caller<CallData>, ptr: u32) {
+    let memory = caller.get_export("memory").unwrap().into_memory().unwrap();
+    let Some(buffer) = caller.data_mut().buffer.take() else { return };
+    if memory.write(&mut caller, ptr as _, buffer.as_slice()).is_err() {
+        caller.data_mut().memory_error = Some(MemoryError {
+            offset: ptr,
+            length: buffer.len() as u32,
+            write: true,
+        });
+    }
+}
+
+/// Emits a warning at the plugin call.
+fn typst_host_warn(
+    mut caller: wasmi::Caller<CallData>,
+    ptr: u32,
+    len: u32,
+) -> Result<(), wasmi::Error> {
+    match read_str(&mut caller, ptr, len) {
+        Some(message) => Err(wasmi::Error::host(HostRequest::Warn(message))),
+        None => Ok(()),
+    }
+}
@@ -917,10 +917,7 @@ fn captures_to_dict(cap: regex::Captures) -> Dict {
         "start" => m.start(),
         "end" => m.end(),
         "text" => m.as_str(),
-        "captures" =>  cap.iter()
-            .skip(1)
-            .map(|opt| opt.map_or(Value::None, |m| m.as_str().into_value()))
-            .collect::<Array>(),
+        "captures" => Regex::positional_captures(&cap),
     }
 }
 
@@ -970,6 +967,27 @@ fn string_is_empty() -> EcoString {
 ///
 /// The numbers 1 to 10.
 /// ```
+///
+/// # Capture groups in show rules
+/// When a show rule with a regex selector is given a function with two
+/// parameters, the second one receives a dictionary with details about the
+/// match. It has the following keys:
+///
+/// - `text`: The text that matched.
+/// - `captures`: An array containing a string for each capturing group, or
+///   `{none}` if the group did not participate in the match.
+/// - `named-captures`: A dictionary mapping the name of each named capturing
+///   group to its string, or to `{none}` if it did not participate in the
+///   match.
+///
+/// ```example
+/// #show regex("([A-Z]+)-(\d+)"): (it, m) => {
+///   let (project, number) = m.captures
+///   link("https://example.com/" + project + "/" + number, it)
+/// }
+///
+/// See ABC-123 and XY-7.
+/// ```
 #[ty(scope)]
 #[derive(Debug, Clone)]
 pub struct Regex(regex::Regex);
@@ -979,6 +997,29 @@ impl Regex {
     pub fn new(re: &str) -> StrResult<Self> {
         regex::Regex::new(re).map(Self).map_err(|err| eco_format!("{err}"))
     }
+
+    /// The text of each capturing group of a match, or `None` for groups that
+    /// did not participate in it. Does not include the whole match.
+    pub fn positional_captures(cap: &regex::Captures) -> Array {
+        cap.iter()
+            .skip(1)
+            .map(|opt| opt.map_or(Value::None, |m| m.as_str().into_value()))
+            .collect()
+    }
+
+    /// The text of each named capturing group of a match, or `None` for groups
+    /// that did not participate in it.
+    pub fn named_captures(&self, cap: &regex::Captures) -> Dict {
+        self.0
+            .capture_names()
+            .flatten()
+            .map(|name| {
+                let value =
+                    cap.name(name).map_or(Value::None, |m| m.as_str().into_value());
+                (name.into(), value)
+            })
+            .collect()
+    }
 }
 
 #[scope]
@@ -1000,6 +1041,76 @@ impl Regex {
     ) -> SourceResult<Regex> {
         Self::new(&regex.v).at(regex.span)
     }
+
+    /// Searches for the first match of the regular expression in a string and
+    /// returns a dictionary with details about it, or `{none}` if there is no
+    /// match.
+    ///
+    /// The dictionary has the same shape as the one returned by
+    /// [`str.match`] with an additional `named-captures` key. It maps the name
+    /// of each named capturing group to the text it matched (or `{none}` if it
+    /// did not participate in the match).
+    ///
+    /// ```example
+    /// #let date = regex("(?<year>\d{4})-(?<month>\d{2})")
+    /// #let m = date.captures("Due 2024-05")
+    /// #m.captures \
+    /// #m.named-captures.year
+    /// ```
+    #[func(name = "captures")]
+    pub fn first_captures(
+        &self,
+        /// The string to search in.
+        text: Str,
+    ) -> Option<Dict> {
+        self.0.captures(&text).map(|cap| {
+            let named = self.named_captures(&cap);
+            let mut dict = captures_to_dict(cap);
+            dict.insert("named-captures".into(), named.into_value());
+            dict
+        })
+    }
+
+    /// Splits a string at matches of the regular expression and returns an
+    /// array of the resulting parts.
+    ///
+    /// ```example
+    /// #let sep = regex("\s*[,;]\s*")
+    /// #sep.split("a, b; c") \
+    /// #sep.split("a, b; c", limit: 2)
+    /// ```
+    #[func(name = "split")]
+    pub fn split_str(
+        &self,
+        /// The string to split.
+        text: Str,
+        /// The maximum number of parts to return. The last part contains the
+        /// rest of the string, including any further matches.
+        #[named]
+        limit: Option<usize>,
+    ) -> Array {
+        let to_value = |part: &str| Value::Str(part.into());
+        match limit {
+            Some(limit) => self.0.splitn(&text, limit).map(to_value).collect(),
+            None => self.0.split(&text).map(to_value).collect(),
+        }
+    }
+
+    /// Escapes all characters in a string that have a special meaning in
+    /// regular expressions, so that the resulting regular expression matches
+    /// the string literally.
+    ///
+    /// ```example
+    /// #let price = "$4.99 (incl. tax)"
+    /// #"Only $4.99 (incl. tax)!".contains(regex(regex.escape(price)))
+    /// ```
+    #[func]
+    pub fn escape(
+        /// The string to escape.
+        text: Str,
+    ) -> Str {
+        regex::escape(&text).into()
+    }
 }
 
 impl Deref for Regex {
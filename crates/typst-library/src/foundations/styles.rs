@@ -1019,6 +1019,7 @@ impl NativeRuleMap {
         rules.register_empty::<crate::introspection::StateUpdateElem>();
         rules.register_empty::<crate::introspection::MetadataElem>();
         rules.register_empty::<crate::model::PrefixInfo>();
+        rules.register_empty::<crate::model::IndexEntry>();
 
         rules
     }
@@ -6,10 +6,9 @@ use crate::layout::{Length, Ratio, Rel};
 /// Separates a region into multiple equally sized columns.
 ///
 /// The `column` function lets you separate the interior of any container into
-/// multiple columns. It will currently not balance the height of the columns.
-/// Instead, the columns will take up the height of their container or the
-/// remaining height on the page. Support for balanced columns is planned for
-/// the future.
+/// multiple columns. By default, the columns will take up the height of their
+/// container or the remaining height on the page. To distribute the content
+/// evenly across the columns instead, enable [balancing]($columns.balance).
 ///
 /// # Page-level columns { #page-level }
 /// If you need to insert columns across your whole document, use the `{page}`
@@ -50,6 +49,29 @@ pub struct ColumnsElem {
     #[default(Ratio::new(0.04).into())]
     pub gutter: Rel<Length>,
 
+    /// Whether to balance the height of the columns.
+    ///
+    /// When enabled, the content in the final region (e.g. the last page of a
+    /// multi-column section) is distributed evenly across the columns instead
+    /// of filling them one after another. Columns in earlier regions are
+    /// filled as usual.
+    ///
+    /// For [page-level columns]($page.columns), use the
+    /// [`balance`]($page.balance) parameter of the page function instead.
+    ///
+    /// ```example
+    /// #set columns(balance: true)
+    /// #rect(columns(2)[
+    ///   Balanced columns distribute
+    ///   their content evenly, so that
+    ///   the last page of a section
+    ///   does not end with one full
+    ///   and one nearly empty column.
+    /// ])
+    /// ```
+    #[default(false)]
+    pub balance: bool,
+
     /// The content that should be layouted into the columns.
     #[required]
     pub body: Content,
@@ -11,6 +11,7 @@ use crate::foundations::{Dict, Label, Value, cast, dict};
 use crate::introspection::{Location, Tag};
 use crate::layout::{Abs, Axes, FixedAlignment, Length, Point, Size, Transform};
 use crate::model::Destination;
+use crate::pdf::{Annotation, Layer, Widget};
 use crate::text::TextItem;
 use crate::visualize::{Color, Curve, FixedStroke, Geometry, Image, Paint, Shape};
 
@@ -352,6 +353,13 @@ impl Frame {
         }
     }
 
+    /// Assign the frame's contents to a PDF layer.
+    pub fn assign_layer(&mut self, layer: Layer) {
+        if !self.is_empty() {
+            self.group(|g| g.layer = Some(layer));
+        }
+    }
+
     /// Wrap the frame's contents in a group and modify that group with `f`.
     fn group<F>(&mut self, f: F)
     where
@@ -474,6 +482,10 @@ pub enum FrameItem {
     Image(Image, Size, Span),
     /// An internal or external link to a destination.
     Link(Destination, Size),
+    /// The interactive part of a PDF form field and its size.
+    Widget(Widget, Size),
+    /// A PDF annotation and the size of the area it covers.
+    Annotation(Annotation, Size),
     /// An introspectable element that produced something within this frame.
     Tag(Tag),
 }
@@ -486,6 +498,10 @@ impl Debug for FrameItem {
             Self::Shape(shape, _) => write!(f, "{shape:?}"),
             Self::Image(image, _, _) => write!(f, "{image:?}"),
             Self::Link(dest, _) => write!(f, "Link({dest:?})"),
+            Self::Widget(widget, _) => write!(f, "Widget({:?})", widget.name),
+            Self::Annotation(annotation, _) => {
+                write!(f, "Annotation({:?})", annotation.text)
+            }
             Self::Tag(tag) => write!(f, "{tag:?}"),
         }
     }
@@ -507,6 +523,8 @@ pub struct GroupItem {
     /// thought of as inserting the elements at the end but still inside of the
     /// parent.
     pub parent: Option<FrameParent>,
+    /// The PDF layer the group's contents belong to.
+    pub layer: Option<Layer>,
 }
 
 impl GroupItem {
@@ -518,6 +536,7 @@ impl GroupItem {
             clip: None,
             label: None,
             parent: None,
+            layer: None,
         }
     }
 }
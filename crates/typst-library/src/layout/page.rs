@@ -2,18 +2,18 @@ use std::num::NonZeroUsize;
 use std::ops::RangeInclusive;
 use std::str::FromStr;
 
-use typst_utils::{NonZeroExt, Scalar, singleton};
+use typst_utils::{NonZeroExt, Numeric, Scalar, singleton};
 
 use crate::diag::{SourceResult, bail};
 use crate::engine::Engine;
 use crate::foundations::{
-    Args, AutoValue, Cast, Construct, Content, Dict, Fold, NativeElement, Set, Smart,
-    Value, cast, elem,
+    Args, AutoValue, Cast, Construct, Content, Dict, Fold, NativeElement, NoneValue, Set,
+    Smart, Value, cast, dict, elem,
 };
 use crate::introspection::Introspector;
 use crate::layout::{
-    Abs, Alignment, FlushElem, Frame, HAlignment, Length, OuterVAlignment, Ratio, Rel,
-    Sides, SpecificAlignment,
+    Abs, Alignment, FlushElem, Frame, HAlignment, Length, OuterVAlignment, Point, Ratio,
+    Rect, Rel, Sides, SpecificAlignment,
 };
 use crate::model::{DocumentInfo, Numbering};
 use crate::text::LocalName;
@@ -166,10 +166,90 @@ pub struct PageElem {
     #[ghost]
     pub binding: Smart<Binding>,
 
+    /// How far the page's background extends beyond its trimmed size.
+    ///
+    /// When a document is commercially printed, it is usually printed on a
+    /// larger sheet that is then cut down to the final page size. To ensure
+    /// that backgrounds reaching to the edge of the page don't leave a thin
+    /// white line in case the cut is slightly off, they are printed a bit
+    /// beyond the final page size. This extra area is called the bleed.
+    ///
+    /// The page's [fill]($page.fill), [background]($page.background), and
+    /// [foreground]($page.foreground) extend into the bleed. The latter two
+    /// are laid out relative to the page including its bleed, so [placing]($place)
+    /// something at the `{top + left}` of the background will put it into the
+    /// top left corner of the bleed. All other content is positioned relative
+    /// to the trimmed page, as usual.
+    ///
+    /// By default, pages have no bleed. The bleed can be specified as follows:
+    /// - A single length: The same bleed on all sides.
+    /// - A dictionary: With a dictionary, the bleed of the page's sides can be
+    ///   set individually. The dictionary can contain the following keys in
+    ///   order of precedence:
+    ///   - `top`: The top bleed.
+    ///   - `right`: The right bleed.
+    ///   - `bottom`: The bottom bleed.
+    ///   - `left`: The left bleed.
+    ///   - `x`: The horizontal bleed.
+    ///   - `y`: The vertical bleed.
+    ///   - `rest`: The bleed on all sides except those for which the
+    ///     dictionary explicitly sets a size.
+    ///
+    /// In PDF export, the trimmed page and the page including its bleed are
+    /// written as the page's `TrimBox` and `BleedBox`, respectively.
+    ///
+    /// ```example
+    /// #set page(
+    ///   width: 4cm,
+    ///   height: 3cm,
+    ///   bleed: 3mm,
+    ///   marks: (crop: true),
+    ///   fill: aqua,
+    /// )
+    ///
+    /// A page with bleed.
+    /// ```
+    #[fold]
+    #[ghost]
+    pub bleed: Sides<Option<Length>>,
+
+    /// Which printer's marks to draw around the page.
+    ///
+    /// Printer's marks are drawn in an additional area outside of the page's
+    /// [bleed]($page.bleed). They help the print shop to trim the printed
+    /// sheets and to check the alignment of the printing plates.
+    ///
+    /// - `{none}`: No printer's marks.
+    /// - A dictionary: With a dictionary, individual marks can be enabled. The
+    ///   dictionary can contain the following keys:
+    ///   - `crop`: Whether to draw crop marks, which indicate where the page
+    ///     will be trimmed.
+    ///   - `registration`: Whether to draw registration marks at the center
+    ///     of each side, which are used to align the printing plates.
+    ///   - `color-bar`: Whether to draw a colour bar with swatches of the
+    ///     process colours, which is used to check ink densities.
+    ///
+    /// The marks are drawn in registration colour, which prints on all
+    /// printing plates.
+    ///
+    /// ```example
+    /// #set page(
+    ///   width: 4cm,
+    ///   height: 3cm,
+    ///   bleed: 2mm,
+    ///   marks: (crop: true, registration: true),
+    /// )
+    ///
+    /// Ready for the print shop.
+    /// ```
+    #[ghost]
+    pub marks: PrintMarks,
+
     /// How many columns the page has.
     ///
     /// If you need to insert columns into a page or other container, you can
-    /// also use the [`columns` function]($columns).
+    /// also use the [`columns` function]($columns). The gutter between page
+    /// columns is configured through set rules on that function.
     ///
     /// ```example:single
     /// #set page(columns: 2, height: 4.8cm)
@@ -187,6 +267,25 @@ pub struct PageElem {
     #[ghost]
     pub columns: NonZeroUsize,
 
+    /// Whether to balance the height of the page's columns.
+    ///
+    /// When enabled, the content on the last page before a page break (or the
+    /// end of the document) is distributed evenly across the columns instead
+    /// of filling them one after another. This works like the
+    /// [`balance`]($columns.balance) parameter of the `columns` function.
+    ///
+    /// ```example:single
+    /// #set page(columns: 2, balance: true, height: 4.8cm)
+    /// Climate change is one of the most
+    /// pressing issues of our time, with
+    /// the potential to devastate
+    /// communities, ecosystems, and
+    /// economies around the world.
+    /// ```
+    #[default(false)]
+    #[ghost]
+    pub balance: bool,
+
     /// The page's background fill.
     ///
     /// Setting this to something non-transparent instructs the printer to color
@@ -505,6 +604,11 @@ pub struct Page {
     /// The logical page number (controlled by `counter(page)` and may thus not
     /// match the physical number).
     pub number: u64,
+    /// How far the page's bleed extends beyond the trimmed page on each side.
+    pub bleed: Sides<Abs>,
+    /// How far the area hosting printer's marks extends beyond the page's
+    /// bleed on each side.
+    pub slug: Sides<Abs>,
 }
 
 impl Page {
@@ -521,6 +625,78 @@ impl Page {
     pub fn fill_or_white(&self) -> Option<Paint> {
         self.fill.clone().unwrap_or_else(|| Some(Color::WHITE.into()))
     }
+
+    /// The full extent of the page, including its bleed and printer's marks.
+    ///
+    /// This is the same as the bounds of the page's frame.
+    pub fn media_box(&self) -> Rect {
+        Rect::from_pos_size(Point::zero(), self.frame.size())
+    }
+
+    /// The area of the page including its bleed, but without the area hosting
+    /// the printer's marks.
+    pub fn bleed_box(&self) -> Rect {
+        Rect::new(
+            Point::new(self.slug.left, self.slug.top),
+            self.frame.size().to_point() - Point::new(self.slug.right, self.slug.bottom),
+        )
+    }
+
+    /// The area of the final page after trimming.
+    pub fn trim_box(&self) -> Rect {
+        let bleed = self.bleed_box();
+        Rect::new(
+            bleed.min + Point::new(self.bleed.left, self.bleed.top),
+            bleed.max - Point::new(self.bleed.right, self.bleed.bottom),
+        )
+    }
+
+    /// Whether the page's frame extends beyond its trimmed size.
+    pub fn has_bleed_or_slug(&self) -> bool {
+        !self.bleed.iter().chain(self.slug.iter()).all(|side| side.is_zero())
+    }
+}
+
+/// Which printer's marks to draw around a page.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PrintMarks {
+    /// Whether to draw crop marks.
+    pub crop: bool,
+    /// Whether to draw registration marks.
+    pub registration: bool,
+    /// Whether to draw a colour bar.
+    pub color_bar: bool,
+}
+
+impl PrintMarks {
+    /// Whether any marks are enabled.
+    pub fn any(&self) -> bool {
+        self.crop || self.registration || self.color_bar
+    }
+}
+
+cast! {
+    PrintMarks,
+    self => if self.any() {
+        dict![
+            "crop" => self.crop,
+            "registration" => self.registration,
+            "color-bar" => self.color_bar,
+        ].into_value()
+    } else {
+        Value::None
+    },
+    _: NoneValue => Self::default(),
+    mut dict: Dict => {
+        let mut take = |key| dict.take(key).ok().map(Value::cast).transpose();
+        let marks = Self {
+            crop: take("crop")?.unwrap_or(false),
+            registration: take("registration")?.unwrap_or(false),
+            color_bar: take("color-bar")?.unwrap_or(false),
+        };
+        dict.finish(&["crop", "registration", "color-bar"])?;
+        marks
+    },
 }
 
 /// Specification of the page's margins.
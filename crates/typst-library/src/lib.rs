@@ -33,7 +33,9 @@ use typst_syntax::{FileId, Source, Span};
 use typst_utils::{LazyHash, SmallBitSet};
 
 use crate::diag::FileResult;
-use crate::foundations::{Array, Binding, Bytes, Datetime, Dict, Module, Scope, Styles};
+use crate::foundations::{
+    Array, Binding, Bytes, Datetime, Dict, Module, PluginLimits, Scope, Styles,
+};
 use crate::layout::{Alignment, Dir};
 use crate::routines::Routines;
 use crate::text::{Font, FontBook};
@@ -157,6 +159,8 @@ pub struct Library {
     pub std: Binding,
     /// In-development features that were enabled.
     pub features: Features,
+    /// Resource limits for WebAssembly plugins.
+    pub plugin_limits: PluginLimits,
 }
 
 /// Configurable builder for the standard library.
@@ -167,6 +171,7 @@ pub struct LibraryBuilder {
     routines: &'static Routines,
     inputs: Option<Dict>,
     features: Features,
+    plugin_limits: PluginLimits,
 }
 
 impl LibraryBuilder {
@@ -177,6 +182,7 @@ impl LibraryBuilder {
             routines,
             inputs: None,
             features: Features::default(),
+            plugin_limits: PluginLimits::default(),
         }
     }
 
@@ -194,6 +200,15 @@ impl LibraryBuilder {
         self
     }
 
+    /// Configure resource limits for WebAssembly plugins.
+    ///
+    /// By default, plugins are not limited. Limits should be set when
+    /// compiling untrusted documents.
+    pub fn with_plugin_limits(mut self, limits: PluginLimits) -> Self {
+        self.plugin_limits = limits;
+        self
+    }
+
     /// Consumes the builder and returns a `Library`.
     pub fn build(self) -> Library {
         let math = math::module();
@@ -205,6 +220,7 @@ impl LibraryBuilder {
             styles: Styles::new(),
             std: Binding::detached(global),
             features: self.features,
+            plugin_limits: self.plugin_limits,
         }
     }
 }
@@ -1,9 +1,14 @@
 use az::SaturatingAs;
+use ecow::{EcoString, eco_format};
 use typst_syntax::Spanned;
 
-use crate::diag::{LineCol, LoadError, LoadedWithin, ReportPos, SourceResult, bail};
+use crate::diag::{
+    At, LineCol, LoadError, LoadedWithin, ReportPos, SourceResult, StrResult, bail,
+};
 use crate::engine::Engine;
-use crate::foundations::{Array, Dict, IntoValue, Type, Value, cast, func, scope};
+use crate::foundations::{
+    Array, Dict, IntoValue, Repr, Smart, Str, Type, Value, cast, func, repr, scope,
+};
 use crate::loading::{DataSource, Load, Readable};
 
 /// Reads structured data from a CSV file.
@@ -121,6 +126,149 @@ impl csv {
     ) -> SourceResult<Array> {
         csv(engine, data.map(Readable::into_source), delimiter, row_type)
     }
+
+    /// Encodes structured data into a CSV string.
+    ///
+    /// ```example
+    /// #csv.encode((
+    ///   (name: "Debby", species: "Rhinoceros"),
+    ///   (name: "Fluffy", species: "Tiger"),
+    /// ))
+    /// ```
+    #[func(title = "Encode CSV")]
+    pub fn encode(
+        /// Value to be encoded.
+        ///
+        /// The rows can either all be arrays of fields or all be dictionaries
+        /// mapping from header keys to fields. Fields can be strings, numbers,
+        /// booleans, or `{none}` for an empty field. Numbers are written in
+        /// the same way as by the [`str`] constructor.
+        value: Spanned<Array>,
+        /// The delimiter that separates columns in the CSV string.
+        /// Must be a single ASCII character.
+        #[named]
+        #[default]
+        delimiter: Delimiter,
+        /// The header row.
+        ///
+        /// - If set to `{auto}`, rows that are dictionaries are preceded by a
+        ///   header with their keys in order of first appearance. Rows that are
+        ///   arrays are written without a header.
+        /// - If set to `{none}`, no header row is written. Rows that are
+        ///   dictionaries are still written with their keys in order of first
+        ///   appearance.
+        /// - If set to an array of strings, that header row is written. For
+        ///   rows that are dictionaries, it also determines which keys are
+        ///   written in which column. Keys missing from a row result in empty
+        ///   fields.
+        #[named]
+        #[default]
+        header: Smart<Option<Vec<Str>>>,
+    ) -> SourceResult<Str> {
+        let Spanned { v: value, span } = value;
+        encode_csv(value, delimiter, header).at(span)
+    }
+}
+
+/// Encodes rows into a CSV string.
+fn encode_csv(
+    rows: Array,
+    delimiter: Delimiter,
+    header: Smart<Option<Vec<Str>>>,
+) -> StrResult<Str> {
+    let dicts = match rows.as_slice().first() {
+        Some(Value::Dict(_)) => true,
+        Some(Value::Array(_)) | None => false,
+        Some(other) => bail!("expected array or dictionary, found {}", other.ty()),
+    };
+
+    // For dictionary rows, the columns are given by the header or by the
+    // keys in order of first appearance.
+    let keys = match &header {
+        _ if !dicts => vec![],
+        Smart::Custom(Some(header)) => header.clone(),
+        _ => {
+            let mut keys: Vec<Str> = vec![];
+            for row in &rows {
+                if let Value::Dict(dict) = row {
+                    for (key, _) in dict.iter() {
+                        if !keys.contains(key) {
+                            keys.push(key.clone());
+                        }
+                    }
+                }
+            }
+            keys
+        }
+    };
+
+    let header = match header {
+        Smart::Custom(header) => header,
+        Smart::Auto => dicts.then(|| keys.clone()),
+    };
+
+    let mut writer = ::csv::WriterBuilder::new()
+        .delimiter(delimiter.0 as u8)
+        .flexible(!dicts)
+        .from_writer(vec![]);
+
+    let mut write = |record: Vec<EcoString>| {
+        writer
+            .write_record(record.iter().map(|field| field.as_bytes()))
+            .map_err(|err| eco_format!("failed to encode value as CSV ({err})"))
+    };
+
+    if let Some(header) = &header {
+        write(header.iter().map(|key| key.clone().into()).collect())?;
+    }
+
+    for (i, row) in rows.into_iter().enumerate() {
+        let record = match (row, dicts) {
+            (Value::Array(fields), false) => {
+                fields.into_iter().map(encode_field).collect::<StrResult<_>>()?
+            }
+            (Value::Dict(mut dict), true) => {
+                let record = keys
+                    .iter()
+                    .map(|key| {
+                        dict.take(key).ok().map_or(Ok(EcoString::new()), encode_field)
+                    })
+                    .collect::<StrResult<_>>()?;
+                if let Some((key, _)) = dict.iter().next() {
+                    bail!(
+                        "row {} has key {} that is not in the header",
+                        i + 1,
+                        key.repr()
+                    );
+                }
+                record
+            }
+            (row, _) => bail!(
+                "expected all rows to be {}, found {}",
+                if dicts { "dictionaries" } else { "arrays" },
+                row.ty(),
+            ),
+        };
+        write(record)?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|err| eco_format!("failed to encode value as CSV ({err})"))?;
+    Ok(String::from_utf8(bytes).map_err(|_| "CSV is not valid utf-8")?.into())
+}
+
+/// Encodes a single CSV field.
+fn encode_field(value: Value) -> StrResult<EcoString> {
+    Ok(match value {
+        Value::None => EcoString::new(),
+        Value::Str(v) => v.into(),
+        Value::Bool(v) => v.repr(),
+        Value::Int(v) => repr::format_int_with_base(v, 10),
+        Value::Float(v) => repr::display_float(v),
+        Value::Decimal(v) => eco_format!("{v}"),
+        other => bail!("cannot encode {} as a CSV field", other.ty()),
+    })
 }
 
 /// The delimiter to use when parsing CSV files.
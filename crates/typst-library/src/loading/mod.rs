@@ -8,6 +8,9 @@ mod csv_;
 mod json_;
 #[path = "read.rs"]
 mod read_;
+#[cfg(feature = "sqlite")]
+#[path = "sqlite.rs"]
+mod sqlite_;
 #[path = "toml.rs"]
 mod toml_;
 #[path = "xml.rs"]
@@ -23,6 +26,8 @@ pub use self::cbor_::*;
 pub use self::csv_::*;
 pub use self::json_::*;
 pub use self::read_::*;
+#[cfg(feature = "sqlite")]
+pub use self::sqlite_::*;
 pub use self::toml_::*;
 pub use self::xml_::*;
 pub use self::yaml_::*;
@@ -42,6 +47,8 @@ pub(super) fn define(global: &mut Scope) {
     global.define_func::<yaml>();
     global.define_func::<cbor>();
     global.define_func::<xml>();
+    #[cfg(feature = "sqlite")]
+    global.define_func::<sqlite>();
     global.reset_category();
 }
 
@@ -0,0 +1,251 @@
+use std::ptr::NonNull;
+
+use ecow::{EcoString, eco_format};
+use rusqlite::limits::Limit;
+use rusqlite::serialize::OwnedData;
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::{Connection, DatabaseName, Statement};
+use typst_syntax::Spanned;
+
+use crate::diag::{At, HintedStrResult, SourceResult, StrResult, bail};
+use crate::engine::Engine;
+use crate::foundations::{Array, Bytes, Dict, IntoValue, Str, Value, cast, func};
+use crate::loading::{DataSource, Load};
+
+/// Runs a query against an SQLite database.
+///
+/// The database is opened in read-only mode, so the query must not modify it.
+/// Each row of the result is represented as a dictionary mapping from column
+/// names to values, and all rows are collected into a single array. The
+/// values are converted into corresponding Typst values as listed in the
+/// [table below](#conversion).
+///
+/// # Example
+/// ```typ
+/// #let rows = sqlite(
+///   "measurements.db",
+///   "SELECT sensor, avg(value) AS mean FROM readings
+///    WHERE day = :day GROUP BY sensor",
+///   params: (day: "2024-05-01"),
+/// )
+///
+/// #table(
+///   columns: 2,
+///   [*Sensor*], [*Mean*],
+///   ..rows.map(row => (row.sensor, str(row.mean))).flatten(),
+/// )
+/// ```
+///
+/// # Conversion details { #conversion }
+///
+/// | SQLite value | Converted into Typst |
+/// | ------------ | -------------------- |
+/// | `NULL`       | `{none}`             |
+/// | integer      | [`int`]              |
+/// | real         | [`float`]            |
+/// | text         | [`str`]              |
+/// | blob         | [`bytes`]            |
+///
+/// | Typst value | Bound as SQLite value     |
+/// | ----------- | ------------------------- |
+/// | `{none}`    | `NULL`                    |
+/// | [`bool`]    | integer (`0` or `1`)      |
+/// | [`int`]     | integer                   |
+/// | [`float`]   | real                      |
+/// | [`str`]     | text                      |
+/// | [`bytes`]   | blob                      |
+///
+/// ## Notes
+///
+/// - Queries should be deterministic. Functions like `random()` or
+///   `date('now')` may yield different results each time the document is
+///   compiled.
+///
+/// - Other databases cannot be attached with `ATTACH`.
+#[func(title = "SQLite")]
+pub fn sqlite(
+    engine: &mut Engine,
+    /// A [path]($syntax/#paths) to an SQLite database file or the raw bytes of
+    /// one.
+    source: Spanned<DataSource>,
+    /// The SQL query to run.
+    query: Spanned<Str>,
+    /// Values for the query's parameters.
+    ///
+    /// An array binds its values to the positional parameters (`?` or `?NNN`)
+    /// in order. A dictionary binds its values to the named parameters
+    /// (`:name`, `@name` or `$name`) with matching names.
+    ///
+    /// Parameters are the safe way to insert values into a query. They are
+    /// never interpreted as SQL.
+    #[named]
+    params: Option<Spanned<QueryParams>>,
+) -> SourceResult<Array> {
+    let loaded = source.load(engine.world)?;
+    let conn = open(&loaded.data).at(source.span)?;
+
+    let mut stmt = conn.prepare(&query.v).map_err(format_sqlite_error).at(query.span)?;
+    if !stmt.readonly() {
+        bail!(query.span, "query must not modify the database");
+    }
+
+    let params = params.unwrap_or_else(|| {
+        Spanned::new(QueryParams::Positional(Array::new()), query.span)
+    });
+    bind(&mut stmt, &params.v).at(params.span)?;
+
+    let columns: Vec<Str> = stmt.column_names().into_iter().map(Str::from).collect();
+    if let Some(column) = columns
+        .iter()
+        .enumerate()
+        .find_map(|(i, c)| columns[..i].contains(c).then_some(c))
+    {
+        bail!(
+            query.span,
+            "query returns multiple columns named `{column}`";
+            hint: "give the columns distinct names with `AS`"
+        );
+    }
+
+    let mut rows = stmt.raw_query();
+    let mut array = Array::new();
+    while let Some(row) = rows.next().map_err(format_sqlite_error).at(query.span)? {
+        let mut dict = Dict::new();
+        for (i, column) in columns.iter().enumerate() {
+            let value = row.get_ref(i).map_err(format_sqlite_error).at(query.span)?;
+            dict.insert(column.clone(), convert(value));
+        }
+        array.push(dict.into_value());
+    }
+
+    Ok(array)
+}
+
+/// Values for the parameters of a query.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum QueryParams {
+    /// Values for positional parameters.
+    Positional(Array),
+    /// Values for named parameters.
+    Named(Dict),
+}
+
+cast! {
+    QueryParams,
+    self => match self {
+        Self::Positional(v) => v.into_value(),
+        Self::Named(v) => v.into_value(),
+    },
+    v: Array => Self::Positional(v),
+    v: Dict => Self::Named(v),
+}
+
+/// Opens a read-only, in-memory copy of a database.
+fn open(data: &Bytes) -> StrResult<Connection> {
+    let mut conn = Connection::open_in_memory().map_err(format_open_error)?;
+
+    // An empty file is an empty database.
+    if !data.is_empty() {
+        // SQLite takes ownership of the copy, so it must allocate it itself.
+        let size = data.len();
+        let ptr = unsafe { rusqlite::ffi::sqlite3_malloc64(size as u64) };
+        let ptr = NonNull::new(ptr.cast::<u8>())
+            .ok_or("failed to allocate memory for database")?;
+        // Safety: The allocation has the size of the data and was made by
+        // `sqlite3_malloc64`.
+        let owned = unsafe {
+            std::ptr::copy_nonoverlapping(data.as_slice().as_ptr(), ptr.as_ptr(), size);
+            OwnedData::from_raw_nonnull(ptr, size)
+        };
+        conn.deserialize(DatabaseName::Main, owned, true)
+            .map_err(format_open_error)?;
+    }
+
+    // Queries must neither write nor reach other files on disk.
+    conn.pragma_update(None, "query_only", true)
+        .map_err(format_open_error)?;
+    conn.set_limit(Limit::SQLITE_LIMIT_ATTACHED, 0);
+
+    Ok(conn)
+}
+
+/// Binds values to the parameters of a query.
+fn bind(stmt: &mut Statement, params: &QueryParams) -> HintedStrResult<()> {
+    match params {
+        QueryParams::Positional(values) => {
+            let expected = stmt.parameter_count();
+            if values.len() != expected {
+                bail!(
+                    "query has {expected} parameter{}, but {} {} given",
+                    if expected == 1 { "" } else { "s" },
+                    values.len(),
+                    if values.len() == 1 { "was" } else { "were" },
+                );
+            }
+            for (i, value) in values.iter().enumerate() {
+                stmt.raw_bind_parameter(i + 1, to_sql(value)?)
+                    .map_err(format_sqlite_error)?;
+            }
+        }
+        QueryParams::Named(values) => {
+            let mut bound = vec![false; stmt.parameter_count()];
+            for (key, value) in values.iter() {
+                let index = [':', '@', '$']
+                    .into_iter()
+                    .map(|prefix| stmt.parameter_index(&format!("{prefix}{key}")))
+                    .find_map(Result::transpose)
+                    .transpose()
+                    .map_err(format_sqlite_error)?
+                    .ok_or_else(|| eco_format!("query has no parameter named `{key}`"))?;
+                stmt.raw_bind_parameter(index, to_sql(value)?)
+                    .map_err(format_sqlite_error)?;
+                bound[index - 1] = true;
+            }
+
+            if let Some(i) = bound.iter().position(|&b| !b) {
+                match stmt.parameter_name(i + 1) {
+                    Some(name) => bail!("no value was given for the parameter `{name}`"),
+                    None => bail!(
+                        "no value was given for positional parameter {}", i + 1;
+                        hint: "positional parameters need an array of values"
+                    ),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Converts a Typst value into an SQLite value.
+fn to_sql(value: &Value) -> StrResult<SqlValue> {
+    Ok(match value {
+        Value::None => SqlValue::Null,
+        Value::Bool(v) => SqlValue::Integer(i64::from(*v)),
+        Value::Int(v) => SqlValue::Integer(*v),
+        Value::Float(v) => SqlValue::Real(*v),
+        Value::Str(v) => SqlValue::Text(v.as_str().into()),
+        Value::Bytes(v) => SqlValue::Blob(v.as_slice().to_vec()),
+        v => bail!("cannot bind {} as a query parameter", v.ty()),
+    })
+}
+
+/// Converts an SQLite value into a Typst value.
+fn convert(value: ValueRef) -> Value {
+    match value {
+        ValueRef::Null => Value::None,
+        ValueRef::Integer(v) => v.into_value(),
+        ValueRef::Real(v) => v.into_value(),
+        ValueRef::Text(v) => Str::from(String::from_utf8_lossy(v).as_ref()).into_value(),
+        ValueRef::Blob(v) => Bytes::new(v.to_vec()).into_value(),
+    }
+}
+
+/// Format the user-facing error message for a database that can't be opened.
+fn format_open_error(error: rusqlite::Error) -> EcoString {
+    eco_format!("failed to open database ({error})")
+}
+
+/// Format the user-facing error message for a failed query.
+fn format_sqlite_error(error: rusqlite::Error) -> EcoString {
+    eco_format!("failed to execute query ({error})")
+}
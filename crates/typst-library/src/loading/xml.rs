@@ -1,9 +1,13 @@
 use roxmltree::ParsingOptions;
 use typst_syntax::Spanned;
+use xmlwriter::XmlWriter;
 
-use crate::diag::{LoadError, LoadedWithin, SourceResult, format_xml_like_error};
+use crate::diag::{
+    At, HintedStrResult, LoadError, LoadedWithin, SourceResult, bail,
+    format_xml_like_error,
+};
 use crate::engine::Engine;
-use crate::foundations::{Array, Dict, IntoValue, Str, Value, dict, func, scope};
+use crate::foundations::{Array, Dict, IntoValue, Repr, Str, Value, dict, func, scope};
 use crate::loading::{DataSource, Load, Readable};
 
 /// Reads structured data from an XML file.
@@ -86,6 +90,138 @@ impl xml {
     ) -> SourceResult<Value> {
         xml(engine, data.map(Readable::into_source))
     }
+
+    /// Encodes structured data into an XML string.
+    ///
+    /// This is the inverse of [`xml`]: The value must have the structure that
+    /// `xml` produces. Whitespace is written exactly as given in the text
+    /// nodes, so decoding a file and encoding the result yields an equivalent
+    /// document.
+    ///
+    /// ```example
+    /// #xml.encode((
+    ///   tag: "note",
+    ///   attrs: (lang: "en"),
+    ///   children: ("Hello & goodbye",),
+    /// ))
+    /// ```
+    #[func(title = "Encode XML")]
+    pub fn encode(
+        /// Value to be encoded.
+        ///
+        /// Either a single element or an array of nodes as produced by `xml`.
+        /// An element is a dictionary with a `tag` and optionally `attrs` and
+        /// `children`. A text node is a string. The document must contain
+        /// exactly one element at the top level, surrounded by nothing but
+        /// whitespace.
+        value: Spanned<Value>,
+    ) -> SourceResult<Str> {
+        let Spanned { v: value, span } = value;
+        encode_xml(value).at(span)
+    }
+}
+
+/// Encodes a Typst value into an XML string.
+fn encode_xml(value: Value) -> HintedStrResult<Str> {
+    let nodes = match value {
+        Value::Array(nodes) => nodes,
+        Value::Dict(_) => std::iter::once(value).collect(),
+        other => bail!("expected array or dictionary, found {}", other.ty()),
+    };
+
+    let mut root = None;
+    for node in nodes {
+        match node {
+            Value::Str(text) if text.as_str().trim().is_empty() => {}
+            Value::Str(_) => bail!("text must be inside of an element"),
+            Value::Dict(element) if root.is_none() => root = Some(element),
+            Value::Dict(_) => bail!("XML document must have exactly one root element"),
+            other => bail!("expected string or dictionary, found {}", other.ty()),
+        }
+    }
+
+    let Some(root) = root else {
+        bail!("XML document must have exactly one root element");
+    };
+
+    let mut writer = XmlWriter::new(xmlwriter::Options {
+        indent: xmlwriter::Indent::None,
+        ..Default::default()
+    });
+    encode_element(&mut writer, root)?;
+    Ok(writer.end_document().into())
+}
+
+/// Encodes an XML element with its attributes and children.
+fn encode_element(writer: &mut XmlWriter, mut element: Dict) -> HintedStrResult<()> {
+    let tag: Str = element.take("tag")?.cast()?;
+    check_name("element tag", &tag)?;
+
+    let attrs: Dict = element
+        .take("attrs")
+        .ok()
+        .map(Value::cast)
+        .transpose()?
+        .unwrap_or_default();
+    let children: Array = element
+        .take("children")
+        .ok()
+        .map(Value::cast)
+        .transpose()?
+        .unwrap_or_default();
+    element.finish(&["tag", "attrs", "children"])?;
+
+    writer.start_element(&tag);
+    for (name, value) in attrs {
+        check_name("attribute name", &name)?;
+        let value: Str = value.cast()?;
+        writer.write_attribute(&name, value.as_str());
+    }
+
+    for child in children {
+        match child {
+            Value::Str(text) => writer.write_text(&text),
+            Value::Dict(element) => encode_element(writer, element)?,
+            other => bail!("expected string or dictionary, found {}", other.ty()),
+        }
+    }
+
+    writer.end_element();
+    Ok(())
+}
+
+/// Checks that a tag or attribute name matches the `Name` production of the
+/// XML specification.
+fn check_name(kind: &str, name: &str) -> HintedStrResult<()> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(is_name_start_char) && chars.all(is_name_char);
+    if !valid {
+        bail!(
+            "{kind} {} is not a valid XML name", name.repr();
+            hint: "names must start with a letter, `_` or `:` and may only contain \
+                   letters, digits, `-`, `_`, `.` and `:`"
+        );
+    }
+    Ok(())
+}
+
+/// Whether a character may start an XML name.
+fn is_name_start_char(c: char) -> bool {
+    matches!(c,
+        ':' | 'A'..='Z' | '_' | 'a'..='z' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}'
+        | '\u{F8}'..='\u{2FF}' | '\u{370}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}'
+        | '\u{200C}'..='\u{200D}' | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}'
+        | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+
+/// Whether a character may appear in an XML name after its first character.
+fn is_name_char(c: char) -> bool {
+    is_name_start_char(c)
+        || matches!(c,
+            '-' | '.' | '0'..='9' | '\u{B7}' | '\u{300}'..='\u{36F}' | '\u{203F}'..='\u{2040}'
+        )
 }
 
 /// Convert an XML node to a Typst value.
@@ -1,4 +1,5 @@
 use std::any::TypeId;
+use std::collections::hash_map::Entry;
 use std::ffi::OsStr;
 use std::fmt::{self, Debug, Formatter};
 use std::num::NonZeroUsize;
@@ -6,7 +7,7 @@ use std::path::Path;
 use std::sync::{Arc, LazyLock};
 
 use comemo::{Track, Tracked};
-use ecow::{EcoString, EcoVec, eco_format};
+use ecow::{EcoString, EcoVec, eco_format, eco_vec};
 use hayagriva::archive::ArchivedStyle;
 use hayagriva::io::BibLaTeXError;
 use hayagriva::{
@@ -26,9 +27,9 @@ use crate::diag::{
 };
 use crate::engine::{Engine, Sink};
 use crate::foundations::{
-    Bytes, CastInfo, Content, Derived, FromValue, IntoValue, Label, NativeElement,
-    OneOrMultiple, Packed, Reflect, Scope, ShowSet, Smart, StyleChain, Styles,
-    Synthesize, Value, elem,
+    Bytes, CastInfo, Content, Context, Derived, Dict, FromValue, Func, IntoValue, Label,
+    NativeElement, OneOrMultiple, Packed, Reflect, Scope, Selector, ShowSet, Smart,
+    StyleChain, Styles, Synthesize, Value, elem,
 };
 use crate::introspection::{
     History, Introspect, Introspector, Locatable, Location, QueryIntrospection,
@@ -36,11 +37,13 @@ use crate::introspection::{
 use crate::layout::{BlockBody, BlockElem, Em, HElem, PadElem};
 use crate::loading::{DataSource, Load, LoadSource, Loaded, format_yaml_error};
 use crate::model::{
-    CitationForm, CiteGroup, Destination, DirectLinkElem, FootnoteElem, HeadingElem,
-    LinkElem, Url,
+    CitationForm, CiteElem, CiteGroup, Destination, DirectLinkElem, FootnoteElem,
+    HeadingElem, LinkElem, Url,
 };
 use crate::routines::Routines;
-use crate::text::{Lang, LocalName, Region, SmallcapsElem, SubElem, SuperElem, TextElem};
+use crate::text::{
+    Lang, LocalName, Region, SmallcapsElem, SpaceElem, SubElem, SuperElem, TextElem,
+};
 
 /// A bibliography / reference listing.
 ///
@@ -84,6 +87,39 @@ use crate::text::{Lang, LocalName, Region, SmallcapsElem, SubElem, SuperElem, Te
 ///
 /// #bibliography("works.bib")
 /// ```
+///
+/// # Multiple bibliographies
+/// A document can contain multiple bibliographies, for instance one at the
+/// end of each chapter. They divide the document into sections: A citation
+/// belongs to the next bibliography that follows it in the document, or to
+/// the last one if no bibliography follows. This way, each bibliography lists
+/// the works cited since the previous one.
+///
+/// Bibliographies that directly follow each other, without citations in
+/// between, share their citations. With a [`filter`]($bibliography.filter),
+/// you can split the cited works among them, for example into primary and
+/// secondary sources. A citation then resolves to the first of these
+/// bibliographies that contains and accepts the cited work.
+///
+/// ```example
+/// >>> #set page(height: auto)
+/// = Pirates
+/// This was noted long ago. @arrgh
+/// #bibliography("works.bib", title: none)
+///
+/// = Networks
+/// Recent work shows ... @netwok
+/// #bibliography("works.bib", title: none)
+/// ```
+///
+/// # Exporting cited works
+/// Some publishers require a bibliography file with only the works that are
+/// cited in a document. The CLI can export these from the document's
+/// bibliographies, either as BibLaTeX or in the Hayagriva format:
+///
+/// ```sh
+/// $ typst query main.typ bibliography --cited biblatex > cited.bib
+/// ```
 #[elem(Locatable, Synthesize, ShowSet, LocalName)]
 pub struct BibliographyElem {
     /// One or multiple paths to or raw bytes for Hayagriva `.yaml` and/or
@@ -121,6 +157,33 @@ pub struct BibliographyElem {
     #[default(false)]
     pub full: bool,
 
+    /// A function that selects which works are listed in this bibliography.
+    ///
+    /// The function receives each entry of the bibliography files as a
+    /// dictionary and must return a boolean. The dictionary contains the
+    /// entry's `key` and its fields in the
+    /// [Hayagriva format](https://github.com/typst/hayagriva/blob/main/docs/file-format.md),
+    /// e.g. its `type` and `title`. Citations of works that are filtered out
+    /// resolve to the next bibliography in the same
+    /// [section](#multiple-bibliographies) instead.
+    ///
+    /// ```example
+    /// >>> #set page(height: auto)
+    /// Primary: @arrgh \
+    /// Secondary: @netwok
+    ///
+    /// #bibliography(
+    ///   "works.bib",
+    ///   title: [Primary Sources],
+    ///   filter: entry => entry.type == "book",
+    /// )
+    /// #bibliography(
+    ///   "works.bib",
+    ///   title: [Secondary Sources],
+    /// )
+    /// ```
+    pub filter: Option<Func>,
+
     /// The bibliography style.
     ///
     /// This can be:
@@ -152,22 +215,6 @@ pub struct BibliographyElem {
 }
 
 impl BibliographyElem {
-    /// Find the document's bibliography.
-    pub fn find(engine: &mut Engine, span: Span) -> StrResult<Packed<Self>> {
-        let elems = engine.introspect(QueryIntrospection(Self::ELEM.select(), span));
-
-        let mut iter = elems.iter();
-        let Some(elem) = iter.next() else {
-            bail!("the document does not contain a bibliography");
-        };
-
-        if iter.next().is_some() {
-            bail!("multiple bibliographies are not yet supported");
-        }
-
-        Ok(elem.to_packed::<Self>().unwrap().clone())
-    }
-
     /// Whether the bibliography contains the given key.
     pub fn has(engine: &mut Engine, key: Label, span: Span) -> bool {
         engine
@@ -191,6 +238,35 @@ impl BibliographyElem {
 }
 
 impl Packed<BibliographyElem> {
+    /// Determines the keys of the entries accepted by the bibliography's
+    /// filter, or `None` if it doesn't have one.
+    fn filtered_keys(&self, engine: &mut Engine) -> SourceResult<Option<Vec<Label>>> {
+        let Some(filter) = self.filter.get_ref(StyleChain::default()) else {
+            return Ok(None);
+        };
+
+        let span = filter.span();
+        let mut keys = vec![];
+        for (key, entry) in self.sources.derived.iter() {
+            let mut dict = serde_json::to_value(entry)
+                .and_then(serde_json::from_value::<Dict>)
+                .map_err(|err| {
+                    eco_format!("failed to convert entry `{}` ({err})", key.resolve())
+                })
+                .at(span)?;
+            dict.insert("key".into(), key.resolve().as_str().into_value());
+            if filter
+                .call(engine, Context::none().track(), [dict])?
+                .cast::<bool>()
+                .at(span)?
+            {
+                keys.push(key);
+            }
+        }
+
+        Ok(Some(keys))
+    }
+
     /// Produces the heading for the bibliography, if any.
     pub fn realize_title(&self, styles: StyleChain) -> Option<Content> {
         self.title
@@ -369,6 +445,33 @@ fn decode_library(loaded: &Loaded) -> SourceResult<Library> {
     }
 }
 
+/// Decodes a data source as a BibLaTeX bibliography without converting it
+/// into a Hayagriva library. Returns `None` for Hayagriva files.
+fn decode_biblatex(loaded: &Loaded) -> SourceResult<Option<biblatex::Bibliography>> {
+    let data = loaded.data.as_str().within(loaded)?;
+
+    // Detect the format like `decode_library` does. Since that already
+    // succeeded, bytes are BibLaTeX if they aren't valid Hayagriva YAML.
+    let is_biblatex = match loaded.source.v {
+        LoadSource::Path(file_id) => file_id
+            .vpath()
+            .as_rooted_path()
+            .extension()
+            .and_then(OsStr::to_str)
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("bib")),
+        LoadSource::Bytes => hayagriva::io::from_yaml_str(data).is_err(),
+    };
+
+    if !is_biblatex {
+        return Ok(None);
+    }
+
+    biblatex::Bibliography::parse(data)
+        .map(Some)
+        .map_err(|err| format_biblatex_error(vec![BibLaTeXError::Parse(err)]))
+        .within(loaded)
+}
+
 /// Format a BibLaTeX loading error.
 fn format_biblatex_error(errors: Vec<BibLaTeXError>) -> LoadError {
     // TODO: return multiple errors?
@@ -389,6 +492,96 @@ fn format_biblatex_error(errors: Vec<BibLaTeXError>) -> LoadError {
     LoadError::new(range, "failed to parse BibLaTeX", msg)
 }
 
+/// A format into which cited works can be exported.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum BibliographyFormat {
+    /// A BibLaTeX `.bib` file.
+    BibLaTeX,
+    /// A Hayagriva `.yaml` file.
+    Hayagriva,
+}
+
+/// Exports the works cited in a document that are found in the given
+/// bibliographies.
+///
+/// Each cited key is looked up in the bibliographies in order and exported
+/// once, in the order of its first citation. Keys that aren't found in any of
+/// the bibliographies are skipped. Filters of the bibliographies are not taken
+/// into account.
+///
+/// BibLaTeX entries are written from their parsed fields: String
+/// abbreviations are expanded, BibTeX field names like `journal` are replaced
+/// with their BibLaTeX equivalents, and the formatting and comments of the
+/// original file are not preserved. Works from Hayagriva files can only be
+/// exported in the Hayagriva format.
+pub fn export_cited(
+    world: Tracked<dyn World + '_>,
+    introspector: Tracked<Introspector>,
+    bibliographies: &[Packed<BibliographyElem>],
+    format: BibliographyFormat,
+) -> SourceResult<String> {
+    let mut keys = IndexMap::<Label, (), FxBuildHasher>::default();
+    for group in introspector.query(&CiteGroup::ELEM.select()).iter() {
+        let group = group.to_packed::<CiteGroup>().unwrap();
+        keys.extend(group.children.iter().map(|cite| (cite.key, ())));
+    }
+
+    let cited = keys.into_keys().filter_map(|key| {
+        bibliographies
+            .iter()
+            .find(|elem| elem.sources.derived.has(key))
+            .map(|elem| (key, elem))
+    });
+
+    match format {
+        BibliographyFormat::BibLaTeX => {
+            let mut parsed = FxHashMap::default();
+            let mut out = String::new();
+            for (key, elem) in cited {
+                let location = elem.location();
+                let libraries = match parsed.entry(location) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => {
+                        let sources = Spanned::new(&elem.sources.source, elem.span());
+                        let mut libraries = vec![];
+                        for loaded in sources.load(world)? {
+                            libraries.extend(decode_biblatex(&loaded)?);
+                        }
+                        entry.insert(libraries)
+                    }
+                };
+
+                let Some(entry) = libraries
+                    .iter()
+                    .find_map(|library| library.get(key.resolve().as_str()))
+                else {
+                    bail!(
+                        elem.span(),
+                        "cannot export `{}` as BibLaTeX", key.resolve();
+                        hint: "the work comes from a Hayagriva file";
+                        hint: "try exporting in the Hayagriva format instead"
+                    );
+                };
+
+                if !out.is_empty() {
+                    out.push_str("\n\n");
+                }
+                out.push_str(&entry.to_biblatex_string());
+            }
+            Ok(out)
+        }
+        BibliographyFormat::Hayagriva => {
+            let mut library = Library::new();
+            for (key, elem) in cited {
+                library.push(elem.sources.derived.get(key).unwrap());
+            }
+            hayagriva::io::to_yaml_str(&library)
+                .map_err(|err| eco_format!("failed to export bibliography ({err})"))
+                .at(Span::detached())
+        }
+    }
+}
+
 /// A loaded CSL style.
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub struct CslStyle(Arc<ManuallyHash<citationberg::IndependentStyle>>);
@@ -546,33 +739,72 @@ impl IntoValue for CslSource {
 /// memoization) for the whole document. This setup is necessary because
 /// citation formatting is inherently stateful and we need access to all
 /// citations to do it.
+///
+/// A document can contain multiple bibliographies. They divide the document
+/// into sections: Each citation belongs to the section of the next
+/// bibliography after it (or of the last one if no bibliography follows).
+/// Bibliographies that directly follow each other, without citations in
+/// between, share a section. Within a section, a citation resolves to the
+/// first bibliography that contains its key and whose filter accepts it.
 pub struct Works {
     /// Maps from the location of a citation group to its rendered content.
     pub citations: FxHashMap<Location, SourceResult<Content>>,
+    /// Maps from the location of a bibliography to its rendered references.
+    bibliographies: FxHashMap<Location, RenderedReferences>,
+}
+
+/// The rendered references of one bibliography.
+struct RenderedReferences {
     /// Lists all references in the bibliography, with optional prefix, or
     /// `None` if the citation style can't be used for bibliographies.
-    pub references: Option<Vec<(Option<Content>, Content, Location)>>,
+    references: Option<Vec<(Option<Content>, Content, Location)>>,
     /// Whether the bibliography should have hanging indent.
-    pub hanging_indent: bool,
+    hanging_indent: bool,
 }
 
 impl Works {
-    /// Generate all citations and the whole bibliography.
+    /// Generate all citations and all bibliographies.
     pub fn generate(engine: &mut Engine, span: Span) -> SourceResult<Arc<Works>> {
-        let bibliography = BibliographyElem::find(engine, span).at(span)?;
-        let groups = engine.introspect(CiteGroupIntrospection(span));
-        Self::generate_impl(engine.routines, engine.world, bibliography, groups).at(span)
+        let elems = engine.introspect(CiteGroupIntrospection(span));
+        Self::generate_from(engine, elems, span)
     }
 
-    /// Generate all citations and the whole bibliography, given an existing
-    /// bibliography (no need to query it).
+    /// Generate all citations and all bibliographies, making sure that the
+    /// given bibliography is included even if it isn't known to the
+    /// introspector yet.
     pub fn with_bibliography(
         engine: &mut Engine,
         bibliography: Packed<BibliographyElem>,
     ) -> SourceResult<Arc<Works>> {
         let span = bibliography.span();
-        let groups = engine.introspect(CiteGroupIntrospection(span));
-        Self::generate_impl(engine.routines, engine.world, bibliography, groups).at(span)
+        let mut elems = engine.introspect(CiteGroupIntrospection(span));
+        let location = bibliography.location();
+        if !elems.iter().any(|elem| elem.location() == location) {
+            elems.push(bibliography.pack());
+        }
+        Self::generate_from(engine, elems, span)
+    }
+
+    /// Generate all citations and bibliographies from the given citation
+    /// groups and bibliographies in document order.
+    fn generate_from(
+        engine: &mut Engine,
+        elems: EcoVec<Content>,
+        span: Span,
+    ) -> SourceResult<Arc<Works>> {
+        // Filters are evaluated up front as they need the engine.
+        let mut filters = vec![];
+        for elem in &elems {
+            if let Some(bibliography) = elem.to_packed::<BibliographyElem>() {
+                filters.push(bibliography.filtered_keys(engine)?);
+            }
+        }
+
+        if filters.is_empty() {
+            bail!(span, "the document does not contain a bibliography");
+        }
+
+        Self::generate_impl(engine.routines, engine.world, elems, filters).at(span)
     }
 
     /// The internal implementation of [`Works::generate`].
@@ -580,23 +812,67 @@ impl Works {
     fn generate_impl(
         routines: &Routines,
         world: Tracked<dyn World + '_>,
-        bibliography: Packed<BibliographyElem>,
-        groups: EcoVec<Content>,
+        elems: EcoVec<Content>,
+        filters: Vec<Option<Vec<Label>>>,
     ) -> StrResult<Arc<Works>> {
-        let mut generator = Generator::new(routines, world, bibliography, groups)?;
-        let rendered = generator.drive();
-        let works = generator.display(&rendered)?;
-        Ok(Arc::new(works))
+        let sections = Section::partition(&elems, filters);
+
+        let mut citations = FxHashMap::default();
+        let mut bibliographies = FxHashMap::default();
+        let mut parts = FxHashMap::<Location, Vec<SourceResult<Content>>>::default();
+
+        for section in sections {
+            let (requests, failures) = section.assign();
+            citations.extend(failures);
+
+            for ((bibliography, keys), groups) in
+                section.bibliographies.into_iter().zip(requests)
+            {
+                let location = bibliography.location().unwrap();
+                let mut generator =
+                    Generator::new(routines, world, bibliography, keys, groups)?;
+                let rendered = generator.drive();
+                let (rendered_citations, references) = generator.display(&rendered)?;
+                #[allow(clippy::iter_over_hash_type, reason = "order does not matter")]
+                for (group, result) in rendered_citations {
+                    parts.entry(group).or_default().push(result);
+                }
+                bibliographies.insert(location, references);
+            }
+        }
+
+        // Join the parts of citation groups that cite works from several
+        // bibliographies in the same section.
+        #[allow(clippy::iter_over_hash_type, reason = "order does not matter")]
+        for (location, parts) in parts {
+            citations.entry(location).or_insert_with(|| {
+                let mut seq = vec![];
+                for part in parts {
+                    if !seq.is_empty() {
+                        seq.push(SpaceElem::shared().clone());
+                    }
+                    seq.push(part?);
+                }
+                Ok(Content::sequence(seq))
+            });
+        }
+
+        Ok(Arc::new(Works { citations, bibliographies }))
     }
 
-    /// Extracts the generated references, failing with an error if none have
-    /// been generated.
+    /// Extracts the generated references of a bibliography, failing with an
+    /// error if none have been generated.
     pub fn references<'a>(
         &'a self,
         elem: &Packed<BibliographyElem>,
         styles: StyleChain,
     ) -> SourceResult<&'a [(Option<Content>, Content, Location)]> {
-        self.references
+        let Some(rendered) = self.rendered(elem) else {
+            bail!(elem.span(), "failed to format bibliography");
+        };
+
+        rendered
+            .references
             .as_deref()
             .ok_or_else(|| match elem.style.get_ref(styles).source {
                 CslSource::Named(style, _) => eco_format!(
@@ -609,9 +885,19 @@ impl Works {
             })
             .at(elem.span())
     }
+
+    /// Whether the given bibliography should have hanging indent.
+    pub fn hanging_indent(&self, elem: &Packed<BibliographyElem>) -> bool {
+        self.rendered(elem).is_some_and(|rendered| rendered.hanging_indent)
+    }
+
+    /// The rendered references of the given bibliography.
+    fn rendered(&self, elem: &Packed<BibliographyElem>) -> Option<&RenderedReferences> {
+        self.bibliographies.get(&elem.location()?)
+    }
 }
 
-/// Retrieves all citation groups in the document.
+/// Retrieves all citation groups and bibliographies in the document.
 ///
 /// This is separate from `QueryIntrospection` so that we can customize the
 /// diagnostic as the `CiteGroup` is internal. The default query message is also
@@ -627,7 +913,10 @@ impl Introspect for CiteGroupIntrospection {
         _: &mut Engine,
         introspector: Tracked<Introspector>,
     ) -> Self::Output {
-        introspector.query(&CiteGroup::ELEM.select())
+        introspector.query(&Selector::Or(eco_vec![
+            CiteGroup::ELEM.select(),
+            BibliographyElem::ELEM.select(),
+        ]))
     }
 
     fn diagnose(&self, _: &History<Self::Output>) -> SourceDiagnostic {
@@ -639,21 +928,116 @@ impl Introspect for CiteGroupIntrospection {
     }
 }
 
+/// A part of the document whose citations are listed by a shared set of
+/// bibliographies.
+struct Section {
+    /// The section's bibliographies, each with the keys accepted by its filter
+    /// (`None` if it has no filter).
+    bibliographies: Vec<(Packed<BibliographyElem>, Option<Vec<Label>>)>,
+    /// The section's citation groups.
+    groups: Vec<Packed<CiteGroup>>,
+}
+
+impl Section {
+    /// Divides the document's citation groups and bibliographies, given in
+    /// document order, into sections.
+    fn partition(elems: &[Content], filters: Vec<Option<Vec<Label>>>) -> Vec<Self> {
+        let mut filters = filters.into_iter();
+        let mut sections: Vec<Section> = vec![];
+        let mut pending = vec![];
+        let mut shared = false;
+
+        for elem in elems {
+            if let Some(group) = elem.to_packed::<CiteGroup>() {
+                pending.push(group.clone());
+                shared = false;
+            } else if let Some(bibliography) = elem.to_packed::<BibliographyElem>() {
+                let entry = (bibliography.clone(), filters.next().flatten());
+                match sections.last_mut() {
+                    Some(section) if shared => section.bibliographies.push(entry),
+                    _ => sections.push(Section {
+                        bibliographies: vec![entry],
+                        groups: std::mem::take(&mut pending),
+                    }),
+                }
+                shared = true;
+            }
+        }
+
+        // Citations after the last bibliography belong to the last section.
+        if let Some(section) = sections.last_mut() {
+            section.groups.extend(pending);
+        }
+
+        sections
+    }
+
+    /// Assigns each citation to the bibliography it resolves to.
+    ///
+    /// Returns, for each bibliography, the groups with the citations that
+    /// resolve to it, as well as errors for groups with unresolved keys.
+    #[allow(clippy::type_complexity)]
+    fn assign(
+        &self,
+    ) -> (
+        Vec<Vec<(Location, Vec<Packed<CiteElem>>)>>,
+        Vec<(Location, SourceResult<Content>)>,
+    ) {
+        let mut requests = vec![vec![]; self.bibliographies.len()];
+        let mut failures = vec![];
+
+        for group in &self.groups {
+            let location = group.location().unwrap();
+            let mut children = vec![vec![]; self.bibliographies.len()];
+            let mut errors = EcoVec::new();
+
+            for child in &group.children {
+                let Some(i) = self.bibliographies.iter().position(|(elem, keys)| {
+                    elem.sources.derived.has(child.key)
+                        && keys.as_ref().is_none_or(|keys| keys.contains(&child.key))
+                }) else {
+                    errors.push(error!(
+                        child.span(),
+                        "key `{}` does not exist in the bibliography",
+                        child.key.resolve()
+                    ));
+                    continue;
+                };
+                children[i].push(child.clone());
+            }
+
+            if !errors.is_empty() {
+                failures.push((location, Err(errors)));
+                continue;
+            }
+
+            for (request, children) in requests.iter_mut().zip(children) {
+                if !children.is_empty() {
+                    request.push((location, children));
+                }
+            }
+        }
+
+        (requests, failures)
+    }
+}
+
 /// Context for generating the bibliography.
 struct Generator<'a> {
     /// The routines that are used to evaluate mathematical material in citations.
     routines: &'a Routines,
     /// The world that is used to evaluate mathematical material in citations.
     world: Tracked<'a, dyn World + 'a>,
-    /// The document's bibliography.
+    /// The bibliography that is generated.
     bibliography: Packed<BibliographyElem>,
-    /// The document's citation groups.
-    groups: EcoVec<Content>,
+    /// The keys accepted by the bibliography's filter, if it has one.
+    keys: Option<Vec<Label>>,
+    /// The citation groups that resolve to the bibliography, each with the
+    /// citations that resolve to it.
+    groups: Vec<(Location, Vec<Packed<CiteElem>>)>,
     /// Details about each group that are accumulated while driving hayagriva's
     /// bibliography driver and needed when processing hayagriva's output.
     infos: Vec<GroupInfo>,
-    /// Citations with unresolved keys.
-    failures: FxHashMap<Location, SourceResult<Content>>,
 }
 
 /// Details about a group of merged citations. All citations are put into groups
@@ -686,17 +1070,11 @@ impl<'a> Generator<'a> {
         routines: &'a Routines,
         world: Tracked<'a, dyn World + 'a>,
         bibliography: Packed<BibliographyElem>,
-        groups: EcoVec<Content>,
+        keys: Option<Vec<Label>>,
+        groups: Vec<(Location, Vec<Packed<CiteElem>>)>,
     ) -> StrResult<Self> {
         let infos = Vec::with_capacity(groups.len());
-        Ok(Self {
-            routines,
-            world,
-            bibliography,
-            groups,
-            infos,
-            failures: FxHashMap::default(),
-        })
+        Ok(Self { routines, world, bibliography, keys, groups, infos })
     }
 
     /// Drives hayagriva's citation driver.
@@ -710,29 +1088,21 @@ impl<'a> Generator<'a> {
 
         // Process all citation groups.
         let mut driver = BibliographyDriver::new();
-        for elem in &self.groups {
-            let group = elem.to_packed::<CiteGroup>().unwrap();
-            let location = elem.location().unwrap();
-            let children = &group.children;
+        for (location, children) in &self.groups {
+            let location = *location;
 
             // Groups should never be empty.
             let Some(first) = children.first() else { continue };
 
             let mut subinfos = SmallVec::with_capacity(children.len());
             let mut items = Vec::with_capacity(children.len());
-            let mut errors = EcoVec::new();
             let mut normal = true;
 
-            // Create infos and items for each child in the group.
+            // Create infos and items for each child in the group. The keys
+            // were already resolved when the citations were assigned to this
+            // bibliography.
             for child in children {
-                let Some(entry) = database.get(child.key) else {
-                    errors.push(error!(
-                        child.span(),
-                        "key `{}` does not exist in the bibliography",
-                        child.key.resolve()
-                    ));
-                    continue;
-                };
+                let Some(entry) = database.get(child.key) else { continue };
 
                 let supplement = child.supplement.get_cloned(StyleChain::default());
                 let locator = supplement.as_ref().map(|c| {
@@ -762,11 +1132,6 @@ impl<'a> Generator<'a> {
                 items.push(CitationItem::new(entry, locator, None, hidden, special_form));
             }
 
-            if !errors.is_empty() {
-                self.failures.insert(location, Err(errors));
-                continue;
-            }
-
             let style = match first.style.get_ref(StyleChain::default()) {
                 Smart::Auto => bibliography_style.get(),
                 Smart::Custom(style) => style.derived.get(),
@@ -797,7 +1162,11 @@ impl<'a> Generator<'a> {
         // Add hidden items for everything if we should print the whole
         // bibliography.
         if self.bibliography.full.get(StyleChain::default()) {
-            for (_, entry) in database.iter() {
+            for (key, entry) in database.iter() {
+                if self.keys.as_ref().is_some_and(|keys| !keys.contains(&key)) {
+                    continue;
+                }
+
                 driver.citation(CitationRequest::new(
                     vec![CitationItem::new(entry, None, None, true, None)],
                     bibliography_style.get(),
@@ -816,17 +1185,21 @@ impl<'a> Generator<'a> {
     }
 
     /// Displays hayagriva's output as content for the citations and references.
-    fn display(&mut self, rendered: &hayagriva::Rendered) -> StrResult<Works> {
+    #[allow(clippy::type_complexity)]
+    fn display(
+        &self,
+        rendered: &hayagriva::Rendered,
+    ) -> StrResult<(FxHashMap<Location, SourceResult<Content>>, RenderedReferences)> {
         let citations = self.display_citations(rendered)?;
         let references = self.display_references(rendered)?;
         let hanging_indent =
             rendered.bibliography.as_ref().is_some_and(|b| b.hanging_indent);
-        Ok(Works { citations, references, hanging_indent })
+        Ok((citations, RenderedReferences { references, hanging_indent }))
     }
 
     /// Display the citation groups.
     fn display_citations(
-        &mut self,
+        &self,
         rendered: &hayagriva::Rendered,
     ) -> StrResult<FxHashMap<Location, SourceResult<Content>>> {
         // Determine for each citation key where in the bibliography it is,
@@ -839,7 +1212,7 @@ impl<'a> Generator<'a> {
             }
         }
 
-        let mut output = std::mem::take(&mut self.failures);
+        let mut output = FxHashMap::default();
         for (info, citation) in self.infos.iter().zip(&rendered.citations) {
             let supplement = |i: usize| info.subinfos.get(i)?.supplement.clone();
             let link = |i: usize| {
@@ -0,0 +1,359 @@
+use std::cmp::Ordering;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+
+use ecow::EcoString;
+use typst_syntax::Span;
+use typst_utils::NonZeroExt;
+
+use crate::diag::SourceResult;
+use crate::engine::Engine;
+use crate::foundations::{
+    Content, NativeElement, Packed, ShowSet, Smart, StyleChain, Styles, elem, scope,
+};
+use crate::introspection::{
+    Counter, CounterKey, Locatable, Location, PageIntrospection,
+    PageNumberingIntrospection, QueryIntrospection,
+};
+use crate::layout::BlockElem;
+use crate::model::{Destination, EmphElem, HeadingElem, NumberingPattern, ParElem};
+use crate::text::{LocalName, TextElem, collate, localized_str};
+
+/// A back-of-book index.
+///
+/// The index lists all terms that were marked with
+/// [`index.entry`]($index.entry) throughout the document, together with the
+/// pages on which they occur. The terms are sorted alphabetically according to
+/// the conventions of the [text language]($text.lang) and region. Occurrences
+/// on consecutive pages are merged into page ranges and all page numbers link
+/// to the marked places.
+///
+/// # Example
+/// ```example
+/// #set page(height: 120pt)
+/// = Fruit
+/// Apples#index.entry("Apple") grow
+/// on trees.#index.entry("Tree")
+/// Many apples are red.
+/// #index.entry("Apple", sub: "red")
+///
+/// #pagebreak()
+/// Bananas#index.entry("Banana") grow
+/// on plants.#index.entry(
+///   "Tree",
+///   see-also: "Plant",
+/// )
+///
+/// #index(columns: 1)
+/// ```
+///
+/// # Styling the index
+/// The index is laid out in [columns]($index.columns). Each term is a
+/// [block]($block), so you can adjust the spacing between them with normal
+/// block-spacing rules. Subterms are indented below their term.
+#[elem(scope, keywords = ["Index", "Register"], ShowSet, LocalName, Locatable)]
+pub struct IndexElem {
+    /// The title of the index.
+    ///
+    /// - When set to `{auto}`, an appropriate title for the
+    ///   [text language]($text.lang) will be used.
+    /// - When set to `{none}`, the index will not have a title.
+    /// - A custom title can be set by passing content.
+    ///
+    /// The index's heading will not be numbered by default, but you can
+    /// force it to be with a show-set rule:
+    /// `{show index: set heading(numbering: "1.")}`
+    pub title: Smart<Option<Content>>,
+
+    /// The number of columns the index is laid out in.
+    #[default(NonZeroUsize::new(2).unwrap())]
+    pub columns: NonZeroUsize,
+}
+
+#[scope]
+impl IndexElem {
+    #[elem]
+    type IndexEntry;
+}
+
+impl Packed<IndexElem> {
+    /// Produces the heading for the index, if any.
+    pub fn realize_title(&self, styles: StyleChain) -> Option<Content> {
+        let span = self.span();
+        self.title
+            .get_cloned(styles)
+            .unwrap_or_else(|| {
+                Some(
+                    TextElem::packed(Packed::<IndexElem>::local_name_in(styles))
+                        .spanned(span),
+                )
+            })
+            .map(|title| {
+                HeadingElem::new(title)
+                    .with_depth(NonZeroUsize::ONE)
+                    .pack()
+                    .spanned(span)
+            })
+    }
+
+    /// Collects all index entries in the document into a sorted tree of terms.
+    pub fn realize_terms(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+    ) -> Vec<IndexTerm> {
+        let span = self.span();
+        let elems =
+            engine.introspect(QueryIntrospection(IndexEntry::ELEM.select(), span));
+
+        let mut terms = vec![];
+        for elem in &elems {
+            let Some(entry) = elem.to_packed::<IndexEntry>() else { continue };
+            let Some(loc) = entry.location() else { continue };
+
+            let mut term = IndexTerm::find_or_insert(&mut terms, &entry.term);
+            if let Some(sub) = entry.sub.get_ref(StyleChain::default()) {
+                term = IndexTerm::find_or_insert(&mut term.children, sub);
+            }
+
+            match entry.see_also.get_cloned(StyleChain::default()) {
+                Some(other) if !term.see_also.contains(&other) => {
+                    term.see_also.push(other)
+                }
+                Some(_) => {}
+                None => term.locations.push(loc),
+            }
+        }
+
+        let lang = styles.get(TextElem::lang);
+        let region = styles.get(TextElem::region);
+        IndexTerm::sort(&mut terms, &|a, b| {
+            collate(lang, region, a, b).then_with(|| a.cmp(b))
+        });
+
+        terms
+    }
+}
+
+impl ShowSet for Packed<IndexElem> {
+    fn show_set(&self, styles: StyleChain) -> Styles {
+        let mut out = Styles::new();
+        out.set(HeadingElem::numbering, None);
+        out.set(ParElem::justify, false);
+        out.set(BlockElem::above, Smart::Custom(styles.get(ParElem::leading).into()));
+        out
+    }
+}
+
+impl LocalName for Packed<IndexElem> {
+    const KEY: &'static str = "index";
+}
+
+/// Marks a term for inclusion in the [index].
+///
+/// The marker itself is invisible. Its position determines the page number
+/// that is listed for the term in the index. Multiple markers with the same
+/// term are combined into a single entry.
+///
+/// ```example
+/// The quick brown fox#index.entry("Fox")
+/// jumps over the lazy dog.
+/// #index.entry("Dog", sub: "lazy")
+/// ```
+#[elem(name = "entry", title = "Index Entry", Locatable)]
+pub struct IndexEntry {
+    /// The term under which this entry is listed.
+    #[required]
+    pub term: EcoString,
+
+    /// A subterm under which this entry is listed. Subterms are shown indented
+    /// below their term.
+    pub sub: Option<EcoString>,
+
+    /// Another term that is related to this one.
+    ///
+    /// An entry with this property does not contribute a page number. Instead,
+    /// the index refers the reader to the given term, e.g. "Tree, 3; _see also_
+    /// Plant".
+    pub see_also: Option<EcoString>,
+}
+
+/// A term in the index, with its occurrences and subterms.
+#[derive(Debug, Clone)]
+pub struct IndexTerm {
+    /// The term itself.
+    pub term: EcoString,
+    /// The locations of the term's markers, in document order.
+    pub locations: Vec<Location>,
+    /// Related terms the reader is referred to.
+    pub see_also: Vec<EcoString>,
+    /// The subterms, in sorted order.
+    pub children: Vec<IndexTerm>,
+}
+
+impl IndexTerm {
+    /// Formats the page numbers of the term's occurrences.
+    ///
+    /// Occurrences on the same page are combined and occurrences on
+    /// consecutive pages are merged into a range. Each page number links to
+    /// the first occurrence on its page.
+    pub fn pages(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        span: Span,
+    ) -> SourceResult<Vec<Content>> {
+        let occurrences: Vec<(usize, Location)> = self
+            .locations
+            .iter()
+            .map(|&loc| (engine.introspect(PageIntrospection(loc, span)).get(), loc))
+            .collect();
+
+        let mut pages = vec![];
+        for (start, start_loc, end, end_loc) in page_runs(&occurrences) {
+            let mut formatted = page_number(engine, start_loc, styles, span)?;
+            if end > start {
+                formatted += TextElem::packed('\u{2013}');
+                formatted += page_number(engine, end_loc, styles, span)?;
+            }
+            pages.push(formatted);
+        }
+
+        Ok(pages)
+    }
+
+    /// Formats the term's cross-references, e.g. "_see also_ Plant".
+    pub fn see_also(&self, styles: StyleChain) -> Option<Content> {
+        if self.see_also.is_empty() {
+            return None;
+        }
+
+        let lang = styles.get(TextElem::lang);
+        let region = styles.get(TextElem::region);
+        let prefix = localized_str(lang, region, "see-also");
+        let mut seq =
+            vec![EmphElem::new(TextElem::packed(prefix)).pack(), TextElem::packed(' ')];
+        for (i, other) in self.see_also.iter().enumerate() {
+            if i > 0 {
+                seq.push(TextElem::packed(", "));
+            }
+            seq.push(TextElem::packed(other.clone()));
+        }
+
+        Some(Content::sequence(seq))
+    }
+
+    /// Finds the term with the given text in `terms` or adds it.
+    fn find_or_insert<'a>(terms: &'a mut Vec<Self>, term: &str) -> &'a mut Self {
+        let i = match terms.iter().position(|t| t.term == term) {
+            Some(i) => i,
+            None => {
+                terms.push(Self {
+                    term: term.into(),
+                    locations: vec![],
+                    see_also: vec![],
+                    children: vec![],
+                });
+                terms.len() - 1
+            }
+        };
+        &mut terms[i]
+    }
+
+    /// Recursively sorts the terms with the given comparison function.
+    fn sort(terms: &mut [Self], cmp: &dyn Fn(&str, &str) -> Ordering) {
+        terms.sort_by(|a, b| cmp(&a.term, &b.term));
+        for term in terms {
+            Self::sort(&mut term.children, cmp);
+            term.see_also.sort_by(|a, b| cmp(a, b));
+        }
+    }
+}
+
+/// Groups occurrences, given by their page and in document order, into runs of
+/// consecutive pages. Each run consists of its first and last page, together
+/// with the first occurrence on each of them.
+fn page_runs<T: Copy>(occurrences: &[(usize, T)]) -> Vec<(usize, T, usize, T)> {
+    let mut runs: Vec<(usize, T, usize, T)> = vec![];
+    for &(page, item) in occurrences {
+        match runs.last_mut() {
+            Some((_, _, end, _)) if *end == page => {}
+            Some((_, _, end, end_item)) if *end + 1 == page => {
+                *end = page;
+                *end_item = item;
+            }
+            _ => runs.push((page, item, page, item)),
+        }
+    }
+    runs
+}
+
+/// Formats the page number at the given location and links it there.
+fn page_number(
+    engine: &mut Engine,
+    loc: Location,
+    styles: StyleChain,
+    span: Span,
+) -> SourceResult<Content> {
+    let numbering = engine
+        .introspect(PageNumberingIntrospection(loc, span))
+        .unwrap_or_else(|| NumberingPattern::from_str("1").unwrap().into());
+    Ok(Counter::new(CounterKey::Page)
+        .display_at(engine, loc, styles, &numbering, span)?
+        .linked(Destination::Location(loc), None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_runs() {
+        assert_eq!(page_runs::<char>(&[]), []);
+        assert_eq!(page_runs(&[(3, 'a')]), [(3, 'a', 3, 'a')]);
+
+        // Occurrences on the same page are combined and consecutive pages are
+        // merged, keeping the first occurrence on each page.
+        let occurrences = [(1, 'a'), (1, 'b'), (2, 'c'), (3, 'd'), (3, 'e'), (5, 'f')];
+        assert_eq!(page_runs(&occurrences), [(1, 'a', 3, 'd'), (5, 'f', 5, 'f')]);
+
+        // Gaps always start a new run.
+        let occurrences = [(2, 'a'), (4, 'b'), (5, 'c'), (7, 'd'), (8, 'e'), (9, 'f')];
+        assert_eq!(
+            page_runs(&occurrences),
+            [(2, 'a', 2, 'a'), (4, 'b', 5, 'c'), (7, 'd', 9, 'f')]
+        );
+    }
+
+    #[test]
+    fn test_sort() {
+        let term = |text: &str, children: Vec<IndexTerm>, see_also: &[&str]| IndexTerm {
+            term: text.into(),
+            locations: vec![],
+            see_also: see_also.iter().map(|&s| s.into()).collect(),
+            children,
+        };
+
+        let de = crate::text::Lang::from_str("de").unwrap();
+        let mut terms = vec![
+            term("Zebra", vec![], &[]),
+            term("apfelbaum", vec![], &[]),
+            term(
+                "Birne",
+                vec![term("Kern", vec![], &[]), term("Blüte", vec![], &[])],
+                &["Obst", "Frucht"],
+            ),
+            term("Äpfel", vec![], &[]),
+        ];
+        IndexTerm::sort(&mut terms, &|a, b| {
+            collate(de, None, a, b).then_with(|| a.cmp(b))
+        });
+
+        let order: Vec<&str> = terms.iter().map(|t| t.term.as_str()).collect();
+        assert_eq!(order, ["Äpfel", "apfelbaum", "Birne", "Zebra"]);
+        let children: Vec<&str> =
+            terms[2].children.iter().map(|t| t.term.as_str()).collect();
+        assert_eq!(children, ["Blüte", "Kern"]);
+        assert_eq!(terms[2].see_also, ["Frucht", "Obst"]);
+    }
+}
@@ -9,6 +9,7 @@ mod enum_;
 mod figure;
 mod footnote;
 mod heading;
+mod index;
 mod link;
 mod list;
 #[path = "numbering.rs"]
@@ -30,6 +31,7 @@ pub use self::enum_::*;
 pub use self::figure::*;
 pub use self::footnote::*;
 pub use self::heading::*;
+pub use self::index::*;
 pub use self::link::*;
 pub use self::list::*;
 pub use self::numbering_::*;
@@ -62,6 +64,7 @@ pub fn define(global: &mut Scope) {
     global.define_elem::<QuoteElem>();
     global.define_elem::<FootnoteElem>();
     global.define_elem::<OutlineElem>();
+    global.define_elem::<IndexElem>();
     global.define_elem::<RefElem>();
     global.define_elem::<CiteElem>();
     global.define_elem::<BibliographyElem>();
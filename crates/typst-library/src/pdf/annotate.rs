@@ -0,0 +1,184 @@
+use ecow::EcoString;
+use typst_syntax::Span;
+
+use crate::foundations::{Cast, Content, Datetime, Smart, elem};
+use crate::layout::{Abs, Em, Length, Rel};
+use crate::visualize::{Color, Paint, Stroke};
+
+/// A sticky note with a comment.
+///
+/// PDF viewers display the note as a small icon at the position where it is
+/// placed, without taking up any space in the layout. Clicking the icon shows
+/// the comment.
+///
+/// # Example
+/// ```typ
+/// The results are
+/// #pdf.note(
+///   "Can we back this up with a source?",
+///   author: "Reviewer 2",
+///   date: datetime(year: 2025, month: 3, day: 14),
+/// )
+/// conclusive.
+/// ```
+///
+/// # Notes
+/// - Annotations are ignored when exporting to a format other than PDF.
+/// - Annotations are not supported for PDF/A and PDF/X. Since its text uses a
+///   font that isn't embedded, the [free text]($pdf.free-text) annotation is
+///   not supported for PDF/UA-1 either.
+/// - In PDF, content belongs to at most one annotation. Marked up content that
+///   contains a link is thus not marked up where the link is, and a mark
+///   inside of a link is dropped. Typst warns in both cases.
+#[elem]
+pub struct NoteElem {
+    /// The comment.
+    #[required]
+    pub text: EcoString,
+
+    /// The author of the comment.
+    pub author: Option<EcoString>,
+
+    /// The date at which the comment was last modified.
+    pub date: Option<Datetime>,
+
+    /// The color of the note's icon.
+    #[default(Color::YELLOW)]
+    pub color: Color,
+
+    /// Whether the comment is initially shown in an open pop-up.
+    #[default(false)]
+    pub open: bool,
+}
+
+/// Marks up content and attaches a comment to it.
+///
+/// PDF viewers highlight, underline or strike through the content and show the
+/// comment when hovering or clicking on it. The content itself is laid out as
+/// usual.
+///
+/// Links within marked content are not marked up.
+///
+/// # Example
+/// ```typ
+/// #pdf.mark(comment: "Typo?")[teh] quick brown fox
+/// #pdf.mark(kind: "strike", color: red)[jumps]
+/// ```
+///
+/// See the [note]($pdf.note) for notes that apply to all annotations.
+#[elem]
+pub struct MarkElem {
+    /// The comment on the content.
+    pub comment: Option<EcoString>,
+
+    /// How the content is marked up.
+    #[default(MarkKind::Highlight)]
+    pub kind: MarkKind,
+
+    /// The author of the comment.
+    pub author: Option<EcoString>,
+
+    /// The date at which the comment was last modified.
+    pub date: Option<Datetime>,
+
+    /// The color of the markup.
+    #[default(Color::YELLOW)]
+    pub color: Color,
+
+    /// The content to mark up.
+    #[required]
+    pub body: Content,
+
+    /// An annotation style that should be applied to elements.
+    #[internal]
+    #[ghost]
+    pub current: Option<Annotation>,
+}
+
+/// How marked up content is displayed.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum MarkKind {
+    /// A highlight behind the content.
+    #[default]
+    Highlight,
+    /// A line below the content.
+    Underline,
+    /// A wavy line below the content.
+    Squiggly,
+    /// A line through the content.
+    Strike,
+}
+
+/// A text box on top of the page.
+///
+/// Unlike regular text, the text box is an annotation that PDF viewers allow
+/// to edit, move or delete. Its text is displayed in Helvetica at the current
+/// [text size]($text.size).
+///
+/// # Example
+/// ```typ
+/// #pdf.free-text(
+///   "Move this figure to the appendix.",
+///   author: "Editor",
+///   width: 100%,
+/// )
+/// ```
+///
+/// See the [note]($pdf.note) for notes that apply to all annotations. When
+/// exporting to other formats, only the box around the text is visible.
+#[elem]
+pub struct FreeTextElem {
+    /// The text in the box.
+    #[required]
+    pub text: EcoString,
+
+    /// The author of the text.
+    pub author: Option<EcoString>,
+
+    /// The date at which the text was last modified.
+    pub date: Option<Datetime>,
+
+    /// The box's width.
+    #[default(Em::new(12.0).into())]
+    pub width: Rel<Length>,
+
+    /// The box's height.
+    #[default(Em::new(3.0).into())]
+    pub height: Rel<Length>,
+
+    /// How to fill the box.
+    pub fill: Option<Paint>,
+
+    /// How to stroke the box.
+    ///
+    /// When set to `{auto}`, the box has a thin gray border.
+    pub stroke: Smart<Option<Stroke>>,
+}
+
+/// An annotation, as placed in a frame.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct Annotation {
+    /// What kind of annotation this is.
+    pub kind: AnnotationKind,
+    /// The comment or text of the annotation. May be empty.
+    pub text: EcoString,
+    /// The author of the annotation.
+    pub author: Option<EcoString>,
+    /// The date at which the annotation was last modified.
+    pub date: Option<Datetime>,
+    /// The font size of the annotation's text.
+    pub text_size: Abs,
+    /// The span of the annotation element.
+    pub span: Span,
+}
+
+/// The kind of an [`Annotation`].
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum AnnotationKind {
+    /// A sticky note.
+    Note { color: Color, open: bool },
+    /// Marked up content.
+    Mark { kind: MarkKind, color: Color },
+    /// A text box.
+    FreeText,
+}
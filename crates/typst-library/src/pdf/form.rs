@@ -0,0 +1,297 @@
+use ecow::EcoString;
+use typst_syntax::Span;
+
+use crate::foundations::{Smart, elem};
+use crate::layout::{Abs, Em, Length, Rel};
+use crate::visualize::{Paint, Stroke};
+
+/// A text field in a fillable PDF form.
+///
+/// Readers can type into the field in a PDF viewer. The field is laid out as a
+/// box and its current value is displayed in Helvetica at the current
+/// [text size]($text.size).
+///
+/// # Example
+/// ```typ
+/// Name: #pdf.text-field("name", alt: "Full name")
+///
+/// Comments:
+/// #pdf.text-field(
+///   "comments",
+///   multiline: true,
+///   width: 100%,
+///   height: 4em,
+/// )
+/// ```
+///
+/// # Notes
+/// - Form fields are ignored when exporting to a format other than PDF. There,
+///   only their box is visible.
+/// - Form fields are not supported for PDF/A and PDF/X, since the appearance of
+///   their values uses a font that isn't embedded. For the same reason, text
+///   fields, drop-down lists, and buttons are not supported for PDF/UA-1.
+///   Checkboxes and radio buttons are.
+/// - Each field must have a unique name. Only [radio buttons]($pdf.radio) may
+///   share their name to form a group.
+#[elem]
+pub struct TextFieldElem {
+    /// The name of the field.
+    ///
+    /// When the form's data is extracted or submitted, the field's value is
+    /// reported under this name. It may not contain periods.
+    #[required]
+    pub name: EcoString,
+
+    /// The text that is initially filled in.
+    pub value: EcoString,
+
+    /// Whether the field accepts multiple lines of text.
+    #[default(false)]
+    pub multiline: bool,
+
+    /// A description of the field for Assistive Technology (AT), such as
+    /// screen readers. PDF viewers also show it as a tooltip.
+    ///
+    /// If omitted, the field's name is used.
+    pub alt: Option<EcoString>,
+
+    /// The field's width.
+    #[default(Em::new(10.0).into())]
+    pub width: Rel<Length>,
+
+    /// The field's height.
+    #[default(Em::new(1.5).into())]
+    pub height: Rel<Length>,
+
+    /// How to fill the field's box.
+    pub fill: Option<Paint>,
+
+    /// How to stroke the field's box.
+    ///
+    /// When set to `{auto}`, the box has a thin gray border.
+    pub stroke: Smart<Option<Stroke>>,
+}
+
+/// A checkbox in a fillable PDF form.
+///
+/// Readers can check and uncheck the box in a PDF viewer.
+///
+/// # Example
+/// ```typ
+/// #pdf.checkbox("newsletter", alt: "Subscribe to the newsletter")
+/// Subscribe to the newsletter
+/// ```
+///
+/// See the [text field]($pdf.text-field) for notes that apply to all form
+/// fields.
+#[elem]
+pub struct CheckboxElem {
+    /// The name of the field.
+    #[required]
+    pub name: EcoString,
+
+    /// Whether the box is initially checked.
+    #[default(false)]
+    pub checked: bool,
+
+    /// A description of the field for Assistive Technology (AT), such as
+    /// screen readers. PDF viewers also show it as a tooltip.
+    ///
+    /// If omitted, the field's name is used.
+    pub alt: Option<EcoString>,
+
+    /// The width and height of the box.
+    #[default(Em::new(0.8).into())]
+    pub size: Length,
+
+    /// How to fill the box.
+    pub fill: Option<Paint>,
+
+    /// How to stroke the box.
+    ///
+    /// When set to `{auto}`, the box has a thin gray border.
+    pub stroke: Smart<Option<Stroke>>,
+}
+
+/// A radio button in a fillable PDF form.
+///
+/// All radio buttons with the same name form a group, of which readers can
+/// select at most one button.
+///
+/// # Example
+/// ```typ
+/// #pdf.radio("size", "s", alt: "Small") Small \
+/// #pdf.radio("size", "m", alt: "Medium", checked: true) Medium \
+/// #pdf.radio("size", "l", alt: "Large") Large
+/// ```
+///
+/// See the [text field]($pdf.text-field) for notes that apply to all form
+/// fields.
+#[elem]
+pub struct RadioElem {
+    /// The name of the group the button belongs to.
+    #[required]
+    pub name: EcoString,
+
+    /// The value the group takes on when this button is selected.
+    ///
+    /// Must be unique within the group.
+    #[required]
+    pub value: EcoString,
+
+    /// Whether the button is initially selected.
+    ///
+    /// At most one button per group may be selected.
+    #[default(false)]
+    pub checked: bool,
+
+    /// A description of the button for Assistive Technology (AT), such as
+    /// screen readers. PDF viewers also show it as a tooltip.
+    ///
+    /// If omitted, the button's value is used.
+    pub alt: Option<EcoString>,
+
+    /// The diameter of the button.
+    #[default(Em::new(0.8).into())]
+    pub size: Length,
+
+    /// How to fill the button.
+    pub fill: Option<Paint>,
+
+    /// How to stroke the button.
+    ///
+    /// When set to `{auto}`, the button has a thin gray border.
+    pub stroke: Smart<Option<Stroke>>,
+}
+
+/// A drop-down list in a fillable PDF form.
+///
+/// Readers can choose one of the options in a PDF viewer.
+///
+/// # Example
+/// ```typ
+/// Country: #pdf.dropdown(
+///   "country",
+///   ("Austria", "Germany", "Switzerland"),
+///   value: "Germany",
+/// )
+/// ```
+///
+/// See the [text field]($pdf.text-field) for notes that apply to all form
+/// fields.
+#[elem]
+pub struct DropdownElem {
+    /// The name of the field.
+    #[required]
+    pub name: EcoString,
+
+    /// The options readers can choose from.
+    #[required]
+    pub options: Vec<EcoString>,
+
+    /// The option that is initially selected.
+    ///
+    /// Must be one of the options. If `{none}`, no option is selected.
+    pub value: Option<EcoString>,
+
+    /// A description of the field for Assistive Technology (AT), such as
+    /// screen readers. PDF viewers also show it as a tooltip.
+    ///
+    /// If omitted, the field's name is used.
+    pub alt: Option<EcoString>,
+
+    /// The field's width.
+    #[default(Em::new(10.0).into())]
+    pub width: Rel<Length>,
+
+    /// The field's height.
+    #[default(Em::new(1.5).into())]
+    pub height: Rel<Length>,
+
+    /// How to fill the field's box.
+    pub fill: Option<Paint>,
+
+    /// How to stroke the field's box.
+    ///
+    /// When set to `{auto}`, the box has a thin gray border.
+    pub stroke: Smart<Option<Stroke>>,
+}
+
+/// A push button in a fillable PDF form.
+///
+/// Pressing the button either submits the form's data to a URL or resets all
+/// fields to their initial values.
+///
+/// # Example
+/// ```typ
+/// #pdf.button("send", "Send", submit: "https://example.com/apply")
+/// #pdf.button("reset", "Clear")
+/// ```
+///
+/// See the [text field]($pdf.text-field) for notes that apply to all form
+/// fields.
+#[elem]
+pub struct ButtonElem {
+    /// The name of the field.
+    #[required]
+    pub name: EcoString,
+
+    /// The button's caption.
+    #[required]
+    pub caption: EcoString,
+
+    /// A URL to submit the form's data to, in HTML form format.
+    ///
+    /// If `{none}`, pressing the button resets the form instead.
+    pub submit: Option<EcoString>,
+
+    /// A description of the button for Assistive Technology (AT), such as
+    /// screen readers. PDF viewers also show it as a tooltip.
+    ///
+    /// If omitted, the button's label is used.
+    pub alt: Option<EcoString>,
+
+    /// The button's width.
+    #[default(Em::new(6.0).into())]
+    pub width: Rel<Length>,
+
+    /// The button's height.
+    #[default(Em::new(1.5).into())]
+    pub height: Rel<Length>,
+
+    /// How to fill the button.
+    pub fill: Option<Paint>,
+
+    /// How to stroke the button.
+    ///
+    /// When set to `{auto}`, the button has a thin gray border.
+    pub stroke: Smart<Option<Stroke>>,
+}
+
+/// The interactive part of a form field, as placed in a frame.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct Widget {
+    /// The name of the field the widget belongs to.
+    pub name: EcoString,
+    /// What kind of field the widget belongs to.
+    pub kind: WidgetKind,
+    /// The font size of the field's text.
+    pub text_size: Abs,
+    /// The span of the field element.
+    pub span: Span,
+}
+
+/// The kind of form field a [`Widget`] belongs to, with its initial state.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum WidgetKind {
+    /// A text field with its initial value.
+    Text { value: EcoString, multiline: bool },
+    /// A checkbox.
+    Checkbox { checked: bool },
+    /// A radio button with the value it selects in its group.
+    Radio { value: EcoString, checked: bool },
+    /// A drop-down list with its options and the selected one.
+    Dropdown { options: Vec<EcoString>, value: Option<EcoString> },
+    /// A push button with its caption and submission URL.
+    Button { caption: EcoString, submit: Option<EcoString> },
+}
@@ -0,0 +1,86 @@
+use ecow::EcoString;
+use typst_syntax::Span;
+
+use crate::foundations::{Content, Smart, elem};
+
+/// Content that viewers can show or hide.
+///
+/// In PDF, the content becomes part of an optional content group, commonly
+/// called a layer. Viewers list the document's layers and let readers toggle
+/// them, for example to show the solutions of an exercise sheet or to hide a
+/// watermark. All layers with the same name are toggled together.
+///
+/// # Example
+/// ```typ
+/// What is $2 + 2$?
+///
+/// #pdf.layer("Solutions", visible: false)[
+///   The answer is $4$.
+/// ]
+/// ```
+///
+/// # Notes
+/// - When exporting to PNG or SVG, layers are included if they are visible.
+///   This can be changed with the `--show-layer` and `--hide-layer` options of
+///   the CLI.
+/// - Content belongs to the innermost layer it is in.
+/// - Layers are not supported for PDF/A, PDF/UA and PDF/X.
+#[elem]
+pub struct LayerElem {
+    /// The name of the layer, which viewers display when listing layers.
+    #[required]
+    pub name: EcoString,
+
+    /// Whether the layer is initially visible.
+    #[default(true)]
+    pub visible: bool,
+
+    /// Whether the layer is printed.
+    ///
+    /// When set to `{auto}`, the layer is printed if it is visible.
+    pub print: Smart<bool>,
+
+    /// The content of the layer.
+    #[required]
+    pub body: Content,
+
+    /// A layer style that should be applied to elements.
+    #[internal]
+    #[ghost]
+    pub current: Option<Layer>,
+}
+
+/// A layer, as attached to a frame group.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Layer {
+    /// The name of the layer.
+    pub name: EcoString,
+    /// Whether the layer is initially visible.
+    pub visible: bool,
+    /// Whether the layer is printed.
+    pub print: bool,
+    /// The span of the layer element.
+    pub span: Span,
+}
+
+/// Which layers to include when exporting to a format that doesn't support
+/// toggling them.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct LayerFilter {
+    /// Names of layers to include, even if they aren't visible.
+    pub show: Vec<EcoString>,
+    /// Names of layers to exclude, even if they are visible. Takes precedence
+    /// over `show`.
+    pub hide: Vec<EcoString>,
+}
+
+impl LayerFilter {
+    /// Whether content in the given layer should be included.
+    pub fn includes(&self, layer: &Layer) -> bool {
+        if self.hide.contains(&layer.name) {
+            false
+        } else {
+            self.show.contains(&layer.name) || layer.visible
+        }
+    }
+}
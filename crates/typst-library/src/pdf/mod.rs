@@ -1,10 +1,16 @@
 //! PDF-specific functionality.
 
 mod accessibility;
+mod annotate;
 mod attach;
+mod form;
+mod layer;
 
 pub use self::accessibility::*;
+pub use self::annotate::*;
 pub use self::attach::*;
+pub use self::form::*;
+pub use self::layer::*;
 
 use crate::foundations::{Deprecation, Element, Module, Scope};
 use crate::{Feature, Features};
@@ -21,6 +27,15 @@ pub fn module(features: &Features) -> Module {
             .with_until("0.15.0"),
     );
     pdf.define_elem::<ArtifactElem>();
+    pdf.define_elem::<TextFieldElem>();
+    pdf.define_elem::<CheckboxElem>();
+    pdf.define_elem::<RadioElem>();
+    pdf.define_elem::<DropdownElem>();
+    pdf.define_elem::<ButtonElem>();
+    pdf.define_elem::<NoteElem>();
+    pdf.define_elem::<MarkElem>();
+    pdf.define_elem::<FreeTextElem>();
+    pdf.define_elem::<LayerElem>();
     if features.is_enabled(Feature::A11yExtras) {
         pdf.define_func::<table_summary>();
         pdf.define_func::<header_cell>();
@@ -1,10 +1,18 @@
+use std::cmp::Ordering;
 use std::str::FromStr;
+use std::sync::{Arc, LazyLock, Mutex};
 
+use comemo::Tracked;
 use ecow::{EcoString, eco_format};
+use icu_collator::{CaseLevel, Collator, CollatorOptions, Strength};
+use icu_locid::LanguageIdentifier;
+use icu_provider::DataLocale;
 use rustc_hash::FxHashMap;
 
-use crate::diag::Hint;
-use crate::foundations::{StyleChain, cast};
+use crate::diag::{Hint, HintedStrResult};
+use crate::foundations::{
+    AutoValue, Context, Dict, Smart, StyleChain, Value, cast, dict,
+};
 use crate::layout::Dir;
 use crate::text::TextElem;
 
@@ -678,6 +686,107 @@ fn lang_str(lang: Lang, region: Option<Region>) -> EcoString {
         + region.map_or_else(EcoString::new, |r| EcoString::from("-") + r.as_str())
 }
 
+/// Compares two strings according to the collation rules of the given
+/// language and region.
+///
+/// Falls back to the root collation (which sorts by Unicode's default
+/// collation element table) if no rules exist for the language.
+pub fn collate(lang: Lang, region: Option<Region>, a: &str, b: &str) -> Ordering {
+    collator(lang, region, true, true).compare(a, b)
+}
+
+/// Retrieves a cached collator for the given language and region.
+///
+/// The `case` and `accents` flags determine whether strings that only differ
+/// in case or accents, respectively, are distinguished.
+pub fn collator(
+    lang: Lang,
+    region: Option<Region>,
+    case: bool,
+    accents: bool,
+) -> Arc<Collator> {
+    type Key = (Lang, Option<Region>, bool, bool);
+    type Cache = Mutex<FxHashMap<Key, Arc<Collator>>>;
+    static CACHE: LazyLock<Cache> = LazyLock::new(Default::default);
+
+    let mut cache = CACHE.lock().unwrap();
+    let collator = cache.entry((lang, region, case, accents)).or_insert_with(|| {
+        let tag = match region {
+            Some(region) => eco_format!("{}-{}", lang.as_str(), region.as_str()),
+            None => lang.as_str().into(),
+        };
+        let locale = LanguageIdentifier::try_from_bytes(tag.as_bytes())
+            .map(|id| DataLocale::from(&id))
+            .unwrap_or_default();
+        let mut options = CollatorOptions::new();
+        options.strength = Some(match (case, accents) {
+            (true, true) => Strength::Tertiary,
+            (false, true) => Strength::Secondary,
+            (_, false) => Strength::Primary,
+        });
+        if case && !accents {
+            options.case_level = Some(CaseLevel::On);
+        }
+        let collator = Collator::try_new(&locale, options)
+            .or_else(|_| Collator::try_new(&DataLocale::default(), options))
+            .expect("root collation should always be available");
+        Arc::new(collator)
+    });
+
+    collator.clone()
+}
+
+/// Rules for ordering strings, as used by [`array.sorted`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Collation {
+    /// The language whose rules to use. `auto` uses the text language.
+    pub lang: Smart<Lang>,
+    /// The region whose rules to use. If `None` and the language is `auto`,
+    /// the text region is used.
+    pub region: Option<Region>,
+    /// Whether strings that only differ in case are distinguished.
+    pub case: bool,
+    /// Whether strings that only differ in accents are distinguished.
+    pub accents: bool,
+}
+
+impl Collation {
+    /// Retrieves a collator for these rules, resolving the language in the
+    /// given context if necessary.
+    pub fn collator(&self, context: Tracked<Context>) -> HintedStrResult<Arc<Collator>> {
+        let (lang, region) = match self.lang {
+            Smart::Custom(lang) => (lang, self.region),
+            Smart::Auto => {
+                let styles = context.styles()?;
+                let region = self.region.or(styles.get(TextElem::region));
+                (styles.get(TextElem::lang), region)
+            }
+        };
+        Ok(collator(lang, region, self.case, self.accents))
+    }
+}
+
+cast! {
+    Collation,
+    self => dict! {
+        "lang" => self.lang,
+        "region" => self.region,
+        "case" => self.case,
+        "accents" => self.accents,
+    }.into_value(),
+    _: AutoValue => Self { lang: Smart::Auto, region: None, case: true, accents: true },
+    lang: Lang => Self { lang: Smart::Custom(lang), region: None, case: true, accents: true },
+    mut dict: Dict => {
+        let mut take = |key| dict.take(key).ok();
+        let lang = take("lang").map(Value::cast).transpose()?.unwrap_or(Smart::Auto);
+        let region = take("region").map(Value::cast).transpose()?.flatten();
+        let case = take("case").map(Value::cast).transpose()?.unwrap_or(true);
+        let accents = take("accents").map(Value::cast).transpose()?.unwrap_or(true);
+        dict.finish(&["lang", "region", "case", "accents"])?;
+        Self { lang, region, case, accents }
+    },
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -701,6 +810,15 @@ mod tests {
         assert!(!option_eq(region, "AB"));
     }
 
+    #[test]
+    fn test_collate() {
+        let de = Lang::from_str("de").unwrap();
+        let sv = Lang::from_str("sv").unwrap();
+        assert_eq!(collate(Lang::ENGLISH, None, "apple", "Banana"), Ordering::Less);
+        assert_eq!(collate(de, None, "Äpfel", "Birnen"), Ordering::Less);
+        assert_eq!(collate(sv, None, "Äpple", "Zebra"), Ordering::Greater);
+    }
+
     #[test]
     fn test_all_translations_included() {
         let defined_keys =
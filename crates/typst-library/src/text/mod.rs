@@ -8,6 +8,7 @@ mod lang;
 mod linebreak;
 #[path = "lorem.rs"]
 mod lorem_;
+mod number;
 mod raw;
 mod shift;
 #[path = "smallcaps.rs"]
@@ -22,6 +23,7 @@ pub use self::item::*;
 pub use self::lang::*;
 pub use self::linebreak::*;
 pub use self::lorem_::*;
+pub use self::number::*;
 pub use self::raw::*;
 pub use self::shift::*;
 pub use self::smallcaps_::*;
@@ -72,6 +74,7 @@ pub(super) fn define(global: &mut Scope) {
     global.define_func::<lower>();
     global.define_func::<upper>();
     global.define_func::<lorem>();
+    global.define_func::<num>();
     global.reset_category();
 }
 
@@ -752,6 +755,36 @@ pub struct TextElem {
     #[required]
     pub text: EcoString,
 
+    /// The text of each capturing group, if the text was matched by a
+    /// [regex show rule]($styling/#show-rules). Groups that did not
+    /// participate in the match are `{none}`.
+    ///
+    /// ```example
+    /// #show regex("([A-Z]+)-(\d+)"): it => {
+    ///   let (project, number) = it.captures
+    ///   [#project ticket no. #number]
+    /// }
+    ///
+    /// Fixed in ABC-123.
+    /// ```
+    #[synthesized]
+    pub captures: Array,
+
+    /// The text of each named capturing group, if the text was matched by a
+    /// [regex show rule]($styling/#show-rules). Groups that did not
+    /// participate in the match are `{none}`.
+    ///
+    /// ```example
+    /// #show regex("v(?<major>\d+)\.(?<minor>\d+)"): it => {
+    ///   let (major, minor) = it.named-captures
+    ///   [version #major (update #minor)]
+    /// }
+    ///
+    /// Requires v2.3.
+    /// ```
+    #[synthesized]
+    pub named_captures: Dict,
+
     /// The offset of the text in the text syntax node referenced by this
     /// element's span.
     #[internal]
@@ -1483,6 +1516,13 @@ mod tests {
 
     #[test]
     fn test_text_elem_size() {
-        assert_eq!(std::mem::size_of::<TextElem>(), std::mem::size_of::<EcoString>());
+        // Besides its text, a text element only stores the captures of a
+        // regex match.
+        assert_eq!(
+            std::mem::size_of::<TextElem>(),
+            std::mem::size_of::<EcoString>()
+                + std::mem::size_of::<Option<Array>>()
+                + std::mem::size_of::<Option<Dict>>()
+        );
     }
 }
@@ -0,0 +1,338 @@
+use comemo::Tracked;
+use ecow::{EcoString, eco_format};
+
+use crate::diag::{HintedStrResult, StrResult, bail};
+use crate::foundations::calc::DecNum;
+use crate::foundations::{Cast, Context, Decimal, Smart, Str, func, repr};
+use crate::text::{Lang, Region, TextElem};
+
+/// Formats a number according to the conventions of the current
+/// [language]($text.lang) and [region]($text.region).
+///
+/// The language determines the decimal separator, how the digits of the
+/// integer part are grouped, and how percentages and currency amounts are
+/// written. Languages without specific conventions use the English ones.
+/// Since this depends on the text language, the function is
+/// [contextual]($context).
+///
+/// # Example
+/// ```example
+/// #context num(1234567.891) \
+/// #set text(lang: "de")
+/// #context num(1234567.891) \
+/// #context num(0.25, style: "percent") \
+/// #context num(
+///   decimal("1499.5"),
+///   style: "currency",
+///   currency: "EUR",
+/// )
+/// ```
+#[func(contextual, title = "Number")]
+pub fn num(
+    context: Tracked<Context>,
+    /// The number to format.
+    value: DecNum,
+    /// How to format the number.
+    #[named]
+    #[default(NumberStyle::Decimal)]
+    style: NumberStyle,
+    /// The number of digits after the decimal separator.
+    ///
+    /// When set to `{auto}`, integers are shown without fractional digits,
+    /// floats with as many as needed, and decimals with as many as they
+    /// store. Currency amounts are shown with the usual number of fractional
+    /// digits for the currency.
+    ///
+    /// ```example
+    /// #context num(calc.pi, digits: 3)
+    /// ```
+    #[named]
+    #[default]
+    digits: Smart<usize>,
+    /// The currency of an amount. Required for the `{"currency"}` style.
+    ///
+    /// Either an [ISO 4217](https://en.wikipedia.org/wiki/ISO_4217) currency
+    /// code like `{"EUR"}`, which is replaced by its symbol where one is
+    /// known, or a custom symbol.
+    #[named]
+    currency: Option<Str>,
+    /// Whether to group the digits of the integer part.
+    ///
+    /// ```example
+    /// #context num(1234567, grouping: false)
+    /// ```
+    #[named]
+    #[default(true)]
+    grouping: bool,
+) -> HintedStrResult<Str> {
+    let styles = context.styles()?;
+    let lang = styles.get(TextElem::lang);
+    let region = styles.get(TextElem::region);
+    let format = NumberFormat::get(lang, region);
+
+    let currency = match (style, currency) {
+        (NumberStyle::Currency, Some(code)) => Some(Currency::new(&code)),
+        (NumberStyle::Currency, None) => {
+            bail!("the currency style requires a currency")
+        }
+        (_, _) => None,
+    };
+
+    let digits = match (digits, &currency) {
+        (Smart::Custom(digits), _) => Some(digits),
+        (Smart::Auto, Some(currency)) => Some(currency.digits),
+        (Smart::Auto, None) => None,
+    };
+
+    let value = match style {
+        NumberStyle::Percent => percent(value)?,
+        _ => value,
+    };
+
+    // The sign precedes the whole pattern, e.g. "−€3.00".
+    let (negative, formatted) = format.format(value, digits, grouping);
+    let mut out = EcoString::new();
+    if negative {
+        out.push_str(repr::MINUS_SIGN);
+    }
+    out.push_str(&match style {
+        NumberStyle::Decimal => formatted,
+        NumberStyle::Percent => format.percent(&formatted),
+        NumberStyle::Currency => format.currency(&formatted, &currency.unwrap()),
+    });
+    Ok(out.into())
+}
+
+/// Converts a fraction into a percentage.
+fn percent(value: DecNum) -> StrResult<DecNum> {
+    Ok(match value {
+        DecNum::Int(v) => {
+            DecNum::Int(v.checked_mul(100).ok_or("the percentage is too large")?)
+        }
+        // Scale the shortest representation of the float in decimal, so
+        // that `0.07` becomes `7` instead of `7.000000000000001`.
+        DecNum::Float(v) => DecNum::Float(format!("{v}e2").parse().unwrap_or(v * 100.0)),
+        DecNum::Decimal(v) => DecNum::Decimal(
+            v.checked_mul(Decimal::from(100))
+                .ok_or("the percentage is too large")?,
+        ),
+    })
+}
+
+/// Writes the absolute value of a number with the given number of fractional
+/// digits, using `.` as the decimal separator. Also returns whether the number
+/// is negative.
+fn plain(value: DecNum, digits: Option<usize>) -> (bool, EcoString) {
+    let (negative, plain) = match (value, digits) {
+        (DecNum::Int(v), None) => (v < 0, eco_format!("{}", v.unsigned_abs())),
+        (DecNum::Int(v), Some(digits)) => {
+            (v < 0, eco_format!("{}.{:0>digits$}", v.unsigned_abs(), ""))
+        }
+        (DecNum::Float(v), None) => (v < 0.0, eco_format!("{}", v.abs())),
+        (DecNum::Float(v), Some(digits)) => {
+            (v < 0.0, eco_format!("{:.digits$}", v.abs()))
+        }
+        (DecNum::Decimal(v), None) => (v.is_negative(), eco_format!("{}", v.abs())),
+        (DecNum::Decimal(v), Some(digits)) => {
+            let rounded = v.round(digits.try_into().unwrap_or(i32::MAX)).unwrap_or(v);
+            (v.is_negative(), eco_format!("{:.digits$}", rounded.abs()))
+        }
+    };
+
+    // Avoid a negative zero after rounding.
+    let zero = plain.chars().all(|c| matches!(c, '0' | '.'));
+    (negative && !zero, plain.trim_end_matches('.').into())
+}
+
+/// How a number is formatted.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum NumberStyle {
+    /// A plain number, e.g. "1,234.5".
+    Decimal,
+    /// A fraction shown as a percentage, e.g. `{0.25}` becomes "25%".
+    Percent,
+    /// A currency amount, e.g. "€1,234.50".
+    Currency,
+}
+
+/// The conventions for writing numbers in a language.
+struct NumberFormat {
+    /// Separates the integer and fractional part.
+    decimal: &'static str,
+    /// Separates groups of digits in the integer part.
+    group: &'static str,
+    /// The minimum number of digits in the leading group for grouping to
+    /// apply at all. In some languages, four-digit numbers are not grouped.
+    min_grouping: usize,
+    /// Whether groups after the first one have two instead of three digits.
+    indian: bool,
+    /// How percentages are written, with `#` standing for the number.
+    percent: &'static str,
+    /// How currency amounts are written, with `#` standing for the number and
+    /// `¤` for the currency symbol.
+    currency: &'static str,
+}
+
+impl NumberFormat {
+    /// Retrieves the conventions for a language and region.
+    fn get(lang: Lang, region: Option<Region>) -> Self {
+        let region = region.as_ref().map(Region::as_str);
+
+        const NBSP: &str = "\u{A0}";
+        let format = |decimal, group, percent, currency| Self {
+            decimal,
+            group,
+            min_grouping: 1,
+            indian: false,
+            percent,
+            currency,
+        };
+
+        match lang.as_str() {
+            "de" if matches!(region, Some("CH" | "LI")) => format(".", "’", "#%", "¤ #"),
+            "de" if matches!(region, Some("AT")) => format(",", NBSP, "# %", "¤ #"),
+            "de" => format(",", ".", "# %", "# ¤"),
+            "fr" if matches!(region, Some("CH")) => format(",", "\u{202F}", "#%", "# ¤"),
+            "fr" => format(",", "\u{202F}", "#\u{202F}%", "# ¤"),
+            "es" if matches!(region, Some("ES") | None) => {
+                Self { min_grouping: 2, ..format(",", ".", "# %", "# ¤") }
+            }
+            "es" => format(".", ",", "# %", "¤#"),
+            "pt" if matches!(region, Some("BR") | None) => format(",", ".", "#%", "¤ #"),
+            "pt" => Self { min_grouping: 2, ..format(",", NBSP, "#%", "# ¤") },
+            "it" => format(",", ".", "#%", "# ¤"),
+            "nl" => format(",", ".", "#%", "¤ #"),
+            "da" => format(",", ".", "# %", "# ¤"),
+            "pl" => Self { min_grouping: 2, ..format(",", NBSP, "#%", "# ¤") },
+            "cs" | "fi" | "nb" | "nn" | "no" | "ru" | "sk" | "sv" | "uk" => {
+                format(",", NBSP, "# %", "# ¤")
+            }
+            "bg" | "et" | "hu" | "lt" | "lv" => format(",", NBSP, "#%", "# ¤"),
+            "el" | "hr" | "id" | "ro" | "sl" | "sr" => format(",", ".", "#%", "# ¤"),
+            "tr" => format(",", ".", "%#", "¤#"),
+            "hi" => Self { indian: true, ..format(".", ",", "#%", "¤#") },
+            "en" if matches!(region, Some("IN")) => {
+                Self { indian: true, ..format(".", ",", "#%", "¤#") }
+            }
+            _ => format(".", ",", "#%", "¤#"),
+        }
+    }
+
+    /// Formats the absolute value of a number with this format's separators.
+    /// Also returns whether the number is negative.
+    fn format(
+        &self,
+        value: DecNum,
+        digits: Option<usize>,
+        grouping: bool,
+    ) -> (bool, EcoString) {
+        if let DecNum::Float(v) = value
+            && !v.is_finite()
+        {
+            return if v.is_nan() {
+                (false, "NaN".into())
+            } else {
+                (v < 0.0, "∞".into())
+            };
+        }
+
+        let (negative, plain) = plain(value, digits);
+        let (int, fract) = match plain.split_once('.') {
+            Some((int, fract)) => (int, Some(fract)),
+            None => (plain.as_str(), None),
+        };
+
+        let mut out = EcoString::new();
+        if grouping && int.len() >= 3 + self.min_grouping {
+            // Find the group boundaries from the right. Only the last group
+            // always has three digits.
+            let secondary = if self.indian { 2 } else { 3 };
+            let mut ends = vec![int.len()];
+            let mut end = int.len() - 3;
+            while end > 0 {
+                ends.push(end);
+                end = end.saturating_sub(secondary);
+            }
+            ends.push(0);
+            ends.reverse();
+
+            for (i, window) in ends.windows(2).enumerate() {
+                if i > 0 {
+                    out.push_str(self.group);
+                }
+                out.push_str(&int[window[0]..window[1]]);
+            }
+        } else {
+            out.push_str(int);
+        }
+
+        if let Some(fract) = fract {
+            out.push_str(self.decimal);
+            out.push_str(fract);
+        }
+
+        (negative, out)
+    }
+
+    /// Places a formatted number according to this format's percent pattern.
+    fn percent(&self, number: &str) -> EcoString {
+        // Spaces in the patterns are non-breaking.
+        self.percent.replace(' ', "\u{A0}").replace('#', number).into()
+    }
+
+    /// Places a formatted amount and currency symbol according to this
+    /// format's currency pattern.
+    fn currency(&self, amount: &str, currency: &Currency) -> EcoString {
+        // Alphabetic symbols like "CHF" need to be separated from the amount.
+        let mut pattern = EcoString::from(self.currency);
+        if currency.symbol.ends_with(char::is_alphabetic) {
+            pattern = pattern.replace("¤#", "¤ #");
+        }
+        if currency.symbol.starts_with(char::is_alphabetic) {
+            pattern = pattern.replace("#¤", "# ¤");
+        }
+
+        // Spaces in the patterns are non-breaking.
+        pattern
+            .replace(" ", "\u{A0}")
+            .replace("¤", &currency.symbol)
+            .replace("#", amount)
+    }
+}
+
+/// A currency with its symbol.
+struct Currency {
+    /// The symbol that is shown next to amounts.
+    symbol: EcoString,
+    /// The usual number of fractional digits.
+    digits: usize,
+}
+
+impl Currency {
+    /// Resolves an ISO 4217 code or a custom symbol.
+    fn new(code: &str) -> Self {
+        let symbol = match code {
+            "EUR" => "€",
+            "USD" => "$",
+            "GBP" => "£",
+            "JPY" | "CNY" => "¥",
+            "INR" => "₹",
+            "KRW" => "₩",
+            "RUB" => "₽",
+            "UAH" => "₴",
+            "TRY" => "₺",
+            "ILS" => "₪",
+            "BRL" => "R$",
+            "PLN" => "zł",
+            "DKK" | "NOK" | "SEK" => "kr",
+            _ => code,
+        };
+
+        let digits = match code {
+            "JPY" | "KRW" | "ISK" | "CLP" | "VND" => 0,
+            _ => 2,
+        };
+
+        Self { symbol: symbol.into(), digits }
+    }
+}
@@ -1,7 +1,7 @@
 use std::fmt::{self, Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::str::FromStr;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 
 use ecow::{EcoString, EcoVec, eco_format};
 use palette::encoding::{self, Linear};
@@ -10,13 +10,16 @@ use palette::{
 };
 use qcms::Profile;
 use typst_syntax::{Span, Spanned};
+use typst_utils::PicoStr;
 
 use crate::diag::{At, SourceResult, StrResult, bail};
+use crate::engine::Engine;
 use crate::foundations::{
-    Args, Array, IntoValue, Module, Repr, Scope, Str, Value, array, cast, func, repr,
-    scope, ty,
+    Args, Array, Bytes, IntoValue, Module, Repr, Scope, Str, Value, array, cast, func,
+    repr, scope, ty,
 };
 use crate::layout::{Angle, Ratio};
+use crate::loading::{DataSource, Load};
 
 // Type aliases for `palette` internal types in f32.
 pub type Oklab = palette::oklab::Oklaba<f32>;
@@ -67,7 +70,8 @@ static TO_SRGB: LazyLock<qcms::Transform> = LazyLock::new(|| {
 /// - Linear RGB through the [`color.linear-rgb` function]($color.linear-rgb)
 /// - HSL through the [`color.hsl` function]($color.hsl)
 /// - HSV through the [`color.hsv` function]($color.hsv)
-///
+/// - Spot colors through the [`color.spot` function]($color.spot)
+/// - ICC-based color spaces through the [`color.icc` function]($color.icc)
 ///
 /// # Example
 ///
@@ -189,7 +193,7 @@ static TO_SRGB: LazyLock<qcms::Transform> = LazyLock::new(|| {
 /// }))
 /// ```
 #[ty(scope, cast)]
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub enum Color {
     /// A 32-bit luma color.
     Luma(Luma),
@@ -207,6 +211,13 @@ pub enum Color {
     Hsl(Hsl),
     /// A 32-bit HSV color.
     Hsv(Hsv),
+    /// A tint of a named spot color ink.
+    ///
+    /// This and ICC-based colors are kept behind an `Arc`, as they are larger
+    /// than the other colors.
+    Spot(Arc<Spot>),
+    /// A color in the color space defined by an ICC profile.
+    Icc(Arc<Icc>),
 }
 
 #[scope]
@@ -694,6 +705,108 @@ impl Color {
         })
     }
 
+    /// Create a spot color.
+    ///
+    /// Spot colors are printed with a dedicated, premixed ink instead of being
+    /// composed from the process colors cyan, magenta, yellow, and black. They
+    /// are used when a color must be reproduced exactly, for example for the
+    /// colors of a brand, or when no process color can reproduce it, as for
+    /// metallic inks.
+    ///
+    /// A spot color is identified by the `name` of its ink, which must match
+    /// the name your print shop uses, e.g. `{"PANTONE 286 C"}`. Since screens
+    /// and most printers don't know about the ink, you must also provide a
+    /// `fallback` color that approximates it. Typst uses this fallback for
+    /// previews and in export formats without support for spot colors. PDF
+    /// export writes a separation color space for the ink, with the fallback
+    /// for printers without the ink. PDF/A export only writes the fallback.
+    /// When you manipulate a spot color other than by lightening or darkening
+    /// it, the result is a CMYK color based on the fallback.
+    ///
+    /// A spot color is represented internally by a single component, its
+    /// `tint`. It is also available using the
+    /// [`components`]($color.components) method.
+    ///
+    /// ```example
+    /// #let brand = color.spot(
+    ///   "PANTONE 286 C",
+    ///   cmyk(100%, 66%, 0%, 2%),
+    /// )
+    ///
+    /// #square(fill: brand)
+    /// #square(fill: brand.lighten(50%))
+    /// ```
+    #[func]
+    pub fn spot(
+        /// The name of the ink.
+        name: Spanned<Str>,
+        /// A color that approximates the ink at full tint. It is converted to
+        /// CMYK.
+        fallback: Color,
+        /// How much of the ink to apply.
+        #[named]
+        #[default(RatioComponent(Ratio::one()))]
+        tint: RatioComponent,
+    ) -> SourceResult<Color> {
+        if name.v.is_empty() {
+            bail!(name.span, "spot color name must not be empty");
+        }
+
+        Ok(Spot::new(PicoStr::intern(&name.v), fallback.to_cmyk(), tint.0.get() as f32)
+            .into())
+    }
+
+    /// Create a color in the color space defined by an ICC profile.
+    ///
+    /// This is useful if your colors were specified for a particular device or
+    /// printing condition, for instance by a print shop that provides the ICC
+    /// profile of its press. Profiles for gray, RGB, and CMYK color spaces are
+    /// supported.
+    ///
+    /// Typst approximates the color in sRGB for previews and in export formats
+    /// without support for ICC-based colors. PDF export embeds the profile and
+    /// writes the color in an ICC-based color space. When you manipulate an
+    /// ICC-based color, the result is an RGB color based on this approximation.
+    ///
+    /// An ICC-based color is represented internally by as many components as
+    /// its color space has. They are also available using the
+    /// [`components`]($color.components) method.
+    ///
+    /// ```example:no-eval
+    /// #let press = color.icc.with("coated.icc")
+    /// #square(fill: press(0%, 80%, 95%, 0%))
+    /// ```
+    #[func(title = "ICC")]
+    pub fn icc(
+        engine: &mut Engine,
+        span: Span,
+        /// A [path]($syntax/#paths) to an ICC profile or raw bytes containing
+        /// one.
+        source: Spanned<DataSource>,
+        /// The components of the color, one per component of the profile's
+        /// color space.
+        #[variadic]
+        components: Vec<RatioComponent>,
+    ) -> SourceResult<Color> {
+        let loaded = source.load(engine.world)?;
+        let profile = IccProfile::new(loaded.data).at(source.span)?;
+        let n = profile.components();
+        if components.len() != n {
+            bail!(
+                span,
+                "expected {n} color components for this profile, found {}",
+                components.len()
+            );
+        }
+
+        let mut values = [0.0; 4];
+        for (value, RatioComponent(c)) in values.iter_mut().zip(components) {
+            *value = c.get() as f32;
+        }
+
+        Ok(Icc::new(profile, values).into())
+    }
+
     /// Extracts the components of this color.
     ///
     /// The size and values of this array depends on the color space. You can
@@ -710,6 +823,10 @@ impl Color {
     /// | [`cmyk`]($color.cmyk)   |    Cyan   |   Magenta  |   Yellow  |  Key   |
     /// | [`hsl`]($color.hsl)     |     Hue   | Saturation | Lightness |  Alpha |
     /// | [`hsv`]($color.hsv)     |     Hue   | Saturation |   Value   |  Alpha |
+    /// | [`spot`]($color.spot)   |    Tint   |            |           |        |
+    ///
+    /// The components of an [ICC-based color]($color.icc) are those of the
+    /// profile's color space, without an alpha component.
     ///
     /// For the meaning and type of each individual value, see the documentation
     /// of the corresponding color space. The alpha component is optional and
@@ -723,13 +840,13 @@ impl Color {
     /// ```
     #[func]
     pub fn components(
-        self,
+        &self,
         /// Whether to include the alpha component.
         #[named]
         #[default(true)]
         alpha: bool,
     ) -> Array {
-        let mut components = match self {
+        let mut components = match *self {
             Self::Luma(c) => {
                 array![Ratio::new(c.luma.into()), Ratio::new(c.alpha.into())]
             }
@@ -789,9 +906,15 @@ impl Color {
                     Ratio::new(c.alpha.into()),
                 ]
             }
+            Self::Spot(ref c) => array![Ratio::new(c.tint.into())],
+            Self::Icc(ref c) => c
+                .components()
+                .iter()
+                .map(|&v| Ratio::new(v.into()).into_value())
+                .collect(),
         };
         // Remove the alpha component if the corresponding argument was set.
-        if !alpha && !matches!(self, Self::Cmyk(_)) {
+        if !alpha && self.alpha().is_some() {
             let _ = components.pop();
         }
         components
@@ -808,14 +931,16 @@ impl Color {
     /// - [`cmyk`]($color.cmyk)
     /// - [`hsl`]($color.hsl)
     /// - [`hsv`]($color.hsv)
+    /// - [`spot`]($color.spot)
+    /// - [`icc`]($color.icc)
     ///
     /// ```example
     /// #let color = cmyk(1%, 2%, 3%, 4%)
     /// #(color.space() == cmyk)
     /// ```
     #[func]
-    pub fn space(self) -> ColorSpace {
-        match self {
+    pub fn space(&self) -> ColorSpace {
+        match *self {
             Self::Luma(_) => ColorSpace::D65Gray,
             Self::Oklab(_) => ColorSpace::Oklab,
             Self::Oklch(_) => ColorSpace::Oklch,
@@ -824,6 +949,8 @@ impl Color {
             Self::Cmyk(_) => ColorSpace::Cmyk,
             Self::Hsl(_) => ColorSpace::Hsl,
             Self::Hsv(_) => ColorSpace::Hsv,
+            Self::Spot(_) => ColorSpace::Spot,
+            Self::Icc(_) => ColorSpace::Icc,
         }
     }
 
@@ -831,7 +958,7 @@ impl Color {
     /// `#020304fe`). The alpha component (last two digits in `#020304fe`) is
     /// omitted if it is equal to `ff` (255 / 100%).
     #[func]
-    pub fn to_hex(self) -> EcoString {
+    pub fn to_hex(&self) -> EcoString {
         let (r, g, b, a) = self.to_rgb().into_format::<u8, u8>().into_components();
         if a != 255 {
             eco_format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
@@ -843,12 +970,12 @@ impl Color {
     /// Lightens a color by a given factor.
     #[func]
     pub fn lighten(
-        self,
+        &self,
         /// The factor to lighten the color by.
         factor: Ratio,
     ) -> Color {
         let factor = factor.get() as f32;
-        match self {
+        match *self {
             Self::Luma(c) => Self::Luma(c.lighten(factor)),
             Self::Oklab(c) => Self::Oklab(c.lighten(factor)),
             Self::Oklch(c) => Self::Oklch(c.lighten(factor)),
@@ -857,18 +984,20 @@ impl Color {
             Self::Cmyk(c) => Self::Cmyk(c.lighten(factor)),
             Self::Hsl(c) => Self::Hsl(c.lighten(factor)),
             Self::Hsv(c) => Self::Hsv(c.lighten(factor)),
+            Self::Spot(ref c) => c.lighten(factor).into(),
+            Self::Icc(ref c) => Self::Rgb(c.to_rgba().lighten(factor)),
         }
     }
 
     /// Darkens a color by a given factor.
     #[func]
     pub fn darken(
-        self,
+        &self,
         /// The factor to darken the color by.
         factor: Ratio,
     ) -> Color {
         let factor = factor.get() as f32;
-        match self {
+        match *self {
             Self::Luma(c) => Self::Luma(c.darken(factor)),
             Self::Oklab(c) => Self::Oklab(c.darken(factor)),
             Self::Oklch(c) => Self::Oklch(c.darken(factor)),
@@ -877,19 +1006,21 @@ impl Color {
             Self::Cmyk(c) => Self::Cmyk(c.darken(factor)),
             Self::Hsl(c) => Self::Hsl(c.darken(factor)),
             Self::Hsv(c) => Self::Hsv(c.darken(factor)),
+            Self::Spot(ref c) => c.darken(factor).into(),
+            Self::Icc(ref c) => Self::Rgb(c.to_rgba().darken(factor)),
         }
     }
 
     /// Increases the saturation of a color by a given factor.
     #[func]
     pub fn saturate(
-        self,
+        &self,
         span: Span,
         /// The factor to saturate the color by.
         factor: Ratio,
     ) -> SourceResult<Color> {
         let f = factor.get() as f32;
-        Ok(match self {
+        Ok(match *self {
             Self::Luma(_) => bail!(
                 span, "cannot saturate grayscale color";
                 hint: "try converting your color to RGB first"
@@ -900,7 +1031,9 @@ impl Color {
             | Self::Oklch(_)
             | Self::LinearRgb(_)
             | Self::Rgb(_)
-            | Self::Cmyk(_) => {
+            | Self::Cmyk(_)
+            | Self::Spot(_)
+            | Self::Icc(_) => {
                 Color::Hsv(self.to_hsv().saturate(f)).to_space(self.space())
             }
         })
@@ -909,13 +1042,13 @@ impl Color {
     /// Decreases the saturation of a color by a given factor.
     #[func]
     pub fn desaturate(
-        self,
+        &self,
         span: Span,
         /// The factor to desaturate the color by.
         factor: Ratio,
     ) -> SourceResult<Color> {
         let f = factor.get() as f32;
-        Ok(match self {
+        Ok(match *self {
             Self::Luma(_) => bail!(
                 span, "cannot desaturate grayscale color";
                 hint: "try converting your color to RGB first"
@@ -926,7 +1059,9 @@ impl Color {
             | Self::Oklch(_)
             | Self::LinearRgb(_)
             | Self::Rgb(_)
-            | Self::Cmyk(_) => {
+            | Self::Cmyk(_)
+            | Self::Spot(_)
+            | Self::Icc(_) => {
                 Color::Hsv(self.to_hsv().desaturate(f)).to_space(self.space())
             }
         })
@@ -942,7 +1077,7 @@ impl Color {
     /// ```
     #[func]
     pub fn negate(
-        self,
+        &self,
         /// The color space used for the transformation. By default, a perceptual color space is used.
         #[named]
         #[default(ColorSpace::Oklab)]
@@ -979,6 +1114,8 @@ impl Color {
                 c.value,
                 c.alpha,
             )),
+            // Spot and ICC color spaces can't be selected for manipulation.
+            Self::Spot(_) | Self::Icc(_) => unreachable!(),
         };
         result.to_space(self.space())
     }
@@ -986,7 +1123,7 @@ impl Color {
     /// Rotates the hue of the color by a given angle.
     #[func]
     pub fn rotate(
-        self,
+        &self,
         span: Span,
         /// The angle to rotate the hue by.
         angle: Angle,
@@ -1060,7 +1197,7 @@ impl Color {
     /// ```
     #[func]
     pub fn transparentize(
-        self,
+        &self,
         /// The factor to change the alpha value by.
         scale: Ratio,
     ) -> StrResult<Color> {
@@ -1081,7 +1218,7 @@ impl Color {
     /// ```
     #[func]
     pub fn opacify(
-        self,
+        &self,
         /// The scale to change the alpha value by.
         scale: Ratio,
     ) -> StrResult<Color> {
@@ -1099,6 +1236,10 @@ impl Color {
         space: ColorSpace,
     ) -> StrResult<Color> {
         let mut colors = colors.into_iter();
+        if matches!(space, ColorSpace::Spot | ColorSpace::Icc) {
+            bail!("cannot mix in a spot or ICC color space");
+        }
+
         if space.hue_index().is_some() && colors.len() > 2 {
             bail!("cannot mix more than two colors in a hue-based space");
         }
@@ -1171,6 +1312,7 @@ impl Color {
             }
             ColorSpace::Cmyk => Color::Cmyk(Cmyk::new(m[0], m[1], m[2], m[3])),
             ColorSpace::D65Gray => Color::Luma(Luma::new(m[0], m[3])),
+            ColorSpace::Spot | ColorSpace::Icc => unreachable!(),
         })
     }
 
@@ -1197,7 +1339,7 @@ impl Color {
     /// Returns the alpha channel of the color, if it has one.
     pub fn alpha(&self) -> Option<f32> {
         match self {
-            Color::Cmyk(_) => None,
+            Color::Cmyk(_) | Color::Spot(_) | Color::Icc(_) => None,
             Color::Luma(c) => Some(c.alpha),
             Color::Oklab(c) => Some(c.alpha),
             Color::Oklch(c) => Some(c.alpha),
@@ -1211,7 +1353,7 @@ impl Color {
     /// Sets the alpha channel of the color, if it has one.
     pub fn with_alpha(mut self, alpha: f32) -> Self {
         match &mut self {
-            Color::Cmyk(_) => {}
+            Color::Cmyk(_) | Color::Spot(_) | Color::Icc(_) => {}
             Color::Luma(c) => c.alpha = alpha,
             Color::Oklab(c) => c.alpha = alpha,
             Color::Oklch(c) => c.alpha = alpha,
@@ -1228,7 +1370,7 @@ impl Color {
     ///
     /// For positive scales, computes `alpha + scale - alpha * scale`.
     /// For non-positive scales, computes `alpha + alpha * scale`.
-    fn scale_alpha(self, scale: Ratio) -> StrResult<Color> {
+    fn scale_alpha(&self, scale: Ratio) -> StrResult<Color> {
         #[inline]
         fn transform<C>(mut color: Alpha<C, f32>, scale: Ratio) -> Alpha<C, f32> {
             let scale = scale.get() as f32;
@@ -1237,7 +1379,7 @@ impl Color {
             color
         }
 
-        Ok(match self {
+        Ok(match *self {
             Color::Luma(c) => Color::Luma(transform(c, scale)),
             Color::Oklab(c) => Color::Oklab(transform(c, scale)),
             Color::Oklch(c) => Color::Oklch(transform(c, scale)),
@@ -1246,6 +1388,8 @@ impl Color {
             Color::Cmyk(_) => bail!("CMYK does not have an alpha component"),
             Color::Hsl(c) => Color::Hsl(transform(c, scale)),
             Color::Hsv(c) => Color::Hsv(transform(c, scale)),
+            Color::Spot(_) => bail!("spot colors do not have an alpha component"),
+            Color::Icc(_) => bail!("ICC-based colors do not have an alpha component"),
         })
     }
 
@@ -1269,6 +1413,8 @@ impl Color {
             Color::Hsv(c) => {
                 [c.hue.into_degrees().rem_euclid(360.0), c.saturation, c.value, c.alpha]
             }
+            Color::Spot(c) => [c.tint, 0.0, 0.0, 0.0],
+            Color::Icc(c) => c.values,
         }
     }
 
@@ -1277,7 +1423,13 @@ impl Color {
         self.to_vec4().map(|x| (x * 255.0).round() as u8)
     }
 
-    pub fn to_space(self, space: ColorSpace) -> Self {
+    /// Converts the color into the given color space.
+    ///
+    /// Colors can't be converted into a spot or ICC color space as these are
+    /// not defined by the space alone. Such conversions instead result in the
+    /// space of the approximation: CMYK for spot colors and sRGB for ICC-based
+    /// colors.
+    pub fn to_space(&self, space: ColorSpace) -> Self {
         match space {
             ColorSpace::D65Gray => Self::Luma(self.to_luma()),
             ColorSpace::Oklab => Self::Oklab(self.to_oklab()),
@@ -1287,76 +1439,94 @@ impl Color {
             ColorSpace::Cmyk => Self::Cmyk(self.to_cmyk()),
             ColorSpace::Hsl => Self::Hsl(self.to_hsl()),
             ColorSpace::Hsv => Self::Hsv(self.to_hsv()),
+            ColorSpace::Spot => match *self {
+                Self::Spot(_) => self.clone(),
+                _ => Self::Cmyk(self.to_cmyk()),
+            },
+            ColorSpace::Icc => match *self {
+                Self::Icc(_) => self.clone(),
+                _ => Self::Rgb(self.to_rgb()),
+            },
         }
     }
 
-    pub fn to_luma(self) -> Luma {
-        match self {
+    pub fn to_luma(&self) -> Luma {
+        match *self {
             Self::Luma(c) => c,
             Self::Oklab(c) => Luma::from_color(c),
             Self::Oklch(c) => Luma::from_color(c),
             Self::Rgb(c) => Luma::from_color(c),
             Self::LinearRgb(c) => Luma::from_color(c),
             Self::Cmyk(c) => Luma::from_color(c.to_rgba()),
+            Self::Spot(ref c) => Luma::from_color(c.to_rgba()),
+            Self::Icc(ref c) => Luma::from_color(c.to_rgba()),
             Self::Hsl(c) => Luma::from_color(c),
             Self::Hsv(c) => Luma::from_color(c),
         }
     }
 
-    pub fn to_oklab(self) -> Oklab {
-        match self {
+    pub fn to_oklab(&self) -> Oklab {
+        match *self {
             Self::Luma(c) => Oklab::from_color(c),
             Self::Oklab(c) => c,
             Self::Oklch(c) => Oklab::from_color(c),
             Self::Rgb(c) => Oklab::from_color(c),
             Self::LinearRgb(c) => Oklab::from_color(c),
             Self::Cmyk(c) => Oklab::from_color(c.to_rgba()),
+            Self::Spot(ref c) => Oklab::from_color(c.to_rgba()),
+            Self::Icc(ref c) => Oklab::from_color(c.to_rgba()),
             Self::Hsl(c) => Oklab::from_color(c),
             Self::Hsv(c) => Oklab::from_color(c),
         }
     }
 
-    pub fn to_oklch(self) -> Oklch {
-        match self {
+    pub fn to_oklch(&self) -> Oklch {
+        match *self {
             Self::Luma(c) => Oklch::from_color(c),
             Self::Oklab(c) => Oklch::from_color(c),
             Self::Oklch(c) => c,
             Self::Rgb(c) => Oklch::from_color(c),
             Self::LinearRgb(c) => Oklch::from_color(c),
             Self::Cmyk(c) => Oklch::from_color(c.to_rgba()),
+            Self::Spot(ref c) => Oklch::from_color(c.to_rgba()),
+            Self::Icc(ref c) => Oklch::from_color(c.to_rgba()),
             Self::Hsl(c) => Oklch::from_color(c),
             Self::Hsv(c) => Oklch::from_color(c),
         }
     }
 
-    pub fn to_rgb(self) -> Rgb {
-        match self {
+    pub fn to_rgb(&self) -> Rgb {
+        match *self {
             Self::Luma(c) => Rgb::from_color(c),
             Self::Oklab(c) => Rgb::from_color(c),
             Self::Oklch(c) => Rgb::from_color(c),
             Self::Rgb(c) => c,
             Self::LinearRgb(c) => Rgb::from_linear(c),
             Self::Cmyk(c) => Rgb::from_color(c.to_rgba()),
+            Self::Spot(ref c) => c.to_rgba(),
+            Self::Icc(ref c) => c.to_rgba(),
             Self::Hsl(c) => Rgb::from_color(c),
             Self::Hsv(c) => Rgb::from_color(c),
         }
     }
 
-    pub fn to_linear_rgb(self) -> LinearRgb {
-        match self {
+    pub fn to_linear_rgb(&self) -> LinearRgb {
+        match *self {
             Self::Luma(c) => LinearRgb::from_color(c),
             Self::Oklab(c) => LinearRgb::from_color(c),
             Self::Oklch(c) => LinearRgb::from_color(c),
             Self::Rgb(c) => LinearRgb::from_color(c),
             Self::LinearRgb(c) => c,
             Self::Cmyk(c) => LinearRgb::from_color(c.to_rgba()),
+            Self::Spot(ref c) => LinearRgb::from_color(c.to_rgba()),
+            Self::Icc(ref c) => LinearRgb::from_color(c.to_rgba()),
             Self::Hsl(c) => Rgb::from_color(c).into_linear(),
             Self::Hsv(c) => Rgb::from_color(c).into_linear(),
         }
     }
 
-    pub fn to_cmyk(self) -> Cmyk {
-        match self {
+    pub fn to_cmyk(&self) -> Cmyk {
+        match *self {
             Self::Luma(c) => Cmyk::from_luma(c),
             Self::Oklab(c) => Cmyk::from_rgba(Rgb::from_color(c)),
             Self::Oklch(c) => Cmyk::from_rgba(Rgb::from_color(c)),
@@ -1365,30 +1535,36 @@ impl Color {
             Self::Cmyk(c) => c,
             Self::Hsl(c) => Cmyk::from_rgba(Rgb::from_color(c)),
             Self::Hsv(c) => Cmyk::from_rgba(Rgb::from_color(c)),
+            Self::Spot(ref c) => c.to_cmyk(),
+            Self::Icc(ref c) => c.to_cmyk(),
         }
     }
 
-    pub fn to_hsl(self) -> Hsl {
-        match self {
+    pub fn to_hsl(&self) -> Hsl {
+        match *self {
             Self::Luma(c) => Hsl::from_color(c),
             Self::Oklab(c) => Hsl::from_color(c),
             Self::Oklch(c) => Hsl::from_color(c),
             Self::Rgb(c) => Hsl::from_color(c),
             Self::LinearRgb(c) => Hsl::from_color(Rgb::from_linear(c)),
             Self::Cmyk(c) => Hsl::from_color(c.to_rgba()),
+            Self::Spot(ref c) => Hsl::from_color(c.to_rgba()),
+            Self::Icc(ref c) => Hsl::from_color(c.to_rgba()),
             Self::Hsl(c) => c,
             Self::Hsv(c) => Hsl::from_color(c),
         }
     }
 
-    pub fn to_hsv(self) -> Hsv {
-        match self {
+    pub fn to_hsv(&self) -> Hsv {
+        match *self {
             Self::Luma(c) => Hsv::from_color(c),
             Self::Oklab(c) => Hsv::from_color(c),
             Self::Oklch(c) => Hsv::from_color(c),
             Self::Rgb(c) => Hsv::from_color(c),
             Self::LinearRgb(c) => Hsv::from_color(Rgb::from_linear(c)),
             Self::Cmyk(c) => Hsv::from_color(c.to_rgba()),
+            Self::Spot(ref c) => Hsv::from_color(c.to_rgba()),
+            Self::Icc(ref c) => Hsv::from_color(c.to_rgba()),
             Self::Hsl(c) => Hsv::from_color(c),
             Self::Hsv(c) => c,
         }
@@ -1433,6 +1609,14 @@ impl Debug for Color {
                 v.value,
                 v.alpha
             ),
+            Self::Spot(v) => write!(
+                f,
+                "Spot({:?}, {:?}, {})",
+                v.name.resolve().as_str(),
+                v.fallback,
+                v.tint
+            ),
+            Self::Icc(v) => write!(f, "Icc({:?})", v.components()),
         }
     }
 }
@@ -1551,6 +1735,23 @@ impl Repr for Color {
                     )
                 }
             }
+            Self::Spot(c) => {
+                let name = c.name.resolve().as_str().repr();
+                let fallback = Self::Cmyk(c.fallback).repr();
+                if c.tint == 1.0 {
+                    eco_format!("color.spot({name}, {fallback})")
+                } else {
+                    eco_format!(
+                        "color.spot({name}, {fallback}, tint: {})",
+                        Ratio::new(c.tint.into()).repr(),
+                    )
+                }
+            }
+            Self::Icc(c) => {
+                let components: Vec<_> =
+                    c.components().iter().map(|&v| Ratio::new(v.into()).repr()).collect();
+                eco_format!("color.icc(.., {})", components.join(", "))
+            }
         }
     }
 }
@@ -1574,6 +1775,8 @@ impl PartialEq for Color {
             (Self::Cmyk(a), Self::Cmyk(b)) => a == b,
             (Self::Hsl(a), Self::Hsl(b)) => a == b,
             (Self::Hsv(a), Self::Hsv(b)) => a == b,
+            (Self::Spot(a), Self::Spot(b)) => a == b,
+            (Self::Icc(a), Self::Icc(b)) => a == b,
             _ => false,
         }
     }
@@ -1589,6 +1792,11 @@ impl Hash for Color {
         y.to_bits().hash(state);
         z.to_bits().hash(state);
         w.to_bits().hash(state);
+        match self {
+            Self::Spot(c) => c.name.hash(state),
+            Self::Icc(c) => c.profile.hash(state),
+            _ => {}
+        }
     }
 }
 
@@ -1681,6 +1889,18 @@ impl From<Hsv> for Color {
     }
 }
 
+impl From<Spot> for Color {
+    fn from(c: Spot) -> Self {
+        Self::Spot(Arc::new(c))
+    }
+}
+
+impl From<Icc> for Color {
+    fn from(c: Icc) -> Self {
+        Self::Icc(Arc::new(c))
+    }
+}
+
 /// An 8-bit CMYK color.
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Cmyk {
@@ -1695,7 +1915,8 @@ pub struct Cmyk {
 }
 
 impl Cmyk {
-    fn new(c: f32, m: f32, y: f32, k: f32) -> Self {
+    /// Create a CMYK color from its components.
+    pub fn new(c: f32, m: f32, y: f32, k: f32) -> Self {
         Self { c, m, y, k }
     }
 
@@ -1754,6 +1975,168 @@ impl Cmyk {
     }
 }
 
+/// A tint of a named spot color ink.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Spot {
+    /// The name of the ink.
+    pub name: PicoStr,
+    /// A CMYK approximation of the ink at full tint.
+    pub fallback: Cmyk,
+    /// The amount of ink, between zero and one.
+    pub tint: f32,
+}
+
+impl Spot {
+    /// Create a tint of a spot color.
+    pub fn new(name: PicoStr, fallback: Cmyk, tint: f32) -> Self {
+        Self { name, fallback, tint }
+    }
+
+    /// The CMYK approximation of this tint.
+    pub fn to_cmyk(self) -> Cmyk {
+        let Cmyk { c, m, y, k } = self.fallback;
+        let t = self.tint;
+        Cmyk::new(c * t, m * t, y * t, k * t)
+    }
+
+    fn to_rgba(self) -> Rgb {
+        self.to_cmyk().to_rgba()
+    }
+
+    fn lighten(self, factor: f32) -> Self {
+        Self {
+            tint: (self.tint - self.tint * factor).clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    fn darken(self, factor: f32) -> Self {
+        Self {
+            tint: (self.tint + (1.0 - self.tint) * factor).clamp(0.0, 1.0),
+            ..self
+        }
+    }
+}
+
+/// A color in the color space defined by an ICC profile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Icc {
+    /// The profile that defines the color space.
+    pub profile: IccProfile,
+    /// The components, of which only as many as the color space has are used.
+    values: [f32; 4],
+}
+
+impl Icc {
+    /// Create an ICC-based color from its components.
+    pub fn new(profile: IccProfile, values: [f32; 4]) -> Self {
+        Self { profile, values }
+    }
+
+    /// The components of the color.
+    pub fn components(&self) -> &[f32] {
+        &self.values[..self.profile.components()]
+    }
+
+    /// The sRGB approximation of this color.
+    pub fn to_rgba(&self) -> Rgb {
+        let src: Vec<u8> =
+            self.components().iter().map(|v| (v * 255.0).round() as u8).collect();
+        let mut dest: [u8; 3] = [0; 3];
+        self.profile.0.to_srgb.convert(&src, &mut dest);
+        Rgb::new(
+            f32::from(dest[0]) / 255.0,
+            f32::from(dest[1]) / 255.0,
+            f32::from(dest[2]) / 255.0,
+            1.0,
+        )
+    }
+
+    /// The components if this is a CMYK color, and an approximation otherwise.
+    fn to_cmyk(&self) -> Cmyk {
+        match *self.components() {
+            [c, m, y, k] => Cmyk::new(c, m, y, k),
+            _ => Cmyk::from_rgba(self.to_rgba()),
+        }
+    }
+}
+
+/// An ICC profile defining a gray, RGB, or CMYK color space.
+///
+/// Values of this type are cheap to clone and hash.
+#[derive(Clone, Hash)]
+pub struct IccProfile(Arc<IccProfileRepr>);
+
+/// The internal representation of an [`IccProfile`].
+struct IccProfileRepr {
+    data: Bytes,
+    components: usize,
+    to_srgb: qcms::Transform,
+}
+
+impl IccProfile {
+    /// Parse an ICC profile.
+    pub fn new(data: Bytes) -> StrResult<Self> {
+        Self::new_impl(data)
+    }
+
+    /// The internal implementation. This is memoized to reuse the transform.
+    #[comemo::memoize]
+    fn new_impl(data: Bytes) -> StrResult<IccProfile> {
+        let (components, kind) = match data.get(16..20) {
+            Some(b"GRAY") => (1, qcms::DataType::Gray8),
+            Some(b"RGB ") => (3, qcms::DataType::RGB8),
+            Some(b"CMYK") => (4, qcms::DataType::CMYK),
+            Some(_) => bail!("ICC profile must define a gray, RGB, or CMYK color space"),
+            None => bail!("failed to parse ICC profile"),
+        };
+
+        let profile =
+            Profile::new_from_slice(&data, false).ok_or("failed to parse ICC profile")?;
+        let to_srgb = qcms::Transform::new_to(
+            &profile,
+            &SRGB_PROFILE,
+            kind,
+            qcms::DataType::RGB8,
+            qcms::Intent::Perceptual,
+        )
+        .ok_or("failed to convert from ICC profile to sRGB")?;
+
+        Ok(Self(Arc::new(IccProfileRepr { data, components, to_srgb })))
+    }
+
+    /// The raw data of the profile.
+    pub fn data(&self) -> &Bytes {
+        &self.0.data
+    }
+
+    /// The number of components of the profile's color space.
+    pub fn components(&self) -> usize {
+        self.0.components
+    }
+}
+
+impl Hash for IccProfileRepr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // The profile is fully defined by its data.
+        self.data.hash(state);
+    }
+}
+
+impl Eq for IccProfile {}
+
+impl PartialEq for IccProfile {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.data == other.0.data
+    }
+}
+
+impl Debug for IccProfile {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad("IccProfile(..)")
+    }
+}
+
 /// A color with a weight.
 pub struct WeightedColor {
     color: Color,
@@ -1811,6 +2194,10 @@ pub enum ColorSpace {
     Hsv,
     /// The CMYK color space.
     Cmyk,
+    /// The color space of a spot color.
+    Spot,
+    /// A color space defined by an ICC profile.
+    Icc,
 }
 
 impl ColorSpace {
@@ -1836,6 +2223,8 @@ cast! {
         Self::Hsl => Color::hsl_data(),
         Self::Hsv => Color::hsv_data(),
         Self::Cmyk => Color::cmyk_data(),
+        Self::Spot => Color::spot_data(),
+        Self::Icc => Color::icc_data(),
     }.into_value(),
     v: Value => {
         let expected = "expected `rgb`, `luma`, `cmyk`, `oklab`, `oklch`, `color.linear-rgb`, `color.hsl`, or `color.hsv`";
@@ -1936,7 +2325,7 @@ macro_rules! preset {
         fn $name() -> Array {
             Array::from(
                 [$(Color::from_u32($colors)),*]
-                    .iter()
+                    .into_iter()
                     .map(|c| c.into_value())
                     .collect::<EcoVec<_>>()
             )
@@ -1990,4 +2379,20 @@ mod tests {
         test("hmmm", "color string contains non-hexadecimal letters");
         test("14B2AH", "color string contains non-hexadecimal letters");
     }
+
+    #[test]
+    fn test_icc_profile() {
+        let data = Bytes::new(typst_assets::icc::CMYK_TO_XYZ);
+        let profile = IccProfile::new(data.clone()).unwrap();
+        assert_eq!(profile.components(), 4);
+        assert_eq!(profile.data(), &data);
+        assert_eq!(IccProfile::new(data).unwrap(), profile);
+
+        let black = Icc::new(profile, [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(black.components(), [0.0, 0.0, 0.0, 1.0]);
+        assert!(black.to_rgba().red < 0.3);
+
+        let invalid = Bytes::new(vec![0; 20]);
+        assert!(IccProfile::new(invalid).is_err());
+    }
 }
@@ -488,7 +488,7 @@ impl Gradient {
                 let c = self
                     .sample(RatioOrAngle::Ratio(Ratio::new(i as f64 / (n - 1) as f64)));
 
-                [c, c]
+                [c.clone(), c]
             })
             .collect::<Vec<_>>();
 
@@ -588,12 +588,12 @@ impl Gradient {
             .flat_map(|(i, stops)| {
                 let mut stops = stops
                     .iter()
-                    .map(move |&(color, offset)| {
+                    .map(move |(color, offset)| {
                         let r = offset.get();
                         if i % 2 == 1 && mirror {
-                            (color, Ratio::new((i as f64 + 1.0 - r) / n as f64))
+                            (color.clone(), Ratio::new((i as f64 + 1.0 - r) / n as f64))
                         } else {
-                            (color, Ratio::new((i as f64 + r) / n as f64))
+                            (color.clone(), Ratio::new((i as f64 + r) / n as f64))
                         }
                     })
                     .collect::<Vec<_>>();
@@ -655,7 +655,7 @@ impl Gradient {
                 .stops
                 .iter()
                 .map(|(color, offset)| GradientStop {
-                    color: *color,
+                    color: color.clone(),
                     offset: Some(*offset),
                 })
                 .collect(),
@@ -663,7 +663,7 @@ impl Gradient {
                 .stops
                 .iter()
                 .map(|(color, offset)| GradientStop {
-                    color: *color,
+                    color: color.clone(),
                     offset: Some(*offset),
                 })
                 .collect(),
@@ -671,7 +671,7 @@ impl Gradient {
                 .stops
                 .iter()
                 .map(|(color, offset)| GradientStop {
-                    color: *color,
+                    color: color.clone(),
                     offset: Some(*offset),
                 })
                 .collect(),
@@ -1149,7 +1149,7 @@ pub enum RelativeTo {
 }
 
 /// A color stop.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct GradientStop {
     /// The color for this stop.
     pub color: Color,
@@ -1244,7 +1244,7 @@ fn process_stops(stops: &[Spanned<GradientStop>]) -> SourceResult<Vec<(Color, Ra
                 if offset.unwrap().get() > 1.0 || offset.unwrap().get() < 0.0 {
                     bail!(*span, "offset must be between 0 and 1");
                 }
-                Ok((*color, offset.unwrap()))
+                Ok((color.clone(), offset.unwrap()))
             })
             .collect::<SourceResult<Vec<_>>>()?;
 
@@ -1272,7 +1272,7 @@ fn process_stops(stops: &[Spanned<GradientStop>]) -> SourceResult<Vec<(Color, Ra
         .enumerate()
         .map(|(i, stop)| {
             let offset = i as f64 / (stops.len() - 1) as f64;
-            (stop.v.color, Ratio::new(offset))
+            (stop.v.color.clone(), Ratio::new(offset))
         })
         .collect())
 }
@@ -1286,11 +1286,11 @@ fn sample_stops(stops: &[(Color, Ratio)], mixing_space: ColorSpace, t: f64) -> C
         while stops.get(j + 1).is_some_and(|(_, r)| r.is_zero()) {
             j += 1;
         }
-        return stops[j].0;
+        return stops[j].0.clone();
     }
 
-    let (col_0, pos_0) = stops[j - 1];
-    let (col_1, pos_1) = stops[j];
+    let (col_0, pos_0) = stops[j - 1].clone();
+    let (col_1, pos_1) = stops[j].clone();
     let t = (t - pos_0.get()) / (pos_1.get() - pos_0.get());
 
     Color::mix_iter(
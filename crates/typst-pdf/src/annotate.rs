@@ -0,0 +1,440 @@
+//! Annotations with comments.
+//!
+//! Like form fields, annotations are first written as link annotations with a
+//! placeholder URI, which krilla tags like regular links. When the finished
+//! file is rewritten, each placeholder is replaced with the actual annotation
+//! and its structure element is turned into an `Annot` element.
+//!
+//! The same restrictions as for form fields apply: free text annotations are
+//! rejected where their font can't be used, and retagging fails if a tagged
+//! placeholder lost its structure element.
+
+use std::fmt::Write;
+
+use ecow::eco_vec;
+use krilla::action::{Action, LinkAction};
+use krilla::annotation::Target;
+use typst_library::diag::{SourceResult, StrResult, bail, error};
+use typst_library::foundations::Datetime;
+use typst_library::layout::Size;
+use typst_library::pdf::{Annotation, AnnotationKind, MarkKind};
+use typst_library::visualize::Color;
+use typst_syntax::Span;
+
+use crate::convert::{FrameContext, GlobalContext};
+use crate::form::{find_placeholders, form_xobject, helvetica, retag, text};
+use crate::link::{LinkAnnotation, LinkAnnotationKind, bounding_box};
+use crate::rewrite::{Dict, File, Object, Stream};
+use crate::tags::{self, GroupId};
+
+/// The URI of the link annotations that are replaced by annotations, followed
+/// by the index of the annotation.
+const PLACEHOLDER: &str = "typst-annotation:";
+
+pub(crate) fn handle_annotation(
+    fc: &mut FrameContext,
+    gc: &mut GlobalContext,
+    annotation: &Annotation,
+    size: Size,
+) -> SourceResult<()> {
+    let standard = match gc.options.standards.pdfx {
+        Some(pdfx) => Some(pdfx.as_str()),
+        None if gc.options.is_pdf_a() => {
+            Some(gc.options.standards.config.validator().as_str())
+        }
+        None => None,
+    };
+    if let Some(standard) = standard {
+        bail!(annotation.span, "PDF annotations are not supported in {standard}");
+    }
+
+    // Like a text field, a free text annotation would bring a font that isn't
+    // embedded into the file after krilla validated it.
+    if gc.options.is_pdf_ua() && matches!(annotation.kind, AnnotationKind::FreeText) {
+        let validator = gc.options.standards.config.validator().as_str();
+        bail!(
+            annotation.span,
+            "free text annotations are not supported in {validator}";
+            hint: "the text of a free text annotation is displayed with a font \
+                   that is not embedded"
+        );
+    }
+
+    let rect = bounding_box(fc, size);
+
+    if tags::disabled(gc) {
+        if gc.tags.in_tiling && gc.options.is_pdf_ua() {
+            let validator = gc.options.standards.config.validator().as_str();
+            bail!(
+                annotation.span,
+                "{validator} error: PDF artifacts may not contain annotations";
+                hint: "an annotation was used within a tiling"
+            );
+        }
+
+        let target = placeholder(gc, annotation);
+        fc.push_link_annotation(
+            GroupId::INVALID,
+            LinkAnnotation {
+                kind: LinkAnnotationKind::Artifact,
+                alt: None,
+                span: annotation.span,
+                rects: vec![rect],
+                target,
+            },
+        );
+        return Ok(());
+    }
+
+    let Some((group_id, marker)) = gc.tags.tree.parent_link() else {
+        bail!(
+            annotation.span,
+            "annotation is not part of the tag tree";
+            hint: "please report this as a bug"
+        );
+    };
+    let alt = marker.alt.as_ref().map(Into::into);
+
+    if gc.tags.tree.parent_artifact().is_some() {
+        if gc.options.is_pdf_ua() {
+            let validator = gc.options.standards.config.validator().as_str();
+            bail!(
+                annotation.span,
+                "{validator} error: PDF artifacts may not contain annotations"
+            );
+        }
+
+        let target = placeholder(gc, annotation);
+        fc.push_link_annotation(
+            group_id,
+            LinkAnnotation {
+                kind: LinkAnnotationKind::Artifact,
+                alt,
+                span: annotation.span,
+                rects: vec![rect],
+                target,
+            },
+        );
+        return Ok(());
+    }
+
+    // Marked up content that spans multiple lines on the same page results in
+    // a single annotation with multiple quadrilaterals.
+    let joinable = matches!(annotation.kind, AnnotationKind::Mark { .. });
+    match fc.get_link_annotation(group_id) {
+        Some(existing) if joinable => existing.rects.push(rect),
+        _ => {
+            let target = placeholder(gc, annotation);
+            let annot_id = gc.tags.annotations.reserve();
+            fc.push_link_annotation(
+                group_id,
+                LinkAnnotation {
+                    kind: LinkAnnotationKind::Tagged(annot_id),
+                    alt,
+                    span: annotation.span,
+                    rects: vec![rect],
+                    target,
+                },
+            );
+            let group = gc.tags.tree.groups.get_mut(group_id);
+            group.push_annotation(annot_id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Registers an annotation and returns the target of its placeholder.
+fn placeholder(gc: &mut GlobalContext, annotation: &Annotation) -> Target {
+    let index = gc.annotations.len();
+    gc.annotations.push(annotation.clone());
+    Target::Action(Action::Link(LinkAction::new(format!("{PLACEHOLDER}{index}"))))
+}
+
+/// Replaces the placeholder annotations of a finished PDF file with the
+/// Replaces the placeholder annotations of a finished PDF file with the
+/// annotations.
+pub(crate) fn apply(file: &mut File, annotations: &[Annotation]) -> SourceResult<()> {
+    add_annotations(file, annotations).map_err(|err| {
+        eco_vec![error!(
+            Span::detached(),
+            "failed to write annotations ({err})";
+            hint: "please report this as a bug"
+        )]
+    })
+}
+
+/// Adds the annotations.
+fn add_annotations(file: &mut File, annotations: &[Annotation]) -> StrResult<()> {
+    let (placeholders, elems) = find_placeholders(file, PLACEHOLDER, annotations.len())?;
+    let mut font_id = None;
+
+    for (annotation, placeholder) in annotations.iter().zip(&placeholders) {
+        // Annotations on pages that weren't exported have no placeholder.
+        let Some((id, annot)) = placeholder else { continue };
+        let quads = quads(annot)?;
+        let [x0, y0, x1, y1] = bounds(&quads);
+
+        let subtype = match &annotation.kind {
+            AnnotationKind::Note { .. } => "Text",
+            AnnotationKind::Mark { kind: MarkKind::Highlight, .. } => "Highlight",
+            AnnotationKind::Mark { kind: MarkKind::Underline, .. } => "Underline",
+            AnnotationKind::Mark { kind: MarkKind::Squiggly, .. } => "Squiggly",
+            AnnotationKind::Mark { kind: MarkKind::Strike, .. } => "StrikeOut",
+            AnnotationKind::FreeText => "FreeText",
+        };
+        let mut dict = Dict::new()
+            .with("Type", Object::name("Annot"))
+            .with("Subtype", Object::name(subtype))
+            .with("Rect", Object::numbers([x0, y0, x1, y1]));
+        for key in ["StructParent", "P"] {
+            if let Some(value) = annot.get(key) {
+                dict.set(key, value.clone());
+            }
+        }
+
+        // The comment doubles as the alternative description, which is
+        // required for tagged annotations. Without a comment, the one of the
+        // placeholder is used.
+        if !annotation.text.is_empty() {
+            dict.set("Contents", Object::text(&annotation.text));
+        } else if let Some(alt) = annot.get("Contents") {
+            dict.set("Contents", alt.clone());
+        }
+        if let Some(author) = &annotation.author {
+            dict.set("T", Object::text(author));
+        }
+        if let Some(date) = annotation.date.as_ref().and_then(pdf_date) {
+            dict.set("M", Object::text(&date));
+            dict.set("CreationDate", Object::text(&date));
+        }
+
+        match &annotation.kind {
+            AnnotationKind::Note { color, open } => {
+                // Notes keep their size and orientation when zooming or
+                // rotating, like in other PDF producers.
+                dict.set("F", 28);
+                dict.set("C", rgb(color));
+                dict.set("Name", Object::name("Comment"));
+                dict.set("Open", *open);
+            }
+            AnnotationKind::Mark { kind, color } => {
+                let points = quads.iter().flat_map(|&[qx0, qy0, qx1, qy1]| {
+                    [qx0, qy1, qx1, qy1, qx0, qy0, qx1, qy0]
+                });
+                dict.set("F", 4);
+                dict.set("QuadPoints", Object::numbers(points));
+                dict.set("C", rgb(color));
+
+                let ap = file.alloc();
+                file.set(ap, markup(*kind, color, [x0, y0, x1, y1], &quads));
+                dict.set("AP", Dict::new().with("N", ap));
+            }
+            AnnotationKind::FreeText => {
+                let font = *font_id.get_or_insert_with(|| {
+                    let id = file.alloc();
+                    file.set(id, helvetica());
+                    id
+                });
+                let (w, h) = (x1 - x0, y1 - y0);
+                let size = annotation.text_size.to_pt();
+                let lines = annotation.text.lines().map(Into::into).collect();
+                let content = text(w, h, size, lines, true, false);
+                let ap = file.alloc();
+                file.set(ap, form_xobject(w, h, Some(font), &content));
+                let da = format!("/Helv {size:.2} Tf 0 g");
+                dict.set("F", 4);
+                dict.set("DA", Object::String(da.into_bytes()));
+                dict.set("BS", Dict::new().with("W", 0));
+                dict.set("AP", Dict::new().with("N", ap));
+            }
+        }
+
+        file.set(*id, dict);
+    }
+
+    // Annotations must be tagged as such.
+    retag(file, &placeholders, elems, "Annot")?;
+    Ok(())
+}
+
+/// Reads the areas that a placeholder annotation covers as `[x0 y0 x1 y1]`.
+fn quads(annot: &Dict) -> StrResult<Vec<[f64; 4]>> {
+    let numbers = match annot.get("QuadPoints") {
+        Some(quads) => quads.as_numbers(),
+        None => annot.get("Rect").and_then(Object::as_numbers),
+    }
+    .ok_or("malformed annotation area")?;
+
+    let size = if numbers.len() % 8 == 0 { 8 } else { 4 };
+    if numbers.is_empty() || numbers.len() % size != 0 {
+        bail!("malformed annotation area");
+    }
+
+    Ok(numbers
+        .chunks(size)
+        .map(|points| {
+            let xs = points.iter().step_by(2);
+            let ys = points.iter().skip(1).step_by(2);
+            [
+                xs.clone().copied().fold(f64::INFINITY, f64::min),
+                ys.clone().copied().fold(f64::INFINITY, f64::min),
+                xs.copied().fold(f64::NEG_INFINITY, f64::max),
+                ys.copied().fold(f64::NEG_INFINITY, f64::max),
+            ]
+        })
+        .collect())
+}
+
+/// The bounding box of multiple areas.
+fn bounds(quads: &[[f64; 4]]) -> [f64; 4] {
+    quads.iter().fold(
+        [f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY],
+        |[x0, y0, x1, y1], q| [x0.min(q[0]), y0.min(q[1]), x1.max(q[2]), y1.max(q[3])],
+    )
+}
+
+/// Creates the appearance of marked up content.
+///
+/// The bounding box of the appearance is the annotation's rectangle, so that
+/// it can be drawn in page coordinates.
+fn markup(kind: MarkKind, color: &Color, rect: [f64; 4], quads: &[[f64; 4]]) -> Stream {
+    let [r, g, b] = rgb_components(color);
+    let mut content = String::from("q");
+    let mut resources = None;
+    match kind {
+        MarkKind::Highlight => {
+            // Multiplying keeps the text below the highlight legible.
+            let state = Dict::new().with("BM", Object::name("Multiply"));
+            let states = Dict::new().with("GS0", state);
+            resources = Some(Dict::new().with("ExtGState", states));
+            write!(content, " /GS0 gs {r:.3} {g:.3} {b:.3} rg").unwrap();
+            for [x0, y0, x1, y1] in quads {
+                write!(content, " {x0:.2} {y0:.2} {:.2} {:.2} re", x1 - x0, y1 - y0)
+                    .unwrap();
+            }
+            content.push_str(" f");
+        }
+        MarkKind::Underline | MarkKind::Strike | MarkKind::Squiggly => {
+            write!(content, " {r:.3} {g:.3} {b:.3} RG").unwrap();
+            for [x0, y0, x1, y1] in quads {
+                let h = y1 - y0;
+                let width = (0.04 * h).max(0.5);
+                let y = match kind {
+                    MarkKind::Strike => y0 + 0.45 * h,
+                    _ => y0 + 0.2 * h,
+                };
+                write!(content, " {width:.2} w {x0:.2} {y:.2} m").unwrap();
+                if kind == MarkKind::Squiggly {
+                    let step = 2.0 * width.max(1.0);
+                    let mut x = *x0;
+                    let mut up = true;
+                    while x + step <= *x1 {
+                        x += step;
+                        let dy = if up { step } else { 0.0 };
+                        write!(content, " {x:.2} {:.2} l", y + dy).unwrap();
+                        up = !up;
+                    }
+                } else {
+                    write!(content, " {x1:.2} {y:.2} l").unwrap();
+                }
+                content.push_str(" S");
+            }
+        }
+    }
+    content.push_str(" Q");
+
+    let mut dict = Dict::new()
+        .with("Type", Object::name("XObject"))
+        .with("Subtype", Object::name("Form"))
+        .with("BBox", Object::numbers(rect));
+    if let Some(resources) = resources {
+        dict.set("Resources", resources);
+    }
+    Stream::new(dict, content.into_bytes())
+}
+
+/// Writes a color as a PDF array of RGB components.
+fn rgb(color: &Color) -> Object {
+    Object::numbers(rgb_components(color).map(f64::from))
+}
+
+/// The RGB components of a color.
+fn rgb_components(color: &Color) -> [f32; 3] {
+    let rgb = color.to_rgb();
+    [rgb.red, rgb.green, rgb.blue]
+}
+
+/// Formats a datetime as a PDF date string.
+///
+/// Components that the datetime doesn't have are omitted, which PDF allows
+/// from the month onwards.
+fn pdf_date(datetime: &Datetime) -> Option<String> {
+    let year = datetime.year().filter(|year| (0..=9999).contains(year))?;
+    let mut date = format!("D:{year:04}");
+    let components = [
+        datetime.month(),
+        datetime.day(),
+        datetime.hour(),
+        datetime.minute(),
+        datetime.second(),
+    ];
+    for component in components {
+        let Some(component) = component else { break };
+        write!(date, "{component:02}").unwrap();
+    }
+    if let Some(offset) = datetime.utc_offset() {
+        if offset.is_utc() {
+            date.push('Z');
+        } else {
+            let sign = if offset.is_negative() { '-' } else { '+' };
+            write!(
+                date,
+                "{sign}{:02}'{:02}'",
+                offset.whole_hours().unsigned_abs(),
+                offset.minutes_past_hour().unsigned_abs()
+            )
+            .unwrap();
+        }
+    }
+    Some(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rewrite::parse_object;
+
+    fn quads(annot: &[u8]) -> StrResult<Vec<[f64; 4]>> {
+        super::quads(parse_object(annot).unwrap().as_dict().unwrap())
+    }
+
+    #[test]
+    fn test_quads() {
+        let annot =
+            b"<< /Rect [1 2 3 4] /QuadPoints [1 4 3 4 1 2 3 2 5 8 7 8 5 6 7 6] >>";
+        assert_eq!(
+            quads(annot).unwrap(),
+            vec![[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]
+        );
+        assert_eq!(quads(b"<< /Rect [3 4 1 2] >>").unwrap(), vec![[1.0, 2.0, 3.0, 4.0]]);
+        assert!(quads(b"<< /Rect [1 2 3] >>").is_err());
+        assert!(quads(b"<< /Rect [1 2 3 /Four] >>").is_err());
+    }
+
+    #[test]
+    fn test_bounds() {
+        let quads = [[1.0, 6.0, 3.0, 8.0], [0.0, 2.0, 2.0, 4.0]];
+        assert_eq!(bounds(&quads), [0.0, 2.0, 3.0, 8.0]);
+    }
+
+    #[test]
+    fn test_pdf_date() {
+        let date = Datetime::from_ymd(2025, 3, 14).unwrap();
+        assert_eq!(pdf_date(&date).as_deref(), Some("D:20250314"));
+        let datetime = Datetime::from_ymd_hms(2025, 3, 14, 9, 5, 0).unwrap();
+        assert_eq!(pdf_date(&datetime).as_deref(), Some("D:20250314090500"));
+        let time = Datetime::from_hms(9, 5, 0).unwrap();
+        assert_eq!(pdf_date(&time), None);
+    }
+}
@@ -0,0 +1,185 @@
+//! Spot colors and ICC-based colors.
+//!
+//! krilla can only write colors in device color spaces, but it can embed the
+//! pages of other PDF files. Spot colors and ICC-based colors are thus painted
+//! with a tiling that draws a page, written with pdf-writer, which is filled
+//! with the color in a separation or ICC-based color space.
+//!
+//! Gradients keep writing such colors in their fallback color space.
+
+use std::sync::Arc;
+
+use krilla::paint::Pattern;
+use krilla::pdf::PdfDocument;
+use krilla::surface::Surface;
+use pdf_writer::types::ColorSpaceOperand;
+use pdf_writer::writers::ColorSpace;
+use pdf_writer::{Content, Finish, Name, Rect, Ref};
+use typst_library::layout::Transform;
+use typst_library::visualize::Color;
+
+use crate::convert::GlobalContext;
+use crate::util::TransformExt;
+
+/// The size of the tiling's cell. A single cell covers the largest page that
+/// PDF allows, so that viewers never show seams between cells.
+const CELL: f32 = 14_400.0;
+
+/// Converts a spot color or an ICC-based color into a tiling. Returns `None`
+/// for all other colors and in PDF/A and PDF/UA, which forbid embedded pages,
+/// so that the fallback is written instead.
+pub(crate) fn convert_color(
+    gc: &mut GlobalContext,
+    color: &Color,
+    surface: &mut Surface,
+) -> Option<krilla::paint::Paint> {
+    if !matches!(color, Color::Spot(_) | Color::Icc(_))
+        || gc.options.is_pdf_a()
+        || gc.options.is_pdf_ua()
+    {
+        return None;
+    }
+
+    let document = convert_document(color);
+    let mut stream_builder = surface.stream_builder();
+    let mut surface = stream_builder.surface();
+    surface.draw_pdf_page(&document, krilla::geom::Size::from_wh(CELL, CELL)?, 0);
+    surface.finish();
+    let pattern = Pattern {
+        stream: stream_builder.finish(),
+        transform: Transform::identity().to_krilla(),
+        width: CELL,
+        height: CELL,
+    };
+
+    Some(pattern.into())
+}
+
+#[comemo::memoize]
+fn convert_document(color: &Color) -> PdfDocument {
+    let pdf = hayro_syntax::Pdf::new(Arc::new(write_document(color)))
+        .expect("written document should be readable");
+    PdfDocument::new(Arc::new(pdf))
+}
+
+/// Writes a document with a single page that is filled with the color.
+fn write_document(color: &Color) -> Vec<u8> {
+    let catalog_id = Ref::new(1);
+    let pages_id = Ref::new(2);
+    let page_id = Ref::new(3);
+    let content_id = Ref::new(4);
+    let profile_id = Ref::new(5);
+    let name = Name(b"C0");
+
+    // krilla can only embed documents whose version doesn't exceed the one
+    // of the exported document.
+    let mut pdf = pdf_writer::Pdf::new();
+    pdf.set_version(1, 3);
+    pdf.catalog(catalog_id).pages(pages_id);
+    pdf.pages(pages_id).kids([page_id]).count(1);
+
+    let mut page = pdf.page(page_id);
+    page.parent(pages_id)
+        .media_box(Rect::new(0.0, 0.0, 1.0, 1.0))
+        .contents(content_id);
+    let mut resources = page.resources();
+    let mut spaces = resources.color_spaces();
+    let space = spaces.insert(name).start::<ColorSpace>();
+    let components: Vec<f32> = match color {
+        Color::Spot(spot) => {
+            let fallback = spot.fallback;
+            let mut separation = space.separation(Name(spot.name.resolve().as_bytes()));
+            separation.alternate_color_space().device_cmyk();
+            separation
+                .tint_exponential()
+                .domain([0.0, 1.0])
+                .c0([0.0; 4])
+                .c1([fallback.c, fallback.m, fallback.y, fallback.k])
+                .n(1.0);
+            vec![spot.tint]
+        }
+        Color::Icc(icc) => {
+            space.icc_based(profile_id);
+            icc.components().to_vec()
+        }
+        _ => unreachable!("only spot colors and ICC-based colors are written"),
+    };
+    spaces.finish();
+    resources.finish();
+    page.finish();
+
+    if let Color::Icc(icc) = color {
+        let components = icc.profile.components() as i32;
+        pdf.icc_profile(profile_id, icc.profile.data().as_slice())
+            .n(components);
+    }
+
+    let mut content = Content::new();
+    content.set_fill_color_space(ColorSpaceOperand::Named(name));
+    content.set_fill_color(components);
+    content.rect(0.0, 0.0, 1.0, 1.0);
+    content.fill_nonzero();
+    pdf.stream(content_id, &content.finish());
+
+    pdf.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use typst_library::foundations::Bytes;
+    use typst_library::visualize::{Cmyk, Icc, IccProfile, Spot};
+    use typst_utils::PicoStr;
+
+    use super::*;
+    use crate::rewrite::{File, Object, parse_object};
+
+    fn spot(name: &str, tint: f32) -> Color {
+        let fallback = Cmyk::new(0.0, 0.2, 0.8, 0.1);
+        Spot::new(PicoStr::intern(name), fallback, tint).into()
+    }
+
+    fn icc(values: [f32; 4]) -> Color {
+        let data = Bytes::new(typst_assets::icc::CMYK_TO_XYZ);
+        Icc::new(IccProfile::new(data).unwrap(), values).into()
+    }
+
+    fn written(color: &Color) -> (File, String) {
+        let mut file = File::read(&write_document(color)).unwrap();
+        let Object::Stream(content) = file.get_mut((4, 0)).unwrap() else { panic!() };
+        let content = String::from_utf8(content.data.clone()).unwrap();
+        (file, content.replace('\n', " "))
+    }
+
+    #[test]
+    fn test_spot() {
+        let (mut file, content) = written(&spot("Gold", 0.5));
+        assert_eq!(content, "/C0 cs 0.5 scn 0 0 1 1 re f");
+        let page = file.dict_mut((3, 0)).unwrap();
+        let resources = page.get("Resources").unwrap().as_dict().unwrap();
+        let spaces = resources.get("ColorSpace").unwrap().as_dict().unwrap();
+        assert_eq!(
+            spaces.get("C0").unwrap(),
+            &parse_object(
+                b"[/Separation /Gold /DeviceCMYK << /FunctionType 2 /Domain [0 1] \
+                  /C0 [0 0 0 0] /C1 [0 0.2 0.8 0.1] /N 1 >>]"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_icc() {
+        let (mut file, content) = written(&icc([0.1, 0.2, 0.3, 0.4]));
+        assert_eq!(content, "/C0 cs 0.1 0.2 0.3 0.4 scn 0 0 1 1 re f");
+        let page = file.dict_mut((3, 0)).unwrap();
+        let resources = page.get("Resources").unwrap().as_dict().unwrap();
+        let spaces = resources.get("ColorSpace").unwrap().as_dict().unwrap();
+        assert_eq!(
+            spaces.get("C0").unwrap(),
+            &parse_object(b"[/ICCBased 5 0 R]").unwrap()
+        );
+        let Object::Stream(profile) = file.get_mut((5, 0)).unwrap() else { panic!() };
+        assert_eq!(profile.dict.get("N").unwrap().as_int(), Some(4));
+        assert_eq!(profile.data, typst_assets::icc::CMYK_TO_XYZ);
+    }
+}
@@ -1,4 +1,4 @@
-use ecow::{EcoVec, eco_format};
+use ecow::{EcoVec, eco_format, eco_vec};
 use indexmap::IndexMap;
 use krilla::configure::{Configuration, ValidationError, Validator};
 use krilla::destination::NamedDestination;
@@ -20,21 +20,29 @@ use typst_library::layout::{
     Frame, FrameItem, GroupItem, PagedDocument, Size, Transform,
 };
 use typst_library::model::HeadingElem;
+use typst_library::pdf::{Annotation, Layer, Widget};
 use typst_library::text::{Font, Locale};
 use typst_library::visualize::{Geometry, Paint};
 use typst_syntax::Span;
 
-use crate::PdfOptions;
+use crate::annotate::{self, handle_annotation};
 use crate::attach::attach_files;
+use crate::encrypt;
+use crate::form::{self, handle_widget};
 use crate::image::handle_image;
+use crate::layer::{self, push_layer};
 use crate::link::{LinkAnnotation, handle_link};
 use crate::metadata::build_metadata;
 use crate::outline::build_outline;
 use crate::page::PageLabelExt;
+use crate::pdfx::{self, PdfX};
+use crate::rewrite::File;
 use crate::shape::handle_shape;
+use crate::sign;
 use crate::tags::{self, GroupId, Tags};
 use crate::text::handle_text;
-use crate::util::{AbsExt, TransformExt, convert_path, display_font};
+use crate::util::{AbsExt, RectExt, TransformExt, convert_path, display_font};
+use crate::{PdfOptions, PdfSignature};
 
 #[typst_macros::time(name = "convert document")]
 pub fn convert(
@@ -42,6 +50,15 @@ pub fn convert(
     options: &PdfOptions,
 ) -> SourceResult<Vec<u8>> {
     let (mut document, mut gc) = setup(typst_document, options)?;
+    if let Some(pdfx) = options.standards.pdfx {
+        pdfx::validate(&gc, pdfx)?;
+    }
+    if options.encryption.is_some() {
+        encrypt::validate(options)?;
+    }
+    if let Some(signature) = &options.signature {
+        sign::validate(options, signature, gc.page_index_converter.page_count())?;
+    }
 
     convert_pages(&mut gc, &mut document)?;
     attach_files(&gc, &mut document)?;
@@ -51,7 +68,11 @@ pub fn convert(
     document.set_metadata(build_metadata(&gc, doc_lang));
     document.set_tag_tree(tree);
 
-    finish(document, gc, options.standards.config)
+    let widgets = std::mem::take(&mut gc.widgets);
+    let annotations = std::mem::take(&mut gc.annotations);
+    let layers = std::mem::take(&mut gc.layers);
+    let bytes = finish(document, gc, options.standards.config)?;
+    amend(bytes, options, &widgets, &annotations, &layers, options.signature.as_ref())
 }
 
 pub fn tag_tree(
@@ -75,7 +96,12 @@ pub fn tag_tree(
     document.set_metadata(build_metadata(&gc, doc_lang));
     document.set_tag_tree(tree);
 
-    finish(document, gc, options.standards.config)?;
+    // Run the post-processing that rewrites tagged annotations, such that
+    // missing structure elements are caught here, too.
+    let widgets = std::mem::take(&mut gc.widgets);
+    let annotations = std::mem::take(&mut gc.annotations);
+    let bytes = finish(document, gc, options.standards.config)?;
+    amend(bytes, options, &widgets, &annotations, &[], None)?;
 
     Ok(output)
 }
@@ -86,7 +112,8 @@ fn setup<'a>(
 ) -> SourceResult<(Document, GlobalContext<'a>)> {
     let settings = SerializeSettings {
         compress_content_streams: true,
-        no_device_cs: true,
+        // PDF/X-1a only permits device colour spaces.
+        no_device_cs: options.standards.pdfx != Some(PdfX::X1a),
         ascii_compatible: false,
         xmp_metadata: true,
         cmyk_profile: None,
@@ -143,6 +170,15 @@ fn convert_pages(gc: &mut GlobalContext, document: &mut Document) -> SourceResul
                 settings = settings.with_page_label(label);
             }
 
+            // Pages with bleed or printer's marks are larger than their final
+            // trimmed size, which print shops need to know about. PDF/X
+            // requires the trim box in any case.
+            if typst_page.has_bleed_or_slug() || gc.options.standards.pdfx.is_some() {
+                settings = settings
+                    .with_bleed_box(typst_page.bleed_box().to_krilla())
+                    .with_trim_box(typst_page.trim_box().to_krilla());
+            }
+
             let mut page = document.start_page_with(settings);
             let mut surface = page.surface();
             let page_idx = gc.page_index_converter.pdf_page_index(i);
@@ -286,6 +322,12 @@ pub(crate) struct GlobalContext<'a> {
     pub(crate) page_index_converter: PageIndexConverter,
     /// Tagged PDF context.
     pub(crate) tags: Tags,
+    /// The widgets of form fields, in the order of their placeholders.
+    pub(crate) widgets: Vec<Widget>,
+    /// The annotations with comments, in the order of their placeholders.
+    pub(crate) annotations: Vec<Annotation>,
+    /// The layers, in the order in which they were first used.
+    pub(crate) layers: Vec<Layer>,
 }
 
 impl<'a> GlobalContext<'a> {
@@ -306,6 +348,9 @@ impl<'a> GlobalContext<'a> {
             image_spans: FxHashSet::default(),
             page_index_converter,
             tags,
+            widgets: Vec::new(),
+            annotations: Vec::new(),
+            layers: Vec::new(),
         }
     }
 }
@@ -341,6 +386,10 @@ pub(crate) fn handle_frame(
                 handle_image(gc, fc, image, *size, surface, *span)?
             }
             FrameItem::Link(dest, size) => handle_link(fc, gc, dest, *size)?,
+            FrameItem::Widget(widget, size) => handle_widget(fc, gc, widget, *size)?,
+            FrameItem::Annotation(annotation, size) => {
+                handle_annotation(fc, gc, annotation, *size)?
+            }
             FrameItem::Tag(Tag::Start(_, flags)) => {
                 if flags.tagged {
                     tags::handle_start(gc, surface);
@@ -371,6 +420,10 @@ pub(crate) fn handle_group(
     fc.state_mut().pre_concat(group.transform);
 
     tags::group(gc, surface, group.parent, |gc, surface| -> SourceResult<()> {
+        if let Some(layer) = &group.layer {
+            push_layer(gc, surface, layer)?;
+        }
+
         let clip_path = group
             .clip
             .as_ref()
@@ -391,6 +444,10 @@ pub(crate) fn handle_group(
             surface.pop();
         }
 
+        if group.layer.is_some() {
+            surface.pop();
+        }
+
         res
     })?;
 
@@ -399,6 +456,57 @@ pub(crate) fn handle_group(
     Ok(())
 }
 
+/// Adds what krilla cannot write to the finished file and signs it. The file
+/// is read and written again at most once.
+fn amend(
+    bytes: Vec<u8>,
+    options: &PdfOptions,
+    widgets: &[Widget],
+    annotations: &[Annotation],
+    layers: &[Layer],
+    signature: Option<&PdfSignature>,
+) -> SourceResult<Vec<u8>> {
+    let pdfx = options.standards.pdfx.zip(options.output_intent.as_ref());
+    if widgets.is_empty()
+        && annotations.is_empty()
+        && layers.is_empty()
+        && pdfx.is_none()
+        && options.encryption.is_none()
+        && signature.is_none()
+    {
+        return Ok(bytes);
+    }
+
+    let mut file = File::read(&bytes).map_err(|err| {
+        eco_vec![error!(
+            Span::detached(),
+            "failed to read the exported PDF ({err})";
+            hint: "please report this as a bug"
+        )]
+    })?;
+    if !widgets.is_empty() {
+        form::apply(&mut file, widgets)?;
+    }
+    if !annotations.is_empty() {
+        annotate::apply(&mut file, annotations)?;
+    }
+    if !layers.is_empty() {
+        layer::apply(&mut file, layers)?;
+    }
+    if let Some((pdfx, intent)) = pdfx {
+        pdfx::apply(&mut file, pdfx, intent)?;
+    }
+    if let Some(encryption) = &options.encryption {
+        encrypt::apply(&mut file, encryption, options.standards.config.version())?;
+    }
+
+    // The signature covers the written file, so it is added last.
+    match signature {
+        Some(signature) => sign::finish(file, signature, options),
+        None => Ok(file.write()),
+    }
+}
+
 /// Finish a krilla document and handle export errors.
 #[typst_macros::time(name = "finish export")]
 fn finish(
@@ -771,6 +879,11 @@ impl PageIndexConverter {
         self.skipped_pages > 0
     }
 
+    /// The number of pages in the exported PDF.
+    pub(crate) fn page_count(&self) -> usize {
+        self.page_indices.len()
+    }
+
     /// Get the PDF page index of a page index, if it's not excluded.
     pub(crate) fn pdf_page_index(&self, page_index: usize) -> Option<usize> {
         self.page_indices.get(&page_index).copied()
@@ -0,0 +1,625 @@
+//! Password protection with the AES-256 standard security handler.
+//!
+//! krilla cannot encrypt documents, so we rewrite the finished file instead:
+//! Every string and stream is encrypted with a random file key, which is in
+//! turn stored in the encryption dictionary, wrapped with each of the two
+//! passwords as described in ISO 32000-2, section 7.6.4.
+
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::{Aes128, Aes256};
+use ecow::{EcoString, eco_format, eco_vec};
+use krilla::configure::PdfVersion;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use typst_library::diag::{SourceResult, StrResult, bail, error};
+use typst_syntax::Span;
+use unicode_bidi::{BidiClass, bidi_class};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::rewrite::{Dict, File, Object};
+use crate::{PdfEncryption, PdfOptions, PdfPermissions};
+
+/// Checks that the export target permits encryption and that the passwords
+/// are valid.
+pub(crate) fn validate(options: &PdfOptions) -> SourceResult<()> {
+    let config = options.standards.config;
+    if options.is_pdf_a() {
+        bail!(
+            Span::detached(),
+            "{} does not allow encryption", config.validator().as_str();
+            hint: "remove the passwords or export without PDF/A"
+        );
+    }
+
+    if let Some(pdfx) = options.standards.pdfx {
+        bail!(
+            Span::detached(),
+            "{} does not allow encryption", pdfx.as_str();
+            hint: "remove the passwords or export without PDF/X"
+        );
+    }
+
+    let version = config.version();
+    if matches!(version, PdfVersion::Pdf14 | PdfVersion::Pdf15 | PdfVersion::Pdf16) {
+        bail!(
+            Span::detached(),
+            "AES-256 encryption is not supported by {}", version.as_str();
+            hint: "raise the export target to PDF 1.7 or higher"
+        );
+    }
+
+    if let Some(encryption) = &options.encryption {
+        for (kind, password) in
+            [("user", &encryption.user_password), ("owner", &encryption.owner_password)]
+        {
+            if let Err(err) = prepare(password) {
+                bail!(
+                    Span::detached(),
+                    "invalid {kind} password ({err})";
+                    hint: "passwords may not contain control, private use or \
+                           non-character code points"
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Encrypts all strings and streams of a file.
+pub(crate) fn apply(
+    file: &mut File,
+    encryption: &PdfEncryption,
+    version: PdfVersion,
+) -> SourceResult<()> {
+    encrypt(file, encryption, version).map_err(|err| {
+        eco_vec![error!(
+            Span::detached(),
+            "failed to encrypt PDF ({err})";
+            hint: "please report this as a bug"
+        )]
+    })
+}
+
+/// Encrypts the objects in place and adds the encryption dictionary.
+fn encrypt(
+    file: &mut File,
+    encryption: &PdfEncryption,
+    version: PdfVersion,
+) -> StrResult<()> {
+    if file.id.is_none() {
+        bail!("missing document identifier");
+    }
+
+    // Without an owner password, nobody may lift the restrictions.
+    let user = prepare(&encryption.user_password)?;
+    let owner = match prepare(&encryption.owner_password)? {
+        password if password.is_empty() => random::<32>()?.to_vec(),
+        password => password,
+    };
+
+    let key = random::<32>()?;
+    let handler = SecurityHandler::new(
+        &user,
+        &owner,
+        permission_bits(encryption.permissions),
+        &key,
+        &random()?,
+        random()?,
+    );
+
+    // PDF 1.7 only knows AES-256 through Adobe's extension level 3.
+    if version == PdfVersion::Pdf17 {
+        let adbe = Dict::new()
+            .with("BaseVersion", Object::name("1.7"))
+            .with("ExtensionLevel", 3);
+        file.catalog_mut()?.set("Extensions", Dict::new().with("ADBE", adbe));
+    }
+
+    let refs: Vec<_> = file.refs().collect();
+    for id in refs {
+        encrypt_object(file.get_mut(id)?, &key)?;
+    }
+
+    // The encryption dictionary itself is not encrypted.
+    let encrypt_id = file.alloc();
+    file.set(encrypt_id, handler.dict());
+    file.encrypt = Some(encrypt_id);
+
+    Ok(())
+}
+
+/// The values of the encryption dictionary.
+struct SecurityHandler {
+    o: [u8; 48],
+    u: [u8; 48],
+    oe: [u8; 32],
+    ue: [u8; 32],
+    p: u32,
+    perms: [u8; 16],
+}
+
+impl SecurityHandler {
+    /// Wraps the file key with the prepared passwords (algorithms 8 to 10).
+    /// The salts are the validation and key salts of the user and the owner
+    /// password, in this order.
+    fn new(
+        user: &[u8],
+        owner: &[u8],
+        p: u32,
+        key: &[u8; 32],
+        salts: &[u8; 32],
+        filler: [u8; 4],
+    ) -> Self {
+        let mut u = [0; 48];
+        u[..32].copy_from_slice(&hash(user, &salts[..8], &[]));
+        u[32..].copy_from_slice(&salts[..16]);
+
+        let mut ue = *key;
+        let wrap = hash(user, &salts[8..16], &[]);
+        cbc(&Aes256::new(GenericArray::from_slice(&wrap)), &[0; 16], &mut ue);
+
+        let mut o = [0; 48];
+        o[..32].copy_from_slice(&hash(owner, &salts[16..24], &u));
+        o[32..].copy_from_slice(&salts[16..]);
+
+        let mut oe = *key;
+        let wrap = hash(owner, &salts[24..], &u);
+        cbc(&Aes256::new(GenericArray::from_slice(&wrap)), &[0; 16], &mut oe);
+
+        let mut perms = [0xff; 16];
+        perms[..4].copy_from_slice(&p.to_le_bytes());
+        perms[8..12].copy_from_slice(b"Tadb");
+        perms[12..].copy_from_slice(&filler);
+        let cipher = Aes256::new(GenericArray::from_slice(key));
+        cipher.encrypt_block(GenericArray::from_mut_slice(&mut perms));
+
+        Self { o, u, oe, ue, p, perms }
+    }
+
+    /// The encryption dictionary.
+    fn dict(&self) -> Dict {
+        let filter = Dict::new()
+            .with("Type", Object::name("CryptFilter"))
+            .with("CFM", Object::name("AESV3"))
+            .with("AuthEvent", Object::name("DocOpen"))
+            .with("Length", 32);
+        Dict::new()
+            .with("Filter", Object::name("Standard"))
+            .with("V", 5)
+            .with("R", 6)
+            .with("Length", 256)
+            .with("CF", Dict::new().with("StdCF", filter))
+            .with("StmF", Object::name("StdCF"))
+            .with("StrF", Object::name("StdCF"))
+            .with("O", Object::String(self.o.to_vec()))
+            .with("U", Object::String(self.u.to_vec()))
+            .with("OE", Object::String(self.oe.to_vec()))
+            .with("UE", Object::String(self.ue.to_vec()))
+            .with("Perms", Object::String(self.perms.to_vec()))
+            .with("P", self.p as i32)
+            .with("EncryptMetadata", true)
+    }
+}
+
+/// Computes the user access permissions (table 22 of ISO 32000-2).
+fn permission_bits(permissions: PdfPermissions) -> u32 {
+    // Bits 7, 8 and 13 to 32 are reserved and must be set. Bit 10, the
+    // extraction of content for accessibility, is always granted.
+    let mut p = 0xffff_f2c0;
+    if permissions.print {
+        p |= (1 << 2) | (1 << 11);
+    }
+    if permissions.modify {
+        p |= (1 << 3) | (1 << 5) | (1 << 8) | (1 << 10);
+    }
+    if permissions.copy {
+        p |= 1 << 4;
+    }
+    p
+}
+
+/// Prepares a password with the SASLprep profile of stringprep (RFC 4013),
+/// including its normalization and bidirectional checks, and truncates it to
+/// 127 bytes of UTF-8. Like for queries, unassigned code points are allowed.
+fn prepare(password: &str) -> Result<Vec<u8>, EcoString> {
+    // Map non-ASCII spaces to a space and remove characters that are
+    // commonly mapped to nothing (tables C.1.2 and B.1 of RFC 3454).
+    let mapped: String = password
+        .chars()
+        .filter(|&c| {
+            !matches!(c,
+                '\u{AD}' | '\u{34F}' | '\u{1806}' | '\u{180B}'..='\u{180D}'
+                | '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FE00}'..='\u{FE0F}'
+                | '\u{FEFF}'
+            )
+        })
+        .map(|c| match c {
+            '\u{A0}'
+            | '\u{1680}'
+            | '\u{2000}'..='\u{200B}'
+            | '\u{202F}'
+            | '\u{205F}'
+            | '\u{3000}' => ' ',
+            c => c,
+        })
+        .nfkc()
+        .collect();
+
+    if let Some(c) = mapped.chars().find(|&c| is_prohibited(c)) {
+        bail!("prohibited character U+{:04X}", c as u32);
+    }
+
+    // Strings with right-to-left characters may not contain left-to-right
+    // ones and must start and end with a right-to-left character.
+    let is_rtl = |c: char| matches!(bidi_class(c), BidiClass::R | BidiClass::AL);
+    if mapped.chars().any(is_rtl)
+        && (mapped.chars().any(|c| bidi_class(c) == BidiClass::L)
+            || !mapped.starts_with(is_rtl)
+            || !mapped.ends_with(is_rtl))
+    {
+        bail!("mixed text direction");
+    }
+
+    let mut bytes = mapped.into_bytes();
+    bytes.truncate(127);
+    Ok(bytes)
+}
+
+/// Whether a character is prohibited in a prepared password (tables C.1.2,
+/// C.2.1, C.2.2, C.3, C.4 and C.6 to C.9 of RFC 3454). Surrogates (table C.5)
+/// can't occur in a Rust string.
+fn is_prohibited(c: char) -> bool {
+    let v = c as u32;
+    matches!(v,
+        // Non-ASCII spaces.
+        0xA0 | 0x1680 | 0x2000..=0x200B | 0x202F | 0x205F | 0x3000
+        // Control characters.
+        | 0x00..=0x1F | 0x7F | 0x80..=0x9F | 0x6DD | 0x70F | 0x180E
+        | 0x200C | 0x200D | 0x2028 | 0x2029 | 0x2060..=0x2063 | 0x206A..=0x206F
+        | 0xFEFF | 0xFFF9..=0xFFFC | 0x1D173..=0x1D17A
+        // Private use.
+        | 0xE000..=0xF8FF | 0xF0000..=0xFFFFD | 0x100000..=0x10FFFD
+        // Non-characters.
+        | 0xFDD0..=0xFDEF
+        // Inappropriate for plain text and for canonical representation.
+        | 0xFFFD | 0x2FF0..=0x2FFB
+        // Changing display properties.
+        | 0x340 | 0x341 | 0x200E | 0x200F | 0x202A..=0x202E
+        // Tagging characters.
+        | 0xE0001 | 0xE0020..=0xE007F
+    ) || v & 0xFFFE == 0xFFFE
+}
+
+/// Computes a hash for a password (algorithm 2.B).
+fn hash(password: &[u8], salt: &[u8], udata: &[u8]) -> [u8; 32] {
+    let mut k = Sha256::new()
+        .chain_update(password)
+        .chain_update(salt)
+        .chain_update(udata)
+        .finalize()
+        .to_vec();
+
+    let mut round = 0;
+    loop {
+        let mut e = Vec::with_capacity(64 * (password.len() + k.len() + udata.len()));
+        for _ in 0..64 {
+            e.extend_from_slice(password);
+            e.extend_from_slice(&k);
+            e.extend_from_slice(udata);
+        }
+
+        let iv = k[16..32].try_into().unwrap();
+        cbc(&Aes128::new(GenericArray::from_slice(&k[..16])), &iv, &mut e);
+
+        // The first 16 bytes as a big-endian number modulo 3, which is the
+        // same as their sum modulo 3 because 256 ≡ 1 (mod 3).
+        k = match e[..16].iter().map(|&b| u32::from(b)).sum::<u32>() % 3 {
+            0 => Sha256::digest(&e).to_vec(),
+            1 => Sha384::digest(&e).to_vec(),
+            _ => Sha512::digest(&e).to_vec(),
+        };
+
+        round += 1;
+        if round >= 64 && u32::from(*e.last().unwrap()) + 32 <= round {
+            break;
+        }
+    }
+
+    k[..32].try_into().unwrap()
+}
+
+/// Encrypts all strings and the stream data of an object in place.
+fn encrypt_object(object: &mut Object, key: &[u8; 32]) -> StrResult<()> {
+    match object {
+        Object::String(string) => *string = encrypt_data(key, string)?,
+        Object::Array(array) => {
+            for item in array {
+                encrypt_object(item, key)?;
+            }
+        }
+        Object::Dict(dict) => encrypt_dict(dict, key)?,
+        // The length is updated when the stream is written.
+        Object::Stream(stream) => {
+            encrypt_dict(&mut stream.dict, key)?;
+            stream.data = encrypt_data(key, &stream.data)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Encrypts all strings of a dictionary in place.
+fn encrypt_dict(dict: &mut Dict, key: &[u8; 32]) -> StrResult<()> {
+    for value in dict.values_mut() {
+        encrypt_object(value, key)?;
+    }
+    Ok(())
+}
+
+/// Encrypts a string or stream with AES-256 in CBC mode, prepending the
+/// random initialization vector.
+fn encrypt_data(key: &[u8; 32], data: &[u8]) -> StrResult<Vec<u8>> {
+    let iv = random::<16>()?;
+    let pad = 16 - data.len() % 16;
+    let mut out = Vec::with_capacity(16 + data.len() + pad);
+    out.extend_from_slice(&iv);
+    out.extend_from_slice(data);
+    out.resize(out.len() + pad, pad as u8);
+    cbc(&Aes256::new(GenericArray::from_slice(key)), &iv, &mut out[16..]);
+    Ok(out)
+}
+
+/// Encrypts data whose length is a multiple of the block size in place.
+fn cbc(cipher: &impl BlockEncrypt, iv: &[u8; 16], data: &mut [u8]) {
+    let mut prev = *iv;
+    for block in data.chunks_exact_mut(16) {
+        for (b, p) in block.iter_mut().zip(prev) {
+            *b ^= p;
+        }
+        cipher.encrypt_block(GenericArray::from_mut_slice(block));
+        prev.copy_from_slice(block);
+    }
+}
+
+/// Returns cryptographically secure random bytes.
+fn random<const N: usize>() -> StrResult<[u8; N]> {
+    let mut buf = [0; N];
+    getrandom::fill(&mut buf)
+        .map_err(|err| eco_format!("failed to gather randomness ({err})"))?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use aes::cipher::BlockDecrypt;
+
+    use super::*;
+    use crate::rewrite::{File, Stream, file, parse_object};
+
+    const ALL: PdfPermissions = PdfPermissions { print: true, copy: true, modify: true };
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_permission_bits() {
+        let none = PdfPermissions { print: false, copy: false, modify: false };
+        assert_eq!(permission_bits(ALL) as i32, -4);
+        assert_eq!(permission_bits(none) as i32, -3392);
+    }
+
+    #[test]
+    fn test_prepare() {
+        // The examples of RFC 4013, section 3.
+        assert_eq!(prepare("I\u{AD}X").unwrap(), b"IX");
+        assert_eq!(prepare("user").unwrap(), b"user");
+        assert_eq!(prepare("USER").unwrap(), b"USER");
+        assert_eq!(prepare("\u{AA}").unwrap(), b"a");
+        assert_eq!(prepare("\u{2168}").unwrap(), b"IX");
+        assert!(prepare("\u{7}").is_err());
+        assert!(prepare("\u{627}1").is_err());
+
+        assert_eq!(prepare("a\u{A0}b\u{2003}c").unwrap(), b"a b c");
+        assert_eq!(prepare("e\u{301}").unwrap(), "é".as_bytes());
+        assert_eq!(prepare("\u{5D0}1\u{5D1}").unwrap(), "\u{5D0}1\u{5D1}".as_bytes());
+        assert!(prepare("\u{5D0}a\u{5D1}").is_err());
+        assert!(prepare("\u{E000}").is_err());
+        assert!(prepare("\u{FFFF}").is_err());
+        assert!(prepare("\u{E0041}").is_err());
+        assert_eq!(prepare(&"ä".repeat(100)).unwrap().len(), 127);
+    }
+
+    #[test]
+    fn test_hash() {
+        // Computed with an independent implementation of algorithm 2.B.
+        assert_eq!(
+            hash(b"abc", b"12345678", &[]).to_vec(),
+            hex("52aa1c71d82d9b7b9bb625194fb8e61a5b53de09b7780d7a3bdcf52a465fcf3e")
+        );
+    }
+
+    #[test]
+    fn test_security_handler() {
+        let key: [u8; 32] = std::array::from_fn(|i| i as u8);
+        let salts: [u8; 32] = std::array::from_fn(|i| 100 + i as u8);
+        let handler = SecurityHandler::new(
+            b"user",
+            b"owner",
+            permission_bits(ALL),
+            &key,
+            &salts,
+            [1, 2, 3, 4],
+        );
+        assert_eq!(
+            handler.u.to_vec(),
+            hex("763ce098da904ecb57fe78e9e986466f7b9d28959c782467ec116333aaa57219\
+                 6465666768696a6b6c6d6e6f70717273")
+        );
+        assert_eq!(
+            handler.ue.to_vec(),
+            hex("2bf964d18a7ef6e9019330126e07e3123b983927a7325b9f9273e427cb811db8")
+        );
+        assert_eq!(
+            handler.o.to_vec(),
+            hex("5bd9e646456ee7ee224566bf51e6ede2901a9c122db3c7ead461c511a0251e38\
+                 7475767778797a7b7c7d7e7f80818283")
+        );
+        assert_eq!(
+            handler.oe.to_vec(),
+            hex("3c7b460b3c135b354f14487e67500076de8a9071e0d02b0a43f468cf23e5f9a4")
+        );
+        assert_eq!(handler.perms.to_vec(), hex("1134250a487c05902343c01d3249f305"));
+    }
+
+    /// Recovers the file key with a password (algorithms 2.A, 11, 12 and 13).
+    fn file_key(encrypt: &Dict, password: &str) -> Option<[u8; 32]> {
+        let password = prepare(password).unwrap();
+        let entry = |key| encrypt.get(key).and_then(Object::as_string).unwrap();
+        let (o, u) = (entry("O"), entry("U"));
+
+        let (wrap, wrapped) = if hash(&password, &o[32..40], u) == o[..32] {
+            (hash(&password, &o[40..48], u), entry("OE"))
+        } else if hash(&password, &u[32..40], &[]) == u[..32] {
+            (hash(&password, &u[40..48], &[]), entry("UE"))
+        } else {
+            return None;
+        };
+
+        let mut key: [u8; 32] = wrapped.try_into().unwrap();
+        decrypt_cbc(&wrap, &[0; 16], &mut key);
+
+        let mut perms: [u8; 16] = entry("Perms").try_into().unwrap();
+        let cipher = Aes256::new(GenericArray::from_slice(&key));
+        cipher.decrypt_block(GenericArray::from_mut_slice(&mut perms));
+        let p = encrypt.get("P").and_then(Object::as_int).unwrap() as i32;
+        assert_eq!(&perms[9..12], b"adb");
+        assert_eq!(perms[..4], p.to_le_bytes());
+        assert_eq!(perms[8], b'T');
+
+        Some(key)
+    }
+
+    fn decrypt_cbc(key: &[u8; 32], iv: &[u8; 16], data: &mut [u8]) {
+        let cipher = Aes256::new(GenericArray::from_slice(key));
+        let mut prev = *iv;
+        for block in data.chunks_exact_mut(16) {
+            let next: [u8; 16] = block.try_into().unwrap();
+            cipher.decrypt_block(GenericArray::from_mut_slice(block));
+            for (b, p) in block.iter_mut().zip(prev) {
+                *b ^= p;
+            }
+            prev = next;
+        }
+    }
+
+    fn decrypt_data(key: &[u8; 32], data: &[u8]) -> Vec<u8> {
+        let mut data = data.to_vec();
+        let iv: [u8; 16] = data[..16].try_into().unwrap();
+        decrypt_cbc(key, &iv, &mut data[16..]);
+        let pad = *data.last().unwrap() as usize;
+        assert!((1..=16).contains(&pad));
+        data.truncate(data.len() - pad);
+        data.split_off(16)
+    }
+
+    fn decrypt(object: &mut Object, key: &[u8; 32]) {
+        match object {
+            Object::String(string) => *string = decrypt_data(key, string),
+            Object::Array(array) => array.iter_mut().for_each(|item| decrypt(item, key)),
+            Object::Dict(dict) => dict.values_mut().for_each(|v| decrypt(v, key)),
+            Object::Stream(stream) => {
+                stream.dict.values_mut().for_each(|v| decrypt(v, key));
+                stream.data = decrypt_data(key, &stream.data);
+                stream.dict.remove("Length");
+            }
+            _ => {}
+        }
+    }
+
+    const OBJECTS: [&[u8]; 5] = [
+        b"<< /Type /Catalog /Pages 2 0 R /Lang (en) >>",
+        b"<< /Type /Pages /Kids [] /Count 0 >>",
+        b"<< /Title (A \\(nested\\) title) /Author <feff00e9> >>",
+        b"<< /Length 5 0 R /Note (<<) >>\nstream\n0 0 m\nendstream",
+        b"5",
+    ];
+
+    /// Encrypts a file and checks that it is written without plain text.
+    fn encrypted(encryption: &PdfEncryption, version: PdfVersion) -> File {
+        let mut file = File::read(&file(&OBJECTS)).unwrap();
+        encrypt(&mut file, encryption, version).unwrap();
+        let bytes = file.write();
+        assert!(!bytes.windows(6).any(|w| w == b"nested"));
+        assert!(!bytes.windows(5).any(|w| w == b"0 0 m"));
+        assert!(bytes.windows(17).any(|w| w == b"/Encrypt 6 0 R\n  "));
+        file
+    }
+
+    /// Encrypts a file and decrypts its objects again with a password.
+    fn roundtrip(
+        encryption: &PdfEncryption,
+        version: PdfVersion,
+        password: &str,
+    ) -> Option<Vec<Object>> {
+        let mut file = encrypted(encryption, version);
+        let key = file_key(file.dict_mut(file.encrypt.unwrap()).unwrap(), password)?;
+        let refs: Vec<_> = file.refs().take(5).collect();
+        let objects = refs.into_iter().map(|id| {
+            let mut object = file.get_mut(id).unwrap().clone();
+            decrypt(&mut object, &key);
+            object
+        });
+        Some(objects.collect())
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let encryption = PdfEncryption {
+            user_password: "Benutzer".into(),
+            owner_password: "Eigentümer".into(),
+            permissions: ALL,
+        };
+
+        let note = Dict::new().with("Note", Object::String(b"<<".to_vec()));
+        let stream = Stream::new(note, b"0 0 m".to_vec());
+        let expected = vec![
+            parse_object(b"<< /Type /Catalog /Pages 2 0 R /Lang (en) >>").unwrap(),
+            parse_object(b"<< /Type /Pages /Kids [] /Count 0 >>").unwrap(),
+            parse_object(b"<< /Title (A \\(nested\\) title) /Author <feff00e9> >>")
+                .unwrap(),
+            Object::Stream(stream),
+            Object::Int(5),
+        ];
+
+        // The owner password matches regardless of its normalization form.
+        for password in ["Benutzer", "Eigentu\u{308}mer"] {
+            let objects = roundtrip(&encryption, PdfVersion::Pdf20, password);
+            assert_eq!(objects, Some(expected.clone()));
+        }
+
+        assert_eq!(roundtrip(&encryption, PdfVersion::Pdf20, "wrong"), None);
+
+        let objects = roundtrip(&encryption, PdfVersion::Pdf17, "Benutzer").unwrap();
+        let catalog = objects[0].as_dict().unwrap();
+        assert_eq!(
+            catalog.get("Extensions").unwrap(),
+            &parse_object(b"<< /ADBE << /BaseVersion /1.7 /ExtensionLevel 3 >> >>")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_roundtrip_without_owner_password() {
+        let encryption = PdfEncryption {
+            user_password: "".into(),
+            owner_password: "".into(),
+            permissions: PdfPermissions { print: true, copy: false, modify: false },
+        };
+        assert!(roundtrip(&encryption, PdfVersion::Pdf20, "").is_some());
+    }
+}
@@ -0,0 +1,707 @@
+//! Interactive form fields.
+//!
+//! krilla cannot write widget annotations, so form fields are first written as
+//! link annotations with a placeholder URI. These are tagged like regular
+//! links, which gives them a structure element and an entry in the parent
+//! tree. When the finished file is rewritten, each placeholder is replaced
+//! with a widget, its structure element is turned into a `Form` element and
+//! the fields are registered with the document's interactive form.
+//!
+//! As krilla validates the file before the rewrite, the rewrite must not break
+//! conformance: fields that show text are rejected where their font can't be
+//! used, and each tagged placeholder must have a structure element to retag.
+
+use std::fmt::Write;
+
+use ecow::{EcoString, eco_vec};
+use krilla::action::{Action, LinkAction};
+use krilla::annotation::Target;
+use rustc_hash::FxHashMap;
+use typst_library::diag::{SourceResult, StrResult, bail, error};
+use typst_library::layout::Size;
+use typst_library::pdf::{Widget, WidgetKind};
+use typst_syntax::Span;
+
+use crate::convert::{FrameContext, GlobalContext};
+use crate::link::{LinkAnnotation, LinkAnnotationKind, bounding_box};
+use crate::rewrite::{Dict, File, Object, Ref, Stream};
+use crate::tags::{self, GroupId};
+
+/// The URI of the link annotations that are replaced by widgets, followed by
+/// the index of the widget.
+const PLACEHOLDER: &str = "typst-widget:";
+
+/// The default appearance of variable text, with a font size of zero, which
+/// makes viewers fit the text into the field.
+const DEFAULT_APPEARANCE: &str = "/Helv 0 Tf 0 g";
+
+pub(crate) fn handle_widget(
+    fc: &mut FrameContext,
+    gc: &mut GlobalContext,
+    widget: &Widget,
+    size: Size,
+) -> SourceResult<()> {
+    let standard = match gc.options.standards.pdfx {
+        Some(pdfx) => Some(pdfx.as_str()),
+        None if gc.options.is_pdf_a()
+            || (gc.options.is_pdf_ua() && shows_text(&widget.kind)) =>
+        {
+            Some(gc.options.standards.config.validator().as_str())
+        }
+        None => None,
+    };
+    if let Some(standard) = standard {
+        bail!(
+            widget.span,
+            "{} are not supported in {standard}",
+            describe(&widget.kind);
+            hint: "the values of form fields are displayed with a font that \
+                   is not embedded"
+        );
+    }
+
+    check_name(&gc.widgets, widget)?;
+
+    let index = gc.widgets.len();
+    gc.widgets.push(widget.clone());
+    let target =
+        Target::Action(Action::Link(LinkAction::new(format!("{PLACEHOLDER}{index}"))));
+    let rect = bounding_box(fc, size);
+
+    if tags::disabled(gc) {
+        if gc.tags.in_tiling && gc.options.is_pdf_ua() {
+            let validator = gc.options.standards.config.validator().as_str();
+            bail!(
+                widget.span,
+                "{validator} error: PDF artifacts may not contain form fields";
+                hint: "a form field was used within a tiling"
+            );
+        }
+
+        fc.push_link_annotation(
+            GroupId::INVALID,
+            LinkAnnotation {
+                kind: LinkAnnotationKind::Artifact,
+                alt: None,
+                span: widget.span,
+                rects: vec![rect],
+                target,
+            },
+        );
+        return Ok(());
+    }
+
+    let Some((group_id, marker)) = gc.tags.tree.parent_link() else {
+        bail!(
+            widget.span,
+            "form field is not part of the tag tree";
+            hint: "please report this as a bug"
+        );
+    };
+    let alt = marker.alt.as_ref().map(Into::into);
+
+    if gc.tags.tree.parent_artifact().is_some() {
+        if gc.options.is_pdf_ua() {
+            let validator = gc.options.standards.config.validator().as_str();
+            bail!(
+                widget.span,
+                "{validator} error: PDF artifacts may not contain form fields"
+            );
+        }
+
+        fc.push_link_annotation(
+            group_id,
+            LinkAnnotation {
+                kind: LinkAnnotationKind::Artifact,
+                alt,
+                span: widget.span,
+                rects: vec![rect],
+                target,
+            },
+        );
+        return Ok(());
+    }
+
+    let annot_id = gc.tags.annotations.reserve();
+    fc.push_link_annotation(
+        group_id,
+        LinkAnnotation {
+            kind: LinkAnnotationKind::Tagged(annot_id),
+            alt,
+            span: widget.span,
+            rects: vec![rect],
+            target,
+        },
+    );
+    let group = gc.tags.tree.groups.get_mut(group_id);
+    group.push_annotation(annot_id);
+
+    Ok(())
+}
+
+/// Whether a widget displays text, which requires a font.
+fn shows_text(kind: &WidgetKind) -> bool {
+    !matches!(kind, WidgetKind::Checkbox { .. } | WidgetKind::Radio { .. })
+}
+
+/// Describes a kind of widget in plural, for error messages.
+fn describe(kind: &WidgetKind) -> &'static str {
+    match kind {
+        WidgetKind::Text { .. } => "text fields",
+        WidgetKind::Checkbox { .. } => "checkboxes",
+        WidgetKind::Radio { .. } => "radio buttons",
+        WidgetKind::Dropdown { .. } => "drop-down lists",
+        WidgetKind::Button { .. } => "buttons",
+    }
+}
+
+/// Checks that a field's name doesn't clash with the fields before it.
+fn check_name(widgets: &[Widget], widget: &Widget) -> SourceResult<()> {
+    let name = &widget.name;
+    if name.is_empty() || name.contains('.') {
+        bail!(widget.span, "form field names must not be empty or contain periods");
+    }
+
+    if let WidgetKind::Radio { value, .. } = &widget.kind
+        && value == "Off"
+    {
+        bail!(
+            widget.span,
+            "`Off` cannot be the value of a radio button";
+            hint: "PDF uses this value for groups without a selected button"
+        );
+    }
+
+    for other in widgets.iter().filter(|other| other.name == *name) {
+        match (&other.kind, &widget.kind) {
+            (
+                WidgetKind::Radio { value: a, checked: a_checked },
+                WidgetKind::Radio { value: b, checked: b_checked },
+            ) => {
+                if a == b {
+                    bail!(
+                        widget.span,
+                        "radio group `{name}` already has a button with the value `{b}`"
+                    );
+                }
+                if *a_checked && *b_checked {
+                    bail!(
+                        widget.span,
+                        "radio group `{name}` already has a checked button"
+                    );
+                }
+            }
+            _ => bail!(
+                widget.span,
+                "a form field named `{name}` already exists";
+                hint: "only radio buttons can share their name to form a group"
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+/// Replaces the placeholder annotations of a finished PDF file with widgets.
+pub(crate) fn apply(file: &mut File, widgets: &[Widget]) -> SourceResult<()> {
+    add_fields(file, widgets).map_err(|err| {
+        eco_vec![error!(
+            Span::detached(),
+            "failed to write form fields ({err})";
+            hint: "please report this as a bug"
+        )]
+    })
+}
+
+/// Adds the widgets and the interactive form.
+fn add_fields(file: &mut File, widgets: &[Widget]) -> StrResult<()> {
+    let (placeholders, elems) = find_placeholders(file, PLACEHOLDER, widgets.len())?;
+
+    // Only fields that show text need the font.
+    let font_id = widgets
+        .iter()
+        .any(|widget| shows_text(&widget.kind))
+        .then(|| file.alloc());
+    if let Some(font_id) = font_id {
+        file.set(font_id, helvetica());
+    }
+    let mut fields = vec![];
+    let mut groups: FxHashMap<&EcoString, (Ref, Vec<Object>, Option<&EcoString>)> =
+        FxHashMap::default();
+    let mut group_order = vec![];
+
+    for (widget, placeholder) in widgets.iter().zip(&placeholders) {
+        // Widgets on pages that weren't exported have no placeholder.
+        let Some((id, annot)) = placeholder else { continue };
+        let [x0, y0, x1, y1] = annot
+            .get("Rect")
+            .and_then(Object::as_numbers)
+            .ok_or("malformed annotation rect")?[..]
+        else {
+            bail!("malformed annotation rect");
+        };
+        let (w, h) = ((x1 - x0).abs(), (y1 - y0).abs());
+        let size = widget.text_size.to_pt();
+
+        let mut dict = Dict::new()
+            .with("Type", Object::name("Annot"))
+            .with("Subtype", Object::name("Widget"))
+            .with("Rect", Object::numbers([x0, y0, x1, y1]))
+            .with("F", 4);
+        for key in ["StructParent", "P"] {
+            if let Some(value) = annot.get(key) {
+                dict.set(key, value.clone());
+            }
+        }
+        let alt = annot.get("Contents").cloned();
+
+        match &widget.kind {
+            WidgetKind::Radio { value, checked } => {
+                let (parent, kids, selected) =
+                    groups.entry(&widget.name).or_insert_with(|| {
+                        group_order.push(&widget.name);
+                        (file.alloc(), vec![], None)
+                    });
+                kids.push((*id).into());
+                if *checked {
+                    *selected = Some(value);
+                }
+
+                let state = if *checked { value.as_str() } else { "Off" };
+                let on_id = file.alloc();
+                let off_id = file.alloc();
+                file.set(on_id, form_xobject(w, h, None, &dot(w, h)));
+                file.set(off_id, form_xobject(w, h, None, ""));
+                let states = Dict::new().with(value, on_id).with("Off", off_id);
+                dict.set("Parent", *parent);
+                dict.set("AS", Object::name(state));
+                dict.set("AP", Dict::new().with("N", states));
+                if let Some(alt) = alt {
+                    dict.set("Contents", alt);
+                }
+            }
+            kind => {
+                fields.push((*id).into());
+                dict.set("T", Object::text(&widget.name));
+                dict.set("TU", alt.unwrap_or_else(|| Object::text(&widget.name)));
+                field_entries(&mut dict, file, kind, (w, h, size), font_id);
+            }
+        }
+
+        file.set(*id, dict);
+    }
+
+    // The parent fields of radio groups.
+    for name in group_order {
+        let (parent, kids, selected) = &groups[name];
+        let selected = Object::name(selected.map_or("Off", |value| value.as_str()));
+        let dict = Dict::new()
+            .with("FT", Object::name("Btn"))
+            .with("Ff", 49152)
+            .with("T", Object::text(name))
+            .with("TU", Object::text(name))
+            .with("V", selected.clone())
+            .with("DV", selected)
+            .with("Kids", kids.clone());
+        file.set(*parent, dict);
+        fields.push((*parent).into());
+    }
+
+    // Form fields must be tagged as such.
+    retag(file, &placeholders, elems, "Form")?;
+
+    // Register the fields with the document's interactive form.
+    let mut form = Dict::new().with("Fields", fields);
+    if let Some(font_id) = font_id {
+        form.set("DR", Dict::new().with("Font", Dict::new().with("Helv", font_id)));
+        form.set("DA", Object::String(DEFAULT_APPEARANCE.into()));
+    }
+    file.catalog_mut()?.set("AcroForm", form);
+    Ok(())
+}
+
+/// Writes the field-specific entries of a field that is merged with its
+/// widget and creates its appearance.
+fn field_entries(
+    dict: &mut Dict,
+    file: &mut File,
+    kind: &WidgetKind,
+    (w, h, size): (f64, f64, f64),
+    font_id: Option<Ref>,
+) {
+    // Variable text is enclosed in a `Tx` marked-content sequence.
+    let mut appearance = |dict: &mut Dict, content: String| {
+        let id = file.alloc();
+        let content = format!("/Tx BMC {content} EMC");
+        file.set(id, form_xobject(w, h, font_id, &content));
+        dict.set("DA", Object::String(format!("/Helv {size:.2} Tf 0 g").into_bytes()));
+        dict.set("AP", Dict::new().with("N", id));
+    };
+
+    match kind {
+        WidgetKind::Text { value, multiline } => {
+            dict.set("FT", Object::name("Tx"));
+            dict.set("V", Object::text(value));
+            dict.set("DV", Object::text(value));
+            if *multiline {
+                dict.set("Ff", 4096);
+            }
+            appearance(dict, text(w, h, size, value_lines(kind), *multiline, false));
+        }
+        WidgetKind::Checkbox { checked } => {
+            let state = Object::name(if *checked { "Yes" } else { "Off" });
+            let on_id = file.alloc();
+            let off_id = file.alloc();
+            file.set(on_id, form_xobject(w, h, None, &check(w, h)));
+            file.set(off_id, form_xobject(w, h, None, ""));
+            let states = Dict::new().with("Yes", on_id).with("Off", off_id);
+            dict.set("FT", Object::name("Btn"));
+            dict.set("V", state.clone());
+            dict.set("DV", state.clone());
+            dict.set("AS", state);
+            dict.set("AP", Dict::new().with("N", states));
+        }
+        WidgetKind::Dropdown { options, value } => {
+            let options: Vec<Object> =
+                options.iter().map(|option| Object::text(option)).collect();
+            dict.set("FT", Object::name("Ch"));
+            dict.set("Ff", 131072);
+            dict.set("Opt", options);
+            if let Some(value) = value {
+                dict.set("V", Object::text(value));
+                dict.set("DV", Object::text(value));
+            }
+            appearance(dict, text(w, h, size, value_lines(kind), false, false));
+        }
+        WidgetKind::Button { caption, submit } => {
+            dict.set("FT", Object::name("Btn"));
+            dict.set("Ff", 65536);
+            dict.set("MK", Dict::new().with("CA", Object::text(caption)));
+            let action = match submit {
+                Some(url) => Dict::new()
+                    .with("S", Object::name("SubmitForm"))
+                    .with(
+                        "F",
+                        Dict::new()
+                            .with("FS", Object::name("URL"))
+                            .with("F", Object::text(url)),
+                    )
+                    .with("Flags", 4),
+                None => Dict::new().with("S", Object::name("ResetForm")),
+            };
+            dict.set("A", action);
+            appearance(dict, text(w, h, size, value_lines(kind), false, true));
+        }
+        WidgetKind::Radio { .. } => unreachable!("radio buttons are grouped"),
+    }
+}
+
+/// A placeholder annotation or structure element and its object ID.
+pub(crate) type Placeholder = (Ref, Dict);
+
+/// Finds the placeholder annotations with the given URI prefix, indexed by
+/// the number after the prefix, and the `Link` structure elements that may
+/// refer to them.
+pub(crate) fn find_placeholders(
+    file: &File,
+    prefix: &str,
+    count: usize,
+) -> StrResult<(Vec<Option<Placeholder>>, Vec<Placeholder>)> {
+    let mut placeholders = vec![None; count];
+    let mut elems = vec![];
+    for id in file.refs() {
+        let Object::Dict(dict) = file.get(id)? else { continue };
+        if let Some(index) = placeholder(dict, prefix) {
+            let slot = placeholders.get_mut(index).ok_or("invalid placeholder")?;
+            *slot = Some((id, dict.clone()));
+        } else if dict.is("S", "Link") && dict.get("K").is_some() {
+            elems.push((id, dict.clone()));
+        }
+    }
+    Ok((placeholders, elems))
+}
+
+/// Changes the type of the structure elements that refer to placeholders.
+///
+/// Fails if a tagged placeholder has no such element, since the file would
+/// then contain an annotation that is only tagged as a link.
+pub(crate) fn retag(
+    file: &mut File,
+    placeholders: &[Option<Placeholder>],
+    elems: Vec<Placeholder>,
+    role: &str,
+) -> StrResult<()> {
+    let annots: Vec<Ref> = placeholders.iter().flatten().map(|(id, _)| *id).collect();
+    let mut untagged: Vec<Ref> = placeholders
+        .iter()
+        .flatten()
+        .filter(|(_, annot)| annot.get("StructParent").is_some())
+        .map(|(id, _)| *id)
+        .collect();
+    for (id, mut dict) in elems {
+        if refers_to_any(&dict, &annots) {
+            untagged.retain(|annot| !refers_to_any(&dict, &[*annot]));
+            dict.set("S", Object::name(role));
+            file.set(id, dict);
+        }
+    }
+    if !untagged.is_empty() {
+        bail!("missing structure element of tagged annotation");
+    }
+    Ok(())
+}
+
+/// Returns the index of the object a placeholder annotation stands for.
+fn placeholder(dict: &Dict, prefix: &str) -> Option<usize> {
+    if !dict.is("Subtype", "Link") {
+        return None;
+    }
+    let uri = dict.get("A")?.as_dict()?.get("URI")?.as_string()?;
+    let index = uri.strip_prefix(prefix.as_bytes())?;
+    std::str::from_utf8(index).ok()?.parse().ok()
+}
+
+/// Whether a structure element has an object reference to any of the given
+/// objects among its direct kids.
+fn refers_to_any(dict: &Dict, ids: &[Ref]) -> bool {
+    let refers = |kid: &Object| {
+        kid.as_dict().is_some_and(|kid| {
+            kid.is("Type", "OBJR")
+                && kid
+                    .get("Obj")
+                    .and_then(Object::as_reference)
+                    .is_some_and(|id| ids.contains(&id))
+        })
+    };
+    match dict.get("K") {
+        Some(Object::Array(kids)) => kids.iter().any(refers),
+        Some(kid) => refers(kid),
+        None => false,
+    }
+}
+
+/// The lines of text a widget displays.
+fn value_lines(kind: &WidgetKind) -> Vec<EcoString> {
+    match kind {
+        WidgetKind::Text { value, .. } => value.lines().map(Into::into).collect(),
+        WidgetKind::Dropdown { value, .. } => value.iter().cloned().collect(),
+        WidgetKind::Button { caption, .. } => vec![caption.clone()],
+        WidgetKind::Checkbox { .. } | WidgetKind::Radio { .. } => vec![],
+    }
+}
+
+/// Creates the content of an appearance that shows text.
+///
+/// Single lines are centered vertically. Since the widths of the font's glyphs
+/// aren't at hand, horizontal centering only estimates the width of the text.
+pub(crate) fn text(
+    w: f64,
+    h: f64,
+    size: f64,
+    lines: Vec<EcoString>,
+    multiline: bool,
+    centered: bool,
+) -> String {
+    let mut content =
+        format!("q 1 1 {:.2} {:.2} re W n BT /Helv {size:.2} Tf 0 g", w - 2.0, h - 2.0);
+    let leading = size * 1.15;
+    let y = if multiline { h - 2.0 - size } else { (h - size * 0.7) / 2.0 };
+    match lines.first() {
+        Some(line) if centered => {
+            let x = ((w - 0.5 * size * line.chars().count() as f64) / 2.0).max(2.0);
+            write!(content, " {x:.2} {y:.2} Td {} Tj", show(line)).unwrap();
+        }
+        Some(_) => {
+            write!(content, " {leading:.2} TL 2 {y:.2} Td").unwrap();
+            let count = if multiline { lines.len() } else { 1 };
+            for line in &lines[..count] {
+                write!(content, " {} Tj T*", show(line)).unwrap();
+            }
+        }
+        None => {}
+    }
+    content.push_str(" ET Q");
+    content
+}
+
+/// Writes a line of text as a hex string operand in the encoding of the
+/// standard font, replacing characters that it cannot show.
+pub(crate) fn show(line: &str) -> String {
+    let string = pdf_latin1(line).unwrap_or_else(|| {
+        let line: String =
+            line.chars()
+                .map(|c| {
+                    if pdf_latin1(c.encode_utf8(&mut [0; 4])).is_some() { c } else { '?' }
+                })
+                .collect();
+        pdf_latin1(&line).expect("only Latin-1 characters are left")
+    });
+    let mut hex = String::from("<");
+    for b in string {
+        write!(hex, "{b:02x}").unwrap();
+    }
+    hex.push('>');
+    hex
+}
+
+/// Encodes text in the encoding of the standard 14 fonts, which is used by
+/// appearance streams. Returns `None` if the text contains characters other
+/// than printable Latin-1.
+pub(crate) fn pdf_latin1(text: &str) -> Option<Vec<u8>> {
+    text.chars()
+        .map(|c| match u32::from(c) {
+            c @ (0x20..=0x7e | 0xa0..=0xff) => Some(c as u8),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Creates the content of a checked checkbox: a check mark.
+fn check(w: f64, h: f64) -> String {
+    let t = 0.1 * w.min(h);
+    format!(
+        "q 0 g 0 G {t:.2} w 1 J 1 j {:.2} {:.2} m {:.2} {:.2} l {:.2} {:.2} l S Q",
+        0.2 * w,
+        0.5 * h,
+        0.42 * w,
+        0.25 * h,
+        0.8 * w,
+        0.75 * h,
+    )
+}
+
+/// Creates the content of a selected radio button: a filled circle.
+fn dot(w: f64, h: f64) -> String {
+    let (cx, cy) = (w / 2.0, h / 2.0);
+    let r = 0.25 * w.min(h);
+    let k = 0.5523 * r;
+    format!(
+        "q 0 g {:.2} {cy:.2} m {:.2} {:.2} {:.2} {:.2} {cx:.2} {:.2} c \
+         {:.2} {:.2} {:.2} {:.2} {:.2} {cy:.2} c \
+         {:.2} {:.2} {:.2} {:.2} {cx:.2} {:.2} c \
+         {:.2} {:.2} {:.2} {:.2} {:.2} {cy:.2} c f Q",
+        cx + r,
+        cx + r,
+        cy + k,
+        cx + k,
+        cy + r,
+        cy + r,
+        cx - k,
+        cy + r,
+        cx - r,
+        cy + k,
+        cx - r,
+        cx - r,
+        cy - k,
+        cx - k,
+        cy - r,
+        cy - r,
+        cx + k,
+        cy - r,
+        cx + r,
+        cy - k,
+        cx + r,
+    )
+}
+
+/// The standard font that the appearances of text are written in.
+pub(crate) fn helvetica() -> Dict {
+    Dict::new()
+        .with("Type", Object::name("Font"))
+        .with("Subtype", Object::name("Type1"))
+        .with("BaseFont", Object::name("Helvetica"))
+        .with("Encoding", Object::name("WinAnsiEncoding"))
+}
+
+/// Wraps appearance content into a form XObject.
+pub(crate) fn form_xobject(w: f64, h: f64, font: Option<Ref>, content: &str) -> Stream {
+    let mut dict = Dict::new()
+        .with("Type", Object::name("XObject"))
+        .with("Subtype", Object::name("Form"))
+        .with("BBox", Object::numbers([0.0, 0.0, w, h]));
+    if let Some(id) = font {
+        let fonts = Dict::new().with("Helv", id);
+        dict.set("Resources", Dict::new().with("Font", fonts));
+    }
+    Stream::new(dict, content.as_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use typst_library::layout::Abs;
+
+    use super::*;
+    use crate::rewrite::{file, parse_object};
+
+    fn dict(data: &[u8]) -> Dict {
+        parse_object(data).unwrap().as_dict().unwrap().clone()
+    }
+
+    #[test]
+    fn test_placeholder() {
+        let annot = dict(
+            b"<< /Type /Annot /Subtype /Link /A << /Type /Action /S /URI \
+              /URI (typst-widget:12) >> >>",
+        );
+        assert_eq!(placeholder(&annot, PLACEHOLDER), Some(12));
+        assert_eq!(placeholder(&annot, "typst-annotation:"), None);
+        let link = dict(b"<< /Subtype /Link /A << /URI (https://typst.app) >> >>");
+        assert_eq!(placeholder(&link, PLACEHOLDER), None);
+        let nested = dict(b"<< /Subtype /Link /Contents (/URI (typst-widget:1)) >>");
+        assert_eq!(placeholder(&nested, PLACEHOLDER), None);
+    }
+
+    #[test]
+    fn test_refers_to_any() {
+        let elem = dict(
+            b"<< /Type /StructElem /S /Link /K [<< /Type /OBJR /Obj 12 0 R >> 3] >>",
+        );
+        assert!(refers_to_any(&elem, &[(4, 0), (12, 0)]));
+        assert!(!refers_to_any(&elem, &[(1, 0), (2, 0)]));
+        let single = dict(b"<< /S /Link /K << /Type /OBJR /Obj 2 0 R >> >>");
+        assert!(refers_to_any(&single, &[(2, 0)]));
+        let alt = dict(b"<< /S /Link /Alt (/Obj 12 0 R) /K 0 >>");
+        assert!(!refers_to_any(&alt, &[(12, 0)]));
+    }
+
+    #[test]
+    fn test_show() {
+        assert_eq!(show("Hello"), "<48656c6c6f>");
+        assert_eq!(show("Grüße"), "<4772fcdf65>");
+        assert_eq!(show("a\u{4e2d}b"), "<613f62>");
+    }
+
+    #[test]
+    fn test_pdf_latin1() {
+        assert_eq!(pdf_latin1("Zürich"), Some(b"Z\xfcrich".to_vec()));
+        assert_eq!(pdf_latin1("€"), None);
+    }
+
+    #[test]
+    fn test_add_fields() {
+        let bytes = file(&[
+            b"<< /Type /Catalog /Pages 2 0 R >>",
+            b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+            b"<< /Type /Page /Parent 2 0 R /Annots [4 0 R] >>",
+            b"<< /Type /Annot /Subtype /Link /Rect [10 20 30 40] /P 3 0 R \
+              /A << /S /URI /URI (typst-widget:0) >> >>",
+        ]);
+        let mut file = File::read(&bytes).unwrap();
+        let widget = Widget {
+            name: "agree".into(),
+            kind: WidgetKind::Checkbox { checked: true },
+            text_size: Abs::pt(11.0),
+            span: Span::detached(),
+        };
+        add_fields(&mut file, &[widget]).unwrap();
+
+        let annot = file.dict((4, 0)).unwrap();
+        assert!(annot.is("Subtype", "Widget"));
+        assert!(annot.is("FT", "Btn"));
+        assert!(annot.is("AS", "Yes"));
+        assert_eq!(annot.get("P"), Some(&Object::Ref((3, 0))));
+        assert_eq!(annot.get("A"), None);
+        let form = file.catalog().unwrap().get("AcroForm").unwrap();
+        assert_eq!(form, &parse_object(b"<< /Fields [4 0 R] >>").unwrap());
+    }
+}
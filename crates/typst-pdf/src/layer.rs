@@ -0,0 +1,300 @@
+//! Layers, also known as optional content groups.
+//!
+//! krilla can't write optional content. Instead, the content of each layer is
+//! drawn in an isolated group, which krilla writes as a form XObject. The
+//! group also draws an empty page of a tiny document, the beacon, whose
+//! properties contain the layer's optional content group. Once the file is
+//! finished, each form XObject that draws a beacon is marked as optional
+//! content of the beacon's group.
+
+use std::sync::Arc;
+
+use ecow::eco_vec;
+use krilla::pdf::PdfDocument;
+use krilla::surface::Surface;
+use pdf_writer::{Finish, Name, Rect, TextStr};
+use rustc_hash::FxHashMap;
+use typst_library::diag::{SourceResult, StrResult, bail, error};
+use typst_library::pdf::Layer;
+use typst_syntax::Span;
+
+use crate::convert::GlobalContext;
+use crate::rewrite::{Dict, File, Object, Ref};
+
+/// Starts the content of a layer by pushing an isolated group, which must be
+/// popped once the content is done.
+pub(crate) fn push_layer(
+    gc: &mut GlobalContext,
+    surface: &mut Surface,
+    layer: &Layer,
+) -> SourceResult<()> {
+    let standard = match gc.options.standards.pdfx {
+        Some(pdfx) => Some(pdfx.as_str()),
+        None if gc.options.is_pdf_a() || gc.options.is_pdf_ua() => {
+            Some(gc.options.standards.config.validator().as_str())
+        }
+        None => None,
+    };
+    if let Some(standard) = standard {
+        bail!(layer.span, "layers are not supported in {standard}");
+    }
+
+    let index = match gc.layers.iter().position(|other| other.name == layer.name) {
+        Some(index) => {
+            let other = &gc.layers[index];
+            if (other.visible, other.print) != (layer.visible, layer.print) {
+                bail!(
+                    layer.span,
+                    "layer `{}` was already used with different settings",
+                    layer.name;
+                    hint: "layers with the same name must have the same settings"
+                );
+            }
+            index
+        }
+        None => {
+            gc.layers.push(layer.clone());
+            gc.layers.len() - 1
+        }
+    };
+
+    surface.push_isolated();
+    let size = krilla::geom::Size::from_wh(1.0, 1.0).unwrap();
+    surface.draw_pdf_page(&convert_beacon(index, layer), size, 0);
+    Ok(())
+}
+
+/// Marks the groups of the layers as optional content.
+pub(crate) fn apply(file: &mut File, layers: &[Layer]) -> SourceResult<()> {
+    add_layers(file, layers).map_err(|err| {
+        eco_vec![error!(
+            Span::detached(),
+            "failed to write layers ({err})";
+            hint: "please report this as a bug"
+        )]
+    })
+}
+
+/// Sets the optional content of the groups and adds the optional content
+/// properties to the document catalog.
+fn add_layers(file: &mut File, layers: &[Layer]) -> StrResult<()> {
+    // The beacons refer to the optional content groups of their layers.
+    let mut beacons = FxHashMap::default();
+    for id in file.refs() {
+        let Some(properties) = resource(file, id, "Properties")? else { continue };
+        for (key, ocg) in properties.iter() {
+            if let Some(index) = parse_name(key, layers.len())
+                && let Some(ocg) = ocg.as_reference()
+                && file.get(ocg)?.as_dict().is_some_and(|ocg| ocg.is("Type", "OCG"))
+            {
+                beacons.insert(id, (index, ocg));
+            }
+        }
+    }
+
+    let mut groups = vec![];
+    for id in file.refs() {
+        let Some(xobjects) = resource(file, id, "XObject")? else { continue };
+        let beacon = xobjects
+            .iter()
+            .find_map(|(_, xobject)| beacons.get(&xobject.as_reference()?));
+        if let Some(&(_, ocg)) = beacon {
+            groups.push((id, ocg));
+        }
+    }
+
+    for (id, ocg) in groups {
+        let Object::Stream(stream) = file.get_mut(id)? else { unreachable!() };
+        stream.dict.set("OC", ocg);
+    }
+
+    let mut used: Vec<(usize, Ref)> = beacons.into_values().collect();
+    used.sort();
+    used.dedup();
+    if !used.is_empty() {
+        file.catalog_mut()?.set("OCProperties", oc_properties(layers, &used));
+    }
+
+    Ok(())
+}
+
+/// Returns an entry of the resources of a form XObject.
+fn resource<'a>(file: &'a File, id: Ref, key: &str) -> StrResult<Option<&'a Dict>> {
+    let Object::Stream(stream) = file.get(id)? else { return Ok(None) };
+    if !stream.dict.is("Subtype", "Form") {
+        return Ok(None);
+    }
+
+    let Some(resources) = stream.dict.get("Resources") else { return Ok(None) };
+    let Object::Dict(resources) = file.resolve(resources)? else {
+        bail!("malformed resources");
+    };
+    let Some(entry) = resources.get(key) else { return Ok(None) };
+    match file.resolve(entry)? {
+        Object::Dict(entry) => Ok(Some(entry)),
+        _ => bail!("malformed resources"),
+    }
+}
+
+/// Builds the optional content properties of the document catalog.
+///
+/// Viewers apply the print state automatically when printing because of the
+/// usage application dictionaries.
+fn oc_properties(layers: &[Layer], used: &[(usize, Ref)]) -> Dict {
+    let refs = |filter: &dyn Fn(&Layer) -> bool| {
+        let refs = used.iter().filter(|&&(i, _)| filter(&layers[i]));
+        Object::Array(refs.map(|&(_, ocg)| Object::Ref(ocg)).collect())
+    };
+
+    let all = refs(&|_| true);
+    let event = |event: &str| {
+        Dict::new()
+            .with("Event", Object::name(event))
+            .with("OCGs", all.clone())
+            .with("Category", vec![Object::name(event)])
+    };
+    let config = Dict::new()
+        .with("Order", all.clone())
+        .with("OFF", refs(&|layer| !layer.visible))
+        .with("AS", vec![event("View").into(), event("Print").into()]);
+    Dict::new().with("OCGs", all.clone()).with("D", config)
+}
+
+#[comemo::memoize]
+fn convert_beacon(index: usize, layer: &Layer) -> PdfDocument {
+    let pdf = hayro_syntax::Pdf::new(Arc::new(write_beacon(index, layer)))
+        .expect("written beacon should be readable");
+    PdfDocument::new(Arc::new(pdf))
+}
+
+/// Writes a document with an empty page whose properties contain the
+/// optional content group of a layer.
+fn write_beacon(index: usize, layer: &Layer) -> Vec<u8> {
+    let catalog_id = pdf_writer::Ref::new(1);
+    let pages_id = pdf_writer::Ref::new(2);
+    let page_id = pdf_writer::Ref::new(3);
+    let content_id = pdf_writer::Ref::new(4);
+    let ocg_id = pdf_writer::Ref::new(5);
+
+    // The version may not exceed the one of the exported document.
+    let mut pdf = pdf_writer::Pdf::new();
+    pdf.set_version(1, 3);
+    pdf.catalog(catalog_id).pages(pages_id);
+    pdf.pages(pages_id).kids([page_id]).count(1);
+
+    let mut page = pdf.page(page_id);
+    page.parent(pages_id)
+        .media_box(Rect::new(0.0, 0.0, 1.0, 1.0))
+        .contents(content_id);
+    page.resources()
+        .insert(Name(b"Properties"))
+        .dict()
+        .pair(Name(name(index).as_bytes()), ocg_id);
+    page.finish();
+    pdf.stream(content_id, &[]);
+
+    let state = |on| Name(if on { b"ON".as_slice() } else { b"OFF" });
+    let mut ocg = pdf.indirect(ocg_id).dict();
+    ocg.pair(Name(b"Type"), Name(b"OCG"));
+    ocg.pair(Name(b"Name"), TextStr(&layer.name));
+    let mut usage = ocg.insert(Name(b"Usage")).dict();
+    usage
+        .insert(Name(b"View"))
+        .dict()
+        .pair(Name(b"ViewState"), state(layer.visible));
+    usage
+        .insert(Name(b"Print"))
+        .dict()
+        .pair(Name(b"PrintState"), state(layer.print));
+    usage.finish();
+    ocg.finish();
+
+    pdf.finish()
+}
+
+/// The name of a layer in the beacon's properties.
+fn name(index: usize) -> String {
+    format!("oc{index}")
+}
+
+/// Parses the name of a layer in a beacon's properties.
+fn parse_name(name: &[u8], count: usize) -> Option<usize> {
+    let index = std::str::from_utf8(name.strip_prefix(b"oc")?).ok()?.parse().ok()?;
+    (index < count).then_some(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rewrite::{file, parse_object};
+
+    fn layer(name: &str, visible: bool) -> Layer {
+        Layer {
+            name: name.into(),
+            visible,
+            print: true,
+            span: Span::detached(),
+        }
+    }
+
+    #[test]
+    fn test_beacon() {
+        let mut file = File::read(&write_beacon(2, &layer("Notes", false))).unwrap();
+        let page = file.dict_mut((3, 0)).unwrap();
+        assert_eq!(
+            page.get("Resources").unwrap(),
+            &parse_object(b"<< /Properties << /oc2 5 0 R >> >>").unwrap()
+        );
+        assert_eq!(
+            file.dict_mut((5, 0)).unwrap(),
+            parse_object(
+                b"<< /Type /OCG /Name (Notes) /Usage << /View << /ViewState /OFF >> \
+                  /Print << /PrintState /ON >> >> >>"
+            )
+            .unwrap()
+            .as_dict()
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_add_layers() {
+        let bytes = file(&[
+            b"<< /Type /Catalog /Pages 6 0 R >>",
+            b"<< /Type /XObject /Subtype /Form /Resources \
+              << /XObject << /x0 3 0 R /x1 5 0 R >> >> /Length 0 >>\nstream\n\nendstream",
+            b"<< /Type /XObject /Subtype /Form /Resources \
+              << /Properties << /oc1 4 0 R >> >> /Length 0 >>\nstream\n\nendstream",
+            b"<< /Type /OCG /Name (Notes) >>",
+            b"<< /Type /XObject /Subtype /Form /Length 0 >>\nstream\n\nendstream",
+            b"<< /Type /Pages /Kids [] /Count 0 >>",
+        ]);
+        let mut file = File::read(&bytes).unwrap();
+        add_layers(&mut file, &[layer("Draft", true), layer("Notes", false)]).unwrap();
+
+        let Object::Stream(group) = file.get((2, 0)).unwrap() else { panic!() };
+        assert_eq!(group.dict.get("OC"), Some(&Object::Ref((4, 0))));
+        for id in [3, 5] {
+            let Object::Stream(other) = file.get((id, 0)).unwrap() else { panic!() };
+            assert_eq!(other.dict.get("OC"), None);
+        }
+
+        let catalog = file.catalog_mut().unwrap();
+        assert_eq!(
+            catalog.get("OCProperties").unwrap(),
+            &parse_object(
+                b"<< /OCGs [4 0 R] /D << /Order [4 0 R] /OFF [4 0 R] /AS [\
+                  << /Event /View /OCGs [4 0 R] /Category [/View] >> \
+                  << /Event /Print /OCGs [4 0 R] /Category [/Print] >>] >> >>"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_name() {
+        assert_eq!(parse_name(name(3).as_bytes(), 4), Some(3));
+        assert_eq!(parse_name(b"oc4", 4), None);
+        assert_eq!(parse_name(b"P0", 4), None);
+    }
+}
@@ -1,14 +1,22 @@
 //! Exporting Typst documents to PDF.
 
+mod annotate;
 mod attach;
+mod color;
 mod convert;
+mod encrypt;
+mod form;
 mod image;
+mod layer;
 mod link;
 mod metadata;
 mod outline;
 mod page;
 mod paint;
+mod pdfx;
+mod rewrite;
 mod shape;
+mod sign;
 mod tags;
 mod text;
 mod util;
@@ -16,13 +24,16 @@ mod util;
 pub use self::metadata::{Timestamp, Timezone};
 
 use std::fmt::{self, Debug, Formatter};
+use std::num::NonZeroUsize;
 
-use ecow::eco_format;
+use ecow::{EcoString, eco_format};
 use krilla::configure::Validator;
 use serde::{Deserialize, Serialize};
 use typst_library::diag::{SourceResult, StrResult, bail};
-use typst_library::foundations::Smart;
-use typst_library::layout::{PageRanges, PagedDocument};
+use typst_library::foundations::{Bytes, Smart};
+use typst_library::layout::{PageRanges, PagedDocument, Point, Size};
+
+use crate::pdfx::PdfX;
 
 /// Export a document into a PDF file.
 ///
@@ -66,6 +77,16 @@ pub struct PdfOptions<'a> {
     /// circumstances, for example when trying to reduce the size of a document,
     /// it can be desirable to disable tagged PDF.
     pub tagged: bool,
+    /// The printing condition the document is prepared for. Required when
+    /// exporting to one of the PDF/X standards and ignored otherwise.
+    pub output_intent: Option<OutputIntent>,
+    /// If not `None`, the document is encrypted with AES-256 and protected by
+    /// the given passwords. Encryption is forbidden by PDF/A and PDF/X and
+    /// requires PDF 1.7 or higher.
+    pub encryption: Option<PdfEncryption>,
+    /// If not `None`, the document is digitally signed. The signing time is
+    /// taken from `timestamp`. Signing can't be combined with encryption.
+    pub signature: Option<PdfSignature>,
 }
 
 impl PdfOptions<'_> {
@@ -74,6 +95,24 @@ impl PdfOptions<'_> {
     pub(crate) fn is_pdf_ua(&self) -> bool {
         self.standards.config.validator() == Validator::UA1
     }
+
+    /// Whether the current export mode is one of the parts of PDF/A.
+    pub(crate) fn is_pdf_a(&self) -> bool {
+        matches!(
+            self.standards.config.validator(),
+            Validator::A1_A
+                | Validator::A1_B
+                | Validator::A2_A
+                | Validator::A2_B
+                | Validator::A2_U
+                | Validator::A3_A
+                | Validator::A3_B
+                | Validator::A3_U
+                | Validator::A4
+                | Validator::A4F
+                | Validator::A4E
+        )
+    }
 }
 
 impl Default for PdfOptions<'_> {
@@ -84,14 +123,90 @@ impl Default for PdfOptions<'_> {
             page_ranges: None,
             standards: PdfStandards::default(),
             tagged: true,
+            output_intent: None,
+            encryption: None,
+            signature: None,
         }
     }
 }
 
+/// The printing condition a PDF/X document is prepared for.
+#[derive(Debug, Clone)]
+pub struct OutputIntent {
+    /// An identifier of the printing condition, preferably one from the ICC's
+    /// registry of characterized printing conditions like `FOGRA39`.
+    pub condition: EcoString,
+    /// An ICC profile that describes the printing condition.
+    pub profile: Bytes,
+}
+
+/// The passwords and permissions of an encrypted PDF document.
+#[derive(Debug, Clone)]
+pub struct PdfEncryption {
+    /// The password needed to open the document. If empty, anyone can open
+    /// the document, but the permissions still apply.
+    pub user_password: EcoString,
+    /// The password that lifts the restrictions of the permissions. If empty,
+    /// a random one is used, so that the restrictions can't be lifted.
+    pub owner_password: EcoString,
+    /// What readers may do with the document without the owner password. Note
+    /// that it's up to the PDF viewer to respect them.
+    pub permissions: PdfPermissions,
+}
+
+/// What readers may do with an encrypted PDF document.
+///
+/// Extracting text for accessibility purposes is always permitted.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PdfPermissions {
+    /// Whether the document may be printed.
+    pub print: bool,
+    /// Whether text and graphics may be copied.
+    pub copy: bool,
+    /// Whether the document may be modified, annotated, and its forms filled
+    /// in.
+    pub modify: bool,
+}
+
+impl Default for PdfPermissions {
+    fn default() -> Self {
+        Self { print: true, copy: true, modify: true }
+    }
+}
+
+/// A digital signature that is added to a PDF document.
+#[derive(Debug, Clone)]
+pub struct PdfSignature {
+    /// A PKCS#12 file with the private key and certificate chain of the
+    /// signer. RSA, P-256, and P-384 keys are supported.
+    pub certificate: Bytes,
+    /// The password of the PKCS#12 file.
+    pub password: EcoString,
+    /// Why the document is signed, e.g. to approve it.
+    pub reason: Option<EcoString>,
+    /// Where the document is signed, e.g. a city.
+    pub location: Option<EcoString>,
+    /// Where the signature is shown. If `None`, the signature is invisible.
+    pub field: Option<PdfSignatureField>,
+}
+
+/// The placement of a visible signature.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PdfSignatureField {
+    /// The one-based number of the page in the exported PDF.
+    pub page: NonZeroUsize,
+    /// The top-left corner of the field, relative to the top-left corner of
+    /// the page.
+    pub pos: Point,
+    /// The size of the field.
+    pub size: Size,
+}
+
 /// Encapsulates a list of compatible PDF standards.
 #[derive(Clone)]
 pub struct PdfStandards {
     pub(crate) config: krilla::configure::Configuration,
+    pub(crate) pdfx: Option<PdfX>,
 }
 
 impl PdfStandards {
@@ -122,6 +237,15 @@ impl PdfStandards {
             Ok(())
         };
 
+        let mut pdfx = None;
+        let mut set_pdfx = |v: PdfX| -> StrResult<()> {
+            if pdfx.is_some() {
+                bail!("Typst currently only supports one PDF substandard at a time");
+            }
+            pdfx = Some(v);
+            Ok(())
+        };
+
         for standard in list {
             match standard {
                 PdfStandard::V_1_4 => set_version(PdfVersion::Pdf14)?,
@@ -141,6 +265,24 @@ impl PdfStandards {
                 PdfStandard::A_4f => set_validator(Validator::A4F)?,
                 PdfStandard::A_4e => set_validator(Validator::A4E)?,
                 PdfStandard::Ua_1 => set_validator(Validator::UA1)?,
+                PdfStandard::X_1a => set_pdfx(PdfX::X1a)?,
+                PdfStandard::X_3 => set_pdfx(PdfX::X3)?,
+                PdfStandard::X_4 => set_pdfx(PdfX::X4)?,
+            }
+        }
+
+        // PDF/X is checked by us and not by krilla, so it can't be combined
+        // with krilla's validators. It instead determines the version.
+        if let Some(pdfx) = pdfx {
+            if validator.is_some() {
+                bail!("Typst currently only supports one PDF substandard at a time");
+            }
+
+            match version {
+                Some(v) if v != pdfx.version() => {
+                    bail!("{} is not compatible with {}", v.as_str(), pdfx.as_str())
+                }
+                _ => version = Some(pdfx.version()),
             }
         }
 
@@ -159,7 +301,7 @@ impl PdfStandards {
             (None, None) => Configuration::new_with_version(PdfVersion::Pdf17),
         };
 
-        Ok(Self { config })
+        Ok(Self { config, pdfx })
     }
 }
 
@@ -174,6 +316,7 @@ impl Default for PdfStandards {
         use krilla::configure::{Configuration, PdfVersion};
         Self {
             config: Configuration::new_with_version(PdfVersion::Pdf17),
+            pdfx: None,
         }
     }
 }
@@ -236,4 +379,13 @@ pub enum PdfStandard {
     /// PDF/UA-1.
     #[serde(rename = "ua-1")]
     Ua_1,
+    /// PDF/X-1a:2003.
+    #[serde(rename = "x-1a")]
+    X_1a,
+    /// PDF/X-3:2003.
+    #[serde(rename = "x-3")]
+    X_3,
+    /// PDF/X-4.
+    #[serde(rename = "x-4")]
+    X_4,
 }
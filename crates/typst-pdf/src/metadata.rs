@@ -51,7 +51,13 @@ pub(crate) fn build_metadata(gc: &GlobalContext, doc_lang: Option<Locale>) -> Me
 /// (3) Otherwise, we don't write date metadata.
 pub fn creation_date(gc: &GlobalContext) -> Option<krilla::metadata::DateTime> {
     let (datetime, tz) = match (gc.document.info.date, gc.options.timestamp) {
-        (Smart::Custom(Some(date)), _) => (date, None),
+        (Smart::Custom(Some(date)), _) => {
+            let tz = date.utc_offset().and_then(|offset| {
+                Timestamp::new_local(date, offset.whole_minutes().into())
+                    .map(|timestamp| timestamp.timezone)
+            });
+            (date, tz)
+        }
         (Smart::Auto, Some(timestamp)) => (timestamp.datetime, Some(timestamp.timezone)),
         _ => return None,
     };
@@ -82,8 +88,16 @@ pub fn creation_date(gc: &GlobalContext) -> Option<krilla::metadata::DateTime> {
 
     match tz {
         Some(Timezone::UTC) => kd = kd.utc_offset_hour(0).utc_offset_minute(0),
-        Some(Timezone::Local { hour_offset, minute_offset }) => {
-            kd = kd.utc_offset_hour(hour_offset).utc_offset_minute(minute_offset)
+        Some(Timezone::Local { offset }) => {
+            let (hours, minutes) = (offset / 60, offset % 60);
+            // The sign of the offset is taken from the hours, so offsets like
+            // -00:30 cannot be expressed and are left out instead of flipping
+            // their sign.
+            if hours != 0 || minutes >= 0 {
+                kd = kd
+                    .utc_offset_hour(hours as i8)
+                    .utc_offset_minute(minutes.unsigned_abs() as u8);
+            }
         }
         None => {}
     }
@@ -108,21 +122,11 @@ impl Timestamp {
 
     /// Create a new timestamp with a given datetime, and a local timezone offset.
     pub fn new_local(datetime: Datetime, whole_minute_offset: i32) -> Option<Self> {
-        let hour_offset = (whole_minute_offset / 60).try_into().ok()?;
-        // Note: the `%` operator in Rust is the remainder operator, not the
-        // modulo operator. The remainder operator can return negative results.
-        // We can simply apply `abs` here because we assume the `minute_offset`
-        // will have the same sign as `hour_offset`.
-        let minute_offset = (whole_minute_offset % 60).abs().try_into().ok()?;
-        match (hour_offset, minute_offset) {
-            // Only accept valid timezone offsets with `-23 <= hours <= 23`,
-            // and `0 <= minutes <= 59`.
-            (-23..=23, 0..=59) => Some(Self {
-                datetime,
-                timezone: Timezone::Local { hour_offset, minute_offset },
-            }),
-            _ => None,
-        }
+        // Only accept valid timezone offsets with `-23:59 <= offset <= 23:59`.
+        let offset = i16::try_from(whole_minute_offset)
+            .ok()
+            .filter(|offset| offset.abs() < 24 * 60)?;
+        Some(Self { datetime, timezone: Timezone::Local { offset } })
     }
 }
 
@@ -131,9 +135,18 @@ impl Timestamp {
 pub enum Timezone {
     /// The UTC timezone.
     UTC,
-    /// The local timezone offset from UTC. And the `minute_offset` will have
-    /// same sign as `hour_offset`.
-    Local { hour_offset: i8, minute_offset: u8 },
+    /// The local timezone offset from UTC in minutes.
+    Local { offset: i16 },
+}
+
+impl Timezone {
+    /// The offset from UTC in minutes.
+    pub fn offset(self) -> i16 {
+        match self {
+            Self::UTC => 0,
+            Self::Local { offset } => offset,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -153,18 +166,18 @@ mod tests {
         };
 
         // Valid timezone offsets
-        test(0, Timezone::Local { hour_offset: 0, minute_offset: 0 });
-        test(480, Timezone::Local { hour_offset: 8, minute_offset: 0 });
-        test(-480, Timezone::Local { hour_offset: -8, minute_offset: 0 });
-        test(330, Timezone::Local { hour_offset: 5, minute_offset: 30 });
-        test(-210, Timezone::Local { hour_offset: -3, minute_offset: 30 });
-        test(-720, Timezone::Local { hour_offset: -12, minute_offset: 0 }); // AoE
+        test(0, Timezone::Local { offset: 0 });
+        test(480, Timezone::Local { offset: 480 });
+        test(-480, Timezone::Local { offset: -480 });
+        test(330, Timezone::Local { offset: 330 });
+        test(-210, Timezone::Local { offset: -210 });
+        test(-720, Timezone::Local { offset: -720 }); // AoE
 
         // Corner cases
-        test(315, Timezone::Local { hour_offset: 5, minute_offset: 15 });
-        test(-225, Timezone::Local { hour_offset: -3, minute_offset: 45 });
-        test(1439, Timezone::Local { hour_offset: 23, minute_offset: 59 });
-        test(-1439, Timezone::Local { hour_offset: -23, minute_offset: 59 });
+        test(30, Timezone::Local { offset: 30 });
+        test(-30, Timezone::Local { offset: -30 });
+        test(1439, Timezone::Local { offset: 1439 });
+        test(-1439, Timezone::Local { offset: -1439 });
 
         // Invalid timezone offsets
         assert!(Timestamp::new_local(dummy_datetime, 1440).is_none());
@@ -78,6 +78,9 @@ fn convert_paint(
 
     match paint {
         Paint::Solid(c) => {
+            if let Some(paint) = crate::color::convert_color(gc, c, surface) {
+                return Ok((paint, 255));
+            }
             let (c, a) = convert_solid(c);
             Ok((c.into(), a))
         }
@@ -87,7 +90,20 @@ fn convert_paint(
 }
 
 fn convert_solid(color: &Color) -> (color::Color, u8) {
-    match color.space() {
+    // Spot colors and ICC-based colors that can't be written in their own
+    // color space are written as their equivalent in the corresponding device
+    // color space.
+    let space = match color {
+        Color::Spot(_) => ColorSpace::Cmyk,
+        Color::Icc(icc) => match icc.profile.components() {
+            1 => ColorSpace::D65Gray,
+            4 => ColorSpace::Cmyk,
+            _ => ColorSpace::Srgb,
+        },
+        _ => color.space(),
+    };
+
+    match space {
         ColorSpace::D65Gray => {
             let (c, a) = convert_luma(color);
             (c.into(), a)
@@ -281,7 +297,7 @@ fn convert_gradient_stops(gradient: &Gradient) -> Vec<Stop> {
 
             // Create the individual gradient functions for each pair of stops.
             for window in gradient.stops().windows(2) {
-                let (first, second) = (window[0], window[1]);
+                let (first, second) = (&window[0], &window[1]);
 
                 // If we have a hue index or are using Oklab, we will create several
                 // stops in-between to make the gradient smoother without interpolation
@@ -310,7 +326,8 @@ fn convert_gradient_stops(gradient: &Gradient) -> Vec<Stop> {
             }
 
             for window in conic.stops.windows(2) {
-                let ((c0, t0), (c1, t1)) = (window[0], window[1]);
+                let (ref c0, t0) = window[0];
+                let (ref c1, t1) = window[1];
 
                 // Precision:
                 // - On an even color, insert a stop every 90deg.
@@ -329,7 +346,7 @@ fn convert_gradient_stops(gradient: &Gradient) -> Vec<Stop> {
 
                 // Special casing for sharp gradients.
                 if t0 == t1 {
-                    add_single(&c1, t1);
+                    add_single(c1, t1);
                     continue;
                 }
 
@@ -341,8 +358,8 @@ fn convert_gradient_stops(gradient: &Gradient) -> Vec<Stop> {
 
                     let c_next = Color::mix_iter(
                         [
-                            WeightedColor::new(c0, 1.0 - t(t_next)),
-                            WeightedColor::new(c1, t(t_next)),
+                            WeightedColor::new(c0.clone(), 1.0 - t(t_next)),
+                            WeightedColor::new(c1.clone(), t(t_next)),
                         ],
                         conic.space,
                     )
@@ -352,7 +369,7 @@ fn convert_gradient_stops(gradient: &Gradient) -> Vec<Stop> {
                     t_x = t_next;
                 }
 
-                add_single(&c1, t1);
+                add_single(c1, t1);
             }
         }
     }
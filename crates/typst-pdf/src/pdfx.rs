@@ -0,0 +1,505 @@
+//! Support for the PDF/X family of standards for print production.
+//!
+//! krilla does not know about PDF/X, so we check the requirements that concern
+//! the content of the document ourselves and add the output intent and
+//! identification keys when the finished file is rewritten.
+
+use std::fmt::Write;
+
+use ecow::{EcoString, EcoVec, eco_format, eco_vec};
+use krilla::configure::PdfVersion;
+use rustc_hash::FxHashSet;
+use typst_library::diag::{SourceDiagnostic, SourceResult, StrResult, bail, error};
+use typst_library::layout::{Frame, FrameItem};
+use typst_library::visualize::{Color, ImageKind, Paint};
+use typst_syntax::Span;
+
+use crate::OutputIntent;
+use crate::convert::GlobalContext;
+use crate::rewrite::{Dict, File, Object, Stream};
+
+/// A supported part of the PDF/X standard.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub(crate) enum PdfX {
+    /// PDF/X-1a:2003, for blind exchange of CMYK and spot colour data.
+    X1a,
+    /// PDF/X-3:2003, which additionally allows colour-managed data.
+    X3,
+    /// PDF/X-4, which additionally allows transparency.
+    X4,
+}
+
+impl PdfX {
+    /// The name of the standard as written into the document.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::X1a => "PDF/X-1a:2003",
+            Self::X3 => "PDF/X-3:2003",
+            Self::X4 => "PDF/X-4",
+        }
+    }
+
+    /// The PDF version the standard is based on.
+    pub fn version(self) -> PdfVersion {
+        match self {
+            Self::X1a | Self::X3 => PdfVersion::Pdf14,
+            Self::X4 => PdfVersion::Pdf16,
+        }
+    }
+
+    /// Whether only device-dependent CMYK and gray colours may be used.
+    fn requires_cmyk(self) -> bool {
+        self == Self::X1a
+    }
+
+    /// Whether transparency may be used.
+    fn allows_transparency(self) -> bool {
+        self == Self::X4
+    }
+}
+
+/// Checks the exported pages of the document for conformance with the PDF/X
+/// standard and verifies the output intent.
+pub(crate) fn validate(gc: &GlobalContext, pdfx: PdfX) -> SourceResult<()> {
+    let prefix = eco_format!("{} error:", pdfx.as_str());
+    let mut checker = Checker {
+        pdfx,
+        prefix: prefix.clone(),
+        errors: EcoVec::new(),
+        seen: FxHashSet::default(),
+    };
+
+    match &gc.options.output_intent {
+        Some(intent) => {
+            if let Err(message) = profile_components(intent, pdfx) {
+                checker.errors.push(error!(Span::detached(), "{prefix} {message}"));
+            }
+        }
+        None => checker.errors.push(error!(
+            Span::detached(),
+            "{prefix} missing output intent";
+            hint: "provide an ICC profile for the printing condition with \
+                   `--pdf-output-intent`"
+        )),
+    }
+
+    if gc.document.info.title.is_none() {
+        checker.errors.push(error!(
+            Span::detached(),
+            "{prefix} missing document title";
+            hint: "set the title of the document with `set document(title: [..])`"
+        ));
+    }
+
+    for (i, page) in gc.document.pages.iter().enumerate() {
+        if gc.page_index_converter.pdf_page_index(i).is_some() {
+            checker.frame(&page.frame);
+            if let Some(fill) = page.fill_or_transparent() {
+                checker.paint(&fill, Span::detached());
+            }
+        }
+    }
+
+    if checker.errors.is_empty() { Ok(()) } else { Err(checker.errors) }
+}
+
+/// Walks through frames and collects PDF/X violations.
+struct Checker {
+    pdfx: PdfX,
+    prefix: EcoString,
+    errors: EcoVec<SourceDiagnostic>,
+    /// Spans that already have an error, so that, for example, a paragraph
+    /// in the wrong colour doesn't result in one error per line.
+    seen: FxHashSet<Span>,
+}
+
+impl Checker {
+    fn frame(&mut self, frame: &Frame) {
+        for (_, item) in frame.items() {
+            match item {
+                FrameItem::Group(group) => self.frame(&group.frame),
+                FrameItem::Text(text) => {
+                    let span = text.glyphs.first().map_or(Span::detached(), |g| g.span.0);
+                    self.paint(&text.fill, span);
+                    if let Some(stroke) = &text.stroke {
+                        self.paint(&stroke.paint, span);
+                    }
+                }
+                FrameItem::Shape(shape, span) => {
+                    if let Some(fill) = &shape.fill {
+                        self.paint(fill, *span);
+                    }
+                    if let Some(stroke) = &shape.stroke {
+                        self.paint(&stroke.paint, *span);
+                    }
+                }
+                FrameItem::Image(image, _, span) => match image.kind() {
+                    ImageKind::Raster(raster) => {
+                        let color = raster.dynamic().color();
+                        if self.pdfx.requires_cmyk() && color.channel_count() > 2 {
+                            self.error(
+                                *span,
+                                "the image is not a grayscale image",
+                                "convert the image to grayscale or use a \
+                                 different PDF/X standard",
+                            );
+                        } else if !self.pdfx.allows_transparency() && color.has_alpha() {
+                            self.error(
+                                *span,
+                                "the image has an alpha channel",
+                                "remove the transparency from the image",
+                            );
+                        }
+                    }
+                    ImageKind::Svg(_) if self.pdfx.requires_cmyk() => self.error(
+                        *span,
+                        "SVG images cannot be used in this export mode",
+                        "convert the image to a PDF with CMYK colors",
+                    ),
+                    // Embedded PDFs are copied verbatim and cannot be checked
+                    // here. Making sure they conform is up to the user.
+                    _ => {}
+                },
+                FrameItem::Link(..)
+                | FrameItem::Widget(..)
+                | FrameItem::Annotation(..)
+                | FrameItem::Tag(_) => {}
+            }
+        }
+    }
+
+    fn paint(&mut self, paint: &Paint, span: Span) {
+        match paint {
+            Paint::Solid(color) => self.color(color, span),
+            Paint::Gradient(gradient) => {
+                for (color, _) in gradient.stops_ref() {
+                    self.color(color, span);
+                }
+            }
+            Paint::Tiling(tiling) => self.frame(tiling.frame()),
+        }
+    }
+
+    fn color(&mut self, color: &Color, span: Span) {
+        // ICC-based colors are written in an ICC-based color space, which
+        // PDF/X-1a doesn't allow.
+        let device = matches!(color, Color::Cmyk(_) | Color::Luma(_) | Color::Spot(_));
+
+        if self.pdfx.requires_cmyk() && !device {
+            self.error(
+                span,
+                "the color is not a CMYK or grayscale color",
+                "convert the color with the `cmyk` function",
+            );
+        } else if !self.pdfx.allows_transparency()
+            && color.alpha().is_some_and(|alpha| alpha < 1.0)
+        {
+            self.error(
+                span,
+                "the color is transparent",
+                "use an opaque color or a different PDF/X standard",
+            );
+        }
+    }
+
+    fn error(&mut self, span: Span, message: &str, hint: &str) {
+        if !self.seen.insert(span) {
+            return;
+        }
+
+        let prefix = &self.prefix;
+        self.errors.push(error!(span, "{prefix} {message}"; hint: "{hint}"));
+    }
+}
+
+/// Checks that the ICC profile of an output intent is usable for the given
+/// standard and returns its number of colour components.
+fn profile_components(intent: &OutputIntent, pdfx: PdfX) -> StrResult<u8> {
+    let data = intent.profile.as_slice();
+    if data.len() < 128 || &data[36..40] != b"acsp" {
+        bail!("the output intent is not a valid ICC profile");
+    }
+
+    let components = match &data[16..20] {
+        b"GRAY" => 1,
+        b"RGB " => 3,
+        b"CMYK" => 4,
+        _ => bail!("the output intent must use a gray, RGB, or CMYK color space"),
+    };
+
+    if pdfx.requires_cmyk() && components != 4 {
+        bail!("the output intent must be a CMYK profile");
+    }
+
+    Ok(components)
+}
+
+/// Adds the output intent and the PDF/X identification to a finished PDF file.
+pub(crate) fn apply(
+    file: &mut File,
+    pdfx: PdfX,
+    intent: &OutputIntent,
+) -> SourceResult<()> {
+    add_output_intent(file, pdfx, intent).map_err(|err| {
+        eco_vec![error!(
+            Span::detached(),
+            "failed to write {} output intent ({err})", pdfx.as_str();
+            hint: "please report this as a bug"
+        )]
+    })
+}
+
+/// Adds the output intent to the catalog and the identification to the
+/// document information and the metadata.
+fn add_output_intent(
+    file: &mut File,
+    pdfx: PdfX,
+    intent: &OutputIntent,
+) -> StrResult<()> {
+    let components = profile_components(intent, pdfx)?;
+    let catalog = file.catalog()?;
+    if catalog.get("OutputIntents").is_some() {
+        bail!("document already has an output intent");
+    }
+    let metadata = catalog.get("Metadata").and_then(Object::as_reference);
+
+    // The document information dictionary, extended with the version and
+    // trapping status that PDF/X requires.
+    let info_id = match file.info {
+        Some(info_id) => info_id,
+        None => {
+            let info_id = file.alloc();
+            file.set(info_id, Dict::new());
+            file.info = Some(info_id);
+            info_id
+        }
+    };
+    let info = file.dict_mut(info_id)?;
+    if info.get("GTS_PDFXVersion").is_none() {
+        info.set("GTS_PDFXVersion", Object::text(pdfx.as_str()));
+    }
+    if info.get("Trapped").is_none() {
+        info.set("Trapped", Object::name("False"));
+    }
+
+    // The XMP metadata, which must repeat the identification.
+    if let Some(metadata) = metadata {
+        let Object::Stream(stream) = file.get(metadata)? else {
+            bail!("malformed metadata");
+        };
+        let stream = update_xmp(stream, pdfx)?;
+        file.set(metadata, stream);
+    }
+
+    // The ICC profile of the printing condition.
+    let profile_id = file.alloc();
+    let profile = Stream::new(
+        Dict::new().with("N", i32::from(components)),
+        intent.profile.as_slice().to_vec(),
+    );
+    file.set(profile_id, profile);
+
+    // The output intent dictionary.
+    let intent_id = file.alloc();
+    let condition = Object::text(&intent.condition);
+    let dict = Dict::new()
+        .with("Type", Object::name("OutputIntent"))
+        .with("S", Object::name("GTS_PDFX"))
+        .with("OutputConditionIdentifier", condition.clone())
+        .with("Info", condition)
+        .with("RegistryName", Object::text("http://www.color.org"))
+        .with("DestOutputProfile", profile_id);
+    file.set(intent_id, dict);
+
+    file.catalog_mut()?.set("OutputIntents", vec![intent_id.into()]);
+    Ok(())
+}
+
+/// Adds the PDF/X identification to an XMP metadata stream.
+fn update_xmp(stream: &Stream, pdfx: PdfX) -> StrResult<Stream> {
+    let data = stream.decode()?;
+    let xml = std::str::from_utf8(&data)
+        .map_err(|_| "metadata is not valid UTF-8")?
+        .trim_end();
+
+    let pos = xml.find("</rdf:RDF>").ok_or("malformed XMP metadata")?;
+    let mut description = String::from(
+        "<rdf:Description rdf:about=\"\" \
+         xmlns:pdfxid=\"http://www.npes.org/pdfx/ns/id/\"",
+    );
+    if !xml.contains("pdf:Trapped") {
+        description.push_str(" xmlns:pdf=\"http://ns.adobe.com/pdf/1.3/\">");
+        description.push_str("<pdf:Trapped>False</pdf:Trapped>");
+    } else {
+        description.push('>');
+    }
+    write!(
+        description,
+        "<pdfxid:GTS_PDFXVersion>{}</pdfxid:GTS_PDFXVersion></rdf:Description>",
+        pdfx.as_str()
+    )
+    .unwrap();
+
+    // Metadata must stay uncompressed, so that it can be read without
+    // understanding PDF.
+    let xml = format!("{}{description}{}", &xml[..pos], &xml[pos..]);
+    let mut dict = stream.dict.clone();
+    dict.remove("Filter");
+    dict.remove("DecodeParms");
+    Ok(Stream::new(dict, xml.into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use typst_library::foundations::{Bytes, Content, Smart};
+    use typst_library::layout::{Abs, Frame, Page, PagedDocument, Sides, Size};
+
+    use super::*;
+    use crate::rewrite::file;
+    use crate::{PdfOptions, PdfStandard, PdfStandards};
+
+    /// A minimal ICC profile header for the given colour space.
+    fn profile(space: &[u8; 4]) -> Bytes {
+        let mut data = vec![0; 128];
+        data[16..20].copy_from_slice(space);
+        data[36..40].copy_from_slice(b"acsp");
+        Bytes::new(data)
+    }
+
+    fn intent(space: &[u8; 4]) -> OutputIntent {
+        OutputIntent {
+            condition: "FOGRA39".into(),
+            profile: profile(space),
+        }
+    }
+
+    /// Exports a blank page with bleed and slug.
+    fn export(standard: PdfStandard) -> Vec<u8> {
+        let page = Page {
+            frame: Frame::hard(Size::new(Abs::pt(130.0), Abs::pt(230.0))),
+            fill: Smart::Auto,
+            numbering: None,
+            supplement: Content::empty(),
+            number: 1,
+            bleed: Sides::splat(Abs::pt(10.0)),
+            slug: Sides::splat(Abs::pt(5.0)),
+        };
+        let mut document = PagedDocument { pages: vec![page], ..Default::default() };
+        document.info.title = Some("Test".into());
+        let options = PdfOptions {
+            standards: PdfStandards::new(&[standard]).unwrap(),
+            output_intent: Some(intent(b"CMYK")),
+            ..Default::default()
+        };
+        crate::pdf(&document, &options).unwrap()
+    }
+
+    #[test]
+    fn test_export() {
+        let bytes = export(PdfStandard::X_1a);
+        assert!(bytes.starts_with(b"%PDF-1.4"));
+        let count =
+            |needle: &[u8]| bytes.windows(needle.len()).filter(|w| w == &needle).count();
+        assert_eq!(count(b"%%EOF"), 1);
+        let file = File::read(&bytes).unwrap();
+
+        // The output intent with the embedded profile.
+        let catalog = file.catalog().unwrap();
+        let intents = catalog.get("OutputIntents").unwrap().as_array().unwrap();
+        assert_eq!(intents.len(), 1);
+        let intent = file.resolve(&intents[0]).unwrap().as_dict().unwrap();
+        assert!(intent.is("S", "GTS_PDFX"));
+        assert_eq!(
+            intent.get("OutputConditionIdentifier").unwrap().as_string(),
+            Some(&b"FOGRA39"[..])
+        );
+        let profile = file.resolve(intent.get("DestOutputProfile").unwrap()).unwrap();
+        let Object::Stream(profile) = profile else { panic!() };
+        assert_eq!(profile.dict.get("N").unwrap().as_int(), Some(4));
+        assert_eq!(profile.decode().unwrap(), self::profile(b"CMYK").as_slice());
+
+        // The identification in the document information and the metadata,
+        // which only exists once.
+        let info = file.dict(file.info.unwrap()).unwrap();
+        assert_eq!(
+            info.get("GTS_PDFXVersion").unwrap().as_string(),
+            Some(&b"PDF/X-1a:2003"[..])
+        );
+        assert!(info.is("Trapped", "False"));
+        let metadata = file.resolve(catalog.get("Metadata").unwrap()).unwrap();
+        let Object::Stream(metadata) = metadata else { panic!() };
+        let xml = String::from_utf8(metadata.decode().unwrap()).unwrap();
+        assert!(
+            xml.contains(
+                "<pdfxid:GTS_PDFXVersion>PDF/X-1a:2003</pdfxid:GTS_PDFXVersion>"
+            )
+        );
+        assert_eq!(count(b"<x:xmpmeta"), 1);
+
+        // The page boxes, which exclude the slug and the bleed.
+        let pages = file.resolve(catalog.get("Pages").unwrap()).unwrap();
+        let kids = pages.as_dict().unwrap().get("Kids").unwrap().as_array().unwrap();
+        let page = file.resolve(&kids[0]).unwrap().as_dict().unwrap();
+        let numbers = |key| page.get(key).unwrap().as_numbers().unwrap();
+        assert_eq!(numbers("MediaBox"), [0.0, 0.0, 130.0, 230.0]);
+        assert_eq!(numbers("BleedBox"), [5.0, 5.0, 125.0, 225.0]);
+        assert_eq!(numbers("TrimBox"), [15.0, 15.0, 115.0, 215.0]);
+    }
+
+    #[test]
+    fn test_export_version() {
+        assert!(export(PdfStandard::X_3).starts_with(b"%PDF-1.4"));
+        assert!(export(PdfStandard::X_4).starts_with(b"%PDF-1.6"));
+        assert!(PdfStandards::new(&[PdfStandard::X_4, PdfStandard::V_1_7]).is_err());
+        assert!(PdfStandards::new(&[PdfStandard::X_4, PdfStandard::A_2b]).is_err());
+    }
+
+    #[test]
+    fn test_profile_components() {
+        assert_eq!(profile_components(&intent(b"CMYK"), PdfX::X1a), Ok(4));
+        assert_eq!(profile_components(&intent(b"RGB "), PdfX::X3), Ok(3));
+        assert_eq!(profile_components(&intent(b"GRAY"), PdfX::X4), Ok(1));
+        assert!(profile_components(&intent(b"RGB "), PdfX::X1a).is_err());
+        assert!(profile_components(&intent(b"Lab "), PdfX::X4).is_err());
+        let invalid = OutputIntent {
+            condition: "".into(),
+            profile: Bytes::new(vec![0; 64]),
+        };
+        assert!(profile_components(&invalid, PdfX::X4).is_err());
+    }
+
+    #[test]
+    fn test_add_output_intent() {
+        let xmp = b"<x:xmpmeta><rdf:RDF></rdf:RDF></x:xmpmeta>";
+        let mut metadata =
+            b"<< /Type /Metadata /Subtype /XML /Length 42 >>\nstream\n".to_vec();
+        metadata.extend_from_slice(xmp);
+        metadata.extend_from_slice(b"\nendstream");
+        let bytes = file(&[
+            b"<< /Type /Catalog /Pages 3 0 R /Metadata 2 0 R >>",
+            &metadata,
+            b"<< /Type /Pages /Kids [] /Count 0 >>",
+        ]);
+        let mut file = File::read(&bytes).unwrap();
+        add_output_intent(&mut file, PdfX::X4, &intent(b"CMYK")).unwrap();
+
+        let info = file.dict(file.info.unwrap()).unwrap();
+        assert_eq!(
+            info.get("GTS_PDFXVersion").unwrap().as_string(),
+            Some(&b"PDF/X-4"[..])
+        );
+        let Object::Stream(metadata) = file.get((2, 0)).unwrap() else { panic!() };
+        assert_eq!(
+            std::str::from_utf8(&metadata.data).unwrap(),
+            "<x:xmpmeta><rdf:RDF><rdf:Description rdf:about=\"\" \
+             xmlns:pdfxid=\"http://www.npes.org/pdfx/ns/id/\" \
+             xmlns:pdf=\"http://ns.adobe.com/pdf/1.3/\">\
+             <pdf:Trapped>False</pdf:Trapped>\
+             <pdfxid:GTS_PDFXVersion>PDF/X-4</pdfxid:GTS_PDFXVersion>\
+             </rdf:Description></rdf:RDF></x:xmpmeta>"
+        );
+
+        // An existing output intent is not replaced.
+        assert!(add_output_intent(&mut file, PdfX::X4, &intent(b"CMYK")).is_err());
+    }
+}
@@ -0,0 +1,786 @@
+//! Rewriting a finished PDF file.
+//!
+//! krilla cannot write everything that Typst supports, so the file that it
+//! has produced is read with hayro-syntax, amended and written again with
+//! pdf-writer. All features work on the same [`File`], so that it is only
+//! read and written once.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::ops::Range;
+use std::sync::Arc;
+
+use flate2::read::ZlibDecoder;
+use hayro_syntax::object::{self as hayro, MaybeRef};
+use hayro_syntax::reader::Reader;
+use hayro_syntax::xref::XRef;
+use pdf_writer::{Chunk, Name, Str};
+use typst_library::diag::{StrResult, bail};
+
+/// The objects of a PDF file.
+pub(crate) struct File {
+    /// The objects, by ascending reference.
+    objects: BTreeMap<Ref, Object>,
+    /// The document catalog.
+    pub root: Ref,
+    /// The document information dictionary.
+    pub info: Option<Ref>,
+    /// The two parts of the file identifier.
+    pub id: Option<(Vec<u8>, Vec<u8>)>,
+    /// The encryption dictionary, which is referenced from the trailer.
+    pub encrypt: Option<Ref>,
+    /// The version in the header.
+    version: (u8, u8),
+    /// The number of the next new object.
+    next: i32,
+}
+
+impl File {
+    /// Reads all objects of a file.
+    pub fn read(bytes: &[u8]) -> StrResult<Self> {
+        let pdf = hayro_syntax::Pdf::new(Arc::new(bytes.to_vec()))
+            .map_err(|_| "malformed file")?;
+        let trailer = read_trailer(bytes)?;
+        let size = trailer
+            .get("Size")
+            .and_then(Object::as_int)
+            .and_then(|v| i32::try_from(v).ok())
+            .ok_or("missing trailer size")?;
+        let root = trailer
+            .get("Root")
+            .and_then(Object::as_reference)
+            .ok_or("missing document catalog")?;
+        let id = match trailer.get("ID").and_then(Object::as_array) {
+            Some([Object::String(a), Object::String(b)]) => Some((a.clone(), b.clone())),
+            Some(_) => bail!("malformed file identifier"),
+            None => None,
+        };
+
+        // krilla numbers its objects consecutively and never reuses them.
+        let mut objects = BTreeMap::new();
+        for number in 1..size {
+            if let Some(object) = read_object(pdf.xref(), number) {
+                objects.insert((number, 0), object);
+            }
+        }
+        if !objects.contains_key(&root) {
+            bail!("missing document catalog");
+        }
+
+        Ok(Self {
+            objects,
+            root,
+            info: trailer.get("Info").and_then(Object::as_reference),
+            id,
+            encrypt: None,
+            version: read_version(bytes).ok_or("malformed header")?,
+            next: size,
+        })
+    }
+
+    /// Writes the file.
+    pub fn write(&self) -> Vec<u8> {
+        self.write_with(|_, _| {})
+    }
+
+    /// Writes the file and reports the byte range of each indirect object.
+    pub fn write_with(&self, mut written: impl FnMut(Ref, Range<usize>)) -> Vec<u8> {
+        let mut pdf = pdf_writer::Pdf::new();
+        pdf.set_version(self.version.0, self.version.1);
+        if let Some(id) = &self.id {
+            pdf.set_file_id(id.clone());
+        }
+
+        for (&id, object) in &self.objects {
+            let start = pdf.len();
+            match object {
+                // The catalog and the information dictionary are written
+                // through pdf-writer, so that the trailer references them.
+                // The catalog's type is written by pdf-writer.
+                Object::Dict(dict) if id == self.root => {
+                    write_entries(&mut pdf.catalog(writer_ref(id)), dict, Some("Type"));
+                }
+                Object::Dict(dict) if Some(id) == self.info => {
+                    write_entries(&mut pdf.document_info(writer_ref(id)), dict, None);
+                }
+                _ => write_indirect(&mut pdf, id, object),
+            }
+            written(id, start..pdf.len());
+        }
+
+        let mut bytes = pdf.finish();
+
+        // pdf-writer cannot reference the encryption dictionary from the
+        // trailer, so the entry is added to the written trailer.
+        if let Some((id, _)) = self.encrypt
+            && let Some(pos) = rfind(&bytes, b"trailer\n<<")
+        {
+            let entry = format!("\n  /Encrypt {id} 0 R");
+            let pos = pos + b"trailer\n<<".len();
+            bytes.splice(pos..pos, entry.into_bytes());
+        }
+
+        bytes
+    }
+
+    /// The references to all objects, by ascending number.
+    pub fn refs(&self) -> impl Iterator<Item = Ref> + '_ {
+        self.objects.keys().copied()
+    }
+
+    /// Returns an object.
+    pub fn get(&self, id: Ref) -> StrResult<&Object> {
+        match self.objects.get(&id) {
+            Some(object) => Ok(object),
+            None => bail!("missing object {}", id.0),
+        }
+    }
+
+    /// Returns a dictionary.
+    pub fn dict(&self, id: Ref) -> StrResult<&Dict> {
+        match self.get(id)?.as_dict() {
+            Some(dict) => Ok(dict),
+            None => bail!("object {} is not a dictionary", id.0),
+        }
+    }
+
+    /// Returns the document catalog.
+    pub fn catalog(&self) -> StrResult<&Dict> {
+        self.dict(self.root)
+    }
+
+    /// Resolves a reference. Returns other objects as they are.
+    pub fn resolve<'a>(&'a self, object: &'a Object) -> StrResult<&'a Object> {
+        match object {
+            Object::Ref(id) => self.get(*id),
+            _ => Ok(object),
+        }
+    }
+
+    /// Returns an object mutably.
+    pub fn get_mut(&mut self, id: Ref) -> StrResult<&mut Object> {
+        match self.objects.get_mut(&id) {
+            Some(object) => Ok(object),
+            None => bail!("missing object {}", id.0),
+        }
+    }
+
+    /// Returns a dictionary mutably.
+    pub fn dict_mut(&mut self, id: Ref) -> StrResult<&mut Dict> {
+        match self.get_mut(id)?.as_dict_mut() {
+            Some(dict) => Ok(dict),
+            None => bail!("object {} is not a dictionary", id.0),
+        }
+    }
+
+    /// Returns the document catalog mutably.
+    pub fn catalog_mut(&mut self) -> StrResult<&mut Dict> {
+        self.dict_mut(self.root)
+    }
+
+    /// Finds the page with the given one-based number in the page tree.
+    pub fn page(&self, number: usize) -> StrResult<Ref> {
+        let mut node = self
+            .catalog()?
+            .get("Pages")
+            .and_then(Object::as_reference)
+            .ok_or("missing page tree")?;
+        let mut skip = number.checked_sub(1).ok_or("invalid page number")?;
+        'descend: loop {
+            let dict = self.dict(node)?;
+            let Some(kids) = dict.get("Kids") else { return Ok(node) };
+            for kid in self.resolve(kids)?.as_array().ok_or("malformed page tree")? {
+                let kid = kid.as_reference().ok_or("malformed page tree")?;
+                let kid_dict = self.dict(kid)?;
+                let count = match kid_dict.get("Kids") {
+                    Some(_) => kid_dict
+                        .get("Count")
+                        .and_then(Object::as_int)
+                        .and_then(|v| usize::try_from(v).ok())
+                        .ok_or("malformed page tree")?,
+                    None => 1,
+                };
+                if skip < count {
+                    node = kid;
+                    continue 'descend;
+                }
+                skip -= count;
+            }
+            bail!("missing page {number}");
+        }
+    }
+
+    /// Allocates a reference to a new object.
+    pub fn alloc(&mut self) -> Ref {
+        self.next += 1;
+        (self.next - 1, 0)
+    }
+
+    /// Adds or replaces an object.
+    pub fn set(&mut self, id: Ref, object: impl Into<Object>) {
+        self.objects.insert(id, object.into());
+    }
+}
+
+/// A reference to an indirect object: its number and its generation.
+pub(crate) type Ref = (i32, i32);
+
+/// A PDF object.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Object {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Real(f64),
+    /// The decoded bytes of a literal or hex string.
+    String(Vec<u8>),
+    /// A name without its leading slash and with its escapes decoded.
+    Name(Vec<u8>),
+    Array(Vec<Object>),
+    Dict(Dict),
+    Stream(Stream),
+    Ref(Ref),
+}
+
+impl Object {
+    /// Creates a name.
+    pub fn name(name: &str) -> Self {
+        Self::Name(name.as_bytes().to_vec())
+    }
+
+    /// Creates a text string, which is written in PDFDocEncoding if the text is
+    /// printable ASCII and in UTF-16 otherwise.
+    pub fn text(text: &str) -> Self {
+        if text.chars().all(|c| c.is_ascii() && !c.is_ascii_control()) {
+            return Self::String(text.as_bytes().to_vec());
+        }
+
+        let mut bytes = vec![0xfe, 0xff];
+        for unit in text.encode_utf16() {
+            bytes.extend_from_slice(&unit.to_be_bytes());
+        }
+        Self::String(bytes)
+    }
+
+    /// Creates an array of numbers.
+    pub fn numbers(numbers: impl IntoIterator<Item = f64>) -> Self {
+        Self::Array(numbers.into_iter().map(Self::Real).collect())
+    }
+
+    /// Returns the value of an integer.
+    pub fn as_int(&self) -> Option<i64> {
+        match *self {
+            Self::Int(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of an integer or a real number.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Self::Int(v) => Some(v as f64),
+            Self::Real(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes of a name.
+    pub fn as_name(&self) -> Option<&[u8]> {
+        match self {
+            Self::Name(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Whether this is the given name.
+    pub fn is_name(&self, name: &str) -> bool {
+        self.as_name() == Some(name.as_bytes())
+    }
+
+    /// Returns the bytes of a string.
+    pub fn as_string(&self) -> Option<&[u8]> {
+        match self {
+            Self::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Returns the items of an array.
+    pub fn as_array(&self) -> Option<&[Object]> {
+        match self {
+            Self::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of an array of numbers.
+    pub fn as_numbers(&self) -> Option<Vec<f64>> {
+        self.as_array()?.iter().map(Self::as_f64).collect()
+    }
+
+    /// Returns a dictionary or the dictionary of a stream.
+    pub fn as_dict(&self) -> Option<&Dict> {
+        match self {
+            Self::Dict(dict) => Some(dict),
+            Self::Stream(stream) => Some(&stream.dict),
+            _ => None,
+        }
+    }
+
+    /// Returns a dictionary or the dictionary of a stream mutably.
+    pub fn as_dict_mut(&mut self) -> Option<&mut Dict> {
+        match self {
+            Self::Dict(dict) => Some(dict),
+            Self::Stream(stream) => Some(&mut stream.dict),
+            _ => None,
+        }
+    }
+
+    /// Returns the target of a reference.
+    pub fn as_reference(&self) -> Option<Ref> {
+        match *self {
+            Self::Ref(id) => Some(id),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for Object {
+    fn from(v: bool) -> Self {
+        Self::Bool(v)
+    }
+}
+
+impl From<i32> for Object {
+    fn from(v: i32) -> Self {
+        Self::Int(v.into())
+    }
+}
+
+impl From<i64> for Object {
+    fn from(v: i64) -> Self {
+        Self::Int(v)
+    }
+}
+
+impl From<usize> for Object {
+    fn from(v: usize) -> Self {
+        Self::Int(v as i64)
+    }
+}
+
+impl From<f64> for Object {
+    fn from(v: f64) -> Self {
+        Self::Real(v)
+    }
+}
+
+impl From<Ref> for Object {
+    fn from(id: Ref) -> Self {
+        Self::Ref(id)
+    }
+}
+
+impl From<Vec<Object>> for Object {
+    fn from(array: Vec<Object>) -> Self {
+        Self::Array(array)
+    }
+}
+
+impl From<Dict> for Object {
+    fn from(dict: Dict) -> Self {
+        Self::Dict(dict)
+    }
+}
+
+impl From<Stream> for Object {
+    fn from(stream: Stream) -> Self {
+        Self::Stream(stream)
+    }
+}
+
+/// A dictionary, which keeps the order of its entries.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Dict(Vec<(Vec<u8>, Object)>);
+
+impl Dict {
+    /// Creates an empty dictionary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value of a key, given without its leading slash.
+    pub fn get(&self, key: &str) -> Option<&Object> {
+        self.0.iter().find(|(k, _)| k == key.as_bytes()).map(|(_, v)| v)
+    }
+
+    /// Returns the value of a key mutably.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Object> {
+        self.0.iter_mut().find(|(k, _)| k == key.as_bytes()).map(|(_, v)| v)
+    }
+
+    /// Sets the value of a key, keeping its position if it already exists.
+    pub fn set(&mut self, key: &str, value: impl Into<Object>) {
+        let value = value.into();
+        match self.get_mut(key) {
+            Some(slot) => *slot = value,
+            None => self.0.push((key.as_bytes().to_vec(), value)),
+        }
+    }
+
+    /// Sets the value of a key and returns the dictionary.
+    pub fn with(mut self, key: &str, value: impl Into<Object>) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    /// Removes a key and returns its value.
+    pub fn remove(&mut self, key: &str) -> Option<Object> {
+        let index = self.0.iter().position(|(k, _)| k == key.as_bytes())?;
+        Some(self.0.remove(index).1)
+    }
+
+    /// Whether the value of a key is the given name.
+    pub fn is(&self, key: &str, name: &str) -> bool {
+        self.get(key).is_some_and(|value| value.is_name(name))
+    }
+
+    /// The entries of the dictionary.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &Object)> {
+        self.0.iter().map(|(k, v)| (k.as_slice(), v))
+    }
+
+    /// The values of the dictionary, mutably.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Object> {
+        self.0.iter_mut().map(|(_, v)| v)
+    }
+}
+
+/// Dictionaries are equal regardless of the order of their entries.
+impl PartialEq for Dict {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .all(|(k, v)| other.iter().any(|(l, w)| k == l && v == w))
+    }
+}
+
+/// A stream, consisting of a dictionary and data.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Stream {
+    /// The stream dictionary. Its `/Length` is replaced by the length of the
+    /// data when the stream is written, so it is always direct.
+    pub dict: Dict,
+    /// The data, with the filters of the dictionary applied.
+    pub data: Vec<u8>,
+}
+
+impl Stream {
+    /// Creates a stream from a dictionary and its encoded data.
+    pub fn new(dict: Dict, data: Vec<u8>) -> Self {
+        Self { dict, data }
+    }
+
+    /// Returns the decoded data. Only the Flate filter without parameters is
+    /// supported, which is what krilla uses for content streams.
+    pub fn decode(&self) -> StrResult<Vec<u8>> {
+        let filters = match self.dict.get("Filter") {
+            None => &[][..],
+            Some(filter @ Object::Name(_)) => std::slice::from_ref(filter),
+            Some(Object::Array(filters)) => filters,
+            Some(_) => bail!("malformed stream filter"),
+        };
+
+        match filters {
+            [] => Ok(self.data.clone()),
+            [filter]
+                if filter.is_name("FlateDecode")
+                    && self.dict.get("DecodeParms").is_none() =>
+            {
+                let mut out = vec![];
+                ZlibDecoder::new(self.data.as_slice())
+                    .read_to_end(&mut out)
+                    .map_err(|_| "malformed compressed stream")?;
+                Ok(out)
+            }
+            _ => bail!("unsupported stream filter"),
+        }
+    }
+}
+
+/// Reads the dictionary of the last trailer, which is where hayro-syntax
+/// does not look for the information dictionary and the file identifier.
+fn read_trailer(bytes: &[u8]) -> StrResult<Dict> {
+    let pos = rfind(bytes, b"trailer").ok_or("missing trailer")?;
+    let mut reader = Reader::new_with(bytes, pos + b"trailer".len());
+    reader.skip_white_spaces_and_comments();
+    let dict = reader
+        .read_without_context::<hayro::Dict>()
+        .ok_or("malformed trailer")?;
+    Ok(convert_dict(&dict))
+}
+
+/// Reads the version from the header, e.g. `%PDF-1.7`.
+fn read_version(bytes: &[u8]) -> Option<(u8, u8)> {
+    match bytes.strip_prefix(b"%PDF-")? {
+        [major, b'.', minor, ..] if major.is_ascii_digit() && minor.is_ascii_digit() => {
+            Some((major - b'0', minor - b'0'))
+        }
+        _ => None,
+    }
+}
+
+/// Finds the last occurrence of a byte sequence.
+pub(crate) fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).rposition(|w| w == needle)
+}
+
+/// Reads an object in use with generation zero.
+fn read_object(xref: &XRef, number: i32) -> Option<Object> {
+    let id = hayro::ObjectIdentifier::new(number, 0);
+    xref.get::<hayro::Object>(id).map(convert)
+}
+
+/// Converts an object into its owned form.
+fn convert(object: hayro::Object) -> Object {
+    match object {
+        hayro::Object::Null(_) => Object::Null,
+        hayro::Object::Boolean(v) => Object::Bool(v),
+        hayro::Object::Number(number) => {
+            // hayro-syntax doesn't tell integers and reals apart, but whole
+            // reals mean the same as the integer.
+            let v = number.as_f64();
+            if v.fract() == 0.0 && v.abs() <= i32::MAX as f64 {
+                Object::Int(v as i64)
+            } else {
+                Object::Real(v)
+            }
+        }
+        hayro::Object::String(string) => Object::String(string.get().into_owned()),
+        hayro::Object::Name(name) => Object::Name(name.to_vec()),
+        hayro::Object::Dict(dict) => Object::Dict(convert_dict(&dict)),
+        hayro::Object::Array(array) => {
+            Object::Array(array.raw_iter().map(convert_maybe_ref).collect())
+        }
+        hayro::Object::Stream(stream) => {
+            // The length is written together with the data.
+            let mut dict = convert_dict(stream.dict());
+            dict.remove("Length");
+            Object::Stream(Stream::new(dict, stream.raw_data().into_owned()))
+        }
+    }
+}
+
+/// Converts a dictionary into its owned form, keeping its references.
+fn convert_dict(dict: &hayro::Dict) -> Dict {
+    let mut out = Dict::new();
+    for (key, value) in dict.entries() {
+        if let Ok(key) = std::str::from_utf8(&key) {
+            out.set(key, convert_maybe_ref(value));
+        }
+    }
+    out
+}
+
+/// Converts a direct object or keeps a reference.
+fn convert_maybe_ref(object: MaybeRef<hayro::Object>) -> Object {
+    match object {
+        MaybeRef::Ref(id) => Object::Ref((id.obj_number, id.gen_number)),
+        MaybeRef::NotRef(object) => convert(object),
+    }
+}
+
+/// Writes an indirect object.
+fn write_indirect(chunk: &mut Chunk, id: Ref, object: &Object) {
+    match object {
+        Object::Stream(stream) => {
+            let mut writer = chunk.stream(writer_ref(id), &stream.data);
+            write_entries(&mut writer, &stream.dict, Some("Length"));
+        }
+        _ => write_object(chunk.indirect(writer_ref(id)), object),
+    }
+}
+
+/// Writes a direct object.
+fn write_object(obj: pdf_writer::Obj, object: &Object) {
+    match object {
+        Object::Null => obj.primitive(pdf_writer::Null),
+        Object::Bool(v) => obj.primitive(*v),
+        Object::Int(v) => match i32::try_from(*v) {
+            Ok(v) => obj.primitive(v),
+            Err(_) => obj.primitive(*v as f32),
+        },
+        Object::Real(v) if v.is_finite() => obj.primitive(*v as f32),
+        Object::Real(_) => obj.primitive(0),
+        Object::String(string) => obj.primitive(Str(string)),
+        Object::Name(name) => obj.primitive(Name(name)),
+        Object::Array(items) => {
+            let mut array = obj.array();
+            for item in items {
+                write_object(array.push(), item);
+            }
+        }
+        Object::Dict(dict) => write_entries(&mut obj.dict(), dict, None),
+        // Streams are always indirect, so only their dictionary could be
+        // written here.
+        Object::Stream(stream) => write_entries(&mut obj.dict(), &stream.dict, None),
+        Object::Ref(id) => obj.primitive(writer_ref(*id)),
+    }
+}
+
+/// Writes the entries of a dictionary, except for one that pdf-writer has
+/// already written.
+fn write_entries(writer: &mut pdf_writer::Dict, dict: &Dict, written: Option<&str>) {
+    for (key, value) in dict.iter() {
+        if written.is_none_or(|written| key != written.as_bytes()) {
+            write_object(writer.insert(Name(key)), value);
+        }
+    }
+}
+
+/// Converts a reference for pdf-writer, which only writes generation zero.
+fn writer_ref((number, _): Ref) -> pdf_writer::Ref {
+    pdf_writer::Ref::new(number)
+}
+
+/// Parses a single object, which may not be a stream.
+#[cfg(test)]
+pub(crate) fn parse_object(data: &[u8]) -> StrResult<Object> {
+    let mut reader = Reader::new(data);
+    reader.skip_white_spaces_and_comments();
+    let object = reader
+        .read_without_context::<hayro::Object>()
+        .ok_or("malformed object")?;
+    reader.skip_white_spaces_and_comments();
+    if !reader.at_end() {
+        bail!("unexpected data after object");
+    }
+    Ok(convert(object))
+}
+
+/// Builds a file from objects, which are numbered from one. The first one is
+/// the document catalog.
+#[cfg(test)]
+pub(crate) fn file(objects: &[&[u8]]) -> Vec<u8> {
+    use std::io::Write;
+
+    let mut bytes = b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = vec![];
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(bytes.len());
+        writeln!(bytes, "{} 0 obj", i + 1).unwrap();
+        bytes.extend_from_slice(object);
+        bytes.extend_from_slice(b"\nendobj\n");
+    }
+    let startxref = bytes.len();
+    writeln!(bytes, "xref\n0 {}", objects.len() + 1).unwrap();
+    bytes.extend_from_slice(b"0000000000 65535 f\r\n");
+    for offset in offsets {
+        write!(bytes, "{offset:010} 00000 n\r\n").unwrap();
+    }
+    write!(
+        bytes,
+        "trailer\n<< /Size {} /Root 1 0 R /ID [<01> <02>] >>\n\
+         startxref\n{startxref}\n%%EOF\n",
+        objects.len() + 1
+    )
+    .unwrap();
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let bytes = file(&[
+            b"<< /Type /Catalog /Pages 2 0 R >>",
+            b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+            b"<< /Type /Page /Parent 2 0 R /Contents 4 0 R /Rotate 90.0 >>",
+            b"<< /Length 5 0 R >>\nstream\nq endstream Q\nendstream",
+            b"13",
+            b"<< /Title (A \\(nested\\) title) /N /a#20b /R [0.5 -1 null true] >>",
+        ]);
+        let mut file = File::read(&bytes).unwrap();
+        assert_eq!(file.root, (1, 0));
+        assert_eq!(file.id, Some((vec![1], vec![2])));
+        assert_eq!(file.refs().count(), 6);
+        assert_eq!(file.alloc(), (7, 0));
+
+        file.encrypt = Some((5, 0));
+        let written = file.write();
+        assert!(written.starts_with(b"%PDF-1.7\n"));
+        assert!(written.windows(16).any(|w| w == b"/Encrypt 5 0 R\n "));
+
+        file.encrypt = None;
+        file.info = Some((6, 0));
+        let written = file.write();
+        let mut read = File::read(&written).unwrap();
+        assert_eq!(read.info, Some((6, 0)));
+        assert_eq!(read.id, file.id);
+        assert_eq!(
+            read.get_mut((3, 0)).unwrap(),
+            &parse_object(b"<< /Type /Page /Parent 2 0 R /Contents 4 0 R /Rotate 90 >>")
+                .unwrap()
+        );
+        let Object::Stream(stream) = read.get_mut((4, 0)).unwrap() else { panic!() };
+        assert_eq!(stream.data, b"q endstream Q");
+        assert_eq!(stream.dict, Dict::new());
+        assert_eq!(
+            read.dict_mut((6, 0)).unwrap(),
+            parse_object(
+                b"<< /Title (A \\(nested\\) title) /N /a#20b /R [0.5 -1 null true] >>"
+            )
+            .unwrap()
+            .as_dict()
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_page() {
+        let bytes = file(&[
+            b"<< /Type /Catalog /Pages 2 0 R >>",
+            b"<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 3 >>",
+            b"<< /Type /Page /Parent 2 0 R >>",
+            b"<< /Type /Pages /Parent 2 0 R /Kids [5 0 R 6 0 R] /Count 2 >>",
+            b"<< /Type /Page /Parent 4 0 R >>",
+            b"<< /Type /Page /Parent 4 0 R >>",
+        ]);
+        let file = File::read(&bytes).unwrap();
+        assert_eq!(file.page(1).unwrap(), (3, 0));
+        assert_eq!(file.page(3).unwrap(), (6, 0));
+        assert!(file.page(4).is_err());
+    }
+
+    #[test]
+    fn test_write_with() {
+        let file = File::read(&file(&[
+            b"<< /Type /Catalog /Pages 2 0 R >>",
+            b"<< /Type /Pages /Kids [] /Count 0 >>",
+        ]))
+        .unwrap();
+        let mut ranges = vec![];
+        let written = file.write_with(|id, range| ranges.push((id, range)));
+        assert_eq!(ranges.len(), 2);
+        let (id, range) = ranges[1].clone();
+        assert_eq!(id, (2, 0));
+        assert!(written[range.clone()].starts_with(b"2 0 obj"));
+        assert!(written[range].ends_with(b"endobj\n\n"));
+    }
+
+    #[test]
+    fn test_dict_set() {
+        let mut dict =
+            Dict::new().with("Type", Object::name("Page")).with("Annots", (1, 0));
+        dict.set("Annots", Object::Array(vec![]));
+        dict.set("Rotate", 90);
+        let keys: Vec<&[u8]> = dict.iter().map(|(key, _)| key).collect();
+        assert_eq!(keys, [b"Type".as_slice(), b"Annots", b"Rotate"]);
+        assert_eq!(dict.remove("Type"), Some(Object::name("Page")));
+        assert_eq!(dict.remove("Type"), None);
+    }
+}
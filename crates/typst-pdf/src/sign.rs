@@ -0,0 +1,673 @@
+//! Digital signatures.
+//!
+//! krilla cannot sign documents, so we add a signature field when the finished
+//! file is rewritten. The signature covers all bytes of the written file
+//! except for a gap in the signature dictionary that is reserved for the
+//! signature itself.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use cms::builder::{SignedDataBuilder, SignerInfoBuilder};
+use cms::cert::{CertificateChoices, IssuerAndSerialNumber};
+use cms::signed_data::{EncapsulatedContentInfo, SignerIdentifier};
+use der::asn1::{GeneralizedTime, SetOfVec, UtcTime};
+use der::oid::db::{rfc4519, rfc5911, rfc5912};
+use der::{Any, Decode, Encode};
+use ecow::{EcoString, eco_format, eco_vec};
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::signature::Keypair;
+use sha2::{Digest, Sha256};
+use typst_library::diag::{SourceResult, StrResult, bail, error};
+use typst_syntax::Span;
+use x509_cert::Certificate;
+use x509_cert::attr::Attribute;
+use x509_cert::spki::{
+    AlgorithmIdentifierOwned, DynSignatureAlgorithmIdentifier, EncodePublicKey,
+    SignatureBitStringEncoding,
+};
+
+use crate::form::{form_xobject, helvetica, pdf_latin1, show};
+use crate::rewrite::{Dict, File, Object, Ref, Stream, rfind};
+use crate::{PdfOptions, PdfSignature, Timestamp};
+
+/// How the byte range is written before it is known. Its numbers are as wide
+/// as the largest offsets the actual byte range can have.
+const BYTE_RANGE: &str = "[0 2147483647 2147483647 2147483647]";
+
+/// Checks that the signature can be added to the document.
+pub(crate) fn validate(
+    options: &PdfOptions,
+    signature: &PdfSignature,
+    page_count: usize,
+) -> SourceResult<()> {
+    if options.encryption.is_some() {
+        bail!(
+            Span::detached(),
+            "cannot sign an encrypted PDF";
+            hint: "remove either the passwords or the signature"
+        );
+    }
+
+    if page_count == 0 {
+        bail!(Span::detached(), "cannot sign a PDF without pages");
+    }
+
+    let Some(field) = signature.field else { return Ok(()) };
+
+    // The appearance of a visible signature uses a font that isn't embedded
+    // and its field isn't tagged.
+    let standard = match options.standards.pdfx {
+        Some(pdfx) => Some(pdfx.as_str()),
+        None if options.is_pdf_a() || options.is_pdf_ua() => {
+            Some(options.standards.config.validator().as_str())
+        }
+        None => None,
+    };
+    if let Some(standard) = standard {
+        bail!(
+            Span::detached(),
+            "visible signatures are not supported in {standard}";
+            hint: "sign without a signature field to sign invisibly"
+        );
+    }
+
+    if field.page.get() > page_count {
+        bail!(
+            Span::detached(),
+            "the signature field is on page {}, but the PDF only has {page_count} pages",
+            field.page,
+        );
+    }
+
+    Ok(())
+}
+
+/// Adds the signature to a finished PDF file and writes it.
+pub(crate) fn finish(
+    file: File,
+    signature: &PdfSignature,
+    options: &PdfOptions,
+) -> SourceResult<Vec<u8>> {
+    let signer = Signer::load(signature).map_err(|err| {
+        eco_vec![error!(
+            Span::detached(),
+            "failed to load signing certificate ({err})";
+            hint: "make sure that it is a PKCS#12 file and that the password is correct"
+        )]
+    })?;
+
+    if signature.field.is_some() {
+        check_appearance(signature, &signer.name())?;
+    }
+
+    sign(file, signature, &signer, options).map_err(|err| {
+        eco_vec![error!(
+            Span::detached(),
+            "failed to sign PDF ({err})";
+            hint: "please report this as a bug"
+        )]
+    })
+}
+
+/// Checks that the details listed by a visible signature can be shown. Its
+/// appearance uses the standard Helvetica font, which only covers Latin-1.
+fn check_appearance(signature: &PdfSignature, name: &str) -> SourceResult<()> {
+    let texts = [Some(name), signature.reason.as_deref(), signature.location.as_deref()];
+    for text in texts.into_iter().flatten() {
+        if let Some(c) = text
+            .chars()
+            .find(|c| pdf_latin1(c.encode_utf8(&mut [0; 4])).is_none())
+        {
+            bail!(
+                Span::detached(),
+                "visible signature cannot show the character {c:?}";
+                hint: "the details of a visible signature are limited to Latin-1 characters";
+                hint: "sign without a signature field to sign invisibly"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Adds the signature field, writes the file and signs it.
+fn sign(
+    mut file: File,
+    signature: &PdfSignature,
+    signer: &Signer,
+    options: &PdfOptions,
+) -> StrResult<Vec<u8>> {
+    let number = signature.field.map_or(1, |field| field.page.get());
+    let page = file.page(number)?;
+    let mut page_dict = file.dict(page)?.clone();
+    let mut catalog = file.catalog()?.clone();
+
+    let sig_id = file.alloc();
+    let field_id = file.alloc();
+
+    // The signature dictionary, with room for the byte range and the
+    // signature. The reserved signature isn't ASCII, so that it is written
+    // as a hexadecimal string.
+    let time = options.timestamp.as_ref().and_then(utc_time);
+    let name = signer.name();
+    let reserved = signer.reserve();
+    let mut sig = Dict::new()
+        .with("ByteRange", byte_range_placeholder())
+        .with("Contents", Object::String(vec![0xff; reserved]))
+        .with("Type", Object::name("Sig"))
+        .with("Filter", Object::name("Adobe.PPKLite"))
+        .with("SubFilter", Object::name("adbe.pkcs7.detached"))
+        .with("Name", Object::text(&name));
+    if let Some(time) = time {
+        let date = format!(
+            "D:{:04}{:02}{:02}{:02}{:02}{:02}Z",
+            time.year(),
+            time.month(),
+            time.day(),
+            time.hour(),
+            time.minutes(),
+            time.seconds()
+        );
+        sig.set("M", Object::text(&date));
+    }
+    if let Some(reason) = &signature.reason {
+        sig.set("Reason", Object::text(reason));
+    }
+    if let Some(location) = &signature.location {
+        sig.set("Location", Object::text(location));
+    }
+    file.set(sig_id, sig);
+
+    // The signature field, merged with its widget annotation.
+    let mut field = Dict::new()
+        .with("Type", Object::name("Annot"))
+        .with("Subtype", Object::name("Widget"))
+        .with("FT", Object::name("Sig"))
+        .with("T", Object::text("Signature"))
+        .with("TU", Object::text("Digital signature"))
+        .with("V", sig_id)
+        .with("P", page);
+    match signature.field {
+        Some(placement) => {
+            let media = page_dict
+                .get("MediaBox")
+                .map(|media| file.resolve(media))
+                .transpose()?
+                .ok_or("missing media box")?;
+            let [x0, _, _, y1] = media.as_numbers().ok_or("malformed media box")?[..]
+            else {
+                bail!("malformed media box")
+            };
+            let (w, h) = (placement.size.x.to_pt(), placement.size.y.to_pt());
+            let x = x0 + placement.pos.x.to_pt();
+            let y = y1 - placement.pos.y.to_pt() - h;
+
+            let ap_id = file.alloc();
+            file.set(ap_id, appearance(signature, &name, time, w, h));
+            field.set("F", 132);
+            field.set("Rect", Object::numbers([x, y, x + w, y + h]));
+            field.set("AP", Dict::new().with("N", ap_id));
+        }
+        // PDF/A requires annotations to be printable, while PDF/UA requires
+        // visible annotations to be tagged. An invisible signature has no
+        // area, so only hide it when necessary.
+        None => {
+            field.set("F", if options.is_pdf_ua() { 130 } else { 132 });
+            field.set("Rect", Object::numbers([0.0; 4]));
+        }
+    }
+    file.set(field_id, field);
+
+    // Register the field with the document's form.
+    match catalog.get_mut("AcroForm") {
+        Some(Object::Dict(form)) => add_field(form, field_id)?,
+        Some(Object::Ref(form_ref)) => add_field(file.dict_mut(*form_ref)?, field_id)?,
+        Some(_) => bail!("malformed form"),
+        None => {
+            let mut form = Dict::new();
+            add_field(&mut form, field_id)?;
+            catalog.set("AcroForm", form);
+        }
+    }
+    file.set(file.root, catalog);
+
+    // Add the widget to the annotations of the page.
+    match page_dict.get_mut("Annots") {
+        Some(Object::Array(annots)) => annots.push(field_id.into()),
+        Some(Object::Ref(annots_ref)) => match file.get_mut(*annots_ref)? {
+            Object::Array(annots) => annots.push(field_id.into()),
+            _ => bail!("malformed page annotations"),
+        },
+        Some(_) => bail!("malformed page annotations"),
+        None => page_dict.set("Annots", vec![field_id.into()]),
+    }
+    file.set(page, page_dict);
+
+    let mut sig_range = 0..0;
+    let mut bytes = file.write_with(|id, range| {
+        if id == sig_id {
+            sig_range = range;
+        }
+    });
+
+    // Fill in the byte range, which is covered by the signature.
+    let sig_bytes = &bytes[sig_range.clone()];
+    let range_start = rfind(sig_bytes, BYTE_RANGE.as_bytes())
+        .ok_or("signature dictionary was not written as expected")?
+        + sig_range.start;
+    let gap_start = rfind(sig_bytes, b"/Contents <")
+        .ok_or("signature dictionary was not written as expected")?
+        + sig_range.start
+        + b"/Contents ".len();
+    let gap_end = gap_start + 2 * reserved + 2;
+    if bytes.get(gap_end - 1) != Some(&b'>') {
+        bail!("signature dictionary was not written as expected");
+    }
+    let byte_range =
+        format!("[0 {gap_start:<10} {gap_end:<10} {:<10}]", bytes.len() - gap_end);
+    bytes[range_start..range_start + BYTE_RANGE.len()]
+        .copy_from_slice(byte_range.as_bytes());
+
+    let digest = Sha256::new()
+        .chain_update(&bytes[..gap_start])
+        .chain_update(&bytes[gap_end..])
+        .finalize();
+    let signed = signer.sign(&digest, time)?;
+    if signed.len() > reserved {
+        bail!("signature is larger than expected");
+    }
+
+    // The rest of the reserved room is padded with zeros.
+    let mut hex = String::with_capacity(2 * reserved);
+    for b in &signed {
+        write!(hex, "{b:02x}").unwrap();
+    }
+    hex.extend(std::iter::repeat_n('0', 2 * (reserved - signed.len())));
+    bytes[gap_start + 1..gap_end - 1].copy_from_slice(hex.as_bytes());
+
+    Ok(bytes)
+}
+
+/// The byte range before it is known, which is written as [`BYTE_RANGE`].
+fn byte_range_placeholder() -> Object {
+    let max = i64::from(i32::MAX);
+    vec![Object::Int(0), Object::Int(max), Object::Int(max), Object::Int(max)].into()
+}
+
+/// Adds a field to an interactive form dictionary.
+fn add_field(form: &mut Dict, field_id: Ref) -> StrResult<()> {
+    match form.get_mut("Fields") {
+        Some(Object::Array(fields)) => fields.push(field_id.into()),
+        Some(_) => bail!("indirect form fields are not supported"),
+        None => form.set("Fields", vec![field_id.into()]),
+    }
+    form.set("SigFlags", 3);
+    Ok(())
+}
+
+/// Creates the appearance stream of a visible signature, which lists the
+/// details of the signature.
+fn appearance(
+    signature: &PdfSignature,
+    name: &str,
+    time: Option<der::DateTime>,
+    w: f64,
+    h: f64,
+) -> Stream {
+    let mut lines = vec![eco_format!("Digitally signed by {name}")];
+    if let Some(time) = time {
+        lines.push(eco_format!(
+            "Date: {:04}.{:02}.{:02} {:02}:{:02}:{:02} Z",
+            time.year(),
+            time.month(),
+            time.day(),
+            time.hour(),
+            time.minutes(),
+            time.seconds()
+        ));
+    }
+    if let Some(reason) = &signature.reason {
+        lines.push(eco_format!("Reason: {reason}"));
+    }
+    if let Some(location) = &signature.location {
+        lines.push(eco_format!("Location: {location}"));
+    }
+
+    let size = (h / (lines.len() as f64 * 1.2 + 0.4)).min(10.0);
+    let mut content =
+        format!("BT /Helv {size:.2} Tf {:.2} TL 2 {:.2} Td", size * 1.2, h - size * 1.2);
+    for line in &lines {
+        write!(content, " {} Tj T*", show(line)).unwrap();
+    }
+    content.push_str(" ET");
+
+    let fonts = Dict::new().with("Helv", helvetica());
+    let mut stream = form_xobject(w, h, None, &content);
+    stream.dict.set("Resources", Dict::new().with("Font", fonts));
+    stream
+}
+
+/// Converts a timestamp into a UTC date and time.
+fn utc_time(timestamp: &Timestamp) -> Option<der::DateTime> {
+    let datetime = timestamp.datetime;
+    let year = i64::from(datetime.year().filter(|&y| y >= 1970)?);
+    let days_before = |y: i64| y * 365 + y / 4 - y / 100 + y / 400;
+    let days =
+        days_before(year - 1) - days_before(1969) + i64::from(datetime.ordinal()?) - 1;
+    let offset = i64::from(timestamp.timezone.offset());
+    let seconds = days * 86400
+        + i64::from(datetime.hour()?) * 3600
+        + (i64::from(datetime.minute()?) - offset) * 60
+        + i64::from(datetime.second()?);
+    der::DateTime::from_unix_duration(Duration::from_secs(seconds.try_into().ok()?)).ok()
+}
+
+/// The private key and certificate chain of the signer.
+struct Signer {
+    key: SigningKey,
+    chain: Vec<Certificate>,
+}
+
+/// A supported private key.
+enum SigningKey {
+    Rsa(Box<rsa::pkcs1v15::SigningKey<Sha256>>),
+    P256(p256::ecdsa::SigningKey),
+    P384(p384::ecdsa::SigningKey),
+}
+
+impl Signer {
+    /// Loads the signer from a PKCS#12 file.
+    fn load(signature: &PdfSignature) -> StrResult<Self> {
+        let store = p12_keystore::KeyStore::from_pkcs12(
+            signature.certificate.as_slice(),
+            &signature.password,
+        )
+        .map_err(|err| eco_format!("{err}"))?;
+        let (_, entry) = store.private_key_chain().ok_or("it contains no private key")?;
+
+        let der = entry.key();
+        let key = if let Ok(key) = rsa::RsaPrivateKey::from_pkcs8_der(der) {
+            SigningKey::Rsa(Box::new(rsa::pkcs1v15::SigningKey::new(key)))
+        } else if let Ok(key) = p256::ecdsa::SigningKey::from_pkcs8_der(der) {
+            SigningKey::P256(key)
+        } else if let Ok(key) = p384::ecdsa::SigningKey::from_pkcs8_der(der) {
+            SigningKey::P384(key)
+        } else {
+            bail!("only RSA, P-256, and P-384 keys are supported");
+        };
+
+        let chain = entry
+            .chain()
+            .iter()
+            .map(|cert| {
+                Certificate::from_der(cert.as_der())
+                    .map_err(|err| eco_format!("invalid certificate: {err}"))
+            })
+            .collect::<StrResult<Vec<_>>>()?;
+        if chain.is_empty() {
+            bail!("it contains no certificate");
+        }
+
+        Ok(Self { key, chain })
+    }
+
+    /// The common name of the signer.
+    fn name(&self) -> EcoString {
+        let subject = &self.chain[0].tbs_certificate.subject;
+        subject
+            .0
+            .iter()
+            .flat_map(|rdn| rdn.0.iter())
+            .find(|attr| attr.oid == rfc4519::CN)
+            .and_then(|attr| std::str::from_utf8(attr.value.value()).ok())
+            .map(Into::into)
+            .unwrap_or_else(|| eco_format!("{subject}"))
+    }
+
+    /// An upper bound for the size of the encoded signature.
+    fn reserve(&self) -> usize {
+        let certs: usize = self
+            .chain
+            .iter()
+            .filter_map(|cert| cert.to_der().ok())
+            .map(|der| der.len())
+            .sum();
+        certs + 4096
+    }
+
+    /// Creates a detached CMS signature for the digest of the signed bytes.
+    fn sign(&self, digest: &[u8], time: Option<der::DateTime>) -> StrResult<Vec<u8>> {
+        let cert = &self.chain[0];
+        let content =
+            EncapsulatedContentInfo { econtent_type: rfc5911::ID_DATA, econtent: None };
+        let sid = SignerIdentifier::IssuerAndSerialNumber(IssuerAndSerialNumber {
+            issuer: cert.tbs_certificate.issuer.clone(),
+            serial_number: cert.tbs_certificate.serial_number.clone(),
+        });
+        let algorithm =
+            AlgorithmIdentifierOwned { oid: rfc5912::ID_SHA_256, parameters: None };
+        let time = time.map(signing_time).transpose()?;
+
+        let mut builder = SignedDataBuilder::new(&content);
+        builder
+            .add_digest_algorithm(algorithm.clone())
+            .map_err(|err| eco_format!("{err}"))?;
+        for cert in &self.chain {
+            builder
+                .add_certificate(CertificateChoices::Certificate(cert.clone()))
+                .map_err(|err| eco_format!("{err}"))?;
+        }
+
+        let info = (sid, algorithm, &content, digest, time);
+        match &self.key {
+            SigningKey::Rsa(key) => {
+                add_signer::<_, rsa::pkcs1v15::Signature>(&mut builder, &**key, info)
+            }
+            SigningKey::P256(key) => {
+                add_signer::<_, p256::ecdsa::DerSignature>(&mut builder, key, info)
+            }
+            SigningKey::P384(key) => {
+                add_signer::<_, p384::ecdsa::DerSignature>(&mut builder, key, info)
+            }
+        }?;
+
+        builder
+            .build()
+            .map_err(|err| eco_format!("{err}"))?
+            .to_der()
+            .map_err(|err| eco_format!("{err}"))
+    }
+}
+
+/// The details of a signer.
+type SignerDetails<'a> = (
+    SignerIdentifier,
+    AlgorithmIdentifierOwned,
+    &'a EncapsulatedContentInfo,
+    &'a [u8],
+    Option<Attribute>,
+);
+
+/// Signs the digest with a key and adds the resulting signer info.
+fn add_signer<S, Sig>(
+    builder: &mut SignedDataBuilder,
+    key: &S,
+    (sid, algorithm, content, digest, time): SignerDetails,
+) -> StrResult<()>
+where
+    S: Keypair + DynSignatureAlgorithmIdentifier + rsa::signature::Signer<Sig>,
+    S::VerifyingKey: EncodePublicKey,
+    Sig: SignatureBitStringEncoding,
+{
+    let mut info = SignerInfoBuilder::new(key, sid, algorithm, content, Some(digest))
+        .map_err(|err| eco_format!("{err}"))?;
+    if let Some(time) = time {
+        info.add_signed_attribute(time).map_err(|err| eco_format!("{err}"))?;
+    }
+    builder
+        .add_signer_info::<S, Sig>(info)
+        .map_err(|err| eco_format!("{err}"))?;
+    Ok(())
+}
+
+/// Creates the signing time attribute, which must use UTC time for the years
+/// 1950 to 2049.
+fn signing_time(time: der::DateTime) -> StrResult<Attribute> {
+    let value = if (1950..2050).contains(&time.year()) {
+        UtcTime::from_date_time(time).and_then(|time| time.to_der())
+    } else {
+        GeneralizedTime::from_date_time(time).to_der()
+    }
+    .map_err(|err| eco_format!("{err}"))?;
+
+    let mut values = SetOfVec::new();
+    values
+        .insert(Any::from_der(&value).map_err(|err| eco_format!("{err}"))?)
+        .map_err(|err| eco_format!("{err}"))?;
+    Ok(Attribute { oid: rfc5911::ID_SIGNING_TIME, values })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use cms::content_info::ContentInfo;
+    use cms::signed_data::SignedData;
+    use der::SliceReader;
+    use der::asn1::OctetString;
+    use rsa::RsaPublicKey;
+    use rsa::pkcs8::DecodePublicKey;
+    use rsa::signature::Verifier;
+    use typst_library::foundations::{Bytes, Datetime};
+    use typst_library::layout::{Abs, Point, Size};
+
+    use super::*;
+    use crate::PdfSignatureField;
+    use crate::rewrite::file;
+
+    /// A self-signed RSA certificate for "Typst Test Signer" with the password
+    /// "typst".
+    const CERTIFICATE: &[u8] = include_bytes!("../testdata/signer.p12");
+
+    fn signature() -> PdfSignature {
+        PdfSignature {
+            certificate: Bytes::new(CERTIFICATE),
+            password: "typst".into(),
+            reason: Some("Approval".into()),
+            location: None,
+            field: None,
+        }
+    }
+
+    fn sign_file(signature: &PdfSignature) -> SourceResult<Vec<u8>> {
+        let bytes = file(&[
+            b"<< /Type /Catalog /Pages 2 0 R >>",
+            b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+            b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 200 100] >>",
+        ]);
+        let datetime = Datetime::from_ymd_hms(2024, 12, 17, 10, 10, 10).unwrap();
+        let options = PdfOptions {
+            timestamp: Timestamp::new_local(datetime, 60),
+            ..PdfOptions::default()
+        };
+        finish(File::read(&bytes).unwrap(), signature, &options)
+    }
+
+    #[test]
+    fn test_sign() {
+        let bytes = sign_file(&signature()).unwrap();
+
+        // The byte range covers the whole file except for the contents of the
+        // signature.
+        let start = bytes.windows(12).position(|w| w == b"/ByteRange [").unwrap() + 12;
+        let end = start + bytes[start..].iter().position(|&b| b == b']').unwrap();
+        let range: Vec<usize> = std::str::from_utf8(&bytes[start..end])
+            .unwrap()
+            .split_whitespace()
+            .map(|n| n.parse().unwrap())
+            .collect();
+        let [0, gap_start, gap_end, rest] = range[..] else { panic!("{range:?}") };
+        assert_eq!(gap_end + rest, bytes.len());
+        assert!(bytes[..gap_start].ends_with(b"/Contents "));
+        assert_eq!(bytes[gap_start], b'<');
+        assert_eq!(bytes[gap_end - 1], b'>');
+
+        // The signature is a valid CMS signature of the covered bytes.
+        let hex = std::str::from_utf8(&bytes[gap_start + 1..gap_end - 1]).unwrap();
+        let der: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+        let info = ContentInfo::decode(&mut SliceReader::new(&der).unwrap()).unwrap();
+        let data: SignedData = info.content.decode_as().unwrap();
+        let signer = data.signer_infos.0.get(0).unwrap();
+        let attrs = signer.signed_attrs.as_ref().unwrap();
+
+        let digest = Sha256::new()
+            .chain_update(&bytes[..gap_start])
+            .chain_update(&bytes[gap_end..])
+            .finalize();
+        let message_digest = attrs
+            .iter()
+            .find(|attr| attr.oid == rfc5911::ID_MESSAGE_DIGEST)
+            .and_then(|attr| attr.values.get(0))
+            .map(|value| value.decode_as::<OctetString>().unwrap())
+            .unwrap();
+        assert_eq!(message_digest.as_bytes(), digest.as_slice());
+
+        let time = attrs
+            .iter()
+            .find(|attr| attr.oid == rfc5911::ID_SIGNING_TIME)
+            .and_then(|attr| attr.values.get(0))
+            .map(|value| value.decode_as::<UtcTime>().unwrap())
+            .unwrap();
+        assert_eq!(time.to_date_time().hour(), 9);
+
+        let cert = match data.certificates.unwrap().0.get(0).unwrap() {
+            CertificateChoices::Certificate(cert) => cert.clone(),
+            _ => panic!("expected a certificate"),
+        };
+        let spki = cert.tbs_certificate.subject_public_key_info.to_der().unwrap();
+        let key = rsa::pkcs1v15::VerifyingKey::<Sha256>::new(
+            RsaPublicKey::from_public_key_der(&spki).unwrap(),
+        );
+        let signature =
+            rsa::pkcs1v15::Signature::try_from(signer.signature.as_bytes()).unwrap();
+        key.verify(&attrs.to_der().unwrap(), &signature).unwrap();
+    }
+
+    #[test]
+    fn test_sign_visible_non_latin1() {
+        let field = PdfSignatureField {
+            page: NonZeroUsize::MIN,
+            pos: Point::zero(),
+            size: Size::new(Abs::pt(100.0), Abs::pt(40.0)),
+        };
+        let signature = PdfSignature {
+            location: Some("東京".into()),
+            field: Some(field),
+            ..signature()
+        };
+        let errors = sign_file(&signature).unwrap_err();
+        assert_eq!(errors[0].message, "visible signature cannot show the character '東'");
+
+        let signature = PdfSignature { location: Some("Zürich".into()), ..signature };
+        assert!(sign_file(&signature).is_ok());
+    }
+
+    #[test]
+    fn test_utc_time() {
+        let datetime = Datetime::from_ymd_hms(2024, 12, 17, 10, 10, 10).unwrap();
+        let test = |offset, hour, minute| {
+            let time =
+                utc_time(&Timestamp::new_local(datetime, offset).unwrap()).unwrap();
+            assert_eq!((time.hour(), time.minutes()), (hour, minute));
+        };
+        test(0, 10, 10);
+        test(30, 9, 40);
+        test(-30, 10, 40);
+        test(330, 4, 40);
+        test(-210, 13, 40);
+        assert_eq!(utc_time(&Timestamp::new_utc(datetime)).unwrap().hour(), 10);
+    }
+}
@@ -243,6 +243,8 @@ fn visit_frame(tree: &mut TreeBuilder, frame: &Frame) -> SourceResult<()> {
             FrameItem::Shape(..) => (),
             FrameItem::Image(..) => (),
             FrameItem::Link(..) => (),
+            FrameItem::Widget(..) => (),
+            FrameItem::Annotation(..) => (),
         }
     }
     Ok(())
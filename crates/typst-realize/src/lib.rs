@@ -15,9 +15,9 @@ use ecow::EcoString;
 use typst_library::diag::{At, SourceResult, bail};
 use typst_library::engine::Engine;
 use typst_library::foundations::{
-    Content, Context, ContextElem, Element, NativeElement, NativeShowRule, Packed,
-    Recipe, RecipeIndex, Selector, SequenceElem, ShowSet, Style, StyleChain, StyledElem,
-    Styles, SymbolElem, Synthesize, TargetElem, Transformation,
+    Array, Content, Context, ContextElem, Dict, Element, NativeElement, NativeShowRule,
+    Packed, Recipe, RecipeIndex, Regex, Selector, SequenceElem, ShowSet, Style,
+    StyleChain, StyledElem, Styles, SymbolElem, Synthesize, TargetElem, Transformation,
 };
 use typst_library::introspection::{
     Locatable, LocationKey, SplitLocator, Tag, TagElem, TagFlags, Tagged,
@@ -171,6 +171,10 @@ struct RegexMatch<'a> {
     offset: usize,
     /// The text that matched.
     text: EcoString,
+    /// The text of each capturing group, like in [`str.match`].
+    captures: Array,
+    /// The text of each named capturing group.
+    named_captures: Dict,
     /// The style chain of the matching grouping.
     styles: StyleChain<'a>,
     /// The index of the recipe that matched.
@@ -1213,7 +1217,13 @@ fn find_regex_match_in_str<'a>(
 ) -> Option<RegexMatch<'a>> {
     let mut r = 0;
     let mut revoked = SmallBitSet::new();
-    let mut leftmost: Option<(regex::Match, RecipeIndex, &Recipe)> = None;
+    let mut leftmost: Option<(
+        regex::Match,
+        regex::Captures,
+        RecipeIndex,
+        &Recipe,
+        &Regex,
+    )> = None;
 
     let depth = LazyCell::new(|| styles.recipes().count());
 
@@ -1229,7 +1239,8 @@ fn find_regex_match_in_str<'a>(
         r += 1;
 
         let Some(Selector::Regex(regex)) = recipe.selector() else { continue };
-        let Some(m) = regex.find(text) else { continue };
+        let Some(cap) = regex.captures_at(text, 0) else { continue };
+        let Some(m) = cap.get(0) else { continue };
 
         // Make sure we don't get any empty matches.
         if m.range().is_empty() {
@@ -1238,7 +1249,7 @@ fn find_regex_match_in_str<'a>(
 
         // If we already have a match that is equally or more to the left, we're
         // not interested in this new match.
-        if leftmost.is_some_and(|(p, ..)| p.start() <= m.start()) {
+        if leftmost.as_ref().is_some_and(|(p, ..)| p.start() <= m.start()) {
             continue;
         }
 
@@ -1250,12 +1261,14 @@ fn find_regex_match_in_str<'a>(
             continue;
         }
 
-        leftmost = Some((m, index, recipe));
+        leftmost = Some((m, cap, index, recipe, regex));
     }
 
-    leftmost.map(|(m, id, recipe)| RegexMatch {
+    leftmost.map(|(m, cap, id, recipe, regex)| RegexMatch {
         offset: m.start(),
         text: m.as_str().into(),
+        captures: Regex::positional_captures(&cap),
+        named_captures: regex.named_captures(&cap),
         id,
         recipe,
         styles,
@@ -1280,7 +1293,12 @@ fn visit_regex_match<'a>(
     // realization (`RealizationKind::Math`).
     let piece = match elems {
         &[(lone, _)] if lone.is::<SymbolElem>() => lone.clone(),
-        _ => TextElem::packed(m.text),
+        _ => {
+            let mut elem = TextElem::new(m.text);
+            elem.captures = Some(m.captures);
+            elem.named_captures = Some(m.named_captures);
+            elem.pack()
+        }
     };
 
     let context = Context::new(None, Some(m.styles));
@@ -36,7 +36,11 @@ pub fn render_image(
     let w = (scale_x * view_width.max(aspect * view_height)).ceil() as u32;
     let h = ((w as f32) / aspect).ceil() as u32;
 
-    let pixmap = build_texture(image, w, h)?;
+    let smooth = state
+        .options
+        .image_smoothing
+        .unwrap_or_else(|| image.scaling() != Smart::Custom(ImageScaling::Pixelated));
+    let pixmap = build_texture(image, w, h, smooth)?;
     let paint_scale_x = view_width / pixmap.width() as f32;
     let paint_scale_y = view_height / pixmap.height() as f32;
 
@@ -59,7 +63,7 @@ pub fn render_image(
 
 /// Prepare a texture for an image at a scaled size.
 #[comemo::memoize]
-fn build_texture(image: &Image, w: u32, h: u32) -> Option<Arc<sk::Pixmap>> {
+fn build_texture(image: &Image, w: u32, h: u32, smooth: bool) -> Option<Arc<sk::Pixmap>> {
     let texture = match image.kind() {
         ImageKind::Raster(raster) => {
             let mut texture = sk::Pixmap::new(w, h)?;
@@ -73,10 +77,12 @@ fn build_texture(image: &Image, w: u32, h: u32) -> Option<Arc<sk::Pixmap>> {
                 dynamic
             } else {
                 let upscale = w > dynamic.width();
-                let filter = match image.scaling() {
-                    Smart::Custom(ImageScaling::Pixelated) => FilterType::Nearest,
-                    _ if upscale => FilterType::CatmullRom,
-                    _ => FilterType::Lanczos3, // downscale
+                let filter = if !smooth {
+                    FilterType::Nearest
+                } else if upscale {
+                    FilterType::CatmullRom
+                } else {
+                    FilterType::Lanczos3 // downscale
                 };
                 buf = dynamic.resize_exact(w, h, filter);
                 &buf
@@ -5,58 +5,310 @@ mod paint;
 mod shape;
 mod text;
 
+use std::cell::OnceCell;
+
 use tiny_skia as sk;
+use typst_library::diag::{EcoString, StrResult, eco_format};
+use typst_library::foundations::Smart;
 use typst_library::layout::{
-    Abs, Axes, Frame, FrameItem, FrameKind, GroupItem, Page, PagedDocument, Point, Size,
-    Transform,
+    Abs, Axes, Frame, FrameItem, FrameKind, GroupItem, Page, PagedDocument, Point, Rect,
+    Size, Transform,
 };
+use typst_library::pdf::LayerFilter;
 use typst_library::visualize::{Color, Geometry, Paint};
 
+/// Settings for rendering.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct RenderOptions {
+    /// Whether to leave the background transparent instead of filling it with
+    /// the page's fill.
+    pub transparent: bool,
+    /// Whether to smooth the edges of shapes and text. Without anti-aliasing,
+    /// each pixel is either fully covered or not at all.
+    pub anti_alias: bool,
+    /// Whether raster images are smoothed or pixelated when scaled. When
+    /// `Auto`, the scaling that each image requests is respected.
+    pub image_smoothing: Smart<bool>,
+    /// The color space of the resulting pixels.
+    pub color_space: RenderColorSpace,
+    /// Which of the document's [layers](typst_library::pdf::LayerElem) to
+    /// include. By default, the visible ones are included.
+    pub layers: LayerFilter,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            transparent: false,
+            anti_alias: true,
+            image_smoothing: Smart::Auto,
+            color_space: RenderColorSpace::Srgb,
+            layers: LayerFilter::default(),
+        }
+    }
+}
+
+/// The color space of rendered pixels.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RenderColorSpace {
+    /// Regular sRGB colors.
+    #[default]
+    Srgb,
+    /// Shades of gray, e.g. for e-ink displays. The pixels still have red,
+    /// green and blue components, which are all equal.
+    Grayscale,
+}
+
 /// Export a page into a raster image.
 ///
 /// This renders the page at the given number of pixels per point and returns
 /// the resulting `tiny-skia` pixel buffer.
+///
+/// Fails if the image is too large to allocate. Use [`render_tiles`] to render
+/// such a page in parts.
+pub fn render(page: &Page, pixel_per_pt: f32) -> StrResult<sk::Pixmap> {
+    render_with_options(page, pixel_per_pt, &RenderOptions::default())
+}
+
+/// Export a page into a raster image with the given settings.
+///
+/// Fails if the image is too large to allocate, like [`render`].
 #[typst_macros::time(name = "render")]
-pub fn render(page: &Page, pixel_per_pt: f32) -> sk::Pixmap {
+pub fn render_with_options(
+    page: &Page,
+    pixel_per_pt: f32,
+    options: &RenderOptions,
+) -> StrResult<sk::Pixmap> {
+    let (pxw, pxh) = pixel_size(page, pixel_per_pt);
+    let rect =
+        sk::IntRect::from_xywh(0, 0, pxw, pxh).ok_or_else(|| too_large(pxw, pxh))?;
+    render_pixels(page, pixel_per_pt, rect, options)
+}
+
+/// Export a rectangular region of a page into a raster image.
+///
+/// The region is given in points relative to the top left corner of the page
+/// and may extend beyond the page. Its edges are rounded to whole pixels in the
+/// same way as the page's size, so that adjacent regions fit together
+/// seamlessly and a region that covers the whole page yields the same image as
+/// [`render_with_options`].
+///
+/// Fails if the image is too large to allocate.
+#[typst_macros::time(name = "render region")]
+pub fn render_region(
+    page: &Page,
+    pixel_per_pt: f32,
+    region: Rect,
+    options: &RenderOptions,
+) -> StrResult<sk::Pixmap> {
+    let to_px = |v: Abs| (pixel_per_pt * v.to_f32()).round();
+    let (x0, y0) = (to_px(region.min.x), to_px(region.min.y));
+    let (x1, y1) = (to_px(region.max.x), to_px(region.max.y));
+    let width = (x1 - x0).max(1.0) as u32;
+    let height = (y1 - y0).max(1.0) as u32;
+    let rect = sk::IntRect::from_xywh(x0 as i32, y0 as i32, width, height)
+        .ok_or_else(|| too_large(width, height))?;
+    render_pixels(page, pixel_per_pt, rect, options)
+}
+
+/// Export a page into raster image tiles of at most `tile_size` pixels in
+/// each dimension.
+///
+/// The tiles are produced row by row, from the top left to the bottom right.
+/// Pieced together, they yield the same image as [`render_with_options`], but
+/// only one tile needs to be held in memory at a time. This makes it possible
+/// to process pages that are too large to render at once. The only exception
+/// are curves that cross the border of a tile: Since they are clipped to the
+/// tile before rasterization, their edges may be shaded slightly differently.
+///
+/// A tile fails to render if a paint of the page, like a gradient relative to
+/// the whole page, is too large to allocate.
+///
+/// # Panics
+/// Panics if `tile_size` is zero.
+pub fn render_tiles<'a>(
+    page: &'a Page,
+    pixel_per_pt: f32,
+    tile_size: u32,
+    options: &'a RenderOptions,
+) -> Tiles<'a> {
+    assert!(tile_size > 0, "tile size must be positive");
+    let (width, height) = pixel_size(page, pixel_per_pt);
+    Tiles {
+        page,
+        pixel_per_pt,
+        options,
+        tile_size,
+        width,
+        height,
+        x: 0,
+        y: 0,
+    }
+}
+
+/// An iterator over the tiles of a page, created by [`render_tiles`].
+pub struct Tiles<'a> {
+    page: &'a Page,
+    pixel_per_pt: f32,
+    options: &'a RenderOptions,
+    tile_size: u32,
+    /// The width of the whole page in pixels.
+    width: u32,
+    /// The height of the whole page in pixels.
+    height: u32,
+    /// The pixel position of the next tile.
+    x: u32,
+    y: u32,
+}
+
+impl Tiles<'_> {
+    /// The width of the whole page in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the whole page in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+impl Iterator for Tiles<'_> {
+    type Item = StrResult<Tile>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.y >= self.height {
+            return None;
+        }
+
+        let (x, y) = (self.x, self.y);
+        let w = self.tile_size.min(self.width - x);
+        let h = self.tile_size.min(self.height - y);
+        self.x += w;
+        if self.x >= self.width {
+            self.x = 0;
+            self.y += h;
+        }
+
+        let result = sk::IntRect::from_xywh(x as i32, y as i32, w, h)
+            .ok_or_else(|| too_large(self.width, self.height))
+            .and_then(|rect| {
+                render_pixels(self.page, self.pixel_per_pt, rect, self.options)
+            })
+            .map(|pixmap| Tile { x, y, pixmap });
+        Some(result)
+    }
+}
+
+/// A part of a page, rendered by [`render_tiles`].
+pub struct Tile {
+    /// The horizontal pixel position of the tile within the page.
+    pub x: u32,
+    /// The vertical pixel position of the tile within the page.
+    pub y: u32,
+    /// The tile's pixels.
+    pub pixmap: sk::Pixmap,
+}
+
+/// The size of a whole page in pixels.
+fn pixel_size(page: &Page, pixel_per_pt: f32) -> (u32, u32) {
     let size = page.frame.size();
     let pxw = (pixel_per_pt * size.x.to_f32()).round().max(1.0) as u32;
     let pxh = (pixel_per_pt * size.y.to_f32()).round().max(1.0) as u32;
+    (pxw, pxh)
+}
 
-    let ts = sk::Transform::from_scale(pixel_per_pt, pixel_per_pt);
-    let state = State::new(size, ts, pixel_per_pt);
+/// Render the pixels of a page within the given rectangle.
+fn render_pixels(
+    page: &Page,
+    pixel_per_pt: f32,
+    rect: sk::IntRect,
+    options: &RenderOptions,
+) -> StrResult<sk::Pixmap> {
+    let size = page.frame.size();
+    let ts = sk::Transform::from_translate(-(rect.x() as f32), -(rect.y() as f32))
+        .pre_scale(pixel_per_pt, pixel_per_pt);
+    let error = OnceCell::new();
+    let state = State::new(size, ts, pixel_per_pt, options, &error);
 
-    let mut canvas = sk::Pixmap::new(pxw, pxh).unwrap();
+    let mut canvas = create_pixmap(rect.width(), rect.height())?;
 
-    if let Some(fill) = page.fill_or_white() {
+    if !options.transparent
+        && let Some(fill) = page.fill_or_white()
+    {
         if let Paint::Solid(color) = fill {
             canvas.fill(paint::to_sk_color(color));
         } else {
-            let rect = Geometry::Rect(page.frame.size()).filled(fill);
+            let rect = Geometry::Rect(size).filled(fill);
             shape::render_shape(&mut canvas, state, &rect);
         }
     }
 
     render_frame(&mut canvas, state, &page.frame);
+    if let Some(error) = error.into_inner() {
+        return Err(error);
+    }
+
+    if options.color_space == RenderColorSpace::Grayscale {
+        to_grayscale(&mut canvas);
+    }
+
+    Ok(canvas)
+}
+
+/// Convert the pixels of a pixmap to shades of gray.
+fn to_grayscale(canvas: &mut sk::Pixmap) {
+    for pixel in canvas.pixels_mut() {
+        // Since the weights add up to one, the gray value doesn't exceed the
+        // premultiplied alpha.
+        let (r, g, b) = (pixel.red() as f32, pixel.green() as f32, pixel.blue() as f32);
+        let gray = (0.2126 * r + 0.7152 * g + 0.0722 * b).round() as u8;
+        *pixel = sk::PremultipliedColorU8::from_rgba(gray, gray, gray, pixel.alpha())
+            .unwrap_or(*pixel);
+    }
+}
 
-    canvas
+/// Create an empty pixmap, failing instead of aborting if there isn't enough
+/// memory for it.
+fn create_pixmap(width: u32, height: u32) -> StrResult<sk::Pixmap> {
+    let size =
+        sk::IntSize::from_wh(width, height).ok_or_else(|| too_large(width, height))?;
+    let len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|len| len.checked_mul(4))
+        .ok_or_else(|| too_large(width, height))?;
+    let mut data = Vec::new();
+    data.try_reserve_exact(len).map_err(|_| too_large(width, height))?;
+    data.resize(len, 0);
+    sk::Pixmap::from_vec(data, size).ok_or_else(|| too_large(width, height))
+}
+
+/// The error for an image that can't be allocated.
+fn too_large(width: u32, height: u32) -> EcoString {
+    eco_format!("image of {width}x{height} pixels is too large")
 }
 
 /// Export a document with potentially multiple pages into a single raster image.
+///
+/// Fails if the image is too large to allocate.
 pub fn render_merged(
     document: &PagedDocument,
     pixel_per_pt: f32,
     gap: Abs,
     fill: Option<Color>,
-) -> sk::Pixmap {
-    let pixmaps: Vec<_> =
-        document.pages.iter().map(|page| render(page, pixel_per_pt)).collect();
+) -> StrResult<sk::Pixmap> {
+    let pixmaps = document
+        .pages
+        .iter()
+        .map(|page| render(page, pixel_per_pt))
+        .collect::<StrResult<Vec<_>>>()?;
 
     let gap = (pixel_per_pt * gap.to_f32()).round() as u32;
     let pxw = pixmaps.iter().map(sk::Pixmap::width).max().unwrap_or_default();
     let pxh = pixmaps.iter().map(|pixmap| pixmap.height()).sum::<u32>()
         + gap * pixmaps.len().saturating_sub(1) as u32;
 
-    let mut canvas = sk::Pixmap::new(pxw, pxh).unwrap();
+    let mut canvas = create_pixmap(pxw.max(1), pxh.max(1))?;
     if let Some(fill) = fill {
         canvas.fill(paint::to_sk_color(fill));
     }
@@ -75,11 +327,11 @@ pub fn render_merged(
         y += pixmap.height() + gap;
     }
 
-    canvas
+    Ok(canvas)
 }
 
 /// Additional metadata carried through the rendering process.
-#[derive(Default, Copy, Clone)]
+#[derive(Copy, Clone)]
 struct State<'a> {
     /// The transform of the current item.
     transform: sk::Transform,
@@ -91,19 +343,43 @@ struct State<'a> {
     pixel_per_pt: f32,
     /// The size of the first hard frame in the hierarchy.
     size: Size,
+    /// The settings for rendering.
+    options: &'a RenderOptions,
+    /// The first error that occurred while rendering, e.g. because the pixels
+    /// of a paint couldn't be allocated.
+    error: &'a OnceCell<EcoString>,
 }
 
 impl<'a> State<'a> {
-    fn new(size: Size, transform: sk::Transform, pixel_per_pt: f32) -> Self {
+    fn new(
+        size: Size,
+        transform: sk::Transform,
+        pixel_per_pt: f32,
+        options: &'a RenderOptions,
+        error: &'a OnceCell<EcoString>,
+    ) -> Self {
         Self {
             size,
             transform,
             container_transform: transform,
+            mask: None,
             pixel_per_pt,
-            ..Default::default()
+            options,
+            error,
         }
     }
 
+    /// Turns a failure into `None` and records its error, which is reported
+    /// once rendering is done.
+    fn ok<T>(&self, result: StrResult<T>) -> Option<T> {
+        result
+            .map_err(|error| {
+                // Only the first error is reported.
+                let _ = self.error.set(error);
+            })
+            .ok()
+    }
+
     /// Pre translate the current item's transform.
     fn pre_translate(self, pos: Point) -> Self {
         Self {
@@ -153,7 +429,13 @@ fn render_frame(canvas: &mut sk::Pixmap, state: State, frame: &Frame) {
     for (pos, item) in frame.items() {
         match item {
             FrameItem::Group(group) => {
-                render_group(canvas, state, *pos, group);
+                let included = group
+                    .layer
+                    .as_ref()
+                    .is_none_or(|layer| state.options.layers.includes(layer));
+                if included {
+                    render_group(canvas, state, *pos, group);
+                }
             }
             FrameItem::Text(text) => {
                 text::render_text(canvas, state.pre_translate(*pos), text);
@@ -165,6 +447,8 @@ fn render_frame(canvas: &mut sk::Pixmap, state: State, frame: &Frame) {
                 image::render_image(canvas, state.pre_translate(*pos), image, *size);
             }
             FrameItem::Link(_, _) => {}
+            FrameItem::Widget(_, _) => {}
+            FrameItem::Annotation(_, _) => {}
             FrameItem::Tag(_) => {}
         }
     }
@@ -199,7 +483,7 @@ fn render_group(canvas: &mut sk::Pixmap, state: State, pos: Point, group: &Group
             mask.intersect_path(
                 &path,
                 sk::FillRule::default(),
-                true,
+                state.options.anti_alias,
                 sk::Transform::default(),
             );
             storage = mask;
@@ -215,7 +499,7 @@ fn render_group(canvas: &mut sk::Pixmap, state: State, pos: Point, group: &Group
             mask.fill_path(
                 &path,
                 sk::FillRule::default(),
-                true,
+                state.options.anti_alias,
                 sk::Transform::default(),
             );
             storage = mask;
@@ -250,3 +534,203 @@ impl AbsExt for Abs {
         self.to_pt() as f32
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    use typst_library::foundations::{Bytes, Content};
+    use typst_library::layout::{Angle, Ratio, Sides};
+    use typst_library::visualize::{
+        ColorSpace, Curve, ExchangeFormat, FixedStroke, Gradient, Image, ImageScaling,
+        LinearGradient, RasterImage, RelativeTo,
+    };
+    use typst_syntax::Span;
+
+    use super::*;
+
+    /// Creates a page with a stroked rectangle and a gradient that spans the
+    /// whole page.
+    fn page() -> Page {
+        let gradient = Gradient::Linear(Arc::new(LinearGradient {
+            stops: vec![(Color::BLACK, Ratio::zero()), (Color::YELLOW, Ratio::one())],
+            angle: Angle::zero(),
+            space: ColorSpace::Srgb,
+            relative: Smart::Custom(RelativeTo::Parent),
+            anti_alias: true,
+        }));
+
+        let rect = Geometry::Rect(Size::new(Abs::pt(61.7), Abs::pt(30.3)));
+        let bar = Geometry::Rect(Size::new(Abs::pt(33.3), Abs::pt(20.1)));
+        let mut frame = Frame::hard(Size::new(Abs::pt(100.0), Abs::pt(50.0)));
+        for (pos, shape) in [
+            (at(5.3, 7.9), rect.clone().filled(Color::GREEN)),
+            (at(5.3, 7.9), rect.stroked(FixedStroke::default())),
+            (at(60.3, 25.2), bar.filled(gradient)),
+        ] {
+            frame.push(pos, FrameItem::Shape(shape, Span::detached()));
+        }
+
+        wrap(frame)
+    }
+
+    /// Creates a page with a black circle.
+    fn circle() -> Page {
+        let circle = Geometry::Curve(Curve::ellipse(Size::splat(Abs::pt(30.0))));
+        let mut frame = Frame::hard(Size::splat(Abs::pt(40.0)));
+        let shape = circle.filled(Color::BLACK);
+        frame.push(at(5.0, 5.0), FrameItem::Shape(shape, Span::detached()));
+        wrap(frame)
+    }
+
+    /// Creates a page with a black and a white pixel scaled up to fill it.
+    fn pixels(scaling: Smart<ImageScaling>) -> Page {
+        let mut image = ::image::RgbImage::new(2, 1);
+        image.put_pixel(1, 0, ::image::Rgb([255, 255, 255]));
+        let mut data = vec![];
+        image
+            .write_to(&mut Cursor::new(&mut data), ::image::ImageFormat::Png)
+            .unwrap();
+
+        let raster = RasterImage::plain(Bytes::new(data), ExchangeFormat::Png).unwrap();
+        let size = Size::new(Abs::pt(20.0), Abs::pt(10.0));
+        let mut frame = Frame::hard(size);
+        let image = Image::new(raster, None, scaling);
+        frame.push(Point::zero(), FrameItem::Image(image, size, Span::detached()));
+        wrap(frame)
+    }
+
+    /// Wraps a frame into a page with the default fill.
+    fn wrap(frame: Frame) -> Page {
+        Page {
+            frame,
+            fill: Smart::Auto,
+            numbering: None,
+            supplement: Content::empty(),
+            number: 1,
+            bleed: Sides::splat(Abs::zero()),
+            slug: Sides::splat(Abs::zero()),
+        }
+    }
+
+    /// A position on the page.
+    fn at(x: f64, y: f64) -> Point {
+        Point::new(Abs::pt(x), Abs::pt(y))
+    }
+
+    #[test]
+    fn test_render_tiles() {
+        let page = page();
+        let options = RenderOptions::default();
+        let whole = render(&page, 1.5).unwrap();
+
+        let tiles = render_tiles(&page, 1.5, 32, &options);
+        assert_eq!((tiles.width(), tiles.height()), (150, 75));
+
+        let mut stitched = vec![0; whole.data().len()];
+        let mut count = 0;
+        for tile in tiles {
+            let tile = tile.unwrap();
+            assert!(tile.pixmap.width() <= 32 && tile.pixmap.height() <= 32);
+            let row = 4 * tile.pixmap.width() as usize;
+            for (i, pixels) in tile.pixmap.data().chunks(row).enumerate() {
+                let start = 4 * ((tile.y as usize + i) * 150 + tile.x as usize);
+                stitched[start..start + row].copy_from_slice(pixels);
+            }
+            count += 1;
+        }
+
+        assert_eq!(count, 5 * 3);
+        assert!(stitched == whole.data());
+    }
+
+    #[test]
+    fn test_render_region() {
+        let page = page();
+        let whole = render(&page, 2.0).unwrap();
+        let region = Rect::new(at(10.0, 20.0), at(60.0, 45.0));
+        let part = render_region(&page, 2.0, region, &RenderOptions::default()).unwrap();
+
+        assert_eq!((part.width(), part.height()), (100, 50));
+        for y in 0..50 {
+            for x in 0..100 {
+                assert_eq!(part.pixel(x, y), whole.pixel(x + 20, y + 40));
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_too_large() {
+        let page = page();
+        assert!(render(&page, 1e6).is_err());
+
+        // The gradient's pixels span the whole page, so even a small tile
+        // can't be rendered.
+        let options = RenderOptions::default();
+        let mut tiles = render_tiles(&page, 1e6, 32, &options);
+        assert!(tiles.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_render_transparent() {
+        let page = page();
+        let opaque = render(&page, 1.0).unwrap();
+        let options = RenderOptions { transparent: true, ..Default::default() };
+        let transparent = render_with_options(&page, 1.0, &options).unwrap();
+
+        assert_eq!(opaque.pixel(1, 1).unwrap().alpha(), 255);
+        assert_eq!(transparent.pixel(1, 1).unwrap().alpha(), 0);
+        assert_eq!(opaque.pixel(20, 20), transparent.pixel(20, 20));
+    }
+
+    #[test]
+    fn test_render_anti_alias() {
+        let page = circle();
+        let blends = |anti_alias| {
+            let options = RenderOptions { anti_alias, ..Default::default() };
+            let pixmap = render_with_options(&page, 2.0, &options).unwrap();
+            pixmap.pixels().iter().any(|pixel| !matches!(pixel.red(), 0 | 255))
+        };
+
+        assert!(blends(true));
+        assert!(!blends(false));
+    }
+
+    #[test]
+    fn test_render_grayscale() {
+        let page = page();
+        let srgb = render(&page, 1.5).unwrap();
+        let options = RenderOptions {
+            color_space: RenderColorSpace::Grayscale,
+            ..Default::default()
+        };
+        let gray = render_with_options(&page, 1.5, &options).unwrap();
+
+        for (color, gray) in srgb.pixels().iter().zip(gray.pixels()) {
+            let (r, g, b) =
+                (color.red() as f32, color.green() as f32, color.blue() as f32);
+            let expected = (0.2126 * r + 0.7152 * g + 0.0722 * b).round() as u8;
+            assert_eq!(
+                (gray.red(), gray.green(), gray.blue()),
+                (expected, expected, expected)
+            );
+            assert_eq!(gray.alpha(), color.alpha());
+        }
+    }
+
+    #[test]
+    fn test_render_image_smoothing() {
+        let blends = |scaling, image_smoothing| {
+            let options = RenderOptions { image_smoothing, ..Default::default() };
+            let pixmap = render_with_options(&pixels(scaling), 2.0, &options).unwrap();
+            pixmap.pixels().iter().any(|pixel| !matches!(pixel.red(), 0 | 255))
+        };
+
+        let pixelated = Smart::Custom(ImageScaling::Pixelated);
+        assert!(blends(Smart::Auto, Smart::Auto));
+        assert!(!blends(pixelated, Smart::Auto));
+        assert!(!blends(Smart::Auto, Smart::Custom(false)));
+        assert!(blends(pixelated, Smart::Custom(true)));
+    }
+}
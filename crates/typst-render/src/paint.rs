@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use tiny_skia as sk;
+use typst_library::diag::StrResult;
 use typst_library::layout::{Axes, Point, Ratio, Size};
 use typst_library::visualize::{Color, Gradient, Paint, RelativeTo, Tiling};
 
@@ -128,6 +129,9 @@ impl PaintSampler for TilingSampler<'_> {
 ///
 /// `gradient_map` is used to scale and move the gradient being sampled,
 /// this is used to line up the stroke and the fill of a shape.
+///
+/// Returns `None` and records the error in the state if the pixels of a
+/// gradient or tiling can't be allocated.
 pub fn to_sk_paint<'a>(
     paint: &Paint,
     state: State,
@@ -136,7 +140,7 @@ pub fn to_sk_paint<'a>(
     fill_transform: Option<sk::Transform>,
     pixmap: &'a mut Option<Arc<sk::Pixmap>>,
     gradient_map: Option<(Point, Axes<Ratio>)>,
-) -> sk::Paint<'a> {
+) -> Option<sk::Paint<'a>> {
     /// Actual sampling of the gradient, cached for performance.
     #[comemo::memoize]
     fn cached(
@@ -144,10 +148,10 @@ pub fn to_sk_paint<'a>(
         width: u32,
         height: u32,
         gradient_map: Option<(Point, Axes<Ratio>)>,
-    ) -> Arc<sk::Pixmap> {
+    ) -> StrResult<Arc<sk::Pixmap>> {
         let (offset, scale) =
             gradient_map.unwrap_or_else(|| (Point::zero(), Axes::splat(Ratio::one())));
-        let mut pixmap = sk::Pixmap::new(width.max(1), height.max(1)).unwrap();
+        let mut pixmap = crate::create_pixmap(width.max(1), height.max(1))?;
         for x in 0..width {
             for y in 0..height {
                 let color = gradient.sample_at(
@@ -163,13 +167,13 @@ pub fn to_sk_paint<'a>(
             }
         }
 
-        Arc::new(pixmap)
+        Ok(Arc::new(pixmap))
     }
 
     let mut sk_paint: sk::Paint<'_> = sk::Paint::default();
     match paint {
         Paint::Solid(color) => {
-            sk_paint.set_color(to_sk_color(*color));
+            sk_paint.set_color(to_sk_color(color.clone()));
             sk_paint.anti_alias = true;
         }
         Paint::Gradient(gradient) => {
@@ -196,12 +200,12 @@ pub fn to_sk_paint<'a>(
             let height =
                 (container_size.y.to_f32().abs() * state.pixel_per_pt).ceil() as u32;
 
-            *pixmap = Some(cached(
+            *pixmap = Some(state.ok(cached(
                 gradient,
                 width.max(state.pixel_per_pt.ceil() as u32),
                 height.max(state.pixel_per_pt.ceil() as u32),
                 gradient_map,
-            ));
+            ))?);
 
             // We can use FilterQuality::Nearest here because we're
             // rendering to a pixmap that is already at native resolution.
@@ -228,7 +232,7 @@ pub fn to_sk_paint<'a>(
                     .post_concat(state.transform.invert().unwrap()),
             };
 
-            let canvas = render_tiling_frame(&state, tilings);
+            let canvas = state.ok(render_tiling_frame(&state, tilings))?;
             *pixmap = Some(Arc::new(canvas));
 
             let offset = match relative {
@@ -251,7 +255,11 @@ pub fn to_sk_paint<'a>(
         }
     }
 
-    sk_paint
+    if !state.options.anti_alias {
+        sk_paint.anti_alias = false;
+    }
+
+    Some(sk_paint)
 }
 
 pub fn to_sk_color(color: Color) -> sk::Color {
@@ -265,17 +273,17 @@ pub fn to_sk_color_u8(color: Color) -> sk::ColorU8 {
     sk::ColorU8::from_rgba(r, g, b, a)
 }
 
-pub fn render_tiling_frame(state: &State, tilings: &Tiling) -> sk::Pixmap {
+pub fn render_tiling_frame(state: &State, tilings: &Tiling) -> StrResult<sk::Pixmap> {
     let size = tilings.size() + tilings.spacing();
-    let mut canvas = sk::Pixmap::new(
-        (size.x.to_f32() * state.pixel_per_pt).round() as u32,
-        (size.y.to_f32() * state.pixel_per_pt).round() as u32,
-    )
-    .unwrap();
+    let mut canvas = crate::create_pixmap(
+        ((size.x.to_f32() * state.pixel_per_pt).round() as u32).max(1),
+        ((size.y.to_f32() * state.pixel_per_pt).round() as u32).max(1),
+    )?;
 
     // Render the tilings into a new canvas.
     let ts = sk::Transform::from_scale(state.pixel_per_pt, state.pixel_per_pt);
-    let temp_state = State::new(tilings.size(), ts, state.pixel_per_pt);
+    let temp_state =
+        State::new(tilings.size(), ts, state.pixel_per_pt, state.options, state.error);
     crate::render_frame(&mut canvas, temp_state, tilings.frame());
-    canvas
+    Ok(canvas)
 }
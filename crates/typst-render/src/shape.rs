@@ -46,7 +46,7 @@ pub fn render_shape(canvas: &mut sk::Pixmap, state: State, shape: &Shape) -> Opt
             None,
             &mut pixmap,
             None,
-        );
+        )?;
 
         if matches!(shape.geometry, Geometry::Rect(_)) {
             paint.anti_alias = false;
@@ -106,7 +106,7 @@ pub fn render_shape(canvas: &mut sk::Pixmap, state: State, shape: &Shape) -> Opt
                 fill_transform,
                 &mut pixmap,
                 gradient_map,
-            );
+            )?;
             let stroke = sk::Stroke {
                 width,
                 line_cap: to_sk_line_cap(*cap),
@@ -50,9 +50,10 @@ fn render_outline_glyph(
     let ppem = text.size.to_f32() * ts.sy;
 
     // Render a glyph directly as a path. This only happens when the fast glyph
-    // rasterization can't be used due to very large text size or weird
-    // scale/skewing transforms.
+    // rasterization can't be used due to very large text size, weird
+    // scale/skewing transforms or disabled anti-aliasing.
     if ppem > 100.0
+        || !state.options.anti_alias
         || ts.kx != 0.0
         || ts.ky != 0.0
         || ts.sx != ts.sy
@@ -83,7 +84,7 @@ fn render_outline_glyph(
             None,
             &mut pixmap,
             None,
-        );
+        )?;
         canvas.fill_path(&path, &paint, rule, ts, state.mask);
 
         if let Some(FixedStroke { paint, thickness, cap, join, dash, miter_limit }) =
@@ -100,7 +101,7 @@ fn render_outline_glyph(
                 None,
                 &mut pixmap,
                 None,
-            );
+            )?;
             let stroke = sk::Stroke {
                 width: thickness.to_f32() / scale, // When we scale the path, we need to scale the stroke width, too.
                 line_cap: shape::to_sk_line_cap(*cap),
@@ -145,11 +146,11 @@ fn render_outline_glyph(
                 canvas,
                 &bitmap,
                 &state,
-                paint::to_sk_color_u8(*color).premultiply(),
+                paint::to_sk_color_u8(color.clone()).premultiply(),
             )?;
         }
         Paint::Tiling(tiling) => {
-            let pixmap = paint::render_tiling_frame(&state, tiling);
+            let pixmap = state.ok(paint::render_tiling_frame(&state, tiling))?;
             let sampler = TilingSampler::new(tiling, &pixmap, &state, true);
             write_bitmap(canvas, &bitmap, &state, sampler)?;
         }
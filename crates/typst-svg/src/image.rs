@@ -20,10 +20,17 @@ impl SVGRenderer<'_> {
         image: &Image,
         size: &Axes<Abs>,
     ) {
-        let url = convert_image_to_base64_url(image);
+        let url = self
+            .options
+            .image_href
+            .and_then(|href| href(image))
+            .unwrap_or_else(|| convert_image_to_base64_url(image));
         self.xml.start_element("image");
         if !state.transform.is_identity() {
-            self.xml.write_attribute("transform", &SvgMatrix(state.transform));
+            self.xml.write_attribute(
+                "transform",
+                &SvgMatrix(state.transform, self.options.precision),
+            );
         }
         self.xml.write_attribute("xlink:href", &url);
         self.xml.write_attribute("width", &size.x.to_pt());
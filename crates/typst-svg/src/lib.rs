@@ -7,27 +7,158 @@ mod text;
 
 pub use image::{convert_image_scaling, convert_image_to_base64_url};
 use rustc_hash::FxHashMap;
+use typst_library::diag::{StrResult, bail};
 use typst_library::introspection::Introspector;
 use typst_library::model::Destination;
+use typst_library::pdf::LayerFilter;
 
-use std::fmt::{self, Display, Formatter, Write};
+use std::fmt::{self, Debug, Display, Formatter, Write};
 
 use ecow::EcoString;
 use typst_library::layout::{
     Abs, Frame, FrameItem, FrameKind, GroupItem, Page, PagedDocument, Point, Ratio, Size,
     Transform,
 };
-use typst_library::visualize::{Geometry, Gradient, Tiling};
+use typst_library::visualize::{Geometry, Gradient, Image, Tiling};
 use typst_utils::hash128;
 use xmlwriter::XmlWriter;
 
 use crate::paint::{GradientRef, SVGSubGradient, TilingRef};
 use crate::text::RenderedGlyph;
 
+/// Settings for SVG export.
+#[derive(Clone)]
+pub struct SvgOptions<'a> {
+    /// Whether to overlay the rendered glyphs with invisible text. This makes
+    /// the text of the SVG selectable and searchable, and readable by screen
+    /// readers and search engines. It increases the size of the file.
+    pub selectable_text: bool,
+    /// The maximum number of fractional digits of the coordinates in paths,
+    /// transforms and glyph positions. When `None`, they are written with full
+    /// precision. Fewer digits make the file smaller.
+    pub precision: Option<u8>,
+    /// Whether each glyph is defined once and then referenced wherever it
+    /// appears, also across the pages of a [merged](svg_merged) SVG. If not,
+    /// glyphs are drawn in place. This makes the file larger, but easier to
+    /// process for tools that don't support `<use>` elements.
+    pub deduplicate_glyphs: bool,
+    /// Determines the URL of an image. If it returns `None` or is not given,
+    /// the image is embedded into the SVG as a data URL.
+    pub image_href: Option<&'a ImageHref<'a>>,
+    /// A prefix for all ids in the SVG. Several SVGs that are inlined into the
+    /// same HTML page need different prefixes, so that their ids don't clash.
+    pub id_prefix: IdPrefix,
+    /// Which of the document's [layers](typst_library::pdf::LayerElem) to
+    /// include. By default, the visible ones are included.
+    pub layers: LayerFilter,
+}
+
+/// Determines the URL of an image for [`SvgOptions::image_href`].
+pub type ImageHref<'a> = dyn Fn(&Image) -> Option<EcoString> + Sync + 'a;
+
+impl Default for SvgOptions<'_> {
+    fn default() -> Self {
+        Self {
+            selectable_text: false,
+            precision: None,
+            deduplicate_glyphs: true,
+            image_href: None,
+            id_prefix: IdPrefix::default(),
+            layers: LayerFilter::default(),
+        }
+    }
+}
+
+impl Debug for SvgOptions<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("SvgOptions")
+            .field("selectable_text", &self.selectable_text)
+            .field("precision", &self.precision)
+            .field("deduplicate_glyphs", &self.deduplicate_glyphs)
+            .field("image_href", &self.image_href.map(|_| ".."))
+            .field("id_prefix", &self.id_prefix)
+            .field("layers", &self.layers)
+            .finish()
+    }
+}
+
+/// A prefix for the ids in an SVG.
+///
+/// The prefix is either empty or an XML name without colons (an `NCName`), so
+/// that the ids starting with it are valid, too.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct IdPrefix(EcoString);
+
+impl IdPrefix {
+    /// Validates and creates an id prefix.
+    pub fn new(prefix: &str) -> StrResult<Self> {
+        let mut chars = prefix.chars();
+        if let Some(c) = chars.next()
+            && !is_name_start_char(c)
+        {
+            bail!("id prefix must start with a letter or an underscore, found `{c}`");
+        }
+        if let Some(c) = chars.find(|&c| !is_name_start_char(c) && !is_name_char(c)) {
+            bail!("id prefix must not contain `{c}`");
+        }
+        Ok(Self(prefix.into()))
+    }
+
+    /// The prefix as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for IdPrefix {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Whether a character can start an XML name, excluding colons.
+///
+/// See <https://www.w3.org/TR/xml/#NT-NameStartChar>.
+fn is_name_start_char(c: char) -> bool {
+    matches!(c,
+        'A'..='Z' | '_' | 'a'..='z'
+        | '\u{C0}'..='\u{D6}'
+        | '\u{D8}'..='\u{F6}'
+        | '\u{F8}'..='\u{2FF}'
+        | '\u{370}'..='\u{37D}'
+        | '\u{37F}'..='\u{1FFF}'
+        | '\u{200C}'..='\u{200D}'
+        | '\u{2070}'..='\u{218F}'
+        | '\u{2C00}'..='\u{2FEF}'
+        | '\u{3001}'..='\u{D7FF}'
+        | '\u{F900}'..='\u{FDCF}'
+        | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+
+/// Whether a character can appear in an XML name after the first character,
+/// in addition to those that can start it.
+///
+/// See <https://www.w3.org/TR/xml/#NT-NameChar>.
+fn is_name_char(c: char) -> bool {
+    matches!(c,
+        '-' | '.' | '0'..='9' | '\u{B7}'
+        | '\u{300}'..='\u{36F}'
+        | '\u{203F}'..='\u{2040}'
+    )
+}
+
 /// Export a frame into a SVG file.
 #[typst_macros::time(name = "svg")]
 pub fn svg(page: &Page) -> String {
-    let mut renderer = SVGRenderer::new();
+    svg_with_options(page, &SvgOptions::default())
+}
+
+/// Export a frame into a SVG file with custom settings.
+#[typst_macros::time(name = "svg")]
+pub fn svg_with_options(page: &Page, options: &SvgOptions) -> String {
+    let mut renderer = SVGRenderer::new(options);
     renderer.write_header(page.frame.size());
 
     let state = State::new(page.frame.size(), Transform::identity());
@@ -38,7 +169,13 @@ pub fn svg(page: &Page) -> String {
 /// Export a frame into a SVG file.
 #[typst_macros::time(name = "svg frame")]
 pub fn svg_frame(frame: &Frame) -> String {
-    let mut renderer = SVGRenderer::new();
+    svg_frame_with_options(frame, &SvgOptions::default())
+}
+
+/// Export a frame into a SVG file with custom settings.
+#[typst_macros::time(name = "svg frame")]
+pub fn svg_frame_with_options(frame: &Frame, options: &SvgOptions) -> String {
+    let mut renderer = SVGRenderer::new(options);
     renderer.write_header(frame.size());
 
     let state = State::new(frame.size(), Transform::identity());
@@ -54,6 +191,7 @@ pub fn svg_html_frame(
     id: Option<&str>,
     link_points: &[(Point, EcoString)],
     introspector: &Introspector,
+    options: &SvgOptions,
 ) -> String {
     let mut renderer = SVGRenderer::with_options(
         xmlwriter::Options {
@@ -61,6 +199,7 @@ pub fn svg_html_frame(
             ..Default::default()
         },
         Some(introspector),
+        options,
     );
     renderer.write_header_with_custom_attrs(frame.size(), |xml| {
         if let Some(id) = id {
@@ -91,6 +230,18 @@ pub fn svg_html_frame(
 ///
 /// The padding will be added around and between the individual frames.
 pub fn svg_merged(document: &PagedDocument, padding: Abs) -> String {
+    svg_merged_with_options(document, padding, &SvgOptions::default())
+}
+
+/// Export a document with potentially multiple pages into a single SVG file
+/// with custom settings.
+///
+/// The padding will be added around and between the individual frames.
+pub fn svg_merged_with_options(
+    document: &PagedDocument,
+    padding: Abs,
+    options: &SvgOptions,
+) -> String {
     let width = 2.0 * padding
         + document
             .pages
@@ -105,7 +256,7 @@ pub fn svg_merged(document: &PagedDocument, padding: Abs) -> String {
             .map(|page| page.frame.height() + padding)
             .sum::<Abs>();
 
-    let mut renderer = SVGRenderer::new();
+    let mut renderer = SVGRenderer::new(options);
     renderer.write_header(Size::new(width, height));
 
     let [x, mut y] = [padding; 2];
@@ -125,6 +276,8 @@ struct SVGRenderer<'a> {
     xml: XmlWriter,
     /// The document's introspector, if we're writing an HTML frame.
     introspector: Option<&'a Introspector>,
+    /// Settings for the export.
+    options: &'a SvgOptions<'a>,
     /// Prepared glyphs.
     glyphs: Deduplicator<RenderedGlyph>,
     /// Clip paths are used to clip a group. A clip path is a path that defines
@@ -201,25 +354,28 @@ impl State {
 
 impl<'a> SVGRenderer<'a> {
     /// Create a new SVG renderer with empty glyph and clip path.
-    fn new() -> Self {
-        Self::with_options(Default::default(), None)
+    fn new(options: &'a SvgOptions<'a>) -> Self {
+        Self::with_options(Default::default(), None, options)
     }
 
     /// Create a new SVG renderer with the given configuration.
     fn with_options(
-        options: xmlwriter::Options,
+        xml: xmlwriter::Options,
         introspector: Option<&'a Introspector>,
+        options: &'a SvgOptions<'a>,
     ) -> Self {
+        let prefix = &options.id_prefix.0;
         SVGRenderer {
-            xml: XmlWriter::new(options),
+            xml: XmlWriter::new(xml),
             introspector,
-            glyphs: Deduplicator::new('g'),
-            clip_paths: Deduplicator::new('c'),
-            gradient_refs: Deduplicator::new('g'),
-            gradients: Deduplicator::new('f'),
-            conic_subgradients: Deduplicator::new('s'),
-            tiling_refs: Deduplicator::new('p'),
-            tilings: Deduplicator::new('t'),
+            options,
+            glyphs: Deduplicator::new(prefix.clone(), 'g'),
+            clip_paths: Deduplicator::new(prefix.clone(), 'c'),
+            gradient_refs: Deduplicator::new(prefix.clone(), 'g'),
+            gradients: Deduplicator::new(prefix.clone(), 'f'),
+            conic_subgradients: Deduplicator::new(prefix.clone(), 's'),
+            tiling_refs: Deduplicator::new(prefix.clone(), 'p'),
+            tilings: Deduplicator::new(prefix.clone(), 't'),
         }
     }
 
@@ -262,7 +418,8 @@ impl<'a> SVGRenderer<'a> {
 
         if !ts.is_identity() {
             self.xml.start_element("g");
-            self.xml.write_attribute("transform", &SvgMatrix(ts));
+            self.xml
+                .write_attribute("transform", &SvgMatrix(ts, self.options.precision));
         }
 
         self.render_frame(state, &page.frame);
@@ -279,13 +436,23 @@ impl<'a> SVGRenderer<'a> {
         for (pos, item) in frame.items() {
             let state = state.pre_translate(*pos);
             match item {
-                FrameItem::Group(group) => self.render_group(&state, group),
+                FrameItem::Group(group) => {
+                    if group
+                        .layer
+                        .as_ref()
+                        .is_none_or(|layer| self.options.layers.includes(layer))
+                    {
+                        self.render_group(&state, group)
+                    }
+                }
                 FrameItem::Text(text) => self.render_text(&state, text),
                 FrameItem::Shape(shape, _) => self.render_shape(&state, shape),
                 FrameItem::Image(image, size, _) => {
                     self.render_image(&state, image, size)
                 }
                 FrameItem::Link(dest, size) => self.render_link(&state, dest, *size),
+                FrameItem::Widget(_, _) => {}
+                FrameItem::Annotation(_, _) => {}
                 FrameItem::Tag(_) => {}
             };
         }
@@ -304,7 +471,10 @@ impl<'a> SVGRenderer<'a> {
             FrameKind::Hard => {
                 let transform = state.transform.pre_concat(group.transform);
                 if !transform.is_identity() {
-                    self.xml.write_attribute("transform", &SvgMatrix(transform));
+                    self.xml.write_attribute(
+                        "transform",
+                        &SvgMatrix(transform, self.options.precision),
+                    );
                 }
                 state
                     .with_transform(Transform::identity())
@@ -319,9 +489,10 @@ impl<'a> SVGRenderer<'a> {
         if let Some(clip_curve) = &group.clip {
             let offset = Point::new(state.transform.tx, state.transform.ty);
             let hash = hash128(&(&clip_curve, &offset));
-            let id = self
-                .clip_paths
-                .insert_with(hash, || shape::convert_curve(offset, clip_curve));
+            let precision = self.options.precision;
+            let id = self.clip_paths.insert_with(hash, || {
+                shape::convert_curve(offset, clip_curve, precision)
+            });
             self.xml.write_attribute_fmt("clip-path", format_args!("url(#{id})"));
         }
 
@@ -333,7 +504,10 @@ impl<'a> SVGRenderer<'a> {
     fn render_link(&mut self, state: &State, dest: &Destination, size: Size) {
         self.xml.start_element("a");
         if !state.transform.is_identity() {
-            self.xml.write_attribute("transform", &SvgMatrix(state.transform));
+            self.xml.write_attribute(
+                "transform",
+                &SvgMatrix(state.transform, self.options.precision),
+            );
         }
 
         match dest {
@@ -398,7 +572,10 @@ impl<'a> SVGRenderer<'a> {
         }
 
         self.xml.start_element("defs");
-        self.xml.write_attribute("id", "clip-path");
+        self.xml.write_attribute_fmt(
+            "id",
+            format_args!("{}clip-path", self.options.id_prefix),
+        );
 
         for (id, path) in self.clip_paths.iter() {
             self.xml.start_element("clipPath");
@@ -419,14 +596,16 @@ impl<'a> SVGRenderer<'a> {
 /// paths.
 #[derive(Debug, Clone)]
 struct Deduplicator<T> {
+    prefix: EcoString,
     kind: char,
     vec: Vec<(u128, T)>,
     present: FxHashMap<u128, Id>,
 }
 
 impl<T> Deduplicator<T> {
-    fn new(kind: char) -> Self {
+    fn new(prefix: EcoString, kind: char) -> Self {
         Self {
+            prefix,
             kind,
             vec: Vec::new(),
             present: FxHashMap::default(),
@@ -441,11 +620,19 @@ impl<T> Deduplicator<T> {
     where
         F: FnOnce() -> T,
     {
-        *self.present.entry(hash).or_insert_with(|| {
-            let index = self.vec.len();
-            self.vec.push((hash, f()));
-            Id(self.kind, hash, index)
-        })
+        self.present
+            .entry(hash)
+            .or_insert_with(|| {
+                let index = self.vec.len();
+                self.vec.push((hash, f()));
+                Id(self.prefix.clone(), self.kind, hash, index)
+            })
+            .clone()
+    }
+
+    /// Returns the element with the given id.
+    fn get(&self, id: &Id) -> &T {
+        &self.vec[id.3].1
     }
 
     /// Iterate over the elements alongside their ids.
@@ -453,7 +640,7 @@ impl<T> Deduplicator<T> {
         self.vec
             .iter()
             .enumerate()
-            .map(|(i, (id, v))| (Id(self.kind, *id, i), v))
+            .map(|(i, (id, v))| (Id(self.prefix.clone(), self.kind, *id, i), v))
     }
 
     /// Returns true if the deduplicator is empty.
@@ -463,17 +650,17 @@ impl<T> Deduplicator<T> {
 }
 
 /// Identifies a `<def>`.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-struct Id(char, u128, usize);
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+struct Id(EcoString, char, u128, usize);
 
 impl Display for Id {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{:0X}", self.0, self.1)
+        write!(f, "{}{}{:0X}", self.0, self.1, self.2)
     }
 }
 
-/// Displays as an SVG matrix.
-struct SvgMatrix(Transform);
+/// Displays as an SVG matrix, with numbers rounded to the given precision.
+struct SvgMatrix(Transform, Option<u8>);
 
 impl Display for SvgMatrix {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
@@ -482,39 +669,58 @@ impl Display for SvgMatrix {
         write!(
             f,
             "matrix({} {} {} {} {} {})",
-            self.0.sx.get(),
-            self.0.ky.get(),
-            self.0.kx.get(),
-            self.0.sy.get(),
-            self.0.tx.to_pt(),
-            self.0.ty.to_pt()
+            round(self.0.sx.get(), self.1),
+            round(self.0.ky.get(), self.1),
+            round(self.0.kx.get(), self.1),
+            round(self.0.sy.get(), self.1),
+            round(self.0.tx.to_pt(), self.1),
+            round(self.0.ty.to_pt(), self.1),
         )
     }
 }
 
+/// Rounds a number to the given number of fractional digits, if any.
+fn round(value: f64, precision: Option<u8>) -> f64 {
+    match precision {
+        // Adding zero turns a negative zero into a positive one.
+        Some(digits) => {
+            let factor = 10f64.powi(digits.into());
+            (value * factor).round() / factor + 0.0
+        }
+        None => value,
+    }
+}
+
 /// A builder for SVG path using relative coordinates.
 struct SvgPathBuilder {
     pub path: EcoString,
     pub scale: Ratio,
+    pub precision: Option<u8>,
     pub last_close_point: Point,
     pub last_point: Point,
 }
 
 impl SvgPathBuilder {
-    fn with_translate(pos: Point) -> Self {
+    fn with_translate(pos: Point, precision: Option<u8>) -> Self {
         // add initial M node to transform the entire path
         Self {
-            path: EcoString::from(format!("M {} {}", pos.x.to_pt(), pos.y.to_pt())),
+            path: EcoString::from(format!(
+                "M {} {}",
+                round(pos.x.to_pt(), precision),
+                round(pos.y.to_pt(), precision),
+            )),
             scale: Ratio::one(),
+            precision,
             last_close_point: pos,
             last_point: Point::zero(),
         }
     }
 
-    fn with_scale(scale: Ratio) -> Self {
+    fn with_scale(scale: Ratio, precision: Option<u8>) -> Self {
         Self {
             path: EcoString::from("M 0 0"),
             scale,
+            precision,
             last_close_point: Point::zero(),
             last_point: Point::zero(),
         }
@@ -524,21 +730,24 @@ impl SvgPathBuilder {
         self.scale.get() as f32
     }
 
-    fn set_point(&mut self, x: f32, y: f32) {
-        let point = Point::new(
-            Abs::pt(f64::from(x * self.scale())),
-            Abs::pt(f64::from(y * self.scale())),
-        );
+    /// Scales a coordinate and rounds it to the builder's precision. Relative
+    /// coordinates are computed from rounded absolute ones, so that rounding
+    /// errors don't add up along the path.
+    fn scaled(&self, v: f32) -> f64 {
+        round(f64::from(v * self.scale()), self.precision)
+    }
 
+    fn set_point(&mut self, x: f32, y: f32) {
+        let point = Point::new(Abs::pt(self.scaled(x)), Abs::pt(self.scaled(y)));
         self.last_point = point;
     }
 
     fn map_x(&self, x: f32) -> f32 {
-        x * self.scale() - self.last_point.x.to_pt() as f32
+        round(self.scaled(x) - self.last_point.x.to_pt(), self.precision) as f32
     }
 
     fn map_y(&self, y: f32) -> f32 {
-        y * self.scale() - self.last_point.y.to_pt() as f32
+        round(self.scaled(y) - self.last_point.y.to_pt(), self.precision) as f32
     }
 
     /// Create a rectangle path. The rectangle is created with the top-left
@@ -654,8 +863,261 @@ impl Default for SvgPathBuilder {
         Self {
             path: Default::default(),
             scale: Ratio::one(),
+            precision: None,
             last_close_point: Point::zero(),
             last_point: Point::zero(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use typst_library::foundations::{Bytes, Content, Smart};
+    use typst_library::layout::{Em, Sides};
+    use typst_library::text::{Font, Glyph, Lang, TextItem};
+    use typst_library::visualize::{Color, Curve, ExchangeFormat, RasterImage};
+    use typst_syntax::Span;
+
+    use super::*;
+
+    /// Creates a text item that shows the given text in Libertinus Serif.
+    fn text(text: &str) -> FrameItem {
+        let font = typst_assets::fonts()
+            .flat_map(|data| Font::iter(Bytes::new(data)))
+            .find(|font| font.info().family == "Libertinus Serif")
+            .unwrap();
+
+        let glyphs = text
+            .char_indices()
+            .map(|(i, c)| Glyph {
+                id: font.ttf().glyph_index(c).map_or(0, |id| id.0),
+                x_advance: Em::new(0.5),
+                x_offset: Em::zero(),
+                y_advance: Em::zero(),
+                y_offset: Em::zero(),
+                range: i as u16..(i + c.len_utf8()) as u16,
+                span: (Span::detached(), 0),
+            })
+            .collect();
+
+        FrameItem::Text(TextItem {
+            font,
+            size: Abs::pt(10.0),
+            fill: Color::BLACK.into(),
+            stroke: None,
+            lang: Lang::ENGLISH,
+            region: None,
+            text: text.into(),
+            glyphs,
+        })
+    }
+
+    /// Creates a 1x1 PNG image.
+    fn image() -> FrameItem {
+        let mut data = vec![];
+        ::image::RgbImage::new(1, 1)
+            .write_to(&mut Cursor::new(&mut data), ::image::ImageFormat::Png)
+            .unwrap();
+        let raster = RasterImage::plain(Bytes::new(data), ExchangeFormat::Png).unwrap();
+        let size = Size::splat(Abs::pt(10.0));
+        FrameItem::Image(Image::plain(raster), size, Span::detached())
+    }
+
+    /// Creates a group that clips its contents to its bounds.
+    fn clipped(items: impl IntoIterator<Item = FrameItem>) -> FrameItem {
+        let size = Size::new(Abs::pt(50.0), Abs::pt(20.0));
+        let mut frame = Frame::hard(size);
+        for item in items {
+            frame.push(Point::zero(), item);
+        }
+        let mut group = GroupItem::new(frame);
+        group.clip = Some(Curve::rect(size));
+        FrameItem::Group(group)
+    }
+
+    /// Creates a page that contains the given items at the given positions.
+    fn page(items: impl IntoIterator<Item = (Point, FrameItem)>) -> Page {
+        let mut frame = Frame::hard(Size::new(Abs::pt(100.0), Abs::pt(50.0)));
+        for (pos, item) in items {
+            frame.push(pos, item);
+        }
+
+        // Without a fill, the page has no background shape.
+        Page {
+            frame,
+            fill: Smart::Custom(None),
+            numbering: None,
+            supplement: Content::empty(),
+            number: 1,
+            bleed: Sides::splat(Abs::zero()),
+            slug: Sides::splat(Abs::zero()),
+        }
+    }
+
+    /// A position on the page.
+    fn at(x: f64, y: f64) -> Point {
+        Point::new(Abs::pt(x), Abs::pt(y))
+    }
+
+    #[test]
+    fn test_selectable_text() {
+        let page = page([(at(10.0, 20.0), text("Hi there"))]);
+        let options = SvgOptions { selectable_text: true, ..Default::default() };
+        let svg = svg_with_options(&page, &options);
+
+        assert!(svg.contains(r#"class="typst-text-overlay""#));
+        assert!(svg.contains(r#"font-family="Libertinus Serif""#));
+        assert!(svg.contains(r#"textLength="40""#));
+        assert!(svg.contains(r#"fill-opacity="0""#));
+        assert!(svg.contains(r#"lang="en""#));
+        assert!(svg.contains(">Hi there</text>"));
+        assert!(!svg.contains("transparent"));
+
+        // The glyphs are still drawn below the overlay.
+        let glyphs = svg.find(r#"class="typst-text""#).unwrap();
+        let overlay = svg.find(r#"class="typst-text-overlay""#).unwrap();
+        assert!(glyphs < overlay);
+    }
+
+    #[test]
+    fn test_selectable_text_disabled() {
+        let page = page([(at(10.0, 20.0), text("Hi there"))]);
+        assert!(!svg(&page).contains("<text"));
+    }
+
+    #[test]
+    fn test_selectable_text_whitespace() {
+        let page = page([(at(10.0, 20.0), text("  "))]);
+        let options = SvgOptions { selectable_text: true, ..Default::default() };
+        assert!(!svg_with_options(&page, &options).contains("<text"));
+    }
+
+    #[test]
+    fn test_selectable_text_escaped() {
+        let page = page([(at(10.0, 20.0), text("a < b & c"))]);
+        let options = SvgOptions { selectable_text: true, ..Default::default() };
+        let svg = svg_with_options(&page, &options);
+        assert!(svg.contains(">a &lt; b &amp; c</text>"));
+    }
+
+    #[test]
+    fn test_precision() {
+        let page = page([(at(10.123456, 20.987654), text("a"))]);
+        let options = SvgOptions { precision: Some(2), ..Default::default() };
+        let svg = svg_with_options(&page, &options);
+        assert!(svg.contains("matrix(1 0 0 -1 10.12 20.99)"));
+        assert!(!svg.contains("10.123"));
+
+        let options = SvgOptions { precision: Some(0), ..Default::default() };
+        let svg = svg_with_options(&page, &options);
+        assert!(svg.contains("matrix(1 0 0 -1 10 21)"));
+    }
+
+    #[test]
+    fn test_round() {
+        assert_eq!(round(1.23456, None), 1.23456);
+        assert_eq!(round(1.23456, Some(3)), 1.235);
+        assert_eq!(round(1.5, Some(0)), 2.0);
+        assert_eq!(round(-0.0001, Some(2)).to_string(), "0");
+    }
+
+    #[test]
+    fn test_deduplicate_glyphs() {
+        let page = page([(at(10.0, 20.0), text("aab"))]);
+        let svg = svg(&page);
+        assert_eq!(svg.matches("<symbol").count(), 2);
+        assert_eq!(svg.matches("<use").count(), 3);
+    }
+
+    #[test]
+    fn test_deduplicate_glyphs_disabled() {
+        let page = page([(at(10.0, 20.0), text("aab"))]);
+        let options = SvgOptions { deduplicate_glyphs: false, ..Default::default() };
+        let svg = svg_with_options(&page, &options);
+        assert_eq!(svg.matches("<symbol").count(), 0);
+        assert_eq!(svg.matches("<use").count(), 0);
+        assert_eq!(svg.matches("<path").count(), 3);
+    }
+
+    #[test]
+    fn test_deduplicate_glyphs_merged() {
+        let document = PagedDocument {
+            pages: vec![
+                page([(at(10.0, 20.0), text("ab"))]),
+                page([(at(10.0, 20.0), text("ba"))]),
+            ],
+            ..Default::default()
+        };
+
+        let svg = svg_merged(&document, Abs::pt(5.0));
+        assert_eq!(svg.matches("<symbol").count(), 2);
+        assert_eq!(svg.matches("<use").count(), 4);
+    }
+
+    #[test]
+    fn test_id_prefix() {
+        let page = page([(at(10.0, 20.0), clipped([text("ab")]))]);
+        let options = SvgOptions {
+            id_prefix: IdPrefix::new("doc-1.").unwrap(),
+            ..Default::default()
+        };
+
+        let svg = svg_with_options(&page, &options);
+        assert!(svg.contains(r#"<defs id="doc-1.glyph">"#));
+        assert!(svg.contains(r#"<defs id="doc-1.clip-path">"#));
+        assert!(svg.contains(r##"xlink:href="#doc-1.g"##));
+        assert!(svg.contains(r##"clip-path="url(#doc-1.c"##));
+        for (i, _) in svg.match_indices(r#" id=""#) {
+            assert!(svg[i..].starts_with(r#" id="doc-1."#));
+        }
+    }
+
+    #[test]
+    fn test_id_prefix_html_frame() {
+        let frame = page([(at(10.0, 20.0), text("a"))]).frame;
+        let introspector = Introspector::default();
+        let options = SvgOptions {
+            id_prefix: IdPrefix::new("f1_").unwrap(),
+            ..Default::default()
+        };
+
+        let svg =
+            svg_html_frame(&frame, Abs::pt(10.0), None, &[], &introspector, &options);
+        assert!(svg.contains(r#"<defs id="f1_glyph">"#));
+        assert!(svg.contains(r##"xlink:href="#f1_g"##));
+    }
+
+    #[test]
+    fn test_id_prefix_validation() {
+        for valid in ["", "doc", "_a", "doc-1.x", "ü-2", "a\u{B7}b"] {
+            assert_eq!(IdPrefix::new(valid).unwrap().as_str(), valid);
+        }
+        for invalid in ["1doc", "-doc", ".doc", "a:b", "a b", "a#", "a/b"] {
+            assert!(IdPrefix::new(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn test_image_href() {
+        let page = page([(at(10.0, 20.0), image())]);
+        let href = |_: &Image| Some(EcoString::from("images/a.png"));
+        let options = SvgOptions { image_href: Some(&href), ..Default::default() };
+        let svg = svg_with_options(&page, &options);
+        assert!(svg.contains(
+            r#"<image transform="matrix(1 0 0 1 10 20)" xlink:href="images/a.png""#
+        ));
+        assert!(!svg.contains("data:"));
+    }
+
+    #[test]
+    fn test_image_href_fallback() {
+        let page = page([(at(10.0, 20.0), image())]);
+        let href = |_: &Image| None;
+        let options = SvgOptions { image_href: Some(&href), ..Default::default() };
+        let svg = svg_with_options(&page, &options);
+        assert!(svg.contains(r#"xlink:href="data:image/png;base64,"#));
+        assert_eq!(svg, super::svg(&page));
+    }
+}
@@ -73,7 +73,7 @@ impl SVGRenderer<'_> {
         }
 
         self.gradient_refs
-            .insert_with(hash128(&(gradient_id, ts)), || GradientRef {
+            .insert_with(hash128(&(&gradient_id, ts)), || GradientRef {
                 id: gradient_id,
                 kind: gradient.into(),
                 transform: ts,
@@ -96,14 +96,15 @@ impl SVGRenderer<'_> {
         );
 
         let tiling_id = self.tilings.insert_with(hash128(tiling), || tiling.clone());
-        self.tiling_refs.insert_with(hash128(&(tiling_id, ts)), || TilingRef {
-            id: tiling_id,
-            transform: ts,
-            ratio: Axes::new(
-                Ratio::new(tiling_size.x.to_pt() / size.x.to_pt()),
-                Ratio::new(tiling_size.y.to_pt() / size.y.to_pt()),
-            ),
-        })
+        self.tiling_refs
+            .insert_with(hash128(&(&tiling_id, ts)), || TilingRef {
+                id: tiling_id,
+                transform: ts,
+                ratio: Axes::new(
+                    Ratio::new(tiling_size.x.to_pt() / size.x.to_pt()),
+                    Ratio::new(tiling_size.y.to_pt() / size.y.to_pt()),
+                ),
+            })
     }
 
     /// Write the raw gradients (without transform) to the SVG file.
@@ -113,7 +114,10 @@ impl SVGRenderer<'_> {
         }
 
         self.xml.start_element("defs");
-        self.xml.write_attribute("id", "gradients");
+        self.xml.write_attribute_fmt(
+            "id",
+            format_args!("{}gradients", self.options.id_prefix),
+        );
 
         for (id, (gradient, ratio)) in self.gradients.iter() {
             match &gradient {
@@ -236,8 +240,8 @@ impl SVGRenderer<'_> {
             }
 
             for window in gradient.stops_ref().windows(2) {
-                let (start_c, start_t) = window[0];
-                let (end_c, end_t) = window[1];
+                let (ref start_c, start_t) = window[0];
+                let (ref end_c, end_t) = window[1];
 
                 self.xml.start_element("stop");
                 self.xml.write_attribute("offset", &start_t.repr());
@@ -285,7 +289,10 @@ impl SVGRenderer<'_> {
         }
 
         self.xml.start_element("defs");
-        self.xml.write_attribute("id", "subgradients");
+        self.xml.write_attribute_fmt(
+            "id",
+            format_args!("{}subgradients", self.options.id_prefix),
+        );
         for (id, gradient) in self.conic_subgradients.iter() {
             let x1 = 2.0 - gradient.t0.cos() as f32 + gradient.center.x.get() as f32;
             let y1 = gradient.t0.sin() as f32 + gradient.center.y.get() as f32;
@@ -321,28 +328,31 @@ impl SVGRenderer<'_> {
         }
 
         self.xml.start_element("defs");
-        self.xml.write_attribute("id", "gradient-refs");
+        self.xml.write_attribute_fmt(
+            "id",
+            format_args!("{}gradient-refs", self.options.id_prefix),
+        );
         for (id, gradient_ref) in self.gradient_refs.iter() {
             match gradient_ref.kind {
                 GradientKind::Linear => {
                     self.xml.start_element("linearGradient");
                     self.xml.write_attribute(
                         "gradientTransform",
-                        &SvgMatrix(gradient_ref.transform),
+                        &SvgMatrix(gradient_ref.transform, self.options.precision),
                     );
                 }
                 GradientKind::Radial => {
                     self.xml.start_element("radialGradient");
                     self.xml.write_attribute(
                         "gradientTransform",
-                        &SvgMatrix(gradient_ref.transform),
+                        &SvgMatrix(gradient_ref.transform, self.options.precision),
                     );
                 }
                 GradientKind::Conic => {
                     self.xml.start_element("pattern");
                     self.xml.write_attribute(
                         "patternTransform",
-                        &SvgMatrix(gradient_ref.transform),
+                        &SvgMatrix(gradient_ref.transform, self.options.precision),
                     );
                 }
             }
@@ -369,7 +379,8 @@ impl SVGRenderer<'_> {
         }
 
         self.xml.start_element("defs");
-        self.xml.write_attribute("id", "tilings");
+        self.xml
+            .write_attribute_fmt("id", format_args!("{}tilings", self.options.id_prefix));
 
         for (id, tiling) in
             self.tilings.iter().map(|(i, p)| (i, p.clone())).collect::<Vec<_>>()
@@ -402,11 +413,16 @@ impl SVGRenderer<'_> {
         }
 
         self.xml.start_element("defs");
-        self.xml.write_attribute("id", "tilings-refs");
+        self.xml.write_attribute_fmt(
+            "id",
+            format_args!("{}tilings-refs", self.options.id_prefix),
+        );
         for (id, tiling_ref) in self.tiling_refs.iter() {
             self.xml.start_element("pattern");
-            self.xml
-                .write_attribute("patternTransform", &SvgMatrix(tiling_ref.transform));
+            self.xml.write_attribute(
+                "patternTransform",
+                &SvgMatrix(tiling_ref.transform, self.options.precision),
+            );
 
             self.xml.write_attribute("id", &id);
 
@@ -498,10 +514,12 @@ pub trait ColorEncode {
 impl ColorEncode for Color {
     fn encode(&self) -> EcoString {
         match *self {
-            c @ Color::Rgb(_)
-            | c @ Color::Luma(_)
-            | c @ Color::Cmyk(_)
-            | c @ Color::Hsv(_) => c.to_hex(),
+            ref c @ Color::Rgb(_)
+            | ref c @ Color::Luma(_)
+            | ref c @ Color::Cmyk(_)
+            | ref c @ Color::Hsv(_)
+            | ref c @ Color::Spot(_)
+            | ref c @ Color::Icc(_) => c.to_hex(),
             Color::LinearRgb(rgb) => {
                 if rgb.alpha != 1.0 {
                     eco_format!(
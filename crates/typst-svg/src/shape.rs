@@ -33,10 +33,13 @@ impl SVGRenderer<'_> {
         }
 
         if !state.transform.is_identity() {
-            self.xml.write_attribute("transform", &SvgMatrix(state.transform));
+            self.xml.write_attribute(
+                "transform",
+                &SvgMatrix(state.transform, self.options.precision),
+            );
         }
 
-        let path = convert_geometry_to_path(&shape.geometry);
+        let path = convert_geometry_to_path(&shape.geometry, self.options.precision);
         self.xml.write_attribute("d", &path);
         self.xml.end_element();
     }
@@ -157,9 +160,9 @@ impl SVGRenderer<'_> {
 
 /// Convert a geometry to an SVG path.
 #[comemo::memoize]
-fn convert_geometry_to_path(geometry: &Geometry) -> EcoString {
+fn convert_geometry_to_path(geometry: &Geometry, precision: Option<u8>) -> EcoString {
     let mut builder =
-        SvgPathBuilder::with_translate(Point::new(Abs::zero(), Abs::zero()));
+        SvgPathBuilder::with_translate(Point::new(Abs::zero(), Abs::zero()), precision);
 
     match geometry {
         Geometry::Line(t) => {
@@ -172,14 +175,18 @@ fn convert_geometry_to_path(geometry: &Geometry) -> EcoString {
             builder.rect(x, y);
         }
         Geometry::Curve(p) => {
-            return convert_curve(Point::new(Abs::zero(), Abs::zero()), p);
+            return convert_curve(Point::new(Abs::zero(), Abs::zero()), p, precision);
         }
     };
     builder.path
 }
 
-pub fn convert_curve(initial_point: Point, curve: &Curve) -> EcoString {
-    let mut builder = SvgPathBuilder::with_translate(initial_point);
+pub fn convert_curve(
+    initial_point: Point,
+    curve: &Curve,
+    precision: Option<u8>,
+) -> EcoString {
+    let mut builder = SvgPathBuilder::with_translate(initial_point, precision);
     for item in &curve.0 {
         match item {
             CurveItem::Move(m) => builder.move_to(m.x.to_pt() as f32, m.y.to_pt() as f32),
@@ -12,7 +12,7 @@ use typst_library::visualize::{
 };
 use typst_utils::hash128;
 
-use crate::{SVGRenderer, State, SvgMatrix, SvgPathBuilder};
+use crate::{Id, SVGRenderer, State, SvgMatrix, SvgPathBuilder, round};
 
 impl SVGRenderer<'_> {
     /// Render a text item. The text is rendered as a group of glyphs. We will
@@ -30,7 +30,8 @@ impl SVGRenderer<'_> {
                 &SvgMatrix(
                     state
                         .transform
-                        .pre_concat(Transform::scale(Ratio::new(1.0), Ratio::new(-1.0)))
+                        .pre_concat(Transform::scale(Ratio::new(1.0), Ratio::new(-1.0))),
+                    self.options.precision,
                 )
             ),
         );
@@ -66,6 +67,45 @@ impl SVGRenderer<'_> {
         }
 
         self.xml.end_element();
+
+        if self.options.selectable_text {
+            self.render_text_overlay(state, text);
+        }
+    }
+
+    /// Render invisible text on top of the glyphs of a text item, so that the
+    /// text can be selected and searched.
+    ///
+    /// The text is stretched to the width of the glyphs, so that the
+    /// selection matches them closely even if the viewer falls back to a
+    /// different font.
+    fn render_text_overlay(&mut self, state: &State, text: &TextItem) {
+        let width = text.width();
+        if text.text.trim().is_empty() || width <= Abs::zero() {
+            return;
+        }
+
+        self.xml.start_element("text");
+        self.xml.write_attribute("class", "typst-text-overlay");
+        if !state.transform.is_identity() {
+            self.xml.write_attribute(
+                "transform",
+                &SvgMatrix(state.transform, self.options.precision),
+            );
+        }
+        self.xml.write_attribute("font-family", &text.font.info().family);
+        self.xml.write_attribute("font-size", &text.size.to_pt());
+        self.xml.write_attribute("textLength", &width.to_pt());
+        self.xml.write_attribute("lengthAdjust", "spacingAndGlyphs");
+        self.xml.write_attribute("fill-opacity", "0");
+        self.xml.write_attribute("lang", text.lang.as_str());
+        self.xml.write_attribute("xml:space", "preserve");
+        // Indentation would become part of the preserved text.
+        self.xml.set_preserve_whitespaces(true);
+        // The writer only escapes `<`, so ampersands are escaped here.
+        self.xml.write_text(&text.text.replace("&", "&amp;"));
+        self.xml.end_element();
+        self.xml.set_preserve_whitespaces(false);
     }
 
     /// Render a glyph defined by an SVG.
@@ -90,10 +130,7 @@ impl SVGRenderer<'_> {
                 .post_concat(Transform::scale(Ratio::new(scale), Ratio::new(-scale))),
         });
 
-        self.xml.start_element("use");
-        self.xml.write_attribute_fmt("xlink:href", format_args!("#{id}"));
-        self.xml.write_attribute("x", &x_offset);
-        self.xml.write_attribute("y", &y_offset);
+        self.start_glyph(&id, x_offset, y_offset, Transform::identity());
         self.xml.end_element();
 
         Some(())
@@ -126,10 +163,7 @@ impl SVGRenderer<'_> {
                 .pre_concat(Transform::translate(Abs::pt(x_min), -Abs::pt(y_max))),
         });
 
-        self.xml.start_element("use");
-        self.xml.write_attribute_fmt("xlink:href", format_args!("#{id}"));
-        self.xml.write_attribute("x", &(x_offset));
-        self.xml.write_attribute("y", &(y_offset));
+        self.start_glyph(&id, x_offset, y_offset, Transform::identity());
         self.xml.end_element();
 
         Some(())
@@ -159,18 +193,16 @@ impl SVGRenderer<'_> {
         });
 
         let target_height = text.size.to_pt();
-        self.xml.start_element("use");
-        self.xml.write_attribute_fmt("xlink:href", format_args!("#{id}"));
 
         // The image is stored with the height of `image.height()`, but we want
         // to render it with a height of `target_height`. So we need to scale
         // it.
         let scale_factor = target_height / image.height();
-        self.xml.write_attribute("x", &(x_offset / scale_factor));
-        self.xml.write_attribute("y", &(y_offset / scale_factor));
-        self.xml.write_attribute_fmt(
-            "transform",
-            format_args!("scale({scale_factor} -{scale_factor})",),
+        self.start_glyph(
+            &id,
+            x_offset / scale_factor,
+            y_offset / scale_factor,
+            Transform::scale(Ratio::new(scale_factor), Ratio::new(-scale_factor)),
         );
         self.xml.end_element();
 
@@ -188,7 +220,12 @@ impl SVGRenderer<'_> {
         scale: f64,
     ) -> Option<()> {
         let scale = Ratio::new(scale);
-        let path = convert_outline_glyph_to_path(&text.font, glyph_id, scale)?;
+        let path = convert_outline_glyph_to_path(
+            &text.font,
+            glyph_id,
+            scale,
+            self.options.precision,
+        )?;
         let hash = hash128(&(&text.font, glyph_id, scale));
         let id = self.glyphs.insert_with(hash, || RenderedGlyph::Path(path));
 
@@ -196,10 +233,7 @@ impl SVGRenderer<'_> {
         let width = glyph_size.width() as f64 * scale.get();
         let height = glyph_size.height() as f64 * scale.get();
 
-        self.xml.start_element("use");
-        self.xml.write_attribute_fmt("xlink:href", format_args!("#{id}"));
-        self.xml.write_attribute_fmt("x", format_args!("{x_offset}"));
-        self.xml.write_attribute_fmt("y", format_args!("{y_offset}"));
+        self.start_glyph(&id, x_offset, y_offset, Transform::identity());
         self.write_fill(
             &text.fill,
             FillRule::default(),
@@ -218,6 +252,50 @@ impl SVGRenderer<'_> {
         Some(())
     }
 
+    /// Start an element that draws a prepared glyph at the given position.
+    ///
+    /// This is a `<use>` element that references the glyph's definition,
+    /// unless glyphs aren't deduplicated. Then, the glyph is drawn in place.
+    /// Like for a `<use>` element, the transform is applied before the
+    /// translation to the position.
+    fn start_glyph(&mut self, id: &Id, x: f64, y: f64, ts: Transform) {
+        let precision = self.options.precision;
+        let (x, y) = (round(x, precision), round(y, precision));
+
+        if self.options.deduplicate_glyphs {
+            self.xml.start_element("use");
+            self.xml.write_attribute_fmt("xlink:href", format_args!("#{id}"));
+            self.xml.write_attribute("x", &x);
+            self.xml.write_attribute("y", &y);
+            if !ts.is_identity() {
+                self.xml.write_attribute("transform", &SvgMatrix(ts, precision));
+            }
+            return;
+        }
+
+        let ts = ts.pre_concat(Transform::translate(Abs::pt(x), Abs::pt(y)));
+        match self.glyphs.get(id) {
+            RenderedGlyph::Path(path) => {
+                self.xml.start_element("path");
+                self.xml.write_attribute("d", path);
+                if !ts.is_identity() {
+                    self.xml.write_attribute("transform", &SvgMatrix(ts, precision));
+                }
+            }
+            RenderedGlyph::Image { url, width, height, ts: image_ts } => {
+                self.xml.start_element("image");
+                self.xml.write_attribute("xlink:href", url);
+                self.xml.write_attribute("width", width);
+                self.xml.write_attribute("height", height);
+                let ts = ts.pre_concat(*image_ts);
+                if !ts.is_identity() {
+                    self.xml.write_attribute("transform", &SvgMatrix(ts, precision));
+                }
+                self.xml.write_attribute("preserveAspectRatio", "none");
+            }
+        }
+    }
+
     fn text_paint_transform(&self, state: State, paint: &Paint) -> Transform {
         match paint {
             Paint::Solid(_) => Transform::identity(),
@@ -238,12 +316,13 @@ impl SVGRenderer<'_> {
 
     /// Build the glyph definitions.
     pub(super) fn write_glyph_defs(&mut self) {
-        if self.glyphs.is_empty() {
+        if self.glyphs.is_empty() || !self.options.deduplicate_glyphs {
             return;
         }
 
         self.xml.start_element("defs");
-        self.xml.write_attribute("id", "glyph");
+        self.xml
+            .write_attribute_fmt("id", format_args!("{}glyph", self.options.id_prefix));
 
         for (id, glyph) in self.glyphs.iter() {
             self.xml.start_element("symbol");
@@ -262,7 +341,10 @@ impl SVGRenderer<'_> {
                     self.xml.write_attribute("width", &width);
                     self.xml.write_attribute("height", &height);
                     if !ts.is_identity() {
-                        self.xml.write_attribute("transform", &SvgMatrix(*ts));
+                        self.xml.write_attribute(
+                            "transform",
+                            &SvgMatrix(*ts, self.options.precision),
+                        );
                     }
                     self.xml.write_attribute("preserveAspectRatio", "none");
                     self.xml.end_element();
@@ -294,8 +376,9 @@ fn convert_outline_glyph_to_path(
     font: &Font,
     id: GlyphId,
     scale: Ratio,
+    precision: Option<u8>,
 ) -> Option<EcoString> {
-    let mut builder = SvgPathBuilder::with_scale(scale);
+    let mut builder = SvgPathBuilder::with_scale(scale, precision);
     font.ttf().outline_glyph(id, &mut builder)?;
     Some(builder.path)
 }
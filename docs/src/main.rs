@@ -48,7 +48,7 @@ impl Resolver for CliResolver<'_> {
         }
         .iter()
         .map(|(page, filename, alt)| {
-            let pixmap = render(page, 2.0);
+            let pixmap = render(page, 2.0).expect("render page");
             let path = self.assets_dir.join(filename);
             pixmap.save_png(path.as_path()).expect("save png");
             eprintln!("Generated example image {path:?}");
@@ -3,8 +3,9 @@ use std::fmt::Write;
 use typst::World;
 use typst::foundations::Smart;
 use typst::introspection::{Location, Tag};
-use typst::layout::{Frame, FrameItem, PagedDocument};
+use typst::layout::{Abs, Frame, FrameItem, PagedDocument, Point, Sides};
 use typst::model::DocumentInfo;
+use typst::visualize::Geometry;
 
 use crate::collect::Test;
 use crate::world::TestWorld;
@@ -46,11 +47,90 @@ pub fn check(test: &Test, world: &TestWorld, doc: Option<&PagedDocument>) -> Str
                 sink.push_str("missing document");
             }
         }
+        "page-bleed-size" => {
+            let page = doc.and_then(|doc| doc.pages.first());
+            test_eq!(
+                sink,
+                page.map(|page| page.frame.size().to_point()),
+                Some(pt(50.0, 40.0))
+            );
+            test_eq!(sink, page.map(|page| page.bleed), Some(Sides::splat(Abs::pt(5.0))));
+        }
+        "page-marks-crop" => {
+            // The trimmed page spans from (26pt, 26pt) to (66pt, 56pt), the
+            // bleed ends at 5pt around it and the marks start 3pt further.
+            let page = doc.and_then(|doc| doc.pages.first());
+            test_eq!(
+                sink,
+                page.map(|page| page.frame.size().to_point()),
+                Some(pt(92.0, 82.0))
+            );
+            test_eq!(sink, page.map(|page| page.slug), Some(Sides::splat(Abs::pt(21.0))));
+            let lines = page.map(|page| shapes(&page.frame, "line")).unwrap_or_default();
+            test_eq!(
+                sink,
+                lines,
+                [
+                    (pt(26.0, 0.0), pt(0.0, 18.0)),
+                    (pt(26.0, 64.0), pt(0.0, 18.0)),
+                    (pt(66.0, 0.0), pt(0.0, 18.0)),
+                    (pt(66.0, 64.0), pt(0.0, 18.0)),
+                    (pt(0.0, 26.0), pt(18.0, 0.0)),
+                    (pt(74.0, 26.0), pt(18.0, 0.0)),
+                    (pt(0.0, 56.0), pt(18.0, 0.0)),
+                    (pt(74.0, 56.0), pt(18.0, 0.0)),
+                ]
+            );
+        }
+        "page-marks-registration" => {
+            // The marks are centered on the trimmed page's axes within the
+            // 18pt wide area around the bleed.
+            let page = doc.and_then(|doc| doc.pages.first());
+            let circles =
+                page.map(|page| shapes(&page.frame, "curve")).unwrap_or_default();
+            let centers: Vec<_> = circles
+                .iter()
+                .map(|&(pos, size)| {
+                    let center = pos + size / 2.0;
+                    (center.x.to_pt().round(), center.y.to_pt().round())
+                })
+                .collect();
+            test_eq!(
+                sink,
+                centers,
+                [(46.0, 9.0), (46.0, 73.0), (9.0, 41.0), (83.0, 41.0)]
+            );
+            let lines = page.map(|page| shapes(&page.frame, "line")).unwrap_or_default();
+            test_eq!(sink, lines.len(), 8);
+        }
         _ => {}
     }
     sink
 }
 
+/// A point in points.
+fn pt(x: f64, y: f64) -> Point {
+    Point::new(Abs::pt(x), Abs::pt(y))
+}
+
+/// Collect the positions and sizes (or line deltas) of the shapes of a kind
+/// at the top level of a frame.
+fn shapes(frame: &Frame, kind: &str) -> Vec<(Point, Point)> {
+    frame
+        .items()
+        .filter_map(|(pos, item)| match item {
+            FrameItem::Shape(shape, _) => match (&shape.geometry, kind) {
+                (Geometry::Line(delta), "line") => Some((*pos, *delta)),
+                (Geometry::Curve(curve), "curve") => {
+                    Some((*pos, curve.bbox_size().to_point()))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
 /// Extract the document information.
 fn info(doc: Option<&PagedDocument>) -> DocumentInfo {
     doc.map(|doc| doc.info.clone()).unwrap_or_default()
@@ -597,7 +597,8 @@ fn render(document: &PagedDocument, pixel_per_pt: f32) -> sk::Pixmap {
 
     let gap = Abs::pt(1.0);
     let mut pixmap =
-        typst_render::render_merged(document, pixel_per_pt, gap, Some(Color::BLACK));
+        typst_render::render_merged(document, pixel_per_pt, gap, Some(Color::BLACK))
+            .unwrap();
 
     let gap = (pixel_per_pt * gap.to_pt() as f32).round();
 
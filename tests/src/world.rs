@@ -11,7 +11,8 @@ use rustc_hash::FxHashMap;
 use typst::diag::{At, FileError, FileResult, SourceResult, StrResult, bail};
 use typst::engine::Engine;
 use typst::foundations::{
-    Array, Bytes, Context, Datetime, IntoValue, NoneValue, Repr, Smart, Value, func,
+    Array, Bytes, Context, Datetime, IntoValue, NoneValue, PluginLimits, Repr, Smart,
+    Value, func,
 };
 use typst::layout::{Abs, Margin, PageElem};
 use typst::model::{Numbering, NumberingPattern};
@@ -199,6 +200,10 @@ fn library() -> Library {
     // that it multiplies to nice round numbers.
     let mut lib = Library::builder()
         .with_features([Feature::Html, Feature::A11yExtras].into_iter().collect())
+        .with_plugin_limits(PluginLimits {
+            fuel: Some(10_000_000),
+            memory: Some(64 * 1024 * 1024),
+        })
         .build();
 
     // Hook up helpers into the global scope.
@@ -385,6 +385,37 @@
 // Error: 12-18 unexpected argument
 #().sorted(x => x)
 
+--- array-sorted-collation ---
+#let words = ("Zebra", "Ärger", "apfel")
+#test(words.sorted(), ("Zebra", "apfel", "Ärger"))
+#test(words.sorted(collation: "de"), ("apfel", "Ärger", "Zebra"))
+#test(words.sorted(collation: "sv"), ("apfel", "Zebra", "Ärger"))
+#test(words.sorted(collation: (lang: "de")), ("apfel", "Ärger", "Zebra"))
+
+#set text(lang: "sv")
+#context test(words.sorted(collation: auto), ("apfel", "Zebra", "Ärger"))
+
+--- array-sorted-collation-strength ---
+#test(("B", "b", "a").sorted(collation: "en"), ("a", "b", "B"))
+#test(("B", "b", "a").sorted(collation: (lang: "en", case: false)), ("a", "B", "b"))
+#test(("é", "e", "d").sorted(collation: "fr"), ("d", "e", "é"))
+#test(("é", "e", "d").sorted(collation: (lang: "fr", accents: false)), ("d", "é", "e"))
+
+--- array-sorted-collation-key ---
+#let pairs = (("Ö", 1), ("O", 2), ("Z", 0))
+#test(pairs.sorted(collation: "de"), (("O", 2), ("Ö", 1), ("Z", 0)))
+#test(pairs.sorted(key: it => it.first(), collation: "sv"), (("O", 2), ("Z", 0), ("Ö", 1)))
+
+--- array-sorted-collation-by ---
+// Error: 2-53 cannot use `collation` together with `by`
+#(1, 2).sorted(by: (a, b) => a < b, collation: "de")
+
+--- array-sorted-collation-auto-without-context ---
+// Error: 2-32 can only be used when context is known
+// Hint: 2-32 try wrapping this in a `context` expression
+// Hint: 2-32 the `context` expression should wrap everything that depends on this function
+#("a",).sorted(collation: auto)
+
 --- array-zip ---
 // Test the `zip` method.
 #test(().zip(()), ())
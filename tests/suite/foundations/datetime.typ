@@ -91,3 +91,114 @@
 --- datetime-display-insufficient-information ---
 // Error: 2-36 failed to format datetime (insufficient information)
 #datetime.today().display("[hour]")
+
+--- datetime-parse-iso ---
+#test(datetime.parse("2024-03-01"), datetime(year: 2024, month: 3, day: 1))
+#test(datetime.parse("09:30:05"), datetime(hour: 9, minute: 30, second: 5))
+#test(
+  datetime.parse("2024-03-01T09:30:05"),
+  datetime(year: 2024, month: 3, day: 1, hour: 9, minute: 30, second: 5),
+)
+#test(
+  datetime.parse("2024-03-01 09:30:05"),
+  datetime(year: 2024, month: 3, day: 1, hour: 9, minute: 30, second: 5),
+)
+
+#let d = datetime.parse("2024-03-01T09:30:05+01:30")
+#test(d.offset(), duration(hours: 1, minutes: 30))
+#test(d.hour(), 9)
+#test(d.display(), "2024-03-01 09:30:05 +01:30")
+#test(datetime.parse("2024-03-01T08:00:05Z"), d)
+#test(datetime.parse("2024-03-01T08:00:05Z").offset(), duration())
+#test(datetime.parse("2024-03-01T08:00:05").offset(), none)
+
+--- datetime-parse-pattern ---
+#test(
+  datetime.parse("01.03.2024", pattern: "[day].[month].[year]"),
+  datetime(year: 2024, month: 3, day: 1),
+)
+#test(
+  datetime.parse("09:05:00 PM", pattern: "[hour repr:12]:[minute]:[second] [period]"),
+  datetime(hour: 21, minute: 5, second: 0),
+)
+#test(
+  datetime.parse(
+    "2024-03-01 10:00:00 -05:00",
+    pattern: "[year]-[month]-[day] [hour]:[minute]:[second] [offset_hour sign:mandatory]:[offset_minute]",
+  ).offset(),
+  duration(hours: -5),
+)
+
+--- datetime-parse-invalid ---
+// Error: 2-30 failed to parse datetime ("2024-13-01" is not in ISO 8601 format)
+#datetime.parse("2024-13-01")
+
+--- datetime-parse-insufficient-pattern ---
+// Error: 2-43 failed to parse datetime (pattern does not determine a date or time)
+#datetime.parse("2024", pattern: "[year]")
+
+--- datetime-offset ---
+#let d = datetime(year: 2024, month: 3, day: 1, hour: 12, minute: 0, second: 0, offset: 2)
+#test(d, datetime.parse("2024-03-01T10:00:00Z"))
+#test(d.offset(), duration(hours: 2))
+#test(
+  d.to-offset(duration(hours: -3, minutes: -30)).display(),
+  "2024-03-01 06:30:00 -03:30",
+)
+#test(datetime.parse("2024-03-01T12:00:00Z").to-offset(2).display(), "2024-03-01 14:00:00 +02:00")
+#test(d < datetime.parse("2024-03-01T11:00:00Z"), true)
+#test(d - datetime.parse("2024-03-01T12:00:00Z"), duration(hours: -2))
+#test((d + duration(days: 1)).display(), "2024-03-02 12:00:00 +02:00")
+
+--- datetime-offset-without-time ---
+// Error: 2-51 an offset can only be specified for a full datetime
+#datetime(year: 2024, month: 3, day: 1, offset: 2)
+
+--- datetime-add ---
+#let d = datetime(year: 2024, month: 1, day: 31)
+#test(d.add(months: 1), datetime(year: 2024, month: 2, day: 29))
+#test(d.add(months: 13), datetime(year: 2025, month: 2, day: 28))
+#test(d.add(months: -2), datetime(year: 2023, month: 11, day: 30))
+#test(d.add(years: 1, days: -1), datetime(year: 2025, month: 1, day: 30))
+#test(d.add(weeks: 1), datetime(year: 2024, month: 2, day: 7))
+
+#let t = datetime(year: 2024, month: 1, day: 31, hour: 10, minute: 15, second: 0)
+#test(t.add(months: 1), datetime(year: 2024, month: 2, day: 29, hour: 10, minute: 15, second: 0))
+
+--- datetime-add-time ---
+// Error: 2-54 cannot add calendar units to a time
+#datetime(hour: 1, minute: 0, second: 0).add(days: 1)
+
+--- datetime-start-of ---
+#let d = datetime(year: 2024, month: 3, day: 14)
+#test(d.start-of("year"), datetime(year: 2024, month: 1, day: 1))
+#test(d.start-of("month"), datetime(year: 2024, month: 3, day: 1))
+#test(d.start-of("week"), datetime(year: 2024, month: 3, day: 11))
+#test(d.start-of("week", first-day: 7), datetime(year: 2024, month: 3, day: 10))
+#test(d.start-of("day"), d)
+
+#let t = datetime(year: 2024, month: 3, day: 14, hour: 15, minute: 42, second: 7)
+#test(t.start-of("day"), datetime(year: 2024, month: 3, day: 14, hour: 0, minute: 0, second: 0))
+#test(t.start-of("hour"), datetime(year: 2024, month: 3, day: 14, hour: 15, minute: 0, second: 0))
+#test(t.start-of("minute"), datetime(year: 2024, month: 3, day: 14, hour: 15, minute: 42, second: 0))
+#test(t.start-of("month"), datetime(year: 2024, month: 3, day: 1, hour: 0, minute: 0, second: 0))
+
+--- datetime-start-of-hour-of-date ---
+// Error: 2-58 cannot determine the start of the hour of a date
+#datetime(year: 2024, month: 3, day: 14).start-of("hour")
+
+--- datetime-display-localized ---
+#let d = datetime(year: 2024, month: 3, day: 1)
+#let pattern = "[weekday] [weekday repr:short] [month repr:long] [month repr:short]"
+#test(d.display(pattern), "Friday Fri March Mar")
+#context test(d.display(pattern), "Friday Fri March Mar")
+
+#set text(lang: "de")
+#context test(d.display(pattern), "Freitag Fr. März März")
+#context test(d.display("[day].[month].[year]"), "01.03.2024")
+
+#set text(lang: "fr")
+#context test(d.display(pattern), "vendredi ven. mars mars")
+
+#set text(lang: "zu")
+#context test(d.display(pattern), "Friday Fri March Mar")
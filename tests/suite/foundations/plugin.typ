@@ -76,3 +76,127 @@
 
 // Error: 2-27 plugin tried to write out of bounds: pointer 0x40000000 is out of bounds for write of length 3
 #p.write_oob(bytes("xyz"))
+
+--- plugin-host-access ---
+// A module that imports `typst_host.warn` and exports its memory.
+#let wasm = bytes((
+  0, 97, 115, 109, 1, 0, 0, 0, 1, 6, 1, 96, 2, 127, 127, 0, 2, 19, 1, 10,
+  116, 121, 112, 115, 116, 95, 104, 111, 115, 116, 4, 119, 97, 114, 110, 0, 0,
+  5, 3, 1, 0, 1, 7, 10, 1, 6, 109, 101, 109, 111, 114, 121, 2, 0,
+))
+
+#test(type(plugin(wasm, host: true)), module)
+
+--- plugin-host-access-disabled ---
+#let wasm = bytes((
+  0, 97, 115, 109, 1, 0, 0, 0, 1, 6, 1, 96, 2, 127, 127, 0, 2, 19, 1, 10,
+  116, 121, 112, 115, 116, 95, 104, 111, 115, 116, 4, 119, 97, 114, 110, 0, 0,
+  5, 3, 1, 0, 1, 7, 10, 1, 6, 109, 101, 109, 111, 114, 121, 2, 0,
+))
+
+// Error: 9-13 plugin uses the host API, but host access was not enabled (load it with `host: true` to enable it)
+#plugin(wasm)
+
+--- plugin-host-basic ---
+#let p = plugin("/assets/plugins/hello.wasm", host: true)
+#test(p.double_it(bytes("hey!")), bytes("hey!.hey!"))
+
+--- plugin-fuel-limit ---
+// The test world limits plugins to 10 million units of fuel and 64 MiB of
+// memory. This module's `spin` function loops forever.
+#let p = plugin(bytes((
+  0, 97, 115, 109, 1, 0, 0, 0, 1, 5, 1, 96, 0, 1, 127, 3, 3, 2, 0, 0, 5, 3, 1,
+  0, 1, 7, 24, 3, 6, 109, 101, 109, 111, 114, 121, 2, 0, 4, 115, 112, 105, 110,
+  0, 0, 4, 103, 114, 111, 119, 0, 1, 10, 22, 2, 9, 0, 3, 64, 12, 0, 11, 65, 0,
+  11, 10, 0, 65, 128, 16, 64, 0, 26, 65, 0, 11,
+)))
+
+// Error: 2-10 plugin exceeded its fuel limit of 10000000
+#p.spin()
+
+--- plugin-memory-limit ---
+// This module's `grow` function grows the memory by 128 MiB.
+#let p = plugin(bytes((
+  0, 97, 115, 109, 1, 0, 0, 0, 1, 5, 1, 96, 0, 1, 127, 3, 3, 2, 0, 0, 5, 3, 1,
+  0, 1, 7, 24, 3, 6, 109, 101, 109, 111, 114, 121, 2, 0, 4, 115, 112, 105, 110,
+  0, 0, 4, 103, 114, 111, 119, 0, 1, 10, 22, 2, 9, 0, 3, 64, 12, 0, 11, 65, 0,
+  11, 10, 0, 65, 128, 16, 64, 0, 26, 65, 0, 11,
+)))
+
+// Error: 2-10 plugin exceeded its memory limit of 67108864 bytes
+#p.grow()
+
+--- plugin-memory-limit-initial ---
+// A module whose memory initially has 128 MiB.
+#let wasm = bytes((
+  0, 97, 115, 109, 1, 0, 0, 0, 1, 5, 1, 96, 0, 1, 127, 5, 4, 1, 0, 128, 16, 7,
+  10, 1, 6, 109, 101, 109, 111, 114, 121, 2, 0,
+))
+
+// Error: 9-13 plugin exceeded its memory limit of 67108864 bytes
+#plugin(wasm)
+
+--- plugin-host-api ---
+// A module whose `read`, `font`, and `warn` functions forward their argument
+// to the respective host API function. `font` returns the font's length.
+#let p = plugin(host: true, bytes((
+  0, 97, 115, 109, 1, 0, 0, 0, 1, 21, 4, 96, 1, 127, 0, 96, 2, 127, 127, 0, 96,
+  2, 127, 127, 1, 127, 96, 1, 127, 1, 127, 2, 199, 1, 6, 9, 116, 121, 112, 115,
+  116, 95, 101, 110, 118, 42, 119, 97, 115, 109, 95, 109, 105, 110, 105, 109,
+  97, 108, 95, 112, 114, 111, 116, 111, 99, 111, 108, 95, 119, 114, 105, 116,
+  101, 95, 97, 114, 103, 115, 95, 116, 111, 95, 98, 117, 102, 102, 101, 114, 0,
+  0, 9, 116, 121, 112, 115, 116, 95, 101, 110, 118, 41, 119, 97, 115, 109, 95,
+  109, 105, 110, 105, 109, 97, 108, 95, 112, 114, 111, 116, 111, 99, 111, 108,
+  95, 115, 101, 110, 100, 95, 114, 101, 115, 117, 108, 116, 95, 116, 111, 95,
+  104, 111, 115, 116, 0, 1, 10, 116, 121, 112, 115, 116, 95, 104, 111, 115, 116,
+  9, 114, 101, 97, 100, 95, 102, 105, 108, 101, 0, 2, 10, 116, 121, 112, 115,
+  116, 95, 104, 111, 115, 116, 9, 102, 105, 110, 100, 95, 102, 111, 110, 116, 0,
+  2, 10, 116, 121, 112, 115, 116, 95, 104, 111, 115, 116, 11, 116, 97, 107, 101,
+  95, 98, 117, 102, 102, 101, 114, 0, 0, 10, 116, 121, 112, 115, 116, 95, 104,
+  111, 115, 116, 4, 119, 97, 114, 110, 0, 1, 3, 4, 3, 3, 3, 3, 5, 3, 1, 0, 1, 7,
+  31, 4, 6, 109, 101, 109, 111, 114, 121, 2, 0, 4, 114, 101, 97, 100, 0, 6, 4,
+  102, 111, 110, 116, 0, 7, 4, 119, 97, 114, 110, 0, 8, 10, 117, 3, 45, 1, 1,
+  127, 65, 0, 16, 0, 65, 0, 32, 0, 16, 2, 34, 1, 65, 0, 72, 4, 64, 65, 0, 32, 0,
+  16, 1, 65, 1, 15, 11, 65, 128, 8, 16, 4, 65, 128, 8, 32, 1, 16, 1, 65, 0, 11,
+  48, 1, 1, 127, 65, 0, 16, 0, 65, 0, 32, 0, 16, 3, 34, 1, 65, 0, 72, 4, 64, 65,
+  0, 32, 0, 16, 1, 65, 1, 15, 11, 65, 128, 8, 32, 1, 54, 2, 0, 65, 128, 8, 65,
+  4, 16, 1, 65, 0, 11, 20, 0, 65, 0, 16, 0, 65, 0, 32, 0, 16, 5, 65, 0, 65, 0,
+  16, 1, 65, 0, 11,
+)))
+
+#test(p.read(bytes("/assets/text/hello.txt")), bytes("Hello, world!\n"))
+#test(int.from-bytes(p.font(bytes("Libertinus Serif"))) > 0, true)
+
+// Warning: 2-26 careful
+#p.warn(bytes("careful"))
+
+--- plugin-host-read-outside-root ---
+// Paths cannot escape the project root, so this reads `/etc/passwd` relative
+// to the root, which does not exist.
+#let p = plugin(host: true, bytes((
+  0, 97, 115, 109, 1, 0, 0, 0, 1, 21, 4, 96, 1, 127, 0, 96, 2, 127, 127, 0, 96,
+  2, 127, 127, 1, 127, 96, 1, 127, 1, 127, 2, 199, 1, 6, 9, 116, 121, 112, 115,
+  116, 95, 101, 110, 118, 42, 119, 97, 115, 109, 95, 109, 105, 110, 105, 109,
+  97, 108, 95, 112, 114, 111, 116, 111, 99, 111, 108, 95, 119, 114, 105, 116,
+  101, 95, 97, 114, 103, 115, 95, 116, 111, 95, 98, 117, 102, 102, 101, 114, 0,
+  0, 9, 116, 121, 112, 115, 116, 95, 101, 110, 118, 41, 119, 97, 115, 109, 95,
+  109, 105, 110, 105, 109, 97, 108, 95, 112, 114, 111, 116, 111, 99, 111, 108,
+  95, 115, 101, 110, 100, 95, 114, 101, 115, 117, 108, 116, 95, 116, 111, 95,
+  104, 111, 115, 116, 0, 1, 10, 116, 121, 112, 115, 116, 95, 104, 111, 115, 116,
+  9, 114, 101, 97, 100, 95, 102, 105, 108, 101, 0, 2, 10, 116, 121, 112, 115,
+  116, 95, 104, 111, 115, 116, 9, 102, 105, 110, 100, 95, 102, 111, 110, 116, 0,
+  2, 10, 116, 121, 112, 115, 116, 95, 104, 111, 115, 116, 11, 116, 97, 107, 101,
+  95, 98, 117, 102, 102, 101, 114, 0, 0, 10, 116, 121, 112, 115, 116, 95, 104,
+  111, 115, 116, 4, 119, 97, 114, 110, 0, 1, 3, 4, 3, 3, 3, 3, 5, 3, 1, 0, 1, 7,
+  31, 4, 6, 109, 101, 109, 111, 114, 121, 2, 0, 4, 114, 101, 97, 100, 0, 6, 4,
+  102, 111, 110, 116, 0, 7, 4, 119, 97, 114, 110, 0, 8, 10, 117, 3, 45, 1, 1,
+  127, 65, 0, 16, 0, 65, 0, 32, 0, 16, 2, 34, 1, 65, 0, 72, 4, 64, 65, 0, 32, 0,
+  16, 1, 65, 1, 15, 11, 65, 128, 8, 16, 4, 65, 128, 8, 32, 1, 16, 1, 65, 0, 11,
+  48, 1, 1, 127, 65, 0, 16, 0, 65, 0, 32, 0, 16, 3, 34, 1, 65, 0, 72, 4, 64, 65,
+  0, 32, 0, 16, 1, 65, 1, 15, 11, 65, 128, 8, 32, 1, 54, 2, 0, 65, 128, 8, 65,
+  4, 16, 1, 65, 0, 11, 20, 0, 65, 0, 16, 0, 65, 0, 32, 0, 16, 5, 65, 0, 65, 0,
+  16, 1, 65, 0, 11,
+)))
+
+// Error: 2-41 plugin errored with: ../../../../etc/passwd
+#p.read(bytes("../../../../etc/passwd"))
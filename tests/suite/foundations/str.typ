@@ -357,3 +357,26 @@
 --- string-unclosed ---
 // Error: 2-2:1 unclosed string
 #"hello\"
+
+--- regex-captures ---
+#let re = regex("(?<key>\w+)=(\d+)?")
+#test(
+  re.captures("a b=1"),
+  (start: 2, end: 5, text: "b=1", captures: ("b", "1"), named-captures: (key: "b")),
+)
+#test(
+  re.captures("x="),
+  (start: 0, end: 2, text: "x=", captures: ("x", none), named-captures: (key: "x")),
+)
+#test(re.captures("!"), none)
+
+--- regex-split ---
+#let re = regex("\s*,\s*")
+#test(re.split("a , b,c"), ("a", "b", "c"))
+#test(re.split("a , b,c", limit: 2), ("a", "b,c"))
+#test(re.split("a , b,c", limit: 10), ("a", "b", "c"))
+
+--- regex-escape ---
+#test(regex.escape("1+1=2?"), "1\\+1=2\\?")
+#test("a.b".contains(regex(regex.escape("."))), true)
+#test("ab".contains(regex(regex.escape("."))), false)
@@ -123,6 +123,46 @@ Hallo
   Text
 ]
 
+--- columns-balance ---
+// Test that balanced columns distribute their content evenly.
+#context {
+  let body = lorem(40)
+  let unbalanced = measure(width: 200pt, columns(2, body))
+  let balanced = measure(width: 200pt, columns(2, balance: true, body))
+  test(balanced.width, unbalanced.width)
+  test(balanced.height < unbalanced.height * 0.6, true)
+  test(balanced.height > unbalanced.height * 0.4, true)
+}
+
+--- columns-balance-colbreak ---
+// Test that a column break still starts a new column when balancing.
+#context {
+  let body = [A #colbreak() B]
+  let unbalanced = measure(width: 200pt, columns(2, body))
+  let balanced = measure(width: 200pt, columns(2, balance: true, body))
+  test(balanced.height, unbalanced.height)
+}
+
+--- columns-balance-blocks ---
+// Test that balancing moves content into the second column.
+#set block(spacing: 5pt)
+#let b(c) = block(width: 100%, height: 15pt, fill: c, breakable: false)
+#columns(2, balance: true)[
+  #b(rgb("#ff0000"))
+  #b(rgb("#00ff00"))
+  #b(rgb("#0000ff"))
+]
+
+--- page-columns-balance ---
+// Test that page-level columns are balanced.
+#set page(height: 100pt, columns: 2, balance: true)
+#set block(spacing: 5pt)
+#let b(c) = block(width: 100%, height: 15pt, fill: c, breakable: false)
+#b(rgb("#ff0000"))
+#b(rgb("#00ff00"))
+#b(rgb("#0000ff"))
+#b(rgb("#ffff00"))
+
 --- colbreak-weak ---
 #set page(columns: 2)
 #colbreak(weak: true)
@@ -137,6 +137,59 @@
 // Error: 20-23 must be `left` or `right`
 #set page(binding: top)
 
+--- page-marks-bad-key ---
+// Error: 18-30 unexpected key "trim", valid keys are "crop", "registration", and "color-bar"
+#set page(marks: (trim: true))
+
+--- page-marks-get ---
+#context test(page.marks, none)
+
+--- page-bleed-size ---
+// Test that the bleed grows the page around the trimmed area.
+#set page(
+  width: 40pt,
+  height: 30pt,
+  margin: 0pt,
+  bleed: 5pt,
+  fill: rgb("#ff0000"),
+)
+#block(width: 100%, height: 100%, fill: rgb("#0000ff"))
+
+--- page-bleed-sides ---
+// Test that the bleed can differ per side.
+#set page(
+  width: 40pt,
+  height: 30pt,
+  margin: 0pt,
+  bleed: (left: 10pt, top: 5pt, rest: 0pt),
+  fill: rgb("#ff0000"),
+)
+#block(width: 100%, height: 100%, fill: rgb("#0000ff"))
+
+--- page-marks-crop ---
+// Test that crop marks are drawn outside of the bleed.
+#set page(
+  width: 40pt,
+  height: 30pt,
+  margin: 0pt,
+  bleed: 5pt,
+  fill: rgb("#ff0000"),
+  marks: (crop: true),
+)
+#block(width: 100%, height: 100%, fill: rgb("#0000ff"))
+
+--- page-marks-registration ---
+// Test that registration marks are centered on the sides of the page.
+#set page(
+  width: 40pt,
+  height: 30pt,
+  margin: 0pt,
+  bleed: 5pt,
+  fill: rgb("#ff0000"),
+  marks: (registration: true),
+)
+#block(width: 100%, height: 100%, fill: rgb("#0000ff"))
+
 --- page-marginals ---
 #set page(
   paper: "a8",
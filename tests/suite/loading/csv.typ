@@ -34,3 +34,43 @@
 // Warning: 14-20 `csv.decode` is deprecated, directly pass bytes to `csv` instead
 // Hint: 14-20 it will be removed in Typst 0.15.0
 #let _ = csv.decode
+
+--- csv-encode ---
+#test(csv.encode((("a", "b"), ("1", "2"))), "a,b\n1,2\n")
+#test(csv.encode(((1, 2.5), (true, none))), "1,2.5\ntrue,\n")
+#test(csv.encode((("a", "b"),), delimiter: ";"), "a;b\n")
+#test(csv.encode((("x, y", "z"),)), "\"x, y\",z\n")
+
+--- csv-encode-numbers ---
+// Numbers are written like `str` writes them.
+#test(csv.encode(((decimal("1.50"), decimal("-2.25"), 0.1 + 0.2),)), "1.50,−2.25,0.30000000000000004\n")
+#test(csv.encode(((-3, -1.5, float.nan, float.inf),)), "−3,−1.5,NaN,∞\n")
+#test(csv.encode(((1e20, 7),)), str(1e20) + ",7\n")
+
+--- csv-encode-row-type-dict ---
+#test(
+  csv.encode(((a: "1", b: none), (b: "2", c: "3"))),
+  "a,b,c\n1,,\n,2,3\n",
+)
+#test(csv.encode(((a: "1", b: "2"),), header: none), "1,2\n")
+#test(csv.encode(((a: "1", b: "2"),), header: ("b", "a")), "b,a\n2,1\n")
+#test(csv.encode((("1", "2"),), header: ("a", "b")), "a,b\n1,2\n")
+
+--- csv-encode-round-trip ---
+#let data = csv("/assets/data/zoo.csv")
+#test(csv(bytes(csv.encode(data))), data)
+
+#let dicts = csv("/assets/data/zoo.csv", row-type: dictionary)
+#test(csv(bytes(csv.encode(dicts)), row-type: dictionary), dicts)
+
+--- csv-encode-mixed-rows ---
+// Error: 13-27 expected all rows to be dictionaries, found array
+#csv.encode(((a: 1), (2,)))
+
+--- csv-encode-bad-field ---
+// Error: 13-25 cannot encode array as a CSV field
+#csv.encode(((1, (2,)),))
+
+--- csv-encode-key-not-in-header ---
+// Error: 13-28 row 1 has key "b" that is not in the header
+#csv.encode(((a: 1, b: 2),), header: ("a",))
@@ -0,0 +1,58 @@
+--- sqlite ---
+// An empty file is an empty database.
+#let db = bytes(())
+#test(
+  sqlite(db, "SELECT 1 AS a, 2.5 AS b, 'x' AS c, NULL AS d, x'0102' AS e"),
+  ((a: 1, b: 2.5, c: "x", d: none, e: bytes((1, 2))),),
+)
+#test(
+  sqlite(db, "WITH t(v) AS (VALUES (3), (1), (2)) SELECT v FROM t ORDER BY v"),
+  ((v: 1), (v: 2), (v: 3)),
+)
+#test(sqlite(db, "SELECT 1 WHERE 0"), ())
+
+--- sqlite-params ---
+#let db = bytes(())
+#test(sqlite(db, "SELECT ? + ? AS sum", params: (1, 2)), ((sum: 3),))
+#test(sqlite(db, "SELECT ?2 AS a, ?1 AS b", params: ("x", none)), ((a: none, b: "x"),))
+#test(
+  sqlite(db, "SELECT :name AS name, @n * 2 AS n", params: (name: "Typst", n: 21)),
+  ((name: "Typst", n: 42),),
+)
+#test(sqlite(db, "SELECT ? AS v", params: (true,)), ((v: 1),))
+// Values are never interpreted as SQL.
+#test(sqlite(db, "SELECT ? AS v", params: ("1; DROP TABLE t",)), ((v: "1; DROP TABLE t"),))
+
+--- sqlite-write ---
+// Error: 20-40 query must not modify the database
+#sqlite(bytes(()), "CREATE TABLE t (x)")
+
+--- sqlite-missing-params ---
+// Error: 20-30 query has 1 parameter, but 0 were given
+#sqlite(bytes(()), "SELECT ?")
+
+--- sqlite-unknown-param ---
+// Error: 40-46 query has no parameter named `x`
+#sqlite(bytes(()), "SELECT 1", params: (x: 1))
+
+--- sqlite-unbound-named-param ---
+// Error: 55-61 no value was given for the parameter `@b`
+#sqlite(bytes(()), "SELECT :a AS a, @b AS b", params: (a: 1))
+
+--- sqlite-unbound-positional-param ---
+// Error: 54-60 no value was given for positional parameter 2
+// Hint: 54-60 positional parameters need an array of values
+#sqlite(bytes(()), "SELECT :a AS a, ? AS b", params: (a: 1))
+
+--- sqlite-duplicate-columns ---
+// Error: 20-37 query returns multiple columns named `a`
+// Hint: 20-37 give the columns distinct names with `AS`
+#sqlite(bytes(()), "SELECT 1 a, 2 a")
+
+--- sqlite-invalid-param ---
+// Error: 40-46 cannot bind length as a query parameter
+#sqlite(bytes(()), "SELECT ?", params: (1pt,))
+
+--- sqlite-file-not-found ---
+// Error: 9-20 file not found (searched at tests/suite/loading/nope.db)
+#sqlite("nope.db", "SELECT 1")
@@ -31,3 +31,55 @@
 // Warning: 14-20 `xml.decode` is deprecated, directly pass bytes to `xml` instead
 // Hint: 14-20 it will be removed in Typst 0.15.0
 #let _ = xml.decode
+
+--- xml-encode-round-trip ---
+#let data = xml("/assets/data/hello.xml")
+#test(xml(bytes(xml.encode(data))), data)
+
+--- xml-encode-element ---
+#let elem = (
+  tag: "note",
+  attrs: (lang: "en", title: "\"Quotes\" & <brackets>"),
+  children: ("1 < 2 & 3 > 2", (tag: "br", attrs: (:), children: ())),
+)
+#test(xml(bytes(xml.encode(elem))), (elem,))
+#test(xml(bytes(xml.encode((tag: "a")))), ((tag: "a", attrs: (:), children: ()),))
+
+--- xml-encode-unexpected-key ---
+// Error: 13-31 unexpected key "foo", valid keys are "tag", "attrs", and "children"
+#xml.encode((tag: "a", foo: 1))
+
+--- xml-encode-names ---
+#test(
+  xml.encode((tag: "svg:rect", attrs: (data-x: "1", "_y.z": "2"))),
+  "<svg:rect data-x=\"1\" _y.z=\"2\"/>",
+)
+#test(xml.encode((tag: "straße")), "<straße/>")
+
+--- xml-encode-empty-tag ---
+// Error: 13-23 element tag "" is not a valid XML name
+// Hint: 13-23 names must start with a letter, `_` or `:` and may only contain letters, digits, `-`, `_`, `.` and `:`
+#xml.encode((tag: ""))
+
+--- xml-encode-tag-with-space ---
+// Error: 13-26 element tag "a b" is not a valid XML name
+// Hint: 13-26 names must start with a letter, `_` or `:` and may only contain letters, digits, `-`, `_`, `.` and `:`
+#xml.encode((tag: "a b"))
+
+--- xml-encode-tag-with-digit ---
+// Error: 13-25 element tag "1x" is not a valid XML name
+// Hint: 13-25 names must start with a letter, `_` or `:` and may only contain letters, digits, `-`, `_`, `.` and `:`
+#xml.encode((tag: "1x"))
+
+--- xml-encode-bad-attribute ---
+// Error: 13-42 attribute name "<" is not a valid XML name
+// Hint: 13-42 names must start with a letter, `_` or `:` and may only contain letters, digits, `-`, `_`, `.` and `:`
+#xml.encode((tag: "a", attrs: ("<": "1")))
+
+--- xml-encode-multiple-roots ---
+// Error: 13-37 XML document must have exactly one root element
+#xml.encode(((tag: "a"), (tag: "b")))
+
+--- xml-encode-top-level-text ---
+// Error: 13-31 text must be inside of an element
+#xml.encode(("hi", (tag: "a")))
@@ -40,6 +40,31 @@ And quark! @quark
 Now we have multiple bibliographies containing @glacier-melt @keshav2007read
 #bibliography(("/assets/bib/works.bib", "/assets/bib/works_too.bib"))
 
+--- bibliography-multiple-sections html ---
+// Each bibliography lists the works cited since the previous one and numbers
+// them on its own.
+= Glaciers
+Ice melts. @glacier-melt
+#bibliography("/assets/bib/works.bib", title: none)
+
+= Papers
+Read them. @keshav2007read
+#bibliography("/assets/bib/works_too.bib", title: none)
+
+--- bibliography-multiple-sections-missing-key ---
+// Citations resolve against the bibliography that follows them.
+// Error: 1-14 key `glacier-melt` does not exist in the bibliography
+@glacier-melt
+#bibliography("/assets/bib/works_too.bib")
+
+@glacier-melt
+#bibliography("/assets/bib/works.bib")
+
+--- bibliography-filter-bad-return ---
+@netwok
+// Error: 48-58 expected boolean, found integer
+#bibliography("/assets/bib/works.bib", filter: entry => 1)
+
 --- bibliography-duplicate-key ---
 // Error: 15-65 duplicate bibliography keys: netwok, issue201, arrgh, quark, distress, glacier-melt, tolkien54, DBLP:books/lib/Knuth86a, sharing, restful, mcintosh_anxiety, psychology25
 #bibliography(("/assets/bib/works.bib", "/assets/bib/works.bib"))
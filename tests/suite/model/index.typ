@@ -0,0 +1,53 @@
+--- index-entry-fields ---
+#index.entry("Apple")
+#index.entry("Apple", sub: "red")
+#index.entry("Tree", see-also: "Plant")
+
+#context {
+  let entries = query(index.entry)
+  test(entries.len(), 3)
+  test(entries.map(e => e.term), ("Apple", "Apple", "Tree"))
+  test(entries.at(0).sub, none)
+  test(entries.at(1).sub, "red")
+  test(entries.at(2).see-also, "Plant")
+}
+
+--- index-entry-invisible ---
+// Index entries don't produce visible content.
+#context test(measure[A#index.entry("A")B], measure[AB])
+
+--- index-columns ---
+#set page(width: 200pt)
+#index.entry("Apple")
+#index.entry("Banana")
+#index.entry("Cherry")
+#index.entry("Date")
+
+#context {
+  let one = measure(width: 200pt, index(title: none, columns: 1))
+  let two = measure(width: 200pt, index(title: none, columns: 2))
+  test(two.height < one.height, true)
+}
+
+--- index-title ---
+#context {
+  let none-title = measure(index(title: none))
+  let custom = measure(index(title: [Register]))
+  test(none-title.height < custom.height, true)
+}
+
+--- index-entry-missing-term ---
+// Error: 2-15 missing argument: term
+#index.entry()
+
+--- index-html html ---
+// Terms are sorted with the collation rules of the text language and
+// cross-references are listed after the term's occurrences.
+#index.entry("Zebra", see-also: "Horse")
+#index.entry("apple tree", see-also: "Tree")
+#index.entry("Banana", see-also: "Plant")
+#index.entry("Banana", see-also: "Fruit")
+#index.entry("Banana", see-also: "Plant")
+#index.entry("Apple", sub: "red", see-also: "Cherry")
+#index.entry("Apple", see-also: "Fruit")
+#index()
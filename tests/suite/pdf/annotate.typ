@@ -0,0 +1,35 @@
+// Test annotations. The tag trees show the link tags of the placeholder
+// annotations that the exporter turns into annotations.
+
+--- pdf-annotate-note pdftags ---
+#pdf.note("Check this", author: "Editor")
+
+--- pdf-annotate-mark pdftags ---
+Some #pdf.mark(comment: "Why?")[marked] text.
+
+--- pdf-annotate-free-text render pdftags nopdfua ---
+#pdf.free-text("Hello", width: 100%)
+
+--- pdf-annotate-free-text-pdfua pdftags ---
+// Error: 2-24 free text annotations are not supported in PDF/UA-1
+// Hint: 2-24 the text of a free text annotation is displayed with a font that is not embedded
+#pdf.free-text("Hello")
+
+--- pdf-annotate-mark-in-link ---
+// Warning: 29-81 mark is not exported because its content is a link
+// Hint: 29-81 in PDF, content can only belong to a single annotation
+// Hint: 29-81 try marking the link instead
+#link("https://typst.app")[#pdf.mark[#box(width: 20pt, height: 10pt, fill: red)]]
+
+--- pdf-annotate-link-in-mark ---
+// Warning: 54-124 linked content is not covered by the surrounding mark
+// Hint: 54-124 in PDF, content can only belong to a single annotation
+#pdf.mark[#box(width: 20pt, height: 10pt, fill: red)#link("https://typst.app")[#box(width: 20pt, height: 10pt, fill: blue)]]
+
+--- pdf-annotate-mark-kind-invalid ---
+// Error: 17-23 expected "highlight", "underline", "squiggly", or "strike"
+#pdf.mark(kind: "bold")[Text]
+
+--- pdf-annotate-note-date-invalid ---
+// Error: 23-30 expected datetime or none, found string
+#pdf.note("Hi", date: "today")
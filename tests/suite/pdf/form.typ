@@ -0,0 +1,51 @@
+// Test interactive form fields. The tag trees show the link tags of the
+// placeholder annotations that the exporter turns into form fields.
+
+--- pdf-form-text-field render pdftags nopdfua ---
+#pdf.text-field("name", width: 100%)
+
+--- pdf-form-checkbox render pdftags ---
+#pdf.checkbox("agree", alt: "I agree", checked: true)
+
+--- pdf-form-radio render pdftags ---
+#pdf.radio("size", "s")#h(4pt)#pdf.radio("size", "m", checked: true)
+
+--- pdf-form-dropdown render pdftags nopdfua ---
+#pdf.dropdown("country", ("Austria", "Germany"), width: 100%, fill: aqua)
+
+--- pdf-form-button render pdftags nopdfua ---
+#pdf.button("send", "Send", stroke: blue)
+
+--- pdf-form-text-field-pdfua pdftags ---
+// Error: 2-24 text fields are not supported in PDF/UA-1
+// Hint: 2-24 the values of form fields are displayed with a font that is not embedded
+#pdf.text-field("name")
+
+--- pdf-form-dropdown-invalid-value ---
+// Error: 2-66 the selected value must be one of the options
+#pdf.dropdown("country", ("Austria", "Germany"), value: "France")
+
+--- pdf-form-duplicate-name pdftags nopdfua ---
+// Error: 2:2-2:22 a form field named `name` already exists
+// Hint: 2:2-2:22 only radio buttons can share their name to form a group
+#pdf.text-field("name")
+#pdf.checkbox("name")
+
+--- pdf-form-name-with-period pdftags nopdfua ---
+// Error: 2-31 form field names must not be empty or contain periods
+#pdf.text-field("person.name")
+
+--- pdf-form-radio-duplicate-value pdftags ---
+// Error: 2:2-2:24 radio group `size` already has a button with the value `s`
+#pdf.radio("size", "s")
+#pdf.radio("size", "s")
+
+--- pdf-form-radio-multiple-checked pdftags ---
+// Error: 2:2-2:39 radio group `size` already has a checked button
+#pdf.radio("size", "s", checked: true)
+#pdf.radio("size", "m", checked: true)
+
+--- pdf-form-radio-off pdftags ---
+// Error: 2-26 `Off` cannot be the value of a radio button
+// Hint: 2-26 PDF uses this value for groups without a selected button
+#pdf.radio("size", "Off")
@@ -0,0 +1,12 @@
+// Test layers. Rendering only includes the layers that are visible by default.
+
+--- pdf-layer-hidden render ---
+#pdf.layer("A")[#box(width: 20pt, height: 10pt, fill: red)]#pdf.layer("B", visible: false)[#box(width: 20pt, height: 10pt, fill: blue)]
+
+--- pdf-layer-print-invalid ---
+// Error: 24-29 expected boolean or auto, found string
+#pdf.layer("A", print: "yes")[Text]
+
+--- pdf-layer-visible-invalid ---
+// Error: 26-30 expected boolean, found none
+#pdf.layer("A", visible: none)[Text]
@@ -70,6 +70,34 @@ Treeworld, the World of worlds, is a world.
 #show regex("(?i)rust"): it => [#it (🚀)]
 Rust is memory-safe and blazingly fast. Let's rewrite everything in rust.
 
+--- show-text-regex-captures ---
+#show regex("([A-Z]+)-(\d+)"): it => {
+  test(it.text, "ABC-123")
+  test(it.captures, ("ABC", "123"))
+  test(it.named-captures, (:))
+  box(width: int(it.captures.at(1)) * 1pt)
+}
+#context test(measure[ABC-123].width, 123pt)
+
+--- show-text-regex-named-captures ---
+#show regex("v(?<major>\d+)(\.(?<minor>\d+))?"): it => {
+  let (major, minor) = it.named-captures
+  box(width: int(major) * 1pt + if minor == none { 0pt } else { int(minor) * 10pt })
+}
+#context test(measure[v2].width, 2pt)
+#context test(measure[v2.3].width, 32pt)
+
+--- show-text-regex-captures-with-parameter-sink ---
+// Captures don't depend on the signature of the function.
+#show regex("(\d+)"): (..args) => box(width: int(args.at(0).captures.at(0)) * 1pt)
+#context test(measure[42].width, 42pt)
+
+--- show-text-captures-only-on-matches ---
+// String selectors are regexes without capturing groups.
+#show "a": it => test(it.captures, ())
+a
+#context test("captures" in [b].fields(), false)
+
 --- show-text-get-text-on-it ---
 // Test accessing the string itself.
 #show "hello": it => it.text.split("").map(upper).join("|")
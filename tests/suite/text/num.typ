@@ -0,0 +1,82 @@
+// Test locale-aware number formatting.
+
+--- num-decimal ---
+#context test(num(1234567), "1,234,567")
+#context test(num(1234567.891), "1,234,567.891")
+#context test(num(-1234.5), "−1,234.5")
+#context test(num(999), "999")
+#context test(num(1234567, grouping: false), "1234567")
+#context test(num(calc.pi, digits: 3), "3.142")
+#context test(num(5, digits: 2), "5.00")
+#context test(num(-0.001, digits: 2), "0.00")
+#context test(num(decimal("12345.6789")), "12,345.6789")
+#context test(num(decimal("-2.345"), digits: 2), "−2.35")
+#context test(num(float.inf), "∞")
+#context test(num(-float.inf), "−∞")
+#context test(num(float.nan), "NaN")
+
+--- num-decimal-localized ---
+#set text(lang: "de")
+#context test(num(1234567.891), "1.234.567,891")
+#context test(num(decimal("-1234.5")), "−1.234,5")
+
+#set text(lang: "fr")
+#context test(num(1234567.5), "1\u{202F}234\u{202F}567,5")
+
+#set text(lang: "es")
+#context test(num(1234), "1234")
+#context test(num(12345), "12.345")
+
+#set text(lang: "de", region: "CH")
+#context test(num(1234567.5), "1’234’567.5")
+
+#set text(lang: "hi", region: none)
+#context test(num(123456789), "12,34,56,789")
+
+#set text(lang: "en", region: "IN")
+#context test(num(1234567), "12,34,567")
+
+--- num-percent ---
+#context test(num(0.25, style: "percent"), "25%")
+#context test(num(decimal("0.125"), style: "percent", digits: 1), "12.5%")
+#context test(num(1, style: "percent", digits: 1), "100.0%")
+#context test(num(0.07, style: "percent"), "7%")
+#context test(num(0.285, style: "percent"), "28.5%")
+#context test(num(-1e-7, style: "percent"), "−0.00001%")
+
+#set text(lang: "de")
+#context test(num(0.25, style: "percent"), "25\u{A0}%")
+
+#set text(lang: "fr")
+#context test(num(0.25, style: "percent"), "25\u{202F}%")
+
+#set text(lang: "tr")
+#context test(num(0.25, style: "percent"), "%25")
+
+--- num-currency ---
+#context test(num(1234.5, style: "currency", currency: "USD"), "$1,234.50")
+#context test(num(1234.6, style: "currency", currency: "JPY"), "¥1,235")
+#context test(num(-3, style: "currency", currency: "EUR"), "−€3.00")
+#context test(num(10, style: "currency", currency: "CHF"), "CHF\u{A0}10.00")
+
+#set text(lang: "de")
+#context test(
+  num(decimal("1499.5"), style: "currency", currency: "EUR"),
+  "1.499,50\u{A0}€",
+)
+
+#set text(lang: "nl")
+#context test(num(2.5, style: "currency", currency: "EUR"), "€\u{A0}2,50")
+
+#set text(lang: "pt", region: "BR")
+#context test(num(2.5, style: "currency", currency: "BRL"), "R$\u{A0}2,50")
+
+--- num-currency-missing ---
+// Error: 10-35 the currency style requires a currency
+#context num(5, style: "currency")
+
+--- num-no-context ---
+// Error: 2-8 can only be used when context is known
+// Hint: 2-8 try wrapping this in a `context` expression
+// Hint: 2-8 the `context` expression should wrap everything that depends on this function
+#num(5)
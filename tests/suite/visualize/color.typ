@@ -317,6 +317,26 @@
 #test-repr(luma(100%, 50%).opacify(-50%), luma(100%, 25%))
 #test-repr(luma(100%, 0%).opacify(0%), luma(100%, 0%))
 
+--- color-spot ---
+// Test spot colors.
+#let ink = color.spot("PANTONE 286 C", cmyk(100%, 66%, 0%, 2%))
+#test-repr(ink, color.spot("PANTONE 286 C", cmyk(100%, 66%, 0%, 2%)))
+#test(ink.space(), color.spot)
+#test(ink.components(), (100%,))
+#test(ink.components(alpha: false), (100%,))
+#test-repr(ink.lighten(40%), color.spot("PANTONE 286 C", cmyk(100%, 66%, 0%, 2%), tint: 60%))
+#test-repr(ink.lighten(40%).darken(50%), color.spot("PANTONE 286 C", cmyk(100%, 66%, 0%, 2%), tint: 80%))
+#test(ink == color.spot("PANTONE 286 C", cmyk(100%, 66%, 0%, 2%), tint: 100%), true)
+#test(ink == color.spot("Other", cmyk(100%, 66%, 0%, 2%)), false)
+
+--- color-spot-empty-name ---
+// Error: 13-15 spot color name must not be empty
+#color.spot("", red)
+
+--- color-spot-transparentize ---
+// Error: 2-51 spot colors do not have an alpha component
+#color.spot("Gold", luma(50%)).transparentize(50%)
+
 --- issue-color-mix-luma ---
 // When mixing luma colors, we accidentally used the wrong component.
 #rect(fill: gradient.linear(black, silver, space: luma))